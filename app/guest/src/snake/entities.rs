@@ -1,15 +1,14 @@
 use toxoid_api::*;
 use crate::components::*;
-use rand::{Rng, thread_rng};
 
 // TODO: Move this config ECS singleton
 pub const SCREEN_WIDTH: i32 = 800;
 pub const SCREEN_HEIGHT: i32 = 600;
 const DEFAULT_TAIL_LENGTH: u32 = 2;
 
+// Food placement draws from its own stream so it only depends on the world seed
 pub fn get_random(max: i32) -> i32 {
-    let mut rng = thread_rng();
-    rng.gen_range(0..max)
+    Random::stream("food").range_i32(0, max)
 }
 
 pub fn aabb(a: &Position, a2: &Size, b: &Position, b2: &Size) -> bool {
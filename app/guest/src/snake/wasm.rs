@@ -6,6 +6,14 @@ impl CallbacksGuest for ToxoidWasmComponent {
     fn run(iter: ToxoidIter, handle: u64) {
         run_callback(iter, handle);
     }
+
+    fn free(handle: u64) {
+        free_callback(handle);
+    }
+
+    fn compare(handle: u64, e1: EcsEntityT, c1: PointerT, e2: EcsEntityT, c2: PointerT) -> i32 {
+        run_compare(handle, e1, c1, e2, c2)
+    }
 }
 
 impl WorldGuest for ToxoidWasmComponent {
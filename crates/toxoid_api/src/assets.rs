@@ -0,0 +1,179 @@
+use crate::*;
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+use once_cell::sync::Lazy;
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+use std::collections::HashMap;
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+use std::sync::Mutex;
+
+// Native
+// The entity an asset is loaded into
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub type AssetHandle = u64;
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssetStatus {
+    Loading,
+    Loaded,
+    Failed(String)
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetEvent {
+    pub handle: AssetHandle,
+    pub status: AssetStatus
+}
+
+// WASM
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+pub use toxoid_guest::bindings::toxoid_component::component::assets::{
+    self as ToxoidAssets,
+    AssetHandle,
+    AssetStatus,
+    AssetEvent
+};
+
+// Errors of the assets that failed to load
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+static ASSET_ERRORS: Lazy<Mutex<HashMap<AssetHandle, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Assets loaded through `Assets` on the host that haven't been reported by `Assets::poll_events` yet
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+static PENDING_ASSETS: Lazy<Mutex<Vec<AssetHandle>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// Mark an asset as failed, called by the fetch backend when a request fails
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn asset_failed(handle: AssetHandle, error: String) {
+    ASSET_ERRORS.lock().unwrap().insert(handle, error);
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn asset_status(handle: AssetHandle) -> AssetStatus {
+    if let Some(error) = ASSET_ERRORS.lock().unwrap().get(&handle) {
+        return AssetStatus::Failed(error.clone());
+    }
+    if Entity::from_id(handle).has::<Loaded>() {
+        AssetStatus::Loaded
+    } else {
+        AssetStatus::Loading
+    }
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn asset_size(handle: AssetHandle) -> Option<(u32, u32)> {
+    let mut entity = Entity::from_id(handle);
+    if !entity.has::<Loaded>() || !entity.has::<Size>() {
+        return None;
+    }
+    let size = entity.get::<Size>();
+    Some((size.get_width(), size.get_height()))
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn asset_data(handle: AssetHandle) -> Option<Vec<u8>> {
+    let mut entity = Entity::from_id(handle);
+    if !entity.has::<Loaded>() {
+        return None;
+    }
+    if entity.has::<FetchRequest>() {
+        return Some(entity.get::<FetchRequest>().get_data());
+    }
+    if entity.has::<Image>() {
+        return Some(entity.get::<Image>().get_data());
+    }
+    None
+}
+
+// Remove the assets that finished loading or failed from `pending` and return their events
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn poll_asset_events(pending: &mut Vec<AssetHandle>) -> Vec<AssetEvent> {
+    let mut events = Vec::new();
+    pending.retain(|handle| match asset_status(*handle) {
+        AssetStatus::Loading => true,
+        status => {
+            events.push(AssetEvent { handle: *handle, status });
+            false
+        }
+    });
+    events
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+fn track(entity: Entity) -> AssetHandle {
+    let handle = entity.get_id();
+    PENDING_ASSETS.lock().unwrap().push(handle);
+    handle
+}
+
+// Asset loading by handle, for code that runs both natively and as a guest
+pub struct Assets;
+
+impl Assets {
+    pub fn load_image(path: &str) -> AssetHandle {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return track(load_image(path));
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::load_image(path);
+    }
+
+    pub fn load_sprite(path: &str, rendered_on_load: bool) -> AssetHandle {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return track(load_sprite(path, rendered_on_load));
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::load_sprite(path, rendered_on_load);
+    }
+
+    pub fn load_animation(atlas_path: &str, skeleton_path: &str, rendered_on_load: bool) -> AssetHandle {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return track(load_animation(atlas_path, skeleton_path, rendered_on_load));
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::load_animation(atlas_path, skeleton_path, rendered_on_load);
+    }
+
+    pub fn load_worldmap(path: &str, rendered_on_load: bool) -> AssetHandle {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return track(load_worldmap(path, rendered_on_load));
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::load_worldmap(path, rendered_on_load);
+    }
+
+    // Raw file contents, read with `Assets::data` once loaded
+    pub fn load_data(path: &str) -> AssetHandle {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return track(fetch(path, DataType::Raw, None));
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::load_data(path);
+    }
+
+    pub fn status(handle: AssetHandle) -> AssetStatus {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return asset_status(handle);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::status(handle);
+    }
+
+    // Width and height of a loaded image or sprite
+    pub fn size(handle: AssetHandle) -> Option<(u32, u32)> {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return asset_size(handle);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::size(handle);
+    }
+
+    pub fn data(handle: AssetHandle) -> Option<Vec<u8>> {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return asset_data(handle);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::data(handle);
+    }
+
+    // Assets loaded through `Assets` that finished loading or failed since the last call
+    pub fn poll_events() -> Vec<AssetEvent> {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return poll_asset_events(&mut PENDING_ASSETS.lock().unwrap());
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidAssets::poll_events();
+    }
+}
@@ -0,0 +1,166 @@
+use crate::*;
+
+// Entities created with all their components and initial values in one call, instead of
+// `add` for every component and setters for every member, each crossing the guest boundary:
+//
+//     let mut size = Size::default();
+//     size.width = 100;
+//     let entity = Entity::builder()
+//         .add::<Position>()
+//         .set(size)
+//         .with(|target: &mut RenderTarget| target.z_depth = 2)
+//         .build();
+//
+// Values are sent in `EntityDesc::values`, the members of a component in declaration order
+// encoded like in world snapshots.
+
+// Member value that can be encoded for `EntityDesc::values`. `component!` implements it
+// for components and `component_enum!` for enums.
+pub trait MemberValue {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+// Lists and fixed-size arrays are a u32 count and the elements, like in snapshots.
+// The count of an array has to be the length of the member it's set on.
+fn encode_list<T: MemberValue>(values: &[T], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(values.len() as u32).to_le_bytes());
+    values.iter().for_each(|value| value.encode(out));
+}
+
+macro_rules! number_values {
+    ($($number:ty),*) => {
+        $(
+            impl MemberValue for $number {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl MemberValue for Vec<$number> {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    encode_list(self, out);
+                }
+            }
+
+            impl<const N: usize> MemberValue for [$number; N] {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    encode_list(self, out);
+                }
+            }
+        )*
+    };
+}
+
+number_values!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl MemberValue for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+// Raw pointer members are addresses, kept as they are since the values are of this run
+impl<T> MemberValue for *const T {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(*self as usize as u64).to_le_bytes());
+    }
+}
+
+impl<T> MemberValue for *mut T {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(*self as usize as u64).to_le_bytes());
+    }
+}
+
+// Strings have a presence flag in front, they are always present here
+impl MemberValue for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(1);
+        out.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+pub struct EntityBuilder {
+    desc: EntityDesc,
+    inherits: Option<EcsEntityT>
+}
+
+impl EntityBuilder {
+    pub fn new() -> Self {
+        Self {
+            desc: EntityDesc { name: None, add: None, prefab: false, values: None },
+            inherits: None
+        }
+    }
+
+    pub fn named(mut self, name: &str) -> Self {
+        self.desc.name = Some(name.to_string());
+        self
+    }
+
+    pub fn prefab(mut self) -> Self {
+        self.desc.prefab = true;
+        self
+    }
+
+    // Instance of a prefab, components that aren't set are inherited
+    pub fn inherits(mut self, prefab: EcsEntityT) -> Self {
+        self.inherits = Some(prefab);
+        self
+    }
+
+    // Component or tag without initial values
+    pub fn add<T: ComponentType>(self) -> Self {
+        self.add_id(T::get_id())
+    }
+
+    pub fn add_id(mut self, id: EcsEntityT) -> Self {
+        self.desc.add.get_or_insert_with(Vec::new).push(id);
+        self
+    }
+
+    // Component with the values of `value`
+    pub fn set<T: ComponentType + MemberValue>(self, value: T) -> Self {
+        let mut data = Vec::new();
+        value.encode(&mut data);
+        self.set_encoded(T::get_id(), data)
+    }
+
+    // Component with default values changed by `init`
+    pub fn with<T: ComponentType + MemberValue + Default>(self, init: impl FnOnce(&mut T)) -> Self {
+        let mut value = T::default();
+        init(&mut value);
+        self.set(value)
+    }
+
+    // Component with members already encoded, for components registered at runtime
+    pub fn set_encoded(mut self, component: EcsEntityT, data: Vec<u8>) -> Self {
+        self.desc.values.get_or_insert_with(Vec::new).push(ComponentValue { component, data });
+        self
+    }
+
+    // Values that can't be set, such as data of another layout, are logged and none of them are set
+    pub fn build(self) -> Entity {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        let entity = ToxoidEntity::new(self.desc, self.inherits);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        let entity = ToxoidEntity::new(&self.desc, self.inherits);
+        Entity { entity }
+    }
+
+    // Like `build`, the entity isn't created if a value can't be set
+    pub fn try_build(self) -> Result<Entity, String> {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        let id = ToxoidEntity::create(self.desc, self.inherits)?;
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        let id = ToxoidEntity::create(&self.desc, self.inherits)?;
+        Ok(Entity::from_id(id))
+    }
+}
+
+impl Default for EntityBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+// Structural changes made while systems iterate: spawning, despawning, adding and removing.
+// They're applied in order at the start of the next phase, so the systems of the next phase see them.
+// Commands from the last phase and from outside of systems are applied at the start of the next frame
+// or with `Commands::flush`. A command for an entity or component that was deleted in the meantime
+// is skipped and reported by `Commands::take_errors`.
+pub struct Commands;
+
+impl Commands {
+    // The entity is alive right away, its components are added when the commands are applied
+    pub fn spawn() -> Entity {
+        Entity::from_id(ToxoidApi::command_spawn())
+    }
+
+    pub fn despawn(entity: &Entity) {
+        Self::despawn_id(entity.get_id());
+    }
+
+    pub fn despawn_id(entity: EcsEntityT) {
+        ToxoidApi::command_despawn(entity);
+    }
+
+    pub fn add<T: ComponentType>(entity: &Entity) {
+        Self::add_id(entity.get_id(), T::get_id());
+    }
+
+    pub fn add_id(entity: EcsEntityT, component: EcsEntityT) {
+        ToxoidApi::command_add(entity, component);
+    }
+
+    pub fn remove<T: ComponentType>(entity: &Entity) {
+        Self::remove_id(entity.get_id(), T::get_id());
+    }
+
+    pub fn remove_id(entity: EcsEntityT, component: EcsEntityT) {
+        ToxoidApi::command_remove(entity, component);
+    }
+
+    // Apply the pending commands now, returns the ones that failed
+    pub fn flush() -> Vec<CommandError> {
+        ToxoidApi::flush_commands()
+    }
+
+    // Commands that failed at the flush points since the last call
+    pub fn take_errors() -> Vec<CommandError> {
+        ToxoidApi::take_command_errors()
+    }
+}
@@ -140,7 +140,11 @@ component! {
         min_window_width: u32,
         min_window_height: u32,
         default_zoom: f32,
-        zoom_speed: f32
+        zoom_speed: f32,
+        // Seed of the world's random streams
+        seed: u64,
+        // Worker threads for multithreaded systems, 1 runs everything on the main thread
+        threads: u32
     },
 
     // Camera components
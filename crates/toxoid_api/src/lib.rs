@@ -1,6 +1,20 @@
 #![allow(warnings)]
 pub mod components;
 pub use components::*;
+pub mod assets;
+pub use assets::*;
+pub mod render;
+pub use render::*;
+pub mod log;
+pub use log::*;
+pub mod random;
+pub use random::*;
+pub mod query;
+pub use query::*;
+pub mod commands;
+pub use commands::*;
+pub mod builder;
+pub use builder::*;
 
 // Native
 #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
@@ -27,7 +41,12 @@ pub use toxoid_host::{
         GuestPhase,
         GuestPipeline,
         EntityDesc,
+        ComponentValue,
         ComponentDesc,
+        EnumDesc,
+        EnumConstant,
+        CommandError,
+        CommandKind,
         QueryDesc,
         SystemDesc,
         ObserverDesc,
@@ -35,10 +54,20 @@ pub use toxoid_host::{
         MemberType,
         Relationship,
         Event,
+        MonitorKind,
         Guest as WorldGuest,
         EcsEntityT,
         PointerT
     },
+    bindings::exports::toxoid::engine::engine::{
+        Guest as EngineGuest,
+        KeyCode,
+        TimeInfo,
+        WindowInfo,
+        MouseState
+    },
+    worlds::{WorldId, DEFAULT_WORLD},
+    snapshot::{SnapshotError, SNAPSHOT_VERSION},
     ToxoidApi
 };
 // WASM
@@ -56,7 +85,12 @@ pub use toxoid_guest::bindings::{
         Phase as ToxoidPhase,
         Pipeline as ToxoidPipeline,
         EntityDesc,
+        ComponentValue,
         ComponentDesc,
+        EnumDesc,
+        EnumConstant,
+        CommandError,
+        CommandKind,
         QueryDesc,
         SystemDesc,
         ObserverDesc,
@@ -64,10 +98,18 @@ pub use toxoid_guest::bindings::{
         MemberType,
         Relationship,
         Event,
+        MonitorKind,
         EcsEntityT,
         PointerT,
         self as ToxoidApi
     },
+    toxoid_component::component::engine::{
+        self as ToxoidEngine,
+        KeyCode,
+        TimeInfo,
+        WindowInfo,
+        MouseState
+    },
     self,
     exports::toxoid_component::component::callbacks::Guest as CallbacksGuest,
     Guest as WorldGuest,
@@ -75,7 +117,7 @@ pub use toxoid_guest::bindings::{
 #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
 pub use toxoid_guest;
 // Both (Native + WASM)
-pub use toxoid_api_macro::{component, components};
+pub use toxoid_api_macro::{component, component_enum, components};
 
 pub struct ToxoidWasmComponent;
 
@@ -94,12 +136,205 @@ pub trait Component {
     fn set_component_type(&mut self, component_type_id: EcsEntityT);
 }
 
+// Types of component members besides the built-in ones. `component!` implements it so components
+// can be nested in each other and `component_enum!` for enums.
+pub trait NestedMember: Sized {
+    // Flecs type of the member, registered on first use
+    fn member_type_id() -> EcsEntityT;
+    fn get_member(component: &ToxoidComponent, member: u32) -> Self;
+    fn set_member(component: &ToxoidComponent, member: u32, value: Self);
+}
+
+// View of a nested component member, its setters write into the outer component.
+// As a guest the host traps on members that aren't nested components instead.
+pub fn nested_component<T: Component + ComponentType + Default>(component: &ToxoidComponent, member: u32) -> Result<T, String> {
+    let mut nested = T::default();
+    #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+    nested.set_component(component.member_component(member, 0).map_err(|e| e.to_string())?);
+    #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+    nested.set_component(component.get_member_component(member, 0));
+    nested.set_component_type(T::get_id());
+    Ok(nested)
+}
+
+// Stand-in for a nested member that can't be viewed. The error is logged and the getters
+// and setters of the stand-in report the missing component like other member errors.
+pub fn detached_component<T: Component + ComponentType + Default>(error: &str) -> T {
+    Log::error("component", error);
+    let mut detached = T::default();
+    #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+    detached.set_component(ToxoidComponent::from_ptr_host(0, T::get_id()));
+    detached.set_component_type(T::get_id());
+    detached
+}
+
+fn field_view<T: Component + ComponentType + Default>(view: ToxoidComponent) -> T {
+    let mut component = T::default();
+    component.set_component(view);
+    component.set_component_type(T::get_id());
+    component
+}
+
+// Frame time, window and input state of the engine
+pub struct Engine;
+
+impl Engine {
+    pub fn time() -> TimeInfo {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return <ToxoidApi as EngineGuest>::time();
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidEngine::time();
+    }
+
+    pub fn delta_time() -> f32 {
+        Self::time().delta
+    }
+
+    pub fn window() -> WindowInfo {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return <ToxoidApi as EngineGuest>::window();
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidEngine::window();
+    }
+
+    pub fn mouse() -> MouseState {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return <ToxoidApi as EngineGuest>::mouse();
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidEngine::mouse();
+    }
+
+    // Key codes are in `keys`
+    pub fn key_down(key: KeyCode) -> bool {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return <ToxoidApi as EngineGuest>::key_down(key);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidEngine::key_down(key);
+    }
+
+    pub fn keys_down() -> Vec<KeyCode> {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return <ToxoidApi as EngineGuest>::keys_down();
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidEngine::keys_down();
+    }
+}
+
+// Key codes of `Engine::key_down`, these are the Sokol (GLFW) key codes
+pub mod keys {
+    use super::KeyCode;
+
+    pub const SPACE: KeyCode = 32;
+    pub const APOSTROPHE: KeyCode = 39;
+    pub const COMMA: KeyCode = 44;
+    pub const MINUS: KeyCode = 45;
+    pub const PERIOD: KeyCode = 46;
+    pub const SLASH: KeyCode = 47;
+    pub const NUM_0: KeyCode = 48;
+    pub const NUM_1: KeyCode = 49;
+    pub const NUM_2: KeyCode = 50;
+    pub const NUM_3: KeyCode = 51;
+    pub const NUM_4: KeyCode = 52;
+    pub const NUM_5: KeyCode = 53;
+    pub const NUM_6: KeyCode = 54;
+    pub const NUM_7: KeyCode = 55;
+    pub const NUM_8: KeyCode = 56;
+    pub const NUM_9: KeyCode = 57;
+    pub const SEMICOLON: KeyCode = 59;
+    pub const EQUAL: KeyCode = 61;
+    pub const A: KeyCode = 65;
+    pub const B: KeyCode = 66;
+    pub const C: KeyCode = 67;
+    pub const D: KeyCode = 68;
+    pub const E: KeyCode = 69;
+    pub const F: KeyCode = 70;
+    pub const G: KeyCode = 71;
+    pub const H: KeyCode = 72;
+    pub const I: KeyCode = 73;
+    pub const J: KeyCode = 74;
+    pub const K: KeyCode = 75;
+    pub const L: KeyCode = 76;
+    pub const M: KeyCode = 77;
+    pub const N: KeyCode = 78;
+    pub const O: KeyCode = 79;
+    pub const P: KeyCode = 80;
+    pub const Q: KeyCode = 81;
+    pub const R: KeyCode = 82;
+    pub const S: KeyCode = 83;
+    pub const T: KeyCode = 84;
+    pub const U: KeyCode = 85;
+    pub const V: KeyCode = 86;
+    pub const W: KeyCode = 87;
+    pub const X: KeyCode = 88;
+    pub const Y: KeyCode = 89;
+    pub const Z: KeyCode = 90;
+    pub const LEFT_BRACKET: KeyCode = 91;
+    pub const BACKSLASH: KeyCode = 92;
+    pub const RIGHT_BRACKET: KeyCode = 93;
+    pub const GRAVE_ACCENT: KeyCode = 96;
+    pub const ESCAPE: KeyCode = 256;
+    pub const ENTER: KeyCode = 257;
+    pub const TAB: KeyCode = 258;
+    pub const BACKSPACE: KeyCode = 259;
+    pub const INSERT: KeyCode = 260;
+    pub const DELETE: KeyCode = 261;
+    pub const RIGHT: KeyCode = 262;
+    pub const LEFT: KeyCode = 263;
+    pub const DOWN: KeyCode = 264;
+    pub const UP: KeyCode = 265;
+    pub const PAGE_UP: KeyCode = 266;
+    pub const PAGE_DOWN: KeyCode = 267;
+    pub const HOME: KeyCode = 268;
+    pub const END: KeyCode = 269;
+    pub const F1: KeyCode = 290;
+    pub const F2: KeyCode = 291;
+    pub const F3: KeyCode = 292;
+    pub const F4: KeyCode = 293;
+    pub const F5: KeyCode = 294;
+    pub const F6: KeyCode = 295;
+    pub const F7: KeyCode = 296;
+    pub const F8: KeyCode = 297;
+    pub const F9: KeyCode = 298;
+    pub const F10: KeyCode = 299;
+    pub const F11: KeyCode = 300;
+    pub const F12: KeyCode = 301;
+    pub const LEFT_SHIFT: KeyCode = 340;
+    pub const LEFT_CONTROL: KeyCode = 341;
+    pub const LEFT_ALT: KeyCode = 342;
+    pub const LEFT_SUPER: KeyCode = 343;
+    pub const RIGHT_SHIFT: KeyCode = 344;
+    pub const RIGHT_CONTROL: KeyCode = 345;
+    pub const RIGHT_ALT: KeyCode = 346;
+    pub const RIGHT_SUPER: KeyCode = 347;
+}
+
+// Member types that can be copied in bulk with `Iter::column`
+pub trait ColumnValue: Copy {
+    const MEMBER_TYPE: MemberType;
+}
+
+macro_rules! column_value {
+    ($($ty:ty => $member_type:ident),*) => {
+        $(impl ColumnValue for $ty {
+            const MEMBER_TYPE: MemberType = MemberType::$member_type;
+        })*
+    };
+}
+
+column_value!(
+    u8 => U8T, u16 => U16T, u32 => U32T, u64 => U64T,
+    i8 => I8T, i16 => I16T, i32 => I32T, i64 => I64T,
+    f32 => F32T, f64 => F64T, bool => BoolT
+);
+
 pub struct Entity {
     entity: ToxoidEntity
 }
 
-pub struct Query {
-    query: ToxoidQuery
+// `Q` are the terms of a typed query, `()` for a query built from the DSL
+pub struct Query<Q = ()> {
+    query: ToxoidQuery,
+    marker: std::marker::PhantomData<fn() -> Q>
 }
 
 pub struct System {
@@ -128,11 +363,34 @@ pub struct Pipeline {
 
 pub struct World;
 
-pub static mut CALLBACKS: once_cell::sync::Lazy<Vec<Box<dyn Fn(&Iter)>>> = once_cell::sync::Lazy::new(|| Vec::new());
+// Callbacks of native systems and observers by handle, read from the Flecs worker threads.
+// The slot of a deleted system or observer is emptied, which drops its closure.
+pub static CALLBACKS: once_cell::sync::Lazy<std::sync::RwLock<Vec<Option<std::sync::Arc<dyn Fn(&Iter) + Send + Sync>>>>> = once_cell::sync::Lazy::new(|| std::sync::RwLock::new(Vec::new()));
+
+// None once the system or observer is deleted
+pub fn get_callback(handle: u64) -> Option<std::sync::Arc<dyn Fn(&Iter) + Send + Sync>> {
+    CALLBACKS.read().unwrap().get(handle as usize).cloned().flatten()
+}
+
+// Drop a callback along with the state its closure captured. A callback that is running
+// is dropped once it returns. Guests export it for the host, which calls it for systems and
+// observers deleted on its side.
+pub fn free_callback(handle: u64) {
+    let callback = CALLBACKS.write().unwrap().get_mut(handle as usize).and_then(Option::take);
+    drop(callback);
+}
+
+// `toxoid_host::CALLBACK_FREE`, Flecs passes the handle as the callback context
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub unsafe extern "C" fn free_callback_ctx(ctx: *mut std::ffi::c_void) {
+    free_callback(ctx as u64);
+}
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+pub static mut COMPARATORS: once_cell::sync::Lazy<Vec<fn(EcsEntityT, PointerT, EcsEntityT, PointerT) -> i32>> = once_cell::sync::Lazy::new(|| Vec::new());
 
 impl Entity {
     pub fn new(desc: Option<EntityDesc>) -> Self {
-        let desc = desc.unwrap_or(EntityDesc { name: None, add: None, prefab: false });
+        let desc = desc.unwrap_or(EntityDesc { name: None, add: None, prefab: false, values: None });
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
         let entity = ToxoidEntity::new(desc, None);
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
@@ -140,8 +398,12 @@ impl Entity {
         Self { entity }
     }
 
+    pub fn builder() -> EntityBuilder {
+        EntityBuilder::new()
+    }
+
     pub fn named(name: &str) -> Self {
-        let desc = EntityDesc { name: Some(name.to_string()), add: None, prefab: false };
+        let desc = EntityDesc { name: Some(name.to_string()), add: None, prefab: false, values: None };
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
         let entity = ToxoidEntity::new(desc, None);
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
@@ -150,7 +412,7 @@ impl Entity {
     }
 
     pub fn from_prefab(desc: Option<EntityDesc>, prefab: Entity) -> Self {
-        let desc = desc.unwrap_or(EntityDesc { name: None, add: None, prefab: false });
+        let desc = desc.unwrap_or(EntityDesc { name: None, add: None, prefab: false, values: None });
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
         let entity = ToxoidEntity::new(desc, Some(prefab.get_id()));
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
@@ -159,7 +421,7 @@ impl Entity {
     }
 
     pub fn from_prefab_id(desc: Option<EntityDesc>, prefab: EcsEntityT) -> Self {
-        let desc = desc.unwrap_or(EntityDesc { name: None, add: None, prefab: false });
+        let desc = desc.unwrap_or(EntityDesc { name: None, add: None, prefab: false, values: None });
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
         let entity = ToxoidEntity::new(desc, Some(prefab));
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
@@ -168,7 +430,7 @@ impl Entity {
     }
 
     pub fn prefab() -> Self {
-        let desc = EntityDesc { name: None, add: Some(vec![]), prefab: true };
+        let desc = EntityDesc { name: None, add: Some(vec![]), prefab: true, values: None };
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
         let entity = ToxoidEntity::new(desc, None);
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
@@ -177,7 +439,7 @@ impl Entity {
     }
 
     pub fn prefab_named(name: &str) -> Self {
-        let desc = EntityDesc { name: Some(name.to_string()), add: Some(vec![]), prefab: true };
+        let desc = EntityDesc { name: Some(name.to_string()), add: Some(vec![]), prefab: true, values: None };
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
         let entity = ToxoidEntity::new(desc, None);
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
@@ -286,7 +548,11 @@ impl Entity {
 
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
     pub fn parent(&self) -> Entity {
-        Self { entity: ToxoidEntity::from_id(self.entity.parent().get_id()) }
+        Self { entity: self.entity.parent() }
+    }
+
+    pub fn to_json(&self, recursive: bool) -> Result<String, String> {
+        World::entity_to_json(self.get_id(), recursive)
     }
 
     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
@@ -299,8 +565,8 @@ impl Entity {
         return self
             .entity
             .children()
-            .iter()
-            .map(|child| Entity { entity: ToxoidEntity::from_id(child.get_id()) })
+            .into_iter()
+            .map(|child| Entity { entity: child })
             .collect();
     }
 
@@ -319,8 +585,8 @@ impl Entity {
         self
             .entity
             .relationship_entities(relationship)
-            .iter()
-            .map(|relationship| Entity { entity: ToxoidEntity::from_id(relationship.get_id()) })
+            .into_iter()
+            .map(|relationship| Entity { entity: relationship })
             .collect()
     }
 
@@ -340,7 +606,7 @@ impl Query {
         let query = ToxoidQuery::new(desc);
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
         let query = ToxoidQuery::new(&desc);
-        Self { query }
+        Self { query, marker: std::marker::PhantomData }
     }
 
     pub fn dsl(dsl: &str) -> Self {
@@ -360,6 +626,41 @@ impl Query {
         }
         query
     }
+}
+
+impl<Q: QueryData> Query<Q> {
+    pub fn typed() -> Self {
+        let desc = QueryDesc { expr: Q::expr() };
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        let query = ToxoidQuery::new(desc);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        let query = ToxoidQuery::new(&desc);
+        for (index, kind) in Q::monitors() {
+            query.monitor(index, kind);
+        }
+        Self { query, marker: std::marker::PhantomData }
+    }
+
+    // Run `f` for every matched entity with the components of its terms
+    pub fn each(&mut self, mut f: impl FnMut(Entity, Q::Item)) {
+        self.query.iter();
+        while self.next() {
+            let items = Q::fetch(self);
+            for (entity, item) in self.entities().into_iter().zip(items) {
+                if let Some(item) = item {
+                    f(entity, item);
+                }
+            }
+        }
+    }
+}
+
+impl<Q> Query<Q> {
+    // Keep the matched tables in a cache so `changed` and `table_changed` can tell what changed.
+    // Queries aren't cached otherwise, call before `build`.
+    pub fn cache(&mut self) {
+        self.query.cache();
+    }
 
     pub fn build(&mut self) {
         self.query.build();
@@ -373,7 +674,8 @@ impl Query {
 
     pub fn iter(&mut self) -> Iter {
         #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
-        let iter = ToxoidIter::new(self.query.iter());
+        // The host hands out a boxed iterator over the one the query advances
+        let iter = *unsafe { Box::from_raw(self.query.iter() as *mut ToxoidIter) };
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
         let iter = self.query.iter();
         Iter { iter }
@@ -387,22 +689,43 @@ impl Query {
         self.query.count()
     }
 
+    // Whether any table the query matches changed since the last iteration,
+    // so iterating can be skipped altogether
+    pub fn changed(&self) -> bool {
+        self.query.changed()
+    }
+
+    // Whether the current table changed since the last iteration
+    pub fn table_changed(&self) -> bool {
+        self.query.iter_changed()
+    }
+
+    pub fn skip(&self) {
+        self.query.skip();
+    }
+
+    pub fn field_changed(&self, index: i8) -> bool {
+        self.query.field_changed(index)
+    }
+
+    pub fn field_added(&self, index: i8) -> Vec<bool> {
+        self.query.field_added(index)
+    }
+
     pub fn entities(&self) -> Vec<Entity> {
         self
             .query
             .entities()
-            .iter()
+            .into_iter()
             .map(|entity| {
                 #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                 // In native mode, we get a u64 ID directly
                 return Entity {
-                    entity: ToxoidEntity { id: *entity }
+                    entity: ToxoidEntity { id: entity }
                 };
                 #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                 // In WASM mode, we're working with the guest component object
-                return Entity {
-                    entity: ToxoidEntity::from_id(entity.get_id())
-                };
+                return Entity { entity };
             })
             .collect()
     }
@@ -412,18 +735,12 @@ impl Query {
     // }
 
     pub fn components<T: Component + ComponentType + Default + 'static>(&self, index: i8) -> Vec<T> {
-        let field_raw_ptrs = self.query.components(index);
-        let components = field_raw_ptrs
-            .iter()
-            .map(|component_ptr| {
-                let mut component = T::default();
-                let toxoid_component = ToxoidComponent::new(*component_ptr, 0, T::get_id());
-                component.set_component(toxoid_component);
-                component.set_component_type(T::get_id());
-                component
-            })
-            .collect();
-        components
+        self.field_views(index, false)
+    }
+
+    // Setters of read-only views fail like other member errors
+    pub(crate) fn field_views<T: Component + ComponentType + Default + 'static>(&self, index: i8, read_only: bool) -> Vec<T> {
+        self.query.field_views(index, read_only).into_iter().map(field_view).collect()
     }
 }
 
@@ -436,7 +753,7 @@ impl System {
     // }
 
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
-    pub fn new(desc: Option<SystemDesc>, callback_fn: fn(&Iter)) -> Self {
+    pub fn new(desc: Option<SystemDesc>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
         // Register the callback in the guest environment
         let callback = Callback::new(callback_fn);
         // Create the Toxoid callback with the registered callback handle
@@ -453,7 +770,7 @@ impl System {
     }
 
     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
-    pub fn new(desc: Option<SystemDesc>, callback_fn: fn(&Iter)) -> Self {
+    pub fn new(desc: Option<SystemDesc>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
         // Register the callback in the guest environment
         let callback = Callback::new(callback_fn);
         // Create the Toxoid callback with the registered callback handle
@@ -473,10 +790,29 @@ impl System {
         self.system.get_id()
     }
 
+    // The callback can be a closure capturing configuration, e.g. built at runtime from data.
+    // It's dropped when the system is deleted, also when the host deletes it.
+    pub fn dsl(dsl: &str, tick_rate: Option<i32>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
+        Self::dsl_callback(dsl, tick_rate, Callback::new(callback_fn))
+    }
+
+    // System over the terms of `Q`, the callback gets them with `iter.fetch::<Q>()`
+    pub fn typed<Q: QueryData>(tick_rate: Option<i32>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
+        let system = Self::dsl(&Q::expr(), tick_rate, callback_fn);
+        for (index, kind) in Q::monitors() {
+            system.system.monitor(index, kind);
+        }
+        system
+    }
+
+    // A system whose callback mutates the state it captured. The closure runs on one thread at
+    // a time, in multithreaded systems use `dsl` with shared state instead.
+    pub fn dsl_closure(dsl: &str, tick_rate: Option<i32>, callback: impl FnMut(&Iter) + Send + 'static) -> Self {
+        Self::dsl_callback(dsl, tick_rate, Callback::from_closure(callback))
+    }
+
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
-    pub fn dsl(dsl: &str, tick_rate: Option<i32>, callback_fn: fn(&Iter)) -> Self {
-        // Register the callback in the guest environment
-        let callback = Callback::new(callback_fn);
+    fn dsl_callback(dsl: &str, tick_rate: Option<i32>, callback: Callback) -> Self {
         // Create the Toxoid callback with the registered callback handle
         let callback = ToxoidCallback::new(callback.cb_handle());
         let desc = SystemDesc { 
@@ -490,9 +826,7 @@ impl System {
     }
 
     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
-    pub fn dsl(dsl: &str, tick_rate: Option<i32>, callback_fn: fn(&Iter)) -> Self {
-        // Register the callback in the guest environment
-        let callback = Callback::new(callback_fn);
+    fn dsl_callback(dsl: &str, tick_rate: Option<i32>, callback: Callback) -> Self {
         let desc = SystemDesc { 
             name: Some("".to_string()), 
             callback: callback.cb_handle(), 
@@ -516,8 +850,7 @@ impl System {
         self
     }
 
-    // TODO: Use query trampoline instead of C functions directlyand use callback resource to make this work on WASM.
-    #[cfg(not(target_os = "emscripten"))]
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn order_by(mut self, id: EcsEntityT, callback: unsafe extern "C" fn(u64, *const std::ffi::c_void, u64, *const std::ffi::c_void) -> i32) -> Self {
         let sorting = SortingDesc { id, callback: unsafe { std::mem::transmute(callback) } };
         self.system.order_by(sorting);
@@ -532,6 +865,26 @@ impl System {
         self
     }
 
+    // The comparator gets the entities and pointers to their component `id`, which can be read with `ToxoidComponent::from_ptr(ptr, id)`
+    #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+    pub fn order_by(mut self, id: EcsEntityT, callback: fn(EcsEntityT, PointerT, EcsEntityT, PointerT) -> i32) -> Self {
+        // Register the comparator in the guest environment, the host calls it back through its handle
+        let handle = unsafe { COMPARATORS.push(callback); COMPARATORS.len() - 1 };
+        let sorting = SortingDesc { id, callback: ToxoidCallback::new(handle as u64) };
+        self.system.order_by(sorting);
+        self
+    }
+
+    // Spread the matched entities over the worker threads, see `World::set_threads`.
+    // The callback runs on several threads at once and must only touch the entities it is given.
+    // The threads share the system's random stream and draw from it in whatever order they get
+    // to it, so `Iter::random` isn't reproducible from the seed in these systems.
+    #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+    pub fn multi_threaded(mut self) -> Self {
+        self.system.multi_threaded(true);
+        self
+    }
+
     pub fn disable(&mut self) {
         self.system.disable();
     }
@@ -539,12 +892,22 @@ impl System {
     pub fn enable(&mut self) {
         self.system.enable();
     }
+
+    // Delete the system, its callback is dropped
+    pub fn delete(self) {
+        World::remove_entity(self.get_id());
+        // Natively Flecs hands the callback to `free_callback_ctx` once the system is gone,
+        // which can be deferred to the end of the frame. The host frees those of guests between
+        // frames, this drops it right away.
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        free_callback(self.system.callback().cb_handle());
+    }
 }
 
 impl Observer {
     // Not wasm
     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
-    pub fn new(desc: Option<ObserverDesc>, callback_fn: fn(&Iter)) -> Self {
+    pub fn new(desc: Option<ObserverDesc>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
         let callback = Callback::new(callback_fn);
         let desc = desc.unwrap_or(ObserverDesc { 
             name: None, 
@@ -558,7 +921,7 @@ impl Observer {
 
     // WASM
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
-    pub fn new(desc: Option<ObserverDesc>, callback_fn: fn(&Iter)) -> Self {
+    pub fn new(desc: Option<ObserverDesc>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
         // Register the callback in the guest environment
         let callback = Callback::new(callback_fn);
         // Create the Toxoid callback with the registered callback handle
@@ -575,11 +938,25 @@ impl Observer {
         Self { observer: ToxoidObserver::new(desc) }
     }
 
+    // The callback can be a closure, it's dropped when the observer is deleted
+    pub fn dsl(dsl: &str, events: Vec<Event>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
+        Self::dsl_callback(dsl, events, Callback::new(callback_fn))
+    }
+
+    pub fn typed<Q: QueryData>(events: Vec<Event>, callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
+        Self::dsl(&Q::expr(), events, callback_fn)
+    }
+
+    // An observer whose callback mutates the state it captured. The closure can't be entered
+    // again while it runs, an event for its own observer emitted right away by it is logged and skipped.
+    pub fn dsl_closure(dsl: &str, events: Vec<Event>, callback: impl FnMut(&Iter) + Send + 'static) -> Self {
+        Self::dsl_callback(dsl, events, Callback::from_closure(callback))
+    }
+
     // Not wasm
     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
-    pub fn dsl(dsl: &str, events: Vec<Event>, callback_fn: fn(&Iter)) -> Self {
+    fn dsl_callback(dsl: &str, events: Vec<Event>, callback: Callback) -> Self {
         // TODO: Make this work in WASM without using toxoid_host::get_event
-        let callback = Callback::new(callback_fn);
         let desc = ObserverDesc { 
             name: None, 
             query_desc: QueryDesc { expr: dsl.to_string() }, 
@@ -592,9 +969,7 @@ impl Observer {
 
     // WASM
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
-    pub fn dsl(dsl: &str, events: Vec<Event>, callback_fn: fn(&Iter)) -> Self {
-        // Register the callback in the guest environment
-        let callback = Callback::new(callback_fn);
+    fn dsl_callback(dsl: &str, events: Vec<Event>, callback: Callback) -> Self {
         // Create the Toxoid callback with the registered callback handle
         let callback = ToxoidCallback::new(callback.cb_handle());
         let desc = ObserverDesc { 
@@ -611,6 +986,17 @@ impl Observer {
         self.observer.build();
     }
 
+    pub fn get_id(&self) -> EcsEntityT {
+        self.observer.get_id()
+    }
+
+    // Delete the observer, its callback is dropped
+    pub fn delete(self) {
+        World::remove_entity(self.get_id());
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        free_callback(self.observer.callback().cb_handle());
+    }
+
     // Not wasm
     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]      
     pub fn callback(&self) -> u64 {
@@ -625,14 +1011,47 @@ impl Observer {
 }
 
 impl Callback {
-    pub fn new(callback_fn: fn(&Iter)) -> Self {
-        let handle = unsafe { CALLBACKS.push(Box::new(callback_fn)); CALLBACKS.len() - 1 };
+    pub fn new(callback_fn: impl Fn(&Iter) + Send + Sync + 'static) -> Self {
+        Self::register(std::sync::Arc::new(callback_fn))
+    }
+
+    // A closure is run by one thread at a time, also in a multithreaded system.
+    // Entering it again from the thread running it, e.g. an observer triggering its own event, is skipped.
+    pub fn from_closure(callback: impl FnMut(&Iter) + Send + 'static) -> Self {
+        let callback: std::sync::Mutex<Box<dyn FnMut(&Iter) + Send>> = std::sync::Mutex::new(Box::new(callback));
+        let running_on: std::sync::Mutex<Option<std::thread::ThreadId>> = std::sync::Mutex::new(None);
+        Self::register(std::sync::Arc::new(move |iter: &Iter| {
+            let mut callback = match callback.try_lock() {
+                Ok(callback) => callback,
+                Err(std::sync::TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+                Err(std::sync::TryLockError::WouldBlock) => {
+                    if *running_on.lock().unwrap() == Some(std::thread::current().id()) {
+                        Log::error("callback", "Closure entered again while it runs, the call is skipped");
+                        return;
+                    }
+                    // Another thread runs it
+                    callback.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+                }
+            };
+            *running_on.lock().unwrap() = Some(std::thread::current().id());
+            callback(iter);
+            *running_on.lock().unwrap() = None;
+        }))
+    }
+
+    fn register(callback: std::sync::Arc<dyn Fn(&Iter) + Send + Sync>) -> Self {
+        let handle = {
+            let mut callbacks = CALLBACKS.write().unwrap();
+            callbacks.push(Some(callback));
+            callbacks.len() - 1
+        };
         Self { callback: ToxoidCallback::new(handle as u64) }   
     }
 
     pub fn run(&self, iter: &Iter) {
-        let callback = unsafe { CALLBACKS[self.callback.cb_handle() as usize].as_ref() };
-        callback(iter);
+        if let Some(callback) = get_callback(self.callback.cb_handle()) {
+            callback(iter);
+        }
     }
 
     pub fn cb_handle(&self) -> u64 {
@@ -657,47 +1076,121 @@ impl Iter {
             .count()
     }
 
+    // System or observer the iterator belongs to, 0 for queries
+    pub fn system(&self) -> EcsEntityT {
+        self
+            .iter
+            .system()
+    }
+
+    // Random stream of the running system. Deterministic for a seed, except in systems built
+    // with `System::multi_threaded` while `World::set_threads` is above 1.
+    pub fn random(&self) -> RandomStream {
+        Random::system(self.system())
+    }
+
     pub fn entities(&self) -> Vec<Entity> {
         self.iter
             .entities()
-            .iter()
+            .into_iter()
             .map(|entity| {
                 #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                 // In native mode, we get a u64 ID directly
                 return Entity {
-                    entity: ToxoidEntity { id: *entity }
+                    entity: ToxoidEntity { id: entity }
                 };
                 #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                 // In WASM mode, we're working with the guest component object
-                return Entity {
-                    entity: ToxoidEntity::from_id(entity.get_id())
-                };
+                return Entity { entity };
             })
             .collect()
     }
 
+    // Writes through the components mark them as modified on their entities
     pub fn components<T: Component + ComponentType + Default + 'static>(&self, index: i8) -> Vec<T> {
-        let field_raw_ptrs = self.iter.components(index);
-        let components = field_raw_ptrs
-            .iter()
-            .map(|component_ptr| {
-                let mut component = T::default();
-                #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))] {
-                    let toxoid_component = ToxoidComponent::from_ptr_host(*component_ptr);
-                    component.set_component(toxoid_component);
-                    component.set_component_type(T::get_id());
-                    component
-                }
-                #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))] {
-                    let toxoid_component = ToxoidComponent::from_ptr(*component_ptr);
-                    component.set_component(toxoid_component);
-                    component.set_component_type(T::get_id());
-                    component
-                }
-            })
-            .collect();
-        components
+        self.field_views(index, false)
+    }
+
+    // Setters of read-only views fail like other member errors
+    pub(crate) fn field_views<T: Component + ComponentType + Default + 'static>(&self, index: i8, read_only: bool) -> Vec<T> {
+        self.iter.field_views(index, read_only).into_iter().map(field_view).collect()
+    }
+
+    // Entities of the typed query `Q` the system was built from with the components of its terms,
+    // without the ones a `Changed` or `Added` term filters out
+    pub fn fetch<Q: QueryData>(&self) -> Vec<(Entity, Q::Item)> {
+        self.entities()
+            .into_iter()
+            .zip(Q::fetch(self))
+            .filter_map(|(entity, item)| Some((entity, item?)))
+            .collect()
+    }
+
+    // Whether a component the system reads changed in the current table since it last ran
+    pub fn changed(&self) -> bool {
+        self.iter.changed()
+    }
+
+    // Leave the current table marked as changed for the next run
+    pub fn skip(&self) {
+        self.iter.skip();
+    }
+
+    pub fn field_changed(&self, index: i8) -> bool {
+        self.iter.field_changed(index)
+    }
+
+    pub fn field_added(&self, index: i8) -> Vec<bool> {
+        self.iter.field_added(index)
+    }
+
+    // Copy one member of every component in field `index` with a single host call,
+    // `member` is the index of the member in the component and `T` its type
+    pub fn column<T: ColumnValue>(&self, index: i8, member: u32) -> Vec<T> {
+        let data = self.iter.column(index, member, T::MEMBER_TYPE);
+        bytes_to_values(&data)
+    }
+
+    // Write a column back, the components are marked as modified
+    pub fn set_column<T: ColumnValue>(&self, index: i8, member: u32, values: &[T]) {
+        let data = values_to_bytes(values);
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        self.iter.set_column(index, member, T::MEMBER_TYPE, data.to_vec());
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        self.iter.set_column(index, member, T::MEMBER_TYPE, data);
+    }
+
+    // Copy the whole component array of field `index` with a single host call.
+    // Safety: `T` must be a `#[repr(C)]` struct with the same members in the same order as the component,
+    // only components whose members are all numbers or bools can be copied this way
+    pub unsafe fn field_data<T: Copy>(&self, index: i8) -> Vec<T> {
+        let data = self.iter.field_data(index);
+        bytes_to_values(&data)
     }
+
+    // Write a component array back, the components are marked as modified.
+    // Safety: same as `field_data`
+    pub unsafe fn set_field_data<T: Copy>(&self, index: i8, values: &[T]) {
+        let data = values_to_bytes(values);
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        self.iter.set_field_data(index, data.to_vec());
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        self.iter.set_field_data(index, data);
+    }
+}
+
+fn bytes_to_values<T: Copy>(data: &[u8]) -> Vec<T> {
+    let size = std::mem::size_of::<T>();
+    assert_eq!(data.len() % size, 0, "Column data doesn't match the size of {}", std::any::type_name::<T>());
+    data
+        .chunks_exact(size)
+        // The bytes of a list aren't aligned for T
+        .map(|value| unsafe { std::ptr::read_unaligned(value.as_ptr() as *const T) })
+        .collect()
+}
+
+fn values_to_bytes<T: Copy>(values: &[T]) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, std::mem::size_of_val(values)) }
 }
 
 impl World {
@@ -743,34 +1236,153 @@ impl World {
         #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
         return ToxoidApi::has_entity_named(name.as_str());
     }
+
+    // An entity as JSON with its tags, components and relationships, see `toxoid_host::json`
+    // for the format. Children are included when recursive, entities that aren't alive are an error.
+    pub fn entity_to_json(entity_id: u64, recursive: bool) -> Result<String, String> {
+        ToxoidApi::entity_to_json(entity_id, recursive)
+    }
+
+    // Create an entity and its children from JSON, e.g. from a Tiled object property.
+    // Errors name the unknown component, member or value.
+    pub fn entity_from_json(json: &str, parent: Option<u64>) -> Result<Entity, String> {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        let entity_id = ToxoidApi::entity_from_json(json.to_string(), parent)?;
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        let entity_id = ToxoidApi::entity_from_json(json, parent)?;
+        Ok(Self::get_entity(entity_id))
+    }
+}
+
+// Multiple worlds, on the host. The calls above go to the active world, guests run in
+// the world set in their manifest.
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+impl World {
+    // Create a world with the engine components registered
+    pub fn create() -> WorldId {
+        let world = toxoid_host::worlds::create_world();
+        toxoid_host::worlds::with_world(world, components::init);
+        world
+    }
+
+    pub fn destroy(world: WorldId) -> bool {
+        toxoid_host::worlds::destroy_world(world)
+    }
+
+    pub fn exists(world: WorldId) -> bool {
+        toxoid_host::worlds::world_exists(world)
+    }
+
+    pub fn active() -> WorldId {
+        toxoid_host::worlds::active_world()
+    }
+
+    pub fn set_active(world: WorldId) -> bool {
+        toxoid_host::worlds::set_active_world(world)
+    }
+
+    // Run with a world active, for setting up its entities, systems and singletons
+    pub fn with<R>(world: WorldId, f: impl FnOnce() -> R) -> R {
+        toxoid_host::worlds::with_world(world, f)
+    }
+
+    // Worker threads for systems built with `System::multi_threaded`, 1 runs everything on the main thread
+    pub fn set_threads(threads: u32) {
+        toxoid_host::toxoid_set_threads(threads);
+    }
+
+    // Run the systems of a world, the default world is progressed by the engine every frame
+    pub fn progress(world: WorldId, delta_time: f32) -> bool {
+        toxoid_host::worlds::progress_world(world, delta_time)
+    }
+
+    // Serialize the entities of the active world with their names, hierarchies,
+    // relationships and component values
+    pub fn snapshot() -> Vec<u8> {
+        toxoid_host::snapshot::snapshot()
+    }
+
+    // Replace the entities of the active world with a snapshot. Components, systems and
+    // the other entities registered by code have to exist already and are left alone.
+    // Fails inside systems and observers, restore between frames.
+    pub fn restore(bytes: &[u8]) -> Result<(), SnapshotError> {
+        toxoid_host::snapshot::restore(bytes)
+    }
+}
+
+// Flecs explorer, built with the `rest` feature
+#[cfg(all(feature = "rest", any(not(target_arch = "wasm32"), target_os = "emscripten")))]
+pub use toxoid_host::rest::{REST_DEFAULT_ADDRESS, REST_DEFAULT_PORT};
+
+#[cfg(all(feature = "rest", any(not(target_arch = "wasm32"), target_os = "emscripten")))]
+impl World {
+    // Serve the active world over HTTP for the explorer at https://www.flecs.dev/explorer,
+    // on the loopback address unless another one is given
+    pub fn enable_rest(port: u16, bind_address: Option<&str>) {
+        toxoid_host::rest::enable_rest(port, bind_address);
+    }
+
+    pub fn disable_rest() {
+        toxoid_host::rest::disable_rest();
+    }
 }
 
 pub fn run_callback(iter: ToxoidIter, handle: u64) {
     let iter = Iter::new(iter);
-    let callback = unsafe { CALLBACKS[handle as usize].as_ref() };
-    callback(&iter);
+    // The system or observer was deleted earlier in the frame
+    if let Some(callback) = get_callback(handle) {
+        callback(&iter);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+pub fn run_compare(handle: u64, e1: EcsEntityT, c1: PointerT, e2: EcsEntityT, c2: PointerT) -> i32 {
+    let compare = unsafe { COMPARATORS[handle as usize] };
+    compare(e1, c1, e2, c2)
 }
 
+// Member type ids and counts are per member and may be left empty, see `ComponentDesc`
 #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
-pub fn register_component(component_name: &str, member_names: Vec<String>, member_types: Vec<u8>) -> EcsEntityT {
+pub fn register_component(component_name: &str, member_names: Vec<String>, member_types: Vec<u8>, member_type_ids: Vec<EcsEntityT>, member_counts: Vec<u32>) -> EcsEntityT {
     let component_type = ToxoidComponentType::new(&ComponentDesc {
         name: component_name.to_string(),
         member_names,
         member_types,
+        member_type_ids,
+        member_counts,
     });
     component_type.get_id()
 }
 
 #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
-pub fn register_component(component_name: &str, member_names: Vec<String>, member_types: Vec<u8>) -> EcsEntityT {
+pub fn register_component(component_name: &str, member_names: Vec<String>, member_types: Vec<u8>, member_type_ids: Vec<EcsEntityT>, member_counts: Vec<u32>) -> EcsEntityT {
     let component_type = ToxoidComponentType::new(ComponentDesc {
         name: component_name.to_string(),
         member_names,
         member_types,
+        member_type_ids,
+        member_counts,
     });
     component_type.get_id()
 }
 
+// Register an enum or bitmask that component members can have as type, returns the existing
+// one if the name is taken
+pub fn register_enum(enum_name: &str, constants: &[(&str, u32)], bitmask: bool) -> EcsEntityT {
+    let desc = EnumDesc {
+        name: enum_name.to_string(),
+        constants: constants
+            .iter()
+            .map(|(name, value)| EnumConstant { name: name.to_string(), value: *value })
+            .collect(),
+        bitmask
+    };
+    #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+    return ToxoidApi::register_enum(desc);
+    #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+    return ToxoidApi::register_enum(&desc);
+}
+
 pub fn get_component_id(component_name: &str) -> EcsEntityT {
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
     return ToxoidApi::get_component_id(component_name);
@@ -807,7 +1419,7 @@ pub enum ZDepth {
 }
 
 // Fetch assets / resources from the asset server or local file system
-pub fn fetch(path: &str, data_type: DataType, user_data: Option<u64>) {
+pub fn fetch(path: &str, data_type: DataType, user_data: Option<u64>) -> Entity {
     let mut entity = Entity::new(None);
     entity.add::<FetchRequest>();
     let mut fetch_request = entity.get::<FetchRequest>();
@@ -819,6 +1431,7 @@ pub fn fetch(path: &str, data_type: DataType, user_data: Option<u64>) {
     entity.add::<Loading>();
     // println!("Fetch request data type: {}", fetch_request.get_data_type());
     // println!("Entity ID origin: {}", entity.get_id());
+    entity
 }
 
 pub fn load_image(path: &str) -> Entity {
@@ -0,0 +1,62 @@
+// Native
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub use toxoid_host::log::{LogLevel, LogRecord, LogSink, TerminalSink, FileSink, ConsoleSink, set_log_sink, add_log_sink, set_log_level};
+// WASM
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+pub use toxoid_guest::bindings::toxoid_component::component::log::{
+    self as ToxoidLog,
+    Level as LogLevel,
+    Field as LogField
+};
+
+// Structured logging, as a guest the host tags records with the guest and the running system
+pub struct Log;
+
+impl Log {
+    pub fn enabled(level: LogLevel) -> bool {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return toxoid_host::log::log_enabled(level);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidLog::enabled(level);
+    }
+
+    pub fn log(level: LogLevel, target: &str, message: &str, fields: &[(&str, &str)]) {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        {
+            let mut record = LogRecord::new(level, target, message);
+            record.fields = fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            toxoid_host::log::log(record);
+        }
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| LogField { key: key.to_string(), value: value.to_string() })
+                .collect::<Vec<_>>();
+            ToxoidLog::log(level, target, message, &fields);
+        }
+    }
+
+    pub fn trace(target: &str, message: &str) {
+        Self::log(LogLevel::Trace, target, message, &[]);
+    }
+
+    pub fn debug(target: &str, message: &str) {
+        Self::log(LogLevel::Debug, target, message, &[]);
+    }
+
+    pub fn info(target: &str, message: &str) {
+        Self::log(LogLevel::Info, target, message, &[]);
+    }
+
+    pub fn warn(target: &str, message: &str) {
+        Self::log(LogLevel::Warn, target, message, &[]);
+    }
+
+    pub fn error(target: &str, message: &str) {
+        Self::log(LogLevel::Error, target, message, &[]);
+    }
+}
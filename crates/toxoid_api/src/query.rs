@@ -0,0 +1,367 @@
+use crate::*;
+use std::marker::PhantomData;
+
+// Typed queries. The Flecs expression and the field of every term come from a tuple of terms,
+// so a misspelled or misplaced component is a compile error instead of a failing query:
+//
+//     query_var!(Related);
+//     type Sprites = (&'static Sprite, &'static mut Size, With<Blittable>, Not<Renderable>,
+//         Option<&'static Position>, Pair<RenderTargetRelationship, Var<Related>>,
+//         Src<&'static RenderTarget, Related>);
+//
+// `System::typed::<Sprites>` and `Observer::typed::<Sprites>` build from it and `iter.fetch::<Sprites>()`
+// returns every entity with a tuple of its components. Terms without data give `()`. Components of `&T`
+// terms are read-only, their setters fail, and writes through `&mut T` ones mark them as modified.
+//
+// `Changed<T>` skips tables whose `T` wasn't written since the system or query last moved on from
+// them, like Flecs change detection it works per table. `Added<T>` only gives the entities that got
+// `T` since then. Typed systems and queries track both, in observers they let everything through.
+
+// One term of a typed query, it takes up one field
+pub trait QueryTerm {
+    type Item;
+    // Term in the Flecs query DSL, `source` is the query variable the term is matched on
+    fn expr(source: Option<&str>) -> String;
+    fn fetch<F: QueryFields>(fields: &F, index: i8) -> Vec<Self::Item>;
+    // Rows of the current table that pass the term's filter, `None` when all of them do
+    fn filter<F: QueryFields>(_fields: &F, _index: i8) -> Option<Vec<bool>> {
+        None
+    }
+    // Change detection the term needs from the host
+    fn monitor() -> Option<MonitorKind> {
+        None
+    }
+}
+
+// Tuple of terms of a typed query
+pub trait QueryData {
+    type Item;
+    fn expr() -> String;
+    // Items of every row of the current table, `None` for the rows filtered out
+    fn fetch<F: QueryFields>(fields: &F) -> Vec<Option<Self::Item>>;
+    // Fields to monitor, registered before the system or query is built
+    fn monitors() -> Vec<(i8, MonitorKind)>;
+}
+
+// Iterators typed queries read their fields from, the table of a system or a query's current table
+pub trait QueryFields {
+    fn count(&self) -> i32;
+    // Views of a field, read-only for terms that only read it
+    fn components<T: Component + ComponentType + Default + 'static>(&self, index: i8, read_only: bool) -> Vec<T>;
+    fn field_changed(&self, index: i8) -> bool;
+    fn field_added(&self, index: i8) -> Vec<bool>;
+}
+
+impl QueryFields for Iter {
+    fn count(&self) -> i32 {
+        Iter::count(self)
+    }
+
+    fn components<T: Component + ComponentType + Default + 'static>(&self, index: i8, read_only: bool) -> Vec<T> {
+        Iter::field_views(self, index, read_only)
+    }
+
+    fn field_changed(&self, index: i8) -> bool {
+        Iter::field_changed(self, index)
+    }
+
+    fn field_added(&self, index: i8) -> Vec<bool> {
+        Iter::field_added(self, index)
+    }
+}
+
+impl<Q> QueryFields for Query<Q> {
+    fn count(&self) -> i32 {
+        Query::count(self)
+    }
+
+    fn components<T: Component + ComponentType + Default + 'static>(&self, index: i8, read_only: bool) -> Vec<T> {
+        Query::field_views(self, index, read_only)
+    }
+
+    fn field_changed(&self, index: i8) -> bool {
+        Query::field_changed(self, index)
+    }
+
+    fn field_added(&self, index: i8) -> Vec<bool> {
+        Query::field_added(self, index)
+    }
+}
+
+// Variable of a query, declared with `query_var!`
+pub trait QueryVar {
+    const NAME: &'static str;
+}
+
+#[macro_export]
+macro_rules! query_var {
+    ($($vis:vis $name:ident),* $(,)?) => {
+        $(
+            $vis struct $name;
+            impl $crate::QueryVar for $name {
+                const NAME: &'static str = stringify!($name);
+            }
+        )*
+    };
+}
+
+// Entity has the component or tag, without reading it
+pub struct With<T>(PhantomData<T>);
+// Entity doesn't have the component or tag
+pub struct Not<T>(PhantomData<T>);
+// Component was written since the table was last seen, by `ecs_modified`, a setter on an entity's
+// component or a system that doesn't only read it. A system writing `T` itself sees its own changes.
+// New entities aren't changes, they show up in `Added`.
+pub struct Changed<T>(PhantomData<T>);
+// Entities got the component since they were last seen, when they were created with it or it was
+// added later. Entities moving to another table for other components aren't added.
+pub struct Added<T>(PhantomData<T>);
+// Relationship pair, the target is a component, `Var` or `Wildcard`
+pub struct Pair<R, T>(PhantomData<(R, T)>);
+// Term matched on the entity in variable `V` instead of the iterated entity
+pub struct Src<T, V>(PhantomData<(T, V)>);
+// Query variable as a pair target
+pub struct Var<V>(PhantomData<V>);
+// Any pair target
+pub struct Wildcard;
+
+pub trait PairTarget {
+    fn target() -> String;
+}
+
+impl<T: ComponentType> PairTarget for T {
+    fn target() -> String {
+        T::get_name().to_string()
+    }
+}
+
+impl<V: QueryVar> PairTarget for Var<V> {
+    fn target() -> String {
+        format!("${}", V::NAME)
+    }
+}
+
+impl PairTarget for Wildcard {
+    fn target() -> String {
+        "*".to_string()
+    }
+}
+
+fn term(name: &str, source: Option<&str>) -> String {
+    match source {
+        Some(source) => format!("{}(${})", name, source),
+        None => name.to_string()
+    }
+}
+
+fn empty_items<F: QueryFields>(fields: &F) -> Vec<()> {
+    vec![(); fields.count() as usize]
+}
+
+// Optional fields that aren't set in the current table have no components
+fn optional_items<T, F: QueryFields>(fields: &F, components: Vec<T>) -> Vec<Option<T>> {
+    if components.is_empty() {
+        (0..fields.count()).map(|_| None).collect()
+    } else {
+        components.into_iter().map(Some).collect()
+    }
+}
+
+impl<T: Component + ComponentType + Default + 'static> QueryTerm for &T {
+    type Item = T;
+
+    fn expr(source: Option<&str>) -> String {
+        format!("[in] {}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, index: i8) -> Vec<T> {
+        fields.components::<T>(index, true)
+    }
+}
+
+impl<T: Component + ComponentType + Default + 'static> QueryTerm for &mut T {
+    type Item = T;
+
+    fn expr(source: Option<&str>) -> String {
+        term(T::get_name(), source)
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, index: i8) -> Vec<T> {
+        fields.components::<T>(index, false)
+    }
+}
+
+impl<T: Component + ComponentType + Default + 'static> QueryTerm for Option<&T> {
+    type Item = Option<T>;
+
+    fn expr(source: Option<&str>) -> String {
+        format!("[in] ?{}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, index: i8) -> Vec<Option<T>> {
+        optional_items(fields, fields.components::<T>(index, true))
+    }
+}
+
+impl<T: Component + ComponentType + Default + 'static> QueryTerm for Option<&mut T> {
+    type Item = Option<T>;
+
+    fn expr(source: Option<&str>) -> String {
+        format!("?{}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, index: i8) -> Vec<Option<T>> {
+        optional_items(fields, fields.components::<T>(index, false))
+    }
+}
+
+impl<T: ComponentType> QueryTerm for With<T> {
+    type Item = ();
+
+    fn expr(source: Option<&str>) -> String {
+        format!("[none] {}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, _index: i8) -> Vec<()> {
+        empty_items(fields)
+    }
+}
+
+impl<T: ComponentType> QueryTerm for Not<T> {
+    type Item = ();
+
+    fn expr(source: Option<&str>) -> String {
+        format!("!{}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, _index: i8) -> Vec<()> {
+        empty_items(fields)
+    }
+}
+
+impl<T: ComponentType> QueryTerm for Changed<T> {
+    type Item = ();
+
+    // Only fields that are read are monitored by Flecs
+    fn expr(source: Option<&str>) -> String {
+        format!("[in] {}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, _index: i8) -> Vec<()> {
+        empty_items(fields)
+    }
+
+    fn filter<F: QueryFields>(fields: &F, index: i8) -> Option<Vec<bool>> {
+        if fields.field_changed(index) {
+            None
+        } else {
+            Some(vec![false; fields.count() as usize])
+        }
+    }
+
+    fn monitor() -> Option<MonitorKind> {
+        Some(MonitorKind::Changed)
+    }
+}
+
+impl<T: ComponentType> QueryTerm for Added<T> {
+    type Item = ();
+
+    fn expr(source: Option<&str>) -> String {
+        format!("[none] {}", term(T::get_name(), source))
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, _index: i8) -> Vec<()> {
+        empty_items(fields)
+    }
+
+    fn filter<F: QueryFields>(fields: &F, index: i8) -> Option<Vec<bool>> {
+        Some(fields.field_added(index))
+    }
+
+    fn monitor() -> Option<MonitorKind> {
+        Some(MonitorKind::Added)
+    }
+}
+
+impl<R: ComponentType, T: PairTarget> QueryTerm for Pair<R, T> {
+    type Item = ();
+
+    fn expr(source: Option<&str>) -> String {
+        match source {
+            Some(source) => format!("{}(${}, {})", R::get_name(), source, T::target()),
+            None => format!("({}, {})", R::get_name(), T::target())
+        }
+    }
+
+    fn fetch<F: QueryFields>(fields: &F, _index: i8) -> Vec<()> {
+        empty_items(fields)
+    }
+}
+
+impl<T: QueryTerm, V: QueryVar> QueryTerm for Src<T, V> {
+    type Item = T::Item;
+
+    fn expr(_source: Option<&str>) -> String {
+        T::expr(Some(V::NAME))
+    }
+
+    // A component of another entity is the same for every entity of the table
+    fn fetch<F: QueryFields>(fields: &F, index: i8) -> Vec<T::Item> {
+        T::fetch(fields, index)
+    }
+
+    fn filter<F: QueryFields>(fields: &F, index: i8) -> Option<Vec<bool>> {
+        T::filter(fields, index)
+    }
+
+    fn monitor() -> Option<MonitorKind> {
+        T::monitor()
+    }
+}
+
+macro_rules! query_data {
+    ($($term:ident $index:tt),*) => {
+        impl<$($term: QueryTerm),*> QueryData for ($($term,)*) {
+            type Item = ($($term::Item,)*);
+
+            fn expr() -> String {
+                [$($term::expr(None)),*].join(", ")
+            }
+
+            fn fetch<S: QueryFields>(fields: &S) -> Vec<Option<Self::Item>> {
+                let mut rows = vec![true; fields.count() as usize];
+                for filter in [$($term::filter(fields, $index)),*].into_iter().flatten() {
+                    rows.iter_mut().zip(filter).for_each(|(row, passes)| *row &= passes);
+                }
+                if !rows.contains(&true) {
+                    return rows.into_iter().map(|_| None).collect();
+                }
+                let mut columns = ($($term::fetch(fields, $index).into_iter(),)*);
+                rows.into_iter()
+                    .map(|row| {
+                        let item = ($(columns.$index.next().unwrap(),)*);
+                        row.then_some(item)
+                    })
+                    .collect()
+            }
+
+            fn monitors() -> Vec<(i8, MonitorKind)> {
+                [$($term::monitor().map(|kind| ($index as i8, kind))),*].into_iter().flatten().collect()
+            }
+        }
+    };
+}
+
+query_data!(A 0);
+query_data!(A 0, B 1);
+query_data!(A 0, B 1, C 2);
+query_data!(A 0, B 1, C 2, D 3);
+query_data!(A 0, B 1, C 2, D 3, E 4);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+query_data!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
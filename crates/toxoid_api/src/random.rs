@@ -0,0 +1,118 @@
+use crate::*;
+
+// Native
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub type StreamId = u64;
+// WASM
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+pub use toxoid_guest::bindings::toxoid_component::component::random::{
+    self as ToxoidRandom,
+    StreamId
+};
+
+// Deterministic random numbers, seeded from `GameConfig`.
+// Numbers come from streams that are independent of each other, so a replay with the same seed
+// gets the same results in every system regardless of what the other systems draw.
+pub struct Random;
+
+impl Random {
+    pub fn seed() -> u64 {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return toxoid_host::random::seed();
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidRandom::seed();
+    }
+
+    // Reseed every stream, the engine does this with the `GameConfig` seed on startup
+    #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+    pub fn set_seed(seed: u64) {
+        toxoid_host::random::set_seed(seed);
+    }
+
+    // Stream for code that isn't tied to a system
+    pub fn world() -> RandomStream {
+        RandomStream { id: 0 }
+    }
+
+    pub fn stream(name: &str) -> RandomStream {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return RandomStream { id: toxoid_host::random::named_stream(name) };
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return RandomStream { id: ToxoidRandom::named_stream(name) };
+    }
+
+    // Stream of a system or observer, see `Iter::random`
+    pub fn system(system: EcsEntityT) -> RandomStream {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return RandomStream { id: toxoid_host::random::system_stream(system) };
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return RandomStream { id: ToxoidRandom::system_stream(system) };
+    }
+}
+
+// The conversions from `next_u64` are done here rather than on the host,
+// so they are the same code on every platform
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RandomStream {
+    pub id: StreamId
+}
+
+impl RandomStream {
+    pub fn next_u64(&self) -> u64 {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        return toxoid_host::random::next_u64(self.id);
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        return ToxoidRandom::next_u64(self.id);
+    }
+
+    pub fn next_u32(&self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    // In [0, 1)
+    pub fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // In [0, 1)
+    pub fn next_f32(&self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    pub fn next_bool(&self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    // In [min, max), min when the range is empty
+    pub fn range_u32(&self, min: u32, max: u32) -> u32 {
+        if max <= min {
+            return min;
+        }
+        let span = (max - min) as u64;
+        min + ((self.next_u32() as u64 * span) >> 32) as u32
+    }
+
+    // In [min, max), min when the range is empty
+    pub fn range_i32(&self, min: i32, max: i32) -> i32 {
+        if max <= min {
+            return min;
+        }
+        let span = (max as i64 - min as i64) as u64;
+        (min as i64 + ((self.next_u32() as u64 * span) >> 32) as i64) as i32
+    }
+
+    // In [min, max), min when the range is empty
+    pub fn range_f32(&self, min: f32, max: f32) -> f32 {
+        if max <= min {
+            return min;
+        }
+        let value = min + (max - min) * self.next_f32();
+        // Rounding lands on max when the range is small next to its bounds
+        if value < max { value } else { max.next_down() }
+    }
+
+    // True with the given probability
+    pub fn chance(&self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+}
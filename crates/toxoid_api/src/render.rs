@@ -0,0 +1,71 @@
+use crate::*;
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+use once_cell::sync::Lazy;
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+use std::sync::Mutex;
+
+// Native
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32
+}
+
+// WASM
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+pub use toxoid_guest::bindings::toxoid_component::component::render::{
+    self as ToxoidRender,
+    Rgba
+};
+
+// Queued draw command, in world coordinates
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    Sprite { sprite: AssetHandle, x: f32, y: f32 },
+    Rect { x: f32, y: f32, width: f32, height: f32, color: Rgba },
+    Line { ax: f32, ay: f32, bx: f32, by: f32, color: Rgba }
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+static DRAW_COMMANDS: Lazy<Mutex<Vec<DrawCommand>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn queue_draw(command: DrawCommand) {
+    DRAW_COMMANDS.lock().unwrap().push(command);
+}
+
+// Take the draw commands queued since the last call, called by the renderer once per frame
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+pub fn take_draw_commands() -> Vec<DrawCommand> {
+    std::mem::take(&mut *DRAW_COMMANDS.lock().unwrap())
+}
+
+// Immediate mode drawing, commands are drawn at the end of the frame on top of the render targets
+pub struct Draw;
+
+impl Draw {
+    pub fn sprite(sprite: AssetHandle, x: f32, y: f32) {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        queue_draw(DrawCommand::Sprite { sprite, x, y });
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        ToxoidRender::draw_sprite(sprite, x, y);
+    }
+
+    pub fn rect(x: f32, y: f32, width: f32, height: f32, color: Rgba) {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        queue_draw(DrawCommand::Rect { x, y, width, height, color });
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        ToxoidRender::draw_rect(x, y, width, height, color);
+    }
+
+    pub fn line(ax: f32, ay: f32, bx: f32, by: f32, color: Rgba) {
+        #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+        queue_draw(DrawCommand::Line { ax, ay, bx, by, color });
+        #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+        ToxoidRender::draw_line(ax, ay, bx, by, color);
+    }
+}
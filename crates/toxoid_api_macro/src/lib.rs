@@ -3,7 +3,7 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::{quote, format_ident};
 use syn::{
-    parse::{Parse, ParseStream, Parser}, parse_macro_input, punctuated::Punctuated, spanned::Spanned, token::Comma, FieldsNamed, Ident, ItemFn, Type, Stmt, Token
+    parse::{Parse, ParseStream, Parser}, parse_macro_input, punctuated::Punctuated, spanned::Spanned, token::Comma, Expr, FieldsNamed, Ident, ItemEnum, ItemFn, Type, Stmt, Token
 };
 
 #[repr(u8)]
@@ -20,7 +20,20 @@ enum FieldType {
     F64,
     Bool,
     String,
-    Pointer
+    U8List,
+    U16List,
+    U32List,
+    U64List,
+    I8List,
+    I16List,
+    I32List,
+    I64List,
+    F32List,
+    F64List,
+    Pointer,
+    // Type given by id, another component nested as a struct or an enum
+    Nested,
+    Entity
 }
 
 // The input to the macro will be a list of field names and types.
@@ -52,28 +65,15 @@ pub fn component(input: TokenStream) -> TokenStream {
             let field_names = fields.iter().map(|f| &f.ident);
             let field_types = fields.iter().map(|f| &f.ty);
 
-            fn align_offset(offset: u32, align: u32) -> u32 {
-                (offset + align - 1) & !(align - 1)
-            }
-            let mut current_offset = 0;
-            let fields_offsets = fields.iter().map(
-                |field| {
-                    let field_type = &field.ty;
-                    let size = get_type_size(field_type);
-                    let align = get_type_alignment(field_type);
-                    current_offset = align_offset(current_offset, align);
-                    let offset = current_offset;
-                    current_offset += size;
-                    offset
-                }
-            );
+            // Members are accessed by their index in the component type
+            let fields_indices = 0..fields.len() as u32;
 
             let getters_and_setters =
                 field_names
                     .clone()
                     .zip(field_types.clone())
-                    .zip(fields_offsets)
-                    .map(|((field_name, field_type), field_offset)| {
+                    .zip(fields_indices)
+                    .map(|((field_name, field_type), field_index)| {
                         let getter_name = Ident::new(
                             &format!("get_{}", field_name.as_ref().unwrap()),
                             field_name.span(),
@@ -88,12 +88,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u8(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u8(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: u8) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u8(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u8(#field_index, value);
                                         }
                                     }
                                 }
@@ -102,12 +102,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u16(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u16(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: u16) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u16(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u16(#field_index, value);
                                         }
                                     }
                                 }
@@ -116,12 +116,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u32(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u32(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: u32) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u32(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u32(#field_index, value);
                                         }
                                     }
                                 }
@@ -130,12 +130,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u64(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u64(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: u64) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u64(#field_index, value);
                                         }
                                     }
                                 }
@@ -144,12 +144,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_i8(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_i8(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: i8) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_i8(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_i8(#field_index, value);
                                         }
                                     }
                                 }
@@ -158,12 +158,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_i16(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_i16(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: i16) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_i16(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_i16(#field_index, value);
                                         }
                                     }
                                 }
@@ -172,12 +172,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_i32(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_i32(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: i32) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_i32(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_i32(#field_index, value);
                                         }
                                     }
                                 }
@@ -186,12 +186,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_i64(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_i64(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: i64) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_i64(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_i64(#field_index, value);
                                         }
                                     }
                                 }
@@ -200,12 +200,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_f32(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_f32(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: f32) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_f32(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_f32(#field_index, value);
                                         }
                                     }
                                 }
@@ -214,12 +214,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_f64(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_f64(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: f64) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_f64(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_f64(#field_index, value);
                                         }
                                     }
                                 }
@@ -228,12 +228,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> #field_type {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_bool(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_bool(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: bool) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_bool(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_bool(#field_index, value);
                                         }
                                     }
                                 }
@@ -242,12 +242,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> PointerT {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u64(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_pointer(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: EcsEntityT) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_pointer(#field_index, value);
                                         }
                                     }
                                 }
@@ -256,12 +256,12 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> EcsEntityT {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u64(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u64(#field_index)
                                         }
                                     }
                                     pub fn #setter_name(&self, value: u64) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u64(#field_index, value);
                                         }
                                     }
                                 }
@@ -270,19 +270,19 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> String {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_string(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_string(#field_index)
                                         }
                                     }
                                     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                                     pub fn #setter_name(&self, value: String) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_string(#field_offset, value.as_str());
+                                            self.component.as_mut().unwrap().set_member_string(#field_index, value.as_str());
                                         }
                                     }
                                     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                                     pub fn #setter_name(&self, value: String) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_string(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_string(#field_index, value);
                                         }
                                     }
                                 }
@@ -291,19 +291,19 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> Vec<u8> {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u8list(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u8list(#field_index)
                                         }
                                     }
                                     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                                     pub fn #setter_name(&self, value: Vec<u8>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u8list(#field_offset, value.as_slice());
+                                            self.component.as_mut().unwrap().set_member_u8list(#field_index, value.as_slice());
                                         }
                                     }
                                     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                                     pub fn #setter_name(&self, value: Vec<u8>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u8list(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u8list(#field_index, value);
                                         }
                                     }
                                 }
@@ -312,19 +312,19 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> Vec<u64> {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u64list(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u64list(#field_index)
                                         }
                                     }
                                     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                                     pub fn #setter_name(&self, value: Vec<u64>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64list(#field_offset, value.as_slice());
+                                            self.component.as_mut().unwrap().set_member_u64list(#field_index, value.as_slice());
                                         }
                                     }
                                     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                                     pub fn #setter_name(&self, value: Vec<u64>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64list(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u64list(#field_index, value);
                                         }
                                     }
                                 }
@@ -333,19 +333,19 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> Vec<PointerT> {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u64list(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u64list(#field_index)
                                         }
                                     }
                                     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                                     pub fn #setter_name(&self, value: Vec<PointerT>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64list(#field_offset, value.as_slice());
+                                            self.component.as_mut().unwrap().set_member_u64list(#field_index, value.as_slice());
                                         }
                                     }
                                     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                                     pub fn #setter_name(&self, value: Vec<PointerT>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64list(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u64list(#field_index, value);
                                         }
                                     }
                                 }
@@ -354,19 +354,61 @@ pub fn component(input: TokenStream) -> TokenStream {
                                 quote! {
                                     pub fn #getter_name(&self) -> Vec<EcsEntityT> {
                                         unsafe {
-                                            self.component.as_ref().unwrap().get_member_u64list(#field_offset)
+                                            self.component.as_ref().unwrap().get_member_u64list(#field_index)
                                         }
                                     }
                                     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
                                     pub fn #setter_name(&self, value: Vec<EcsEntityT>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64list(#field_offset, value.as_slice());
+                                            self.component.as_mut().unwrap().set_member_u64list(#field_index, value.as_slice());
                                         }
                                     }
                                     #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
                                     pub fn #setter_name(&self, value: Vec<EcsEntityT>) {
                                         unsafe {
-                                            self.component.as_mut().unwrap().set_member_u64list(#field_offset, value);
+                                            self.component.as_mut().unwrap().set_member_u64list(#field_index, value);
+                                        }
+                                    }
+                                }
+                            },
+                            _ if array_element(field_type).is_some() => {
+                                // Fixed-size arrays are stored inline and accessed as lists of their elements
+                                let (element, len) = array_element(field_type).unwrap();
+                                let list_getter = format_ident!("get_member_{}list", element);
+                                let list_setter = format_ident!("set_member_{}list", element);
+                                quote! {
+                                    pub fn #getter_name(&self) -> #field_type {
+                                        unsafe {
+                                            let elements = self.component.as_ref().unwrap().#list_getter(#field_index);
+                                            let mut value: #field_type = [Default::default(); #len];
+                                            value.copy_from_slice(&elements);
+                                            value
+                                        }
+                                    }
+                                    #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+                                    pub fn #setter_name(&self, value: #field_type) {
+                                        unsafe {
+                                            self.component.as_mut().unwrap().#list_setter(#field_index, &value);
+                                        }
+                                    }
+                                    #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+                                    pub fn #setter_name(&self, value: #field_type) {
+                                        unsafe {
+                                            self.component.as_mut().unwrap().#list_setter(#field_index, value.to_vec());
+                                        }
+                                    }
+                                }
+                            },
+                            _ if is_nested(field_type) => {
+                                quote! {
+                                    pub fn #getter_name(&self) -> #field_type {
+                                        unsafe {
+                                            <#field_type as NestedMember>::get_member(self.component.as_ref().unwrap(), #field_index)
+                                        }
+                                    }
+                                    pub fn #setter_name(&self, value: #field_type) {
+                                        unsafe {
+                                            <#field_type as NestedMember>::set_member(self.component.as_ref().unwrap(), #field_index, value);
                                         }
                                     }
                                 }
@@ -407,8 +449,17 @@ pub fn component(input: TokenStream) -> TokenStream {
                     .clone()
                     .zip(field_types.clone())
                     .map(|(field_name, field_type)| {
-                        quote! {
-                            #field_name: #field_type::default(),
+                        match field_type {
+                            // Default is only implemented for arrays up to 32 elements
+                            Type::Array(array) => {
+                                let len = &array.len;
+                                quote! {
+                                    #field_name: [Default::default(); #len],
+                                }
+                            },
+                            _ => quote! {
+                                #field_name: <#field_type>::default(),
+                            }
                         }
                     });
 
@@ -433,14 +484,77 @@ pub fn component(input: TokenStream) -> TokenStream {
             // Create the register component tokens.
             let field_names_str = field_names.clone().map(|f| f.clone().unwrap().to_string());
             let field_types_code = field_types.clone().map(|f| get_type_code(f));
+            let field_type_ids = field_types.clone().map(|f| {
+                if is_nested(f) {
+                    quote! { <#f as NestedMember>::member_type_id() }
+                } else {
+                    quote! { 0 }
+                }
+            });
+            let field_counts = field_types.clone().map(|f| match array_element(f) {
+                Some((_, len)) => quote! { (#len) as u32 },
+                None => quote! { 0 }
+            });
 
             // Create the register implementation.
             let register_fn = quote! {
                 fn register() -> u64 {
-                    register_component(#struct_name_str, vec![#(#field_names_str.to_string()),*], vec![#(#field_types_code),*])
+                    register_component(
+                        #struct_name_str,
+                        vec![#(#field_names_str.to_string()),*],
+                        vec![#(#field_types_code),*],
+                        vec![#(#field_type_ids),*],
+                        vec![#(#field_counts),*]
+                    )
+                }
+            };
+
+            // Nesting this component in others, the values of a standalone instance
+            // are copied into the member by its setters
+            let nested_copies = field_names.clone().zip(field_types.clone()).map(|(field_name, field_type)| {
+                let setter_name = format_ident!("set_{}", field_name.as_ref().unwrap());
+                match field_type {
+                    Type::Array(_) => quote! { nested.#setter_name(value.#field_name); },
+                    _ => quote! { nested.#setter_name(std::mem::take(&mut value.#field_name)); }
+                }
+            });
+            // The values of a view of another component are in that component, not in its fields
+            let nested_view_copies = field_names.clone().map(|field_name| {
+                let getter_name = format_ident!("get_{}", field_name.as_ref().unwrap());
+                let setter_name = format_ident!("set_{}", field_name.as_ref().unwrap());
+                quote! { nested.#setter_name(value.#getter_name()); }
+            });
+            let nested_member_impl = quote! {
+                impl NestedMember for #name {
+                    fn member_type_id() -> EcsEntityT {
+                        Self::register()
+                    }
+                    fn get_member(component: &ToxoidComponent, member: u32) -> Self {
+                        nested_component::<Self>(component, member).unwrap_or_else(|error| detached_component(&error))
+                    }
+                    fn set_member(component: &ToxoidComponent, member: u32, mut value: Self) {
+                        let nested = Self::get_member(component, member);
+                        if value.component.is_null() {
+                            #(#nested_copies)*
+                        } else {
+                            #(#nested_view_copies)*
+                        }
+                    }
                 }
             };
             
+            // Initial values for `EntityBuilder`, nested components are flattened like in their layout
+            let member_values = field_names.clone().map(|field_name| {
+                quote! { MemberValue::encode(&self.#field_name, out); }
+            });
+            let member_value_impl = quote! {
+                impl MemberValue for #name {
+                    fn encode(&self, out: &mut Vec<u8>) {
+                        #(#member_values)*
+                    }
+                }
+            };
+
             let type_name = struct_name_str.as_str();
             let type_name_fn = quote! {
                 fn get_name() -> &'static str {
@@ -478,6 +592,10 @@ pub fn component(input: TokenStream) -> TokenStream {
                     #type_get_id_fn
                 }
 
+                #nested_member_impl
+
+                #member_value_impl
+
                 impl Component for #name {
                     fn set_component(&mut self, component: ToxoidComponent) {
                         // TODO: Remove this boxed pointer for host (and possibly guest)
@@ -511,6 +629,42 @@ pub fn component(input: TokenStream) -> TokenStream {
     })
 }
 
+// Element type name and length of a fixed-size array of numbers
+fn array_element(ty: &Type) -> Option<(String, Expr)> {
+    match ty {
+        Type::Array(array) => {
+            let element = match &*array.elem {
+                Type::Path(tp) => tp.path.get_ident()?.to_string(),
+                _ => return None
+            };
+            let numbers = ["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64", "f32", "f64"];
+            if numbers.contains(&element.as_str()) {
+                Some((element, array.len.clone()))
+            } else {
+                None
+            }
+        },
+        _ => None
+    }
+}
+
+// Any other type name is a component or an enum implementing `NestedMember`
+fn is_nested(ty: &Type) -> bool {
+    match ty {
+        Type::Path(tp) => {
+            let builtin = [
+                "u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64", "f32", "f64", "bool",
+                "String", "PointerT", "EcsEntityT", "Vec"
+            ];
+            match tp.path.segments.last() {
+                Some(segment) => !builtin.contains(&segment.ident.to_string().as_str()),
+                None => false
+            }
+        },
+        _ => false
+    }
+}
+
 fn get_type_code(ty: &Type) -> u8 {
     match ty {
         Type::Path(tp) if tp.path.is_ident("u8") => FieldType::U8 as u8,
@@ -525,19 +679,11 @@ fn get_type_code(ty: &Type) -> u8 {
         Type::Path(tp) if tp.path.is_ident("f64") => FieldType::F64 as u8,
         Type::Path(tp) if tp.path.is_ident("bool") => FieldType::Bool as u8,
         Type::Path(tp) if tp.path.is_ident("PointerT") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("EcsEntityT") => FieldType::Pointer as u8,
+        Type::Path(tp) if tp.path.is_ident("EcsEntityT") => FieldType::Entity as u8,
         Type::Path(tp) if tp.path.is_ident("String") => FieldType::String as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<u8>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<u32>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<u64>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<i8>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<i16>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<i32>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<i64>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<f32>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<f64>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<PointerT>") => FieldType::Pointer as u8,
-        Type::Path(tp) if tp.path.is_ident("Vec<EcsEntityT>") => FieldType::Pointer as u8,
+        // Fixed-size arrays have the type of their elements and a count
+        Type::Array(array) if array_element(ty).is_some() => get_type_code(&array.elem),
+        _ if is_nested(ty) => FieldType::Nested as u8,
         Type::Path(tp) => {
             let segment = match tp.path.segments.last() {
                 Some(seg) => seg,
@@ -570,22 +716,19 @@ fn get_type_code(ty: &Type) -> u8 {
                 }
             };
         
-            // Check if the inner type is supported
-            if inner_type.path.is_ident("u8") || 
-               inner_type.path.is_ident("u16") || 
-               inner_type.path.is_ident("u32") || 
-               inner_type.path.is_ident("u64") || 
-               inner_type.path.is_ident("i8") || 
-               inner_type.path.is_ident("i16") || 
-               inner_type.path.is_ident("i32") || 
-               inner_type.path.is_ident("i64") || 
-               inner_type.path.is_ident("f32") || 
-               inner_type.path.is_ident("f64") ||
-               inner_type.path.is_ident("PointerT") ||
-               inner_type.path.is_ident("EcsEntityT")
-            {
-                return FieldType::Pointer as u8;
-            }
+            // Check if the inner type is supported, the element type is kept so that
+            // list members can be serialized. Flecs stores every list as a pointer.
+            let inner = &inner_type.path;
+            if inner.is_ident("u8") { return FieldType::U8List as u8; }
+            if inner.is_ident("u16") { return FieldType::U16List as u8; }
+            if inner.is_ident("u32") { return FieldType::U32List as u8; }
+            if inner.is_ident("u64") || inner.is_ident("PointerT") || inner.is_ident("EcsEntityT") { return FieldType::U64List as u8; }
+            if inner.is_ident("i8") { return FieldType::I8List as u8; }
+            if inner.is_ident("i16") { return FieldType::I16List as u8; }
+            if inner.is_ident("i32") { return FieldType::I32List as u8; }
+            if inner.is_ident("i64") { return FieldType::I64List as u8; }
+            if inner.is_ident("f32") { return FieldType::F32List as u8; }
+            if inner.is_ident("f64") { return FieldType::F64List as u8; }
         
             println!("Unsupported Vec type: {}", quote!(#ty));
             panic!("Unsupported type code");
@@ -598,186 +741,6 @@ fn get_type_code(ty: &Type) -> u8 {
     }
 }
 
-fn get_type_size(ty: &Type) -> u32 {
-    let target = std::env::var("TARGET").unwrap_or("".to_string());
-    let pointer_size = if target.contains("emscripten") { 4 } else { 8 };
-    match ty {
-        Type::Path(tp) if tp.path.is_ident("u8") => 1,
-        Type::Path(tp) if tp.path.is_ident("u16") => 2,
-        Type::Path(tp) if tp.path.is_ident("u32") => 4,
-        Type::Path(tp) if tp.path.is_ident("u64") => 8,
-        Type::Path(tp) if tp.path.is_ident("i8") => 1,
-        Type::Path(tp) if tp.path.is_ident("i16") => 2,
-        Type::Path(tp) if tp.path.is_ident("i32") => 4,
-        Type::Path(tp) if tp.path.is_ident("i64") => 8,
-        Type::Path(tp) if tp.path.is_ident("f32") => 4,
-        Type::Path(tp) if tp.path.is_ident("f64") => 8,
-        Type::Path(tp) if tp.path.is_ident("bool") => 1,
-        Type::Path(tp) if tp.path.is_ident("PointerT") => 8,
-        Type::Path(tp) if tp.path.is_ident("EcsEntityT") => 8,
-        Type::Path(tp) if tp.path.is_ident("String") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u8>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u16>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u32>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u64>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i8>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i16>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i32>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i64>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<f32>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<f64>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<PointerT>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<EcsEntityT>") => pointer_size,
-        Type::Ptr(_) => pointer_size,
-        Type::Path(tp) => {
-            let segment = match tp.path.segments.last() {
-                Some(seg) => seg,
-                None => {
-                    println!("Invalid type path: {}", quote!(#ty));
-                    panic!("Unsupported type code");
-                }
-            };
-        
-            // If it's not a Vec, we don't need to process further
-            if segment.ident != "Vec" {
-                println!("Unexpected type: {}", quote!(#ty));
-                panic!("Unsupported type code");
-            }
-        
-            // Get the generic type argument
-            let inner_type = match &segment.arguments {
-                syn::PathArguments::AngleBracketed(args) => {
-                    match args.args.first() {
-                        Some(syn::GenericArgument::Type(Type::Path(inner_ty))) => inner_ty,
-                        _ => {
-                            println!("Invalid Vec generic argument: {}", quote!(#ty));
-                            panic!("Unsupported type code");
-                        }
-                    }
-                },
-                _ => {
-                    println!("Invalid Vec arguments: {}", quote!(#ty));
-                    panic!("Unsupported type code");
-                }
-            };
-        
-            // Check if the inner type is supported
-            if inner_type.path.is_ident("u8") || 
-               inner_type.path.is_ident("u16") || 
-               inner_type.path.is_ident("u32") || 
-               inner_type.path.is_ident("u64") || 
-               inner_type.path.is_ident("i8") || 
-               inner_type.path.is_ident("i16") || 
-               inner_type.path.is_ident("i32") || 
-               inner_type.path.is_ident("i64") || 
-               inner_type.path.is_ident("f32") || 
-               inner_type.path.is_ident("f64") ||
-               inner_type.path.is_ident("PointerT") ||
-               inner_type.path.is_ident("EcsEntityT")
-            {
-                return pointer_size;
-            }
-        
-            println!("Unsupported Vec type: {}", quote!(#ty));
-            panic!("Unsupported type code");
-        }
-        _ => {
-            println!("Unsupported field type: {}", quote!(#ty));
-            panic!("Unsupported field type")
-        }
-    }
-}  
-
-fn get_type_alignment(ty: &Type) -> u32 {
-    let target = std::env::var("TARGET").unwrap_or("".to_string());
-    let pointer_size = if target.contains("emscripten") { 4 } else { 8 };
-    match ty {
-        Type::Path(tp) if tp.path.is_ident("u8") => 1,
-        Type::Path(tp) if tp.path.is_ident("u16") => 2,
-        Type::Path(tp) if tp.path.is_ident("u32") => 4,
-        Type::Path(tp) if tp.path.is_ident("u64") => 8,
-        Type::Path(tp) if tp.path.is_ident("i8") => 1,
-        Type::Path(tp) if tp.path.is_ident("i16") => 2,
-        Type::Path(tp) if tp.path.is_ident("i32") => 4,
-        Type::Path(tp) if tp.path.is_ident("i64") => 8,
-        Type::Path(tp) if tp.path.is_ident("f32") => 4,
-        Type::Path(tp) if tp.path.is_ident("f64") => 8,
-        Type::Path(tp) if tp.path.is_ident("bool") => 1,
-        Type::Path(tp) if tp.path.is_ident("PointerT") => 8,
-        Type::Path(tp) if tp.path.is_ident("EcsEntityT") => 8,
-        Type::Path(tp) if tp.path.is_ident("String") => pointer_size, // Assuming String is a pointer
-        Type::Path(tp) if tp.path.is_ident("Vec<u8>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u16>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u32>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<u64>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i8>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i16>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i32>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<i64>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<f32>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<f64>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<PointerT>") => pointer_size,
-        Type::Path(tp) if tp.path.is_ident("Vec<EcsEntityT>") => pointer_size,
-        Type::Ptr(_) => pointer_size, // Pointers are 8 bytes in a 64-bit context
-        Type::Path(tp) => {
-            let segment = match tp.path.segments.last() {
-                Some(seg) => seg,
-                None => {
-                    println!("Invalid type path: {}", quote!(#ty));
-                    panic!("Unsupported type code");
-                }
-            };
-        
-            // If it's not a Vec, we don't need to process further
-            if segment.ident != "Vec" {
-                println!("Unexpected type: {}", quote!(#ty));
-                panic!("Unsupported type code");
-            }
-        
-            // Get the generic type argument
-            let inner_type = match &segment.arguments {
-                syn::PathArguments::AngleBracketed(args) => {
-                    match args.args.first() {
-                        Some(syn::GenericArgument::Type(Type::Path(inner_ty))) => inner_ty,
-                        _ => {
-                            println!("Invalid Vec generic argument: {}", quote!(#ty));
-                            panic!("Unsupported type code");
-                        }
-                    }
-                },
-                _ => {
-                    println!("Invalid Vec arguments: {}", quote!(#ty));
-                    panic!("Unsupported type code");
-                }
-            };
-        
-            // Check if the inner type is supported
-            if inner_type.path.is_ident("u8") || 
-               inner_type.path.is_ident("u16") || 
-               inner_type.path.is_ident("u32") || 
-               inner_type.path.is_ident("u64") || 
-               inner_type.path.is_ident("i8") || 
-               inner_type.path.is_ident("i16") || 
-               inner_type.path.is_ident("i32") || 
-               inner_type.path.is_ident("i64") || 
-               inner_type.path.is_ident("f32") || 
-               inner_type.path.is_ident("f64") ||
-               inner_type.path.is_ident("PointerT") ||
-               inner_type.path.is_ident("EcsEntityT")
-            {
-                return pointer_size;
-            }
-        
-            println!("Unsupported Vec type: {}", quote!(#ty));
-            panic!("Unsupported type code");
-        }
-        _ => {
-            println!("Unsupported field type: {}", quote!(#ty));
-            panic!("Unsupported field type")
-        }
-    }
-}
-
 struct ComponentTuple(Vec<Option<Type>>);
 
 impl Parse for ComponentTuple {
@@ -900,4 +863,75 @@ fn make_variable_name(t: &Type) -> Ident {
     }
 
     format_ident!("{}", snake_case)
-}
\ No newline at end of file
+}
+// Enums for component members, stored as i32 and registered with their constants so reflection,
+// JSON and the Flecs explorer show the constant names. Values that don't match a constant read
+// as the first one.
+//
+// component_enum! {
+//     pub enum Facing { Up, Down, Left = 10, Right }
+// }
+struct EnumItems(Vec<ItemEnum>);
+
+impl Parse for EnumItems {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items = Vec::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(EnumItems(items))
+    }
+}
+
+#[proc_macro]
+pub fn component_enum(input: TokenStream) -> TokenStream {
+    let EnumItems(items) = parse_macro_input!(input as EnumItems);
+    let expanded = items.iter().map(|item| {
+        let name = &item.ident;
+        let name_str = name.to_string();
+        let variants: Vec<_> = item.variants.iter().map(|variant| &variant.ident).collect();
+        let variants_str = variants.iter().map(|variant| variant.to_string());
+        let first = variants.first().expect("Enum needs at least one variant");
+        quote! {
+            #[repr(i32)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            #item
+
+            impl Default for #name {
+                fn default() -> Self {
+                    #name::#first
+                }
+            }
+
+            impl #name {
+                pub fn from_i32(value: i32) -> Option<Self> {
+                    #(if value == #name::#variants as i32 {
+                        return Some(#name::#variants);
+                    })*
+                    None
+                }
+            }
+
+            impl NestedMember for #name {
+                fn member_type_id() -> EcsEntityT {
+                    register_enum(#name_str, &[#((#variants_str, #name::#variants as i32 as u32)),*], false)
+                }
+                fn get_member(component: &ToxoidComponent, member: u32) -> Self {
+                    Self::from_i32(component.get_member_i32(member)).unwrap_or_default()
+                }
+                fn set_member(component: &ToxoidComponent, member: u32, value: Self) {
+                    component.set_member_i32(member, value as i32);
+                }
+            }
+
+            impl MemberValue for #name {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    MemberValue::encode(&(*self as i32), out);
+                }
+            }
+        }
+    });
+    TokenStream::from(quote! {
+        #(#expanded)*
+    })
+}
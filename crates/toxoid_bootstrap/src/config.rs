@@ -17,4 +17,12 @@ pub fn init() {
     // Set zoom settings
     game_config.set_default_zoom(2.0);
     game_config.set_zoom_speed(0.1); // Adjust this value to control zoom sensitivity
-}
\ No newline at end of file
+
+    // Seed random streams, runs with the same seed draw the same numbers
+    game_config.set_seed(0x746F_786F_6964);
+    Random::set_seed(game_config.get_seed());
+
+    // Systems run on the main thread unless more threads are asked for
+    game_config.set_threads(1);
+    World::set_threads(game_config.get_threads());
+}
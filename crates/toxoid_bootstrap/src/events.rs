@@ -2,6 +2,7 @@ use toxoid_sokol::sapp::Mousebutton;
 use toxoid_sokol::sokol::app::{EventType, Event, Keycode};
 use toxoid_sokol::sokol::app as sapp;
 use toxoid_api::*;
+use toxoid_host::engine;
 
 fn clamp_window_size(width: u32, height: u32, game_config: &GameConfig) -> (u32, u32) {
     let base_width = game_config.get_game_width();
@@ -76,9 +77,39 @@ fn handle_mouse_scroll(scroll_y: f32) {
     camera.set_zoom(clamped_zoom);
 }
 
+// Input and window state of the engine interface, which guests read instead of the input singletons
+fn update_engine_state(event: &Event) {
+    match event._type {
+        EventType::KeyDown => engine::set_key(event.key_code as u32, true),
+        EventType::KeyUp => engine::set_key(event.key_code as u32, false),
+        EventType::MouseDown | EventType::MouseUp => {
+            engine::set_mouse_position(event.mouse_x, event.mouse_y, event.mouse_dx, event.mouse_dy);
+            let button = match event.mouse_button {
+                Mousebutton::Left => Some(engine::MouseButton::Left),
+                Mousebutton::Right => Some(engine::MouseButton::Right),
+                Mousebutton::Middle => Some(engine::MouseButton::Middle),
+                _ => None
+            };
+            if let Some(button) = button {
+                engine::set_mouse_button(button, event._type == EventType::MouseDown);
+            }
+        },
+        EventType::MouseMove | EventType::MouseEnter | EventType::MouseLeave => {
+            engine::set_mouse_position(event.mouse_x, event.mouse_y, event.mouse_dx, event.mouse_dy);
+        },
+        EventType::MouseScroll => {
+            engine::set_mouse_position(event.mouse_x, event.mouse_y, event.mouse_dx, event.mouse_dy);
+            engine::add_mouse_scroll(event.scroll_x, event.scroll_y);
+        },
+        EventType::Resized => engine::set_window_size(sapp::width() as u32, sapp::height() as u32),
+        _ => {}
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn sokol_event(event: *const Event) {
     let event = unsafe { *event };
+    update_engine_state(&event);
     match event._type {
         EventType::KeyDown => {
             key_down(event.key_code);
@@ -100,6 +131,7 @@ pub extern "C" fn sokol_event(event: *const Event) {
             // Update game config with the clamped dimensions for rendering
             game_config.set_window_width(clamped_width);
             game_config.set_window_height(clamped_height);
+            engine::set_render_size(clamped_width, clamped_height);
             
             if clamped_width != window_width as u32 || clamped_height != window_height as u32 {
                 println!("Render size clamped to: {}x{} (window: {}x{})", 
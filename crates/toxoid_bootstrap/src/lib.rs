@@ -13,7 +13,12 @@ use toxoid_api::*;
 pub extern "C" fn init_bootstrap(user_data: *mut core::ffi::c_void) {
     // Initialize renderer
     toxoid_sokol::sokol_init();
-    
+
+    // Initial window size of the engine interface, resize events keep it up to date
+    let game_config = World::get_singleton::<GameConfig>();
+    toxoid_host::engine::set_window_size(toxoid_sokol::sokol::app::width() as u32, toxoid_sokol::sokol::app::height() as u32);
+    toxoid_host::engine::set_render_size(game_config.get_window_width(), game_config.get_window_height());
+
     // Enable render systems
     let render_systems = World::get_singleton::<RenderSystems>();
     let mut entity = Entity::from_id(render_systems.get_entity());
@@ -43,6 +48,17 @@ pub fn init(init_host: extern "C" fn()) {
     config::init();
     // Initialize systems
     systems::init();
+    // Serve the world to the Flecs explorer, TOXOID_REST_PORT overrides the port and
+    // TOXOID_REST_ADDRESS serves it beyond this machine
+    #[cfg(feature = "rest")]
+    {
+        let port = std::env::var("TOXOID_REST_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(REST_DEFAULT_PORT);
+        let address = std::env::var("TOXOID_REST_ADDRESS").ok();
+        World::enable_rest(port, address.as_deref());
+    }
 
     // TODO: Possibly change this when we have Flecs system phases
     // so that we can dynamically enable / disable systems and the ECS
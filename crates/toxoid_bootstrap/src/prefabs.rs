@@ -3,25 +3,22 @@ use toxoid_render::Renderer2D;
 use toxoid_sokol::*;
 
 pub fn create_render_target(width: u32, height: u32, z_depth: u32) -> Entity {
-    // Create entity
-    let mut entity = Entity::new(None);
-    entity.add::<RenderTarget>();
-    entity.add::<Position>();
-    entity.add::<Size>();
-    entity.add::<BlendMode>();
-    // Set render target object pointer
+    // Render target object pointer
     let rt = SokolRenderer2D::create_render_target(width, height);
-    let rt_component = entity.get::<RenderTarget>();
-    rt_component
-        .set_render_target(
-            Box::leak(rt) as *const _ as *const std::ffi::c_void as u64
-        );
-    rt_component.set_z_depth(z_depth);
-    // Set size
-    let size = entity.get::<Size>();
-    size.set_width(width);
-    size.set_height(height);
-    entity
+    let render_target = Box::leak(rt) as *const _ as *const std::ffi::c_void as u64;
+    // Create entity with its components in one call
+    Entity::builder()
+        .add::<Position>()
+        .add::<BlendMode>()
+        .with(|rt_component: &mut RenderTarget| {
+            rt_component.render_target = render_target;
+            rt_component.z_depth = z_depth;
+        })
+        .with(|size: &mut Size| {
+            size.width = width;
+            size.height = height;
+        })
+        .build()
 }
 
 pub fn create_sprite_from_data(data: Vec<u8>) -> Entity {
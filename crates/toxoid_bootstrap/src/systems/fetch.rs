@@ -14,6 +14,17 @@ pub extern "C" fn fetch_callback(response: *const sfetch_response_t) {
     let entity_id = unsafe { *(response.user_data as *mut u64) };
     let mut entity = Entity::from_id(entity_id);
     let fetch_request = entity.get::<FetchRequest>();
+    if response.failed {
+        // Report the failure on the asset the request loads into
+        let user_data = fetch_request.get_user_data();
+        let asset = if user_data != 0 { user_data } else { entity_id };
+        let path = fetch_request.get_path();
+        let message = format!("Failed to fetch {}: {:?}", path, response.error_code);
+        toxoid_host::log::log(toxoid_host::log::LogRecord::new(toxoid_host::log::LogLevel::Error, "fetch", &message));
+        asset_failed(asset, message);
+        entity.remove::<Loading>();
+        return;
+    }
     let data = unsafe {
         std::slice::from_raw_parts(response.data.ptr as *const u8, response.data.size) 
     };
@@ -155,6 +166,7 @@ pub fn bone_animation_loaded(entity: &mut Entity) {
     // Make spine instance child of render target
     entity.child_of_id(rt_entity.get_id());
     entity.add::<Blittable>();
+    entity.add::<Loaded>();
 }
 
 // Fetch Observers
@@ -175,6 +187,9 @@ pub fn init() {
             let data_type = fetch_request.get_data_type();
             let size = data.len() as usize;
             match data_type as u8 {
+                d if d == DataType::Raw as u8 => {
+                    // Raw data stays on the fetch request entity
+                },
                 d if d == DataType::BoneAnimationImage as u8 => {
                     let mut image_entity = Entity::from_id(fetch_request.get_user_data());
                     let image = image_entity.get::<BoneAnimationImage>();
@@ -296,6 +311,7 @@ pub fn init() {
                             let mut player_entity = Entity::from_id(player_singleton.get_entity());
                             player_entity.child_of_id(cell_entity.get_id());
                         });
+                    world_entity.add::<Loaded>();
                 },
                 d if d == DataType::Cell as u8 => {
                     let mut cell_entity = Entity::from_id(fetch_request.get_user_data());
@@ -317,6 +333,7 @@ pub fn init() {
                     // cell_entity.add_relationship(Relationship::Custom(TilesetRelationship::get_id()), tileset_entity);
                     tileset_entity.child_of_id(cell_entity.get_id());
                     cell_entity.add::<Blittable>();
+                    cell_entity.add::<Loaded>();
                     // let mut tileset = tileset_entity.get::<Tileset>();
                     // tileset.set_tileset(Box::into_raw(Box::new(tileset.clone())) as u64);
                 },
@@ -335,9 +352,11 @@ pub fn init() {
                     let sprite = tileset_entity.get::<Sprite>();
                     sprite.set_sprite(Box::into_raw(sokol_sprite) as *mut () as u64);
                     tileset_entity.add::<Blittable>();
+                    tileset_entity.add::<Loaded>();
                 },
                 _ => {
-                    println!("File with unknown data type fetched: {:?}", data_type);
+                    let message = format!("File with unknown data type fetched: {:?}", data_type);
+                    toxoid_host::log::log(toxoid_host::log::LogRecord::new(toxoid_host::log::LogLevel::Warn, "fetch", &message));
                 }
             }
 
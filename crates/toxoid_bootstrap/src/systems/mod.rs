@@ -6,50 +6,45 @@ use toxoid_api::*;
 #[no_mangle]
 pub unsafe extern "C" fn query_trampoline(iter: *mut toxoid_host::ecs_iter_t) {
     let handle = (*iter).callback_ctx as u64;
-    let is_guest = (*iter).ctx != std::ptr::null_mut();
+    // Guest systems and observers store the ID of the guest that registered them in ctx
+    let guest_id = (*iter).ctx as usize as u32;
+    let is_guest = guest_id != 0;
     if is_guest {
         // If target is not emscripten
         #[cfg(not(target_os = "emscripten"))]
         {
-            // Get a lock on the store and get a mutable reference to the actual Store
-            let mut store_guard = unsafe { toxoid_runtime::STORE.lock().unwrap() };
-            let store = &mut *store_guard;
-
-            let iter = Box::into_raw(Box::new(toxoid_host::Iter::new(iter as u64)));
-            let iter_resource_id = store
-                .data_mut()
-                .table.push::<toxoid_runtime::IterProxy>(toxoid_runtime::IterProxy { ptr: iter })
-                .unwrap();
-            
-            unsafe {
-                let component_world_result = toxoid_runtime::TOXOID_COMPONENT_WORLD.as_ref();
-                if let Some(component_world) = component_world_result {
-                    component_world
-                        .toxoid_component_component_callbacks()
-                        .call_run(store, iter_resource_id, handle)
-                        .unwrap_or_else(|e| {
-                            println!("Error calling run: {:?}", e);
-                        });
-                }
-            }
+            // A failing guest callback is quarantined by the runtime, the frame keeps going
+            toxoid_runtime::run_guest_callback(guest_id, iter, handle)
+                .unwrap_or_else(|e| {
+                    let message = format!("Callback failed ({:?}): {:?}", e.kind, e.error);
+                    let mut record = toxoid_host::log::LogRecord::new(toxoid_host::log::LogLevel::Error, "runtime", &message);
+                    record.guest = Some(e.guest);
+                    record.system = Some(e.system);
+                    toxoid_host::log::log(record);
+                });
         }
     } else {
-        let callback = unsafe {
-            toxoid_api::CALLBACKS[handle as usize]
-                .as_ref()
+        // The system or observer was deleted earlier in the frame
+        let callback = match toxoid_api::get_callback(handle) {
+            Some(callback) => callback,
+            None => return
         };
+        // Multithreaded systems run on a worker thread and write through its stage
+        let previous_stage = toxoid_host::set_stage((*iter).world);
         let iter = toxoid_api::Iter {
             iter: toxoid_api::ToxoidIter {
                 ptr: iter as *mut core::ffi::c_void
             }
         };
         callback(&iter);
+        toxoid_host::set_stage(previous_stage);
     }
 }
 
 pub fn init() {    
     unsafe {
         toxoid_host::QUERY_TRAMPOLINE = Some(query_trampoline);
+        toxoid_host::CALLBACK_FREE = Some(toxoid_api::free_callback_ctx);
     }
     render::init();
     fetch::init();
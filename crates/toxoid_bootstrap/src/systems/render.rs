@@ -50,11 +50,21 @@ pub fn blit_bone_animation_system(iter: &Iter) {
     }
 }
 
+query_var!(Related);
+
+// Sprites to blit and the render target they're related to
+type BlitSprite = (
+    &'static Sprite,
+    With<Blittable>,
+    &'static Size,
+    Pair<RenderTargetRelationship, Var<Related>>,
+    Src<&'static RenderTarget, Related>,
+    Src<&'static Size, Related>
+);
+
 // Blit sprite to render target
-#[components(Sprite, _, Size, _, RenderTarget, Size)]
 pub fn blit_sprite_system(iter: &Iter) {
-    let mut entities = iter.entities();
-    for (i, (sprite, size, render_target, rt_size)) in components.into_iter().enumerate() {
+    for (entity, (sprite, (), size, (), render_target, rt_size)) in iter.fetch::<BlitSprite>() {
         // Get render target pointer / object / box / trait object
         let rt_ptr = render_target.get_render_target();
         let rt_ptr_box = unsafe { Box::from_raw(rt_ptr as *mut SokolRenderTarget) };
@@ -75,7 +85,7 @@ pub fn blit_sprite_system(iter: &Iter) {
         SokolRenderer2D::blit_sprite(sprite_trait_object, 0., 0., width as f32, height as f32, rt_trait_object, 0., 0.);
         // End render target
         SokolRenderer2D::end_rt();
-        entities[i].remove::<Blittable>();
+        Commands::remove::<Blittable>(&entity);
     }
 }
 
@@ -184,12 +194,18 @@ pub fn blit_cell_system(iter: &Iter) {
                                         .iter()
                                         .for_each(|property| {
                                             if property.name == "entity" {
-                                                // println!("Entity: {}", property.value.as_str());
-                                                // toxoid_json_to_entity(
-                                                //     make_c_string(
-                                                //         property.value.as_str()
-                                                //     )
-                                                // );
+                                                // Entity authored as JSON in the object's properties
+                                                match World::entity_from_json(property.value.as_str(), None) {
+                                                    Ok(mut object_entity) => {
+                                                        // Place it where the object is in the map
+                                                        if object_entity.has::<Position>() {
+                                                            let position = object_entity.get::<Position>();
+                                                            position.set_x(cell_x + object.x as i32);
+                                                            position.set_y(cell_y + object.y as i32);
+                                                        }
+                                                    }
+                                                    Err(e) => Log::warn("tiled", &format!("Object {} has an invalid entity: {}", object.name, e))
+                                                }
                                             }
                                             // if property.name == "filename" {
                                                 
@@ -218,7 +234,7 @@ pub fn blit_cell_system(iter: &Iter) {
                 rt_entity.add::<Renderable>();
                 
                 // Remove the blittable component
-                cell_entity.remove::<Blittable>();
+                Commands::remove::<Blittable>(cell_entity);
             }
         }
     }
@@ -228,7 +244,7 @@ pub fn blit_cell_system(iter: &Iter) {
 // #[components(_, Position, Size, Color, _)]
 // pub fn blit_rect_system(iter: &Iter) {
 //     for (pos, size, color) in components {
-//         SokolRenderer2D::draw_filled_rect(&pos, &size, &color);
+//         SokolRenderer2D::draw_filled_rect(pos.get_x() as f32, pos.get_y() as f32, size.get_width() as f32, size.get_height() as f32, [color.get_r(), color.get_g(), color.get_b(), color.get_a()]);
 //     }
 // }
 
@@ -237,8 +253,8 @@ pub fn blit_cell_system(iter: &Iter) {
 pub extern "C" fn draw_render_target_sort(_e1: EcsEntityT, v1: *const std::ffi::c_void, _e2: EcsEntityT, v2: *const std::ffi::c_void) -> i32 {
     let mut rt1 = RenderTarget::default();
     let mut rt2 = RenderTarget::default();
-    let rt1_component = ToxoidComponent::from_ptr_host(v1 as u64);
-    let rt2_component = ToxoidComponent::from_ptr_host(v2 as u64);
+    let rt1_component = ToxoidComponent::from_ptr_host(v1 as u64, RenderTarget::get_id());
+    let rt2_component = ToxoidComponent::from_ptr_host(v2 as u64, RenderTarget::get_id());
     rt1.set_component(rt1_component);
     rt2.set_component(rt2_component);
     let z1 = rt1.get_z_depth();
@@ -287,6 +303,38 @@ pub fn draw_render_targets_system(iter: &Iter) {
     }
 }
 
+// Draw the commands queued through `Draw` this frame on top of the render targets
+pub fn draw_commands_system(_iter: &Iter) {
+    // Get camera position
+    let main_camera = World::get_singleton::<MainCamera>();
+    let mut camera_entity = Entity::from_id(main_camera.get_entity());
+    let camera_pos = camera_entity.get::<Position>();
+    let camera_x = camera_pos.get_x() as f32;
+    let camera_y = camera_pos.get_y() as f32;
+
+    for command in take_draw_commands() {
+        match command {
+            DrawCommand::Sprite { sprite, x, y } => {
+                let mut sprite_entity = Entity::from_id(sprite);
+                // Sprites that are still loading are skipped
+                if !sprite_entity.has::<Sprite>() || !sprite_entity.has::<Loaded>() {
+                    continue;
+                }
+                let sprite_ptr = sprite_entity.get::<Sprite>().get_sprite();
+                let sprite_box = unsafe { Box::from_raw(sprite_ptr as *mut SokolSprite) };
+                let sprite_trait_object: &Box<dyn toxoid_render::Sprite> = Box::leak(Box::new(sprite_box as Box<dyn toxoid_render::Sprite>));
+                SokolRenderer2D::draw_sprite(sprite_trait_object, x - camera_x, y - camera_y);
+            },
+            DrawCommand::Rect { x, y, width, height, color } => {
+                SokolRenderer2D::draw_filled_rect(x - camera_x, y - camera_y, width, height, [color.r, color.g, color.b, color.a]);
+            },
+            DrawCommand::Line { ax, ay, bx, by, color } => {
+                SokolRenderer2D::draw_line(ax - camera_x, ay - camera_y, bx - camera_x, by - camera_y, [color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+}
+
 // Systems that draw render targets to the screen as a final output
 pub fn draw_systems(render_systems_entity: &mut Entity) {
     // Draw Render Targets
@@ -294,6 +342,11 @@ pub fn draw_systems(render_systems_entity: &mut Entity) {
         .order_by(RenderTarget::get_id(), draw_render_target_sort)
         .build();
     render_systems_entity.parent_of_id(system.get_id());
+
+    // Draw commands, no terms so it runs once per frame
+    let system = System::dsl("", None, draw_commands_system)
+        .build();
+    render_systems_entity.parent_of_id(system.get_id());
 }
 
 // Systems that blit render targets
@@ -309,7 +362,7 @@ pub fn blit_systems(render_systems_entity: &mut Entity) {
     render_systems_entity.parent_of_id(system.get_id());
 
     // Blit sprite to render target
-    let system = System::dsl("Sprite, Blittable, Size, (RenderTargetRelationship, $Related), RenderTarget($Related), Size($Related)", None, blit_sprite_system)
+    let system = System::typed::<BlitSprite>(None, blit_sprite_system)
         .build();
     render_systems_entity.parent_of_id(system.get_id());
 
@@ -1,6 +1,7 @@
 use std::net::{TcpListener, TcpStream};
 use std::io::{BufReader, prelude::*};
 use std::thread;
+use toxoid_host::log::{log, LogLevel, LogRecord};
 
 // TODO: Make this configurable via ENV variable or CLI message
 const HOST_ADDRESS: &str = "127.0.0.1:7878";
@@ -10,12 +11,26 @@ const GUEST_WASM_PATH: &str = "app/host/guest.wasm";
 #[cfg(target_os = "macos")]
 const GUEST_WASM_PATH: &str = "guest.wasm";
 
+fn guest_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+// Reloads and load failures go to the host log sinks, tagged with the guest when there is one
+fn log_watch(level: LogLevel, guest: Option<&str>, message: &str) {
+    let mut record = LogRecord::new(level, "watch", message);
+    record.guest = guest.map(str::to_string);
+    log(record);
+}
+
 fn watch() {
     // Start a thread to listen for TCP messages
     thread::spawn(move || {
         // Define the TCP address and port
         let listener = TcpListener::bind(HOST_ADDRESS).unwrap();
-        println!("Listening on {}", HOST_ADDRESS);
+        log_watch(LogLevel::Info, None, &format!("Listening on {}", HOST_ADDRESS));
         for stream in listener
             .incoming()
             .filter_map(Result::ok)
@@ -25,22 +40,42 @@ fn watch() {
             conn
                 .read_line(&mut buffer)
                 .unwrap();
-            if buffer.contains("reload") {
-                println!("Reloading WASM component...");
-                toxoid_runtime::load_wasm_component(GUEST_WASM_PATH)
-                    .unwrap_or_else(|e| println!("Failed to reload WASM component: {}", e));
+            // Messages are of the form "reload <path>" or "unload <name>",
+            // where a guest is named after the file stem of its WASM component
+            let mut args = buffer.split_whitespace();
+            match (args.next(), args.next()) {
+                // Guests are swapped on the main loop between frames, never while systems run
+                (Some("reload"), path) => {
+                    let path = path.unwrap_or(GUEST_WASM_PATH);
+                    let name = guest_name(path);
+                    log_watch(LogLevel::Info, Some(&name), &format!("Reloading WASM component {}...", name));
+                    toxoid_runtime::queue_load_wasm_component(&name, path);
+                }
+                (Some("unload"), Some(name)) => {
+                    log_watch(LogLevel::Info, Some(name), &format!("Unloading WASM component {}...", name));
+                    toxoid_runtime::queue_unload_wasm_component(name);
+                }
+                _ => log_watch(LogLevel::Warn, None, &format!("Unknown message: {}", buffer.trim()))
             }
         }
     });
+    // Engine settings follow the build profile, TOXOID_CACHE_DIR overrides where compiled guests are cached.
+    // Must happen before the first guest is loaded.
+    let mut settings = toxoid_runtime::EngineSettings::default();
+    if let Ok(cache_dir) = std::env::var("TOXOID_CACHE_DIR") {
+        settings.cache_dir = Some(cache_dir.into());
+    }
+    toxoid_runtime::configure_engine(settings);
     // Initial load of the main WASM component / game engine script
     // TODO: Some kind of deadlock on this when grabbing the engine and trying to
     // run Sokol / render loop / sapp at the same time...
     if std::path::Path::new(GUEST_WASM_PATH).exists() {
-        println!("Loading WASM component...");
-        toxoid_runtime::load_wasm_component(GUEST_WASM_PATH)
-            .unwrap_or_else(|e| println!("Failed to load WASM component: {}", e));
+        let name = guest_name(GUEST_WASM_PATH);
+        log_watch(LogLevel::Info, Some(&name), "Loading WASM component...");
+        toxoid_runtime::load_wasm_component(&name, GUEST_WASM_PATH)
+            .unwrap_or_else(|e| log_watch(LogLevel::Error, Some(&name), &format!("Failed to load WASM component: {}", e)));
     } else {
-        println!("WASM component not found at {}, modify the guest script source file or use `toxoid_cli build` to generate it", GUEST_WASM_PATH);
+        log_watch(LogLevel::Warn, None, &format!("WASM component not found at {}, modify the guest script source file or use `toxoid_cli build` to generate it", GUEST_WASM_PATH));
     }
 }
 
@@ -7,6 +7,8 @@ use std::io::prelude::*;
 use std::path::PathBuf;
 use std::fs;
 use std::thread;
+#[cfg(feature = "precompile")]
+use toxoid_runtime::{EngineSettings, OptLevel};
 
 // TODO: Make this configurable CLI argument
 const HOST_ADDRESS: &str = "127.0.0.1:7878";
@@ -44,6 +46,25 @@ enum Commands {
         // #[arg(long)]
         // static_linking: bool,
     },
+    /// Precompile a guest WASM component so the host doesn't compile it on startup
+    #[cfg(feature = "precompile")]
+    Precompile {
+        /// Path of the guest WASM file
+        #[arg(short, long, default_value = "guest.wasm")]
+        path: String,
+
+        /// Write a .cwasm file here instead of the host's component cache
+        #[arg(short, long)]
+        out_path: Option<PathBuf>,
+
+        /// Component cache directory of the host
+        #[arg(short, long, default_value = toxoid_runtime::DEFAULT_CACHE_DIR)]
+        cache_dir: PathBuf,
+
+        /// Compile with the settings of a debug host (no optimizations, debug info)
+        #[arg(short, long)]
+        debug: bool,
+    },
 }
 
 fn build_guest(path: &str, out_path: &PathBuf, host_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
@@ -68,7 +89,7 @@ fn build_guest(path: &str, out_path: &PathBuf, host_path: &PathBuf) -> Result<()
 
         // Connect to the server using TcpStream
         let mut conn = std::net::TcpStream::connect(HOST_ADDRESS)?;
-        conn.write_all(format!("reload {}", "guest.wasm").as_bytes())?;
+        conn.write_all(format!("reload {}", host_path.display()).as_bytes())?;
         println!("Sent reload message to host...");
     } else {
         println!("Build failed, skipping file move.");
@@ -129,6 +150,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Ensure the host process is terminated when the CLI exits
             host_process.kill().expect("Failed to kill host process");
         }
+        #[cfg(feature = "precompile")]
+        Commands::Precompile { path, out_path, cache_dir, debug } => {
+            // Must match the engine settings of the host that will load the component
+            let settings = if *debug {
+                EngineSettings { opt_level: OptLevel::None, debug_info: true, cache_dir: Some(cache_dir.clone()) }
+            } else {
+                EngineSettings { opt_level: OptLevel::Speed, debug_info: false, cache_dir: Some(cache_dir.clone()) }
+            };
+            toxoid_runtime::configure_engine(settings);
+            let precompiled = toxoid_runtime::precompile_wasm_component(path, out_path.as_deref())
+                .map_err(|e| format!("Failed to precompile {}: {:?}", path, e))?;
+            println!("Precompiled {} to {}", path, precompiled.display());
+        }
     }
 
     Ok(())
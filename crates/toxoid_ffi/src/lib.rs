@@ -141,7 +141,8 @@ pub extern "C" fn toxoid_entity_new(desc_data: *const u8, desc_len: usize) -> *m
     let entity = ToxoidEntity::new(EntityDesc {
         name: Some(name),
         add,
-        prefab
+        prefab,
+        values: None
     }, None);
 
     let id = get_next_id();
@@ -175,7 +176,8 @@ pub extern "C" fn toxoid_entity_remove(entity: *const Entity, component_id: u64)
             let mut api_entity = ToxoidEntity::new(EntityDesc {
                 name: None,
                 add: None,
-                prefab: false
+                prefab: false,
+                values: None
             }, None);
             api_entity.remove(component_id);
         }
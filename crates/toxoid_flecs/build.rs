@@ -51,6 +51,7 @@ fn main() {
     }
     
     // Compile Flecs
+    let rest = std::env::var("CARGO_FEATURE_REST").is_ok();
     let mut build = cc::Build::new();
     build
         .define("FLECS_CUSTOM_BUILD", None)
@@ -72,10 +73,17 @@ fn main() {
         .define("FLECS_LOG", None)
         // .define("FLECS_APP", None)
         .define("FLECS_OS_API_IMPL", None)
-        // .define("FLECS_HTTP", None)
-        // .define("FLECS_REST", None)
         .define("FLECS_JOURNAL", None)
         .define("NDEBUG", None);
+    // HTTP server for the Flecs explorer, only built when asked for since it opens a port
+    if rest {
+        build
+            .define("FLECS_HTTP", None)
+            .define("FLECS_REST", None);
+        if target.contains("windows") {
+            println!("cargo:rustc-link-lib=ws2_32");
+        }
+    }
     // Has a backtrace error otherwise. 
     if is_aarch64 || is_x86_64 {
         build.define("__wasm32__", None);
@@ -260,11 +260,54 @@ pub mod toxoid_component {
                     }
                 }
             }
+            /// Filters on a field of a query or system, see monitor
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum MonitorKind {
+                /// The field's component was written since the table was last seen
+                Changed,
+                /// Entities got the field's component since they were last seen
+                Added,
+            }
+            impl ::core::fmt::Debug for MonitorKind {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        MonitorKind::Changed => {
+                            f.debug_tuple("MonitorKind::Changed").finish()
+                        }
+                        MonitorKind::Added => {
+                            f.debug_tuple("MonitorKind::Added").finish()
+                        }
+                    }
+                }
+            }
+            impl MonitorKind {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> MonitorKind {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => MonitorKind::Changed,
+                        1 => MonitorKind::Added,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
             #[derive(Clone)]
             pub struct ComponentDesc {
                 pub name: _rt::String,
                 pub member_names: _rt::Vec<_rt::String>,
                 pub member_types: _rt::Vec<u8>,
+                /// Per member, may be left empty. The type of a member with the nested type code,
+                /// a component type registered before or an enum from register-enum.
+                pub member_type_ids: _rt::Vec<EcsEntityT>,
+                /// Per member, may be left empty. Elements of a fixed-size array stored inline,
+                /// 0 or 1 for a single value.
+                pub member_counts: _rt::Vec<u32>,
             }
             impl ::core::fmt::Debug for ComponentDesc {
                 fn fmt(
@@ -275,6 +318,118 @@ pub mod toxoid_component {
                         .field("name", &self.name)
                         .field("member-names", &self.member_names)
                         .field("member-types", &self.member_types)
+                        .field("member-type-ids", &self.member_type_ids)
+                        .field("member-counts", &self.member_counts)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct EnumConstant {
+                pub name: _rt::String,
+                pub value: u32,
+            }
+            impl ::core::fmt::Debug for EnumConstant {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("EnumConstant")
+                        .field("name", &self.name)
+                        .field("value", &self.value)
+                        .finish()
+                }
+            }
+            #[derive(Clone)]
+            pub struct EnumDesc {
+                pub name: _rt::String,
+                pub constants: _rt::Vec<EnumConstant>,
+                /// Flags that are combined, stored as a u32 instead of an s32
+                pub bitmask: bool,
+            }
+            impl ::core::fmt::Debug for EnumDesc {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("EnumDesc")
+                        .field("name", &self.name)
+                        .field("constants", &self.constants)
+                        .field("bitmask", &self.bitmask)
+                        .finish()
+                }
+            }
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum CommandKind {
+                Despawn,
+                Add,
+                Remove,
+            }
+            impl ::core::fmt::Debug for CommandKind {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        CommandKind::Despawn => {
+                            f.debug_tuple("CommandKind::Despawn").finish()
+                        }
+                        CommandKind::Add => f.debug_tuple("CommandKind::Add").finish(),
+                        CommandKind::Remove => {
+                            f.debug_tuple("CommandKind::Remove").finish()
+                        }
+                    }
+                }
+            }
+            impl CommandKind {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> CommandKind {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => CommandKind::Despawn,
+                        1 => CommandKind::Add,
+                        2 => CommandKind::Remove,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            /// A deferred command whose entity or component was deleted before it was applied
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct CommandError {
+                pub kind: CommandKind,
+                pub entity: EcsEntityT,
+                /// 0 for despawn
+                pub component: EcsEntityT,
+            }
+            impl ::core::fmt::Debug for CommandError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("CommandError")
+                        .field("kind", &self.kind)
+                        .field("entity", &self.entity)
+                        .field("component", &self.component)
+                        .finish()
+                }
+            }
+            /// Initial value of a component, its members in layout order encoded like in world snapshots
+            #[derive(Clone)]
+            pub struct ComponentValue {
+                pub component: EcsEntityT,
+                pub data: _rt::Vec<u8>,
+            }
+            impl ::core::fmt::Debug for ComponentValue {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ComponentValue")
+                        .field("component", &self.component)
+                        .field("data", &self.data)
                         .finish()
                 }
             }
@@ -283,6 +438,7 @@ pub mod toxoid_component {
                 pub name: Option<_rt::String>,
                 pub add: Option<_rt::Vec<EcsEntityT>>,
                 pub prefab: bool,
+                pub values: Option<_rt::Vec<ComponentValue>>,
             }
             impl ::core::fmt::Debug for EntityDesc {
                 fn fmt(
@@ -293,6 +449,7 @@ pub mod toxoid_component {
                         .field("name", &self.name)
                         .field("add", &self.add)
                         .field("prefab", &self.prefab)
+                        .field("values", &self.values)
                         .finish()
                 }
             }
@@ -761,6 +918,8 @@ pub mod toxoid_component {
                             name: name0,
                             member_names: member_names0,
                             member_types: member_types0,
+                            member_type_ids: member_type_ids0,
+                            member_counts: member_counts0,
                         } = init;
                         let vec1 = name0;
                         let ptr1 = vec1.as_ptr().cast::<u8>();
@@ -793,6 +952,12 @@ pub mod toxoid_component {
                         let vec4 = member_types0;
                         let ptr4 = vec4.as_ptr().cast::<u8>();
                         let len4 = vec4.len();
+                        let vec5 = member_type_ids0;
+                        let ptr5 = vec5.as_ptr().cast::<u8>();
+                        let len5 = vec5.len();
+                        let vec6 = member_counts0;
+                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                        let len6 = vec6.len();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
                         extern "C" {
@@ -804,6 +969,10 @@ pub mod toxoid_component {
                                 _: usize,
                                 _: *mut u8,
                                 _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
                             ) -> i32;
                         }
                         #[cfg(not(target_arch = "wasm32"))]
@@ -814,6 +983,10 @@ pub mod toxoid_component {
                             _: usize,
                             _: *mut u8,
                             _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
                         ) -> i32 {
                             unreachable!()
                         }
@@ -824,6 +997,10 @@ pub mod toxoid_component {
                             len3,
                             ptr4.cast_mut(),
                             len4,
+                            ptr5.cast_mut(),
+                            len5,
+                            ptr6.cast_mut(),
+                            len6,
                         );
                         if layout3.size() != 0 {
                             _rt::alloc::dealloc(result3.cast(), layout3);
@@ -882,27 +1059,30 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn from_ptr(ptr: PointerT) -> Component {
+                pub fn from_ptr(ptr: PointerT, component_type: EcsEntityT) -> Component {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
                         extern "C" {
                             #[link_name = "[static]component.from-ptr"]
-                            fn wit_import(_: i64) -> i32;
+                            fn wit_import(_: i64, _: i64) -> i32;
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i64) -> i32 {
+                        fn wit_import(_: i64, _: i64) -> i32 {
                             unreachable!()
                         }
-                        let ret = wit_import(_rt::as_i64(ptr));
+                        let ret = wit_import(
+                            _rt::as_i64(ptr),
+                            _rt::as_i64(component_type),
+                        );
                         Component::from_handle(ret as u32)
                     }
                 }
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                /// TODO: Change this to offset index instead of offset so that WASM guest does not have direct access to host memory.
-                pub fn set_member_u8(&self, offset: u32, value: u8) {
+                /// Members are addressed by their index in the component type, checked against its Flecs struct metadata
+                pub fn set_member_u8(&self, member: u32, value: u8) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -916,7 +1096,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i32(&value),
                         );
                     }
@@ -924,7 +1104,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u8(&self, offset: u32) -> u8 {
+                pub fn get_member_u8(&self, member: u32) -> u8 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -938,7 +1118,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as u8
                     }
@@ -946,7 +1126,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u16(&self, offset: u32, value: u16) {
+                pub fn set_member_u16(&self, member: u32, value: u16) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -960,7 +1140,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i32(&value),
                         );
                     }
@@ -968,7 +1148,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u16(&self, offset: u32) -> u16 {
+                pub fn get_member_u16(&self, member: u32) -> u16 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -982,7 +1162,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as u16
                     }
@@ -990,7 +1170,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u32(&self, offset: u32, value: u32) {
+                pub fn set_member_u32(&self, member: u32, value: u32) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1004,7 +1184,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i32(&value),
                         );
                     }
@@ -1012,7 +1192,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u32(&self, offset: u32) -> u32 {
+                pub fn get_member_u32(&self, member: u32) -> u32 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1026,7 +1206,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as u32
                     }
@@ -1034,7 +1214,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u64(&self, offset: u32, value: u64) {
+                pub fn set_member_u64(&self, member: u32, value: u64) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1048,7 +1228,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i64(&value),
                         );
                     }
@@ -1056,7 +1236,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u64(&self, offset: u32) -> u64 {
+                pub fn get_member_u64(&self, member: u32) -> u64 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1070,7 +1250,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as u64
                     }
@@ -1078,7 +1258,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i8(&self, offset: u32, value: i8) {
+                pub fn set_member_i8(&self, member: u32, value: i8) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1092,7 +1272,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i32(&value),
                         );
                     }
@@ -1100,7 +1280,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i8(&self, offset: u32) -> i8 {
+                pub fn get_member_i8(&self, member: u32) -> i8 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1114,7 +1294,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as i8
                     }
@@ -1122,7 +1302,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i16(&self, offset: u32, value: i16) {
+                pub fn set_member_i16(&self, member: u32, value: i16) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1136,7 +1316,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i32(&value),
                         );
                     }
@@ -1144,7 +1324,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i16(&self, offset: u32) -> i16 {
+                pub fn get_member_i16(&self, member: u32) -> i16 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1158,7 +1338,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as i16
                     }
@@ -1166,7 +1346,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i32(&self, offset: u32, value: i32) {
+                pub fn set_member_i32(&self, member: u32, value: i32) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1180,7 +1360,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i32(&value),
                         );
                     }
@@ -1188,7 +1368,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i32(&self, offset: u32) -> i32 {
+                pub fn get_member_i32(&self, member: u32) -> i32 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1202,7 +1382,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret
                     }
@@ -1210,7 +1390,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i64(&self, offset: u32, value: i64) {
+                pub fn set_member_i64(&self, member: u32, value: i64) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1224,7 +1404,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i64(&value),
                         );
                     }
@@ -1232,7 +1412,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i64(&self, offset: u32) -> i64 {
+                pub fn get_member_i64(&self, member: u32) -> i64 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1246,7 +1426,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret
                     }
@@ -1254,7 +1434,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_f32(&self, offset: u32, value: f32) {
+                pub fn set_member_f32(&self, member: u32, value: f32) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1268,7 +1448,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_f32(&value),
                         );
                     }
@@ -1276,7 +1456,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_f32(&self, offset: u32) -> f32 {
+                pub fn get_member_f32(&self, member: u32) -> f32 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1290,7 +1470,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret
                     }
@@ -1298,7 +1478,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_f64(&self, offset: u32, value: f64) {
+                pub fn set_member_f64(&self, member: u32, value: f64) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1312,7 +1492,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_f64(&value),
                         );
                     }
@@ -1320,7 +1500,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_f64(&self, offset: u32) -> f64 {
+                pub fn get_member_f64(&self, member: u32) -> f64 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1334,7 +1514,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret
                     }
@@ -1342,7 +1522,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_bool(&self, offset: u32, value: bool) {
+                pub fn set_member_bool(&self, member: u32, value: bool) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1356,7 +1536,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             match &value {
                                 true => 1,
                                 false => 0,
@@ -1367,7 +1547,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_bool(&self, offset: u32) -> bool {
+                pub fn get_member_bool(&self, member: u32) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1381,7 +1561,7 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         _rt::bool_lift(ret as u8)
                     }
@@ -1389,7 +1569,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_string(&self, offset: u32, value: &str) {
+                pub fn set_member_string(&self, member: u32, value: &str) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1406,7 +1586,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1415,7 +1595,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_string(&self, offset: u32) -> _rt::String {
+                pub fn get_member_string(&self, member: u32) -> _rt::String {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1433,7 +1613,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1444,7 +1624,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u8list(&self, offset: u32, value: &[u8]) {
+                pub fn set_member_u8list(&self, member: u32, value: &[u8]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1461,7 +1641,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1470,7 +1650,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u8list(&self, offset: u32) -> _rt::Vec<u8> {
+                pub fn get_member_u8list(&self, member: u32) -> _rt::Vec<u8> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1488,7 +1668,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1498,7 +1678,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u16list(&self, offset: u32, value: &[u16]) {
+                pub fn set_member_u16list(&self, member: u32, value: &[u16]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1515,7 +1695,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1524,7 +1704,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u16list(&self, offset: u32) -> _rt::Vec<u16> {
+                pub fn get_member_u16list(&self, member: u32) -> _rt::Vec<u16> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1542,7 +1722,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1552,7 +1732,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u32list(&self, offset: u32, value: &[u32]) {
+                pub fn set_member_u32list(&self, member: u32, value: &[u32]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1569,7 +1749,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1578,7 +1758,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u32list(&self, offset: u32) -> _rt::Vec<u32> {
+                pub fn get_member_u32list(&self, member: u32) -> _rt::Vec<u32> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1596,7 +1776,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1606,7 +1786,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_u64list(&self, offset: u32, value: &[u64]) {
+                pub fn set_member_u64list(&self, member: u32, value: &[u64]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1623,7 +1803,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1632,7 +1812,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_u64list(&self, offset: u32) -> _rt::Vec<u64> {
+                pub fn get_member_u64list(&self, member: u32) -> _rt::Vec<u64> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1650,7 +1830,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1660,7 +1840,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i8list(&self, offset: u32, value: &[i8]) {
+                pub fn set_member_i8list(&self, member: u32, value: &[i8]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1677,7 +1857,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1686,7 +1866,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i8list(&self, offset: u32) -> _rt::Vec<i8> {
+                pub fn get_member_i8list(&self, member: u32) -> _rt::Vec<i8> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1704,7 +1884,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1714,7 +1894,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i16list(&self, offset: u32, value: &[i16]) {
+                pub fn set_member_i16list(&self, member: u32, value: &[i16]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1731,7 +1911,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1740,7 +1920,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i16list(&self, offset: u32) -> _rt::Vec<i16> {
+                pub fn get_member_i16list(&self, member: u32) -> _rt::Vec<i16> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1758,7 +1938,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1768,7 +1948,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i32list(&self, offset: u32, value: &[i32]) {
+                pub fn set_member_i32list(&self, member: u32, value: &[i32]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1785,7 +1965,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1794,7 +1974,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i32list(&self, offset: u32) -> _rt::Vec<i32> {
+                pub fn get_member_i32list(&self, member: u32) -> _rt::Vec<i32> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1812,7 +1992,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1822,7 +2002,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_i64list(&self, offset: u32, value: &[i64]) {
+                pub fn set_member_i64list(&self, member: u32, value: &[i64]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1839,7 +2019,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1848,7 +2028,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_i64list(&self, offset: u32) -> _rt::Vec<i64> {
+                pub fn get_member_i64list(&self, member: u32) -> _rt::Vec<i64> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1866,7 +2046,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1876,7 +2056,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_f32list(&self, offset: u32, value: &[f32]) {
+                pub fn set_member_f32list(&self, member: u32, value: &[f32]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1893,7 +2073,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1902,7 +2082,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_f32list(&self, offset: u32) -> _rt::Vec<f32> {
+                pub fn get_member_f32list(&self, member: u32) -> _rt::Vec<f32> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1920,7 +2100,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1930,7 +2110,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_f64list(&self, offset: u32, value: &[f64]) {
+                pub fn set_member_f64list(&self, member: u32, value: &[f64]) {
                     unsafe {
                         let vec0 = value;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
@@ -1947,7 +2127,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             ptr0.cast_mut(),
                             len0,
                         );
@@ -1956,7 +2136,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_f64list(&self, offset: u32) -> _rt::Vec<f64> {
+                pub fn get_member_f64list(&self, member: u32) -> _rt::Vec<f64> {
                     unsafe {
                         #[repr(align(4))]
                         struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
@@ -1974,7 +2154,7 @@ pub mod toxoid_component {
                         fn wit_import(_: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, _rt::as_i32(&offset), ptr0);
+                        wit_import((self).handle() as i32, _rt::as_i32(&member), ptr0);
                         let l1 = *ptr0.add(0).cast::<*mut u8>();
                         let l2 = *ptr0.add(4).cast::<usize>();
                         let len3 = l2;
@@ -1984,7 +2164,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn set_member_pointer(&self, offset: u32, value: u64) {
+                pub fn set_member_pointer(&self, member: u32, value: u64) {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -1998,7 +2178,7 @@ pub mod toxoid_component {
                         }
                         wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                             _rt::as_i64(&value),
                         );
                     }
@@ -2006,7 +2186,7 @@ pub mod toxoid_component {
             }
             impl Component {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn get_member_pointer(&self, offset: u32) -> u64 {
+                pub fn get_member_pointer(&self, member: u32) -> u64 {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
@@ -2020,17 +2200,51 @@ pub mod toxoid_component {
                         }
                         let ret = wit_import(
                             (self).handle() as i32,
-                            _rt::as_i32(&offset),
+                            _rt::as_i32(&member),
                         );
                         ret as u64
                     }
                 }
             }
+            impl Component {
+                #[allow(unused_unsafe, clippy::all)]
+                /// View of a nested struct member, element index of a fixed-size array of structs
+                pub fn get_member_component(
+                    &self,
+                    member: u32,
+                    index: u32,
+                ) -> Component {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]component.get-member-component"]
+                            fn wit_import(_: i32, _: i32, _: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&member),
+                            _rt::as_i32(&index),
+                        );
+                        Component::from_handle(ret as u32)
+                    }
+                }
+            }
             impl Entity {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn new(init: &EntityDesc, inherits: Option<EcsEntityT>) -> Self {
                     unsafe {
-                        let EntityDesc { name: name0, add: add0, prefab: prefab0 } = init;
+                        let mut cleanup_list = _rt::Vec::new();
+                        let EntityDesc {
+                            name: name0,
+                            add: add0,
+                            prefab: prefab0,
+                            values: values0,
+                        } = init;
                         let (result2_0, result2_1, result2_2) = match name0 {
                             Some(e) => {
                                 let vec1 = e;
@@ -2049,7 +2263,41 @@ pub mod toxoid_component {
                             }
                             None => (0i32, ::core::ptr::null_mut(), 0usize),
                         };
-                        let (result5_0, result5_1) = match inherits {
+                        let (result8_0, result8_1, result8_2) = match values0 {
+                            Some(e) => {
+                                let vec7 = e;
+                                let len7 = vec7.len();
+                                let layout7 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec7.len() * 16,
+                                    8,
+                                );
+                                let result7 = if layout7.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout7);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec7.into_iter().enumerate() {
+                                    let base = result7.add(i * 16);
+                                    {
+                                        let ComponentValue { component: component5, data: data5 } = e;
+                                        *base.add(0).cast::<i64>() = _rt::as_i64(component5);
+                                        let vec6 = data5;
+                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                        let len6 = vec6.len();
+                                        *base.add(12).cast::<usize>() = len6;
+                                        *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                    }
+                                }
+                                cleanup_list.extend_from_slice(&[(result7, layout7)]);
+                                (1i32, result7, len7)
+                            }
+                            None => (0i32, ::core::ptr::null_mut(), 0usize),
+                        };
+                        let (result9_0, result9_1) = match inherits {
                             Some(e) => (1i32, _rt::as_i64(e)),
                             None => (0i32, 0i64),
                         };
@@ -2066,6 +2314,9 @@ pub mod toxoid_component {
                                 _: usize,
                                 _: i32,
                                 _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: i32,
                                 _: i64,
                             ) -> i32;
                         }
@@ -2079,6 +2330,9 @@ pub mod toxoid_component {
                             _: usize,
                             _: i32,
                             _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
                             _: i64,
                         ) -> i32 {
                             unreachable!()
@@ -2094,13 +2348,187 @@ pub mod toxoid_component {
                                 true => 1,
                                 false => 0,
                             },
-                            result5_0,
-                            result5_1,
+                            result8_0,
+                            result8_1,
+                            result8_2,
+                            result9_0,
+                            result9_1,
                         );
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
                         Entity::from_handle(ret as u32)
                     }
                 }
             }
+            impl Entity {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Like the constructor, fails instead of leaving out initial values that can't be set
+                pub fn create(
+                    init: &EntityDesc,
+                    inherits: Option<EcsEntityT>,
+                ) -> Result<EcsEntityT, _rt::String> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[repr(align(8))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 16],
+                        );
+                        let EntityDesc {
+                            name: name0,
+                            add: add0,
+                            prefab: prefab0,
+                            values: values0,
+                        } = init;
+                        let (result2_0, result2_1, result2_2) = match name0 {
+                            Some(e) => {
+                                let vec1 = e;
+                                let ptr1 = vec1.as_ptr().cast::<u8>();
+                                let len1 = vec1.len();
+                                (1i32, ptr1.cast_mut(), len1)
+                            }
+                            None => (0i32, ::core::ptr::null_mut(), 0usize),
+                        };
+                        let (result4_0, result4_1, result4_2) = match add0 {
+                            Some(e) => {
+                                let vec3 = e;
+                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                let len3 = vec3.len();
+                                (1i32, ptr3.cast_mut(), len3)
+                            }
+                            None => (0i32, ::core::ptr::null_mut(), 0usize),
+                        };
+                        let (result8_0, result8_1, result8_2) = match values0 {
+                            Some(e) => {
+                                let vec7 = e;
+                                let len7 = vec7.len();
+                                let layout7 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec7.len() * 16,
+                                    8,
+                                );
+                                let result7 = if layout7.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout7);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec7.into_iter().enumerate() {
+                                    let base = result7.add(i * 16);
+                                    {
+                                        let ComponentValue { component: component5, data: data5 } = e;
+                                        *base.add(0).cast::<i64>() = _rt::as_i64(component5);
+                                        let vec6 = data5;
+                                        let ptr6 = vec6.as_ptr().cast::<u8>();
+                                        let len6 = vec6.len();
+                                        *base.add(12).cast::<usize>() = len6;
+                                        *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                    }
+                                }
+                                cleanup_list.extend_from_slice(&[(result7, layout7)]);
+                                (1i32, result7, len7)
+                            }
+                            None => (0i32, ::core::ptr::null_mut(), 0usize),
+                        };
+                        let (result9_0, result9_1) = match inherits {
+                            Some(e) => (1i32, _rt::as_i64(e)),
+                            None => (0i32, 0i64),
+                        };
+                        let ptr10 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[static]entity.create"]
+                            fn wit_import(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: i32,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: i32,
+                                _: i64,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: i64,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            result2_0,
+                            result2_1,
+                            result2_2,
+                            result4_0,
+                            result4_1,
+                            result4_2,
+                            match prefab0 {
+                                true => 1,
+                                false => 0,
+                            },
+                            result8_0,
+                            result8_1,
+                            result8_2,
+                            result9_0,
+                            result9_1,
+                            ptr10,
+                        );
+                        let l11 = i32::from(*ptr10.add(0).cast::<u8>());
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        match l11 {
+                            0 => {
+                                let e = {
+                                    let l12 = *ptr10.add(8).cast::<i64>();
+                                    l12 as u64
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l13 = *ptr10.add(8).cast::<*mut u8>();
+                                    let l14 = *ptr10.add(12).cast::<usize>();
+                                    let len15 = l14;
+                                    let bytes15 = _rt::Vec::from_raw_parts(
+                                        l13.cast(),
+                                        len15,
+                                        len15,
+                                    );
+                                    _rt::string_lift(bytes15)
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        }
+                    }
+                }
+            }
             impl Entity {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn from_id(id: u64) -> Entity {
@@ -2551,29 +2979,6 @@ pub mod toxoid_component {
                     }
                 }
             }
-            impl Query {
-                #[allow(unused_unsafe, clippy::all)]
-                pub fn order_by(&self, sorting: SortingDesc) {
-                    unsafe {
-                        let SortingDesc { id: id0, callback: callback0 } = &sorting;
-                        #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
-                        extern "C" {
-                            #[link_name = "[method]query.order-by"]
-                            fn wit_import(_: i32, _: i64, _: i32);
-                        }
-                        #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: i64, _: i32) {
-                            unreachable!()
-                        }
-                        wit_import(
-                            (self).handle() as i32,
-                            _rt::as_i64(id0),
-                            (callback0).take_handle() as i32,
-                        );
-                    }
-                }
-            }
             impl Query {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn iter(&self) -> Iter {
@@ -2698,18 +3103,236 @@ pub mod toxoid_component {
                     }
                 }
             }
-            impl Iter {
+            impl Query {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn new(ptr: u64) -> Self {
+                /// Views of the components of a field, writes through them mark the component as modified
+                /// on its entity. Views of a field the query only reads fail to write.
+                pub fn field_views(
+                    &self,
+                    index: i8,
+                    read_only: bool,
+                ) -> _rt::Vec<Component> {
                     unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 8],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
                         extern "C" {
-                            #[link_name = "[constructor]iter"]
-                            fn wit_import(_: i64) -> i32;
+                            #[link_name = "[method]query.field-views"]
+                            fn wit_import(_: i32, _: i32, _: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i64) -> i32 {
+                        fn wit_import(_: i32, _: i32, _: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            match &read_only {
+                                true => 1,
+                                false => 0,
+                            },
+                            ptr0,
+                        );
+                        let l1 = *ptr0.add(0).cast::<*mut u8>();
+                        let l2 = *ptr0.add(4).cast::<usize>();
+                        let base4 = l1;
+                        let len4 = l2;
+                        let mut result4 = _rt::Vec::with_capacity(len4);
+                        for i in 0..len4 {
+                            let base = base4.add(i * 4);
+                            let e4 = {
+                                let l3 = *base.add(0).cast::<i32>();
+                                Component::from_handle(l3 as u32)
+                            };
+                            result4.push(e4);
+                        }
+                        _rt::cabi_dealloc(base4, len4 * 4, 4);
+                        result4
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Keep the tables the query matches in a cache, which whole query change detection needs.
+                /// Call before build.
+                pub fn cache(&self) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.cache"]
+                            fn wit_import(_: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32);
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Track a field for field-changed or field-added, call before build
+                pub fn monitor(&self, index: i8, kind: MonitorKind) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.monitor"]
+                            fn wit_import(_: i32, _: i32, _: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: i32) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            kind.clone() as i32,
+                        );
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Change detection. Whether a table the query matches changed since the last iteration
+                pub fn changed(&self) -> bool {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.changed"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        _rt::bool_lift(ret as u8)
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Whether the current table changed, skip leaves it marked as changed for the next iteration
+                pub fn iter_changed(&self) -> bool {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.iter-changed"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        _rt::bool_lift(ret as u8)
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn skip(&self) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.skip"]
+                            fn wit_import(_: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32);
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Whether the component of a monitored field was written since the query last moved on from
+                /// the table, and per entity whether it got the component since then. Fields that aren't
+                /// monitored report whether the table changed.
+                pub fn field_changed(&self, index: i8) -> bool {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.field-changed"]
+                            fn wit_import(_: i32, _: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                        );
+                        _rt::bool_lift(ret as u8)
+                    }
+                }
+            }
+            impl Query {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn field_added(&self, index: i8) -> _rt::Vec<bool> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 8],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]query.field-added"]
+                            fn wit_import(_: i32, _: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, _rt::as_i32(&index), ptr0);
+                        let l1 = *ptr0.add(0).cast::<*mut u8>();
+                        let l2 = *ptr0.add(4).cast::<usize>();
+                        let base4 = l1;
+                        let len4 = l2;
+                        let mut result4 = _rt::Vec::with_capacity(len4);
+                        for i in 0..len4 {
+                            let base = base4.add(i * 1);
+                            let e4 = {
+                                let l3 = i32::from(*base.add(0).cast::<u8>());
+                                _rt::bool_lift(l3 as u8)
+                            };
+                            result4.push(e4);
+                        }
+                        _rt::cabi_dealloc(base4, len4 * 1, 1);
+                        result4
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn new(ptr: u64) -> Self {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[constructor]iter"]
+                            fn wit_import(_: i64) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i64) -> i32 {
                             unreachable!()
                         }
                         let ret = wit_import(_rt::as_i64(&ptr));
@@ -2755,6 +3378,26 @@ pub mod toxoid_component {
                     }
                 }
             }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                /// System or observer the iterator belongs to, 0 for queries
+                pub fn system(&self) -> EcsEntityT {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.system"]
+                            fn wit_import(_: i32) -> i64;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i64 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        ret as u64
+                    }
+                }
+            }
             impl Iter {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn entities(&self) -> _rt::Vec<Entity> {
@@ -2822,53 +3465,348 @@ pub mod toxoid_component {
                     }
                 }
             }
-            impl Callback {
+            impl Iter {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn new(handle: u64) -> Self {
+                /// See the query resource
+                pub fn field_views(
+                    &self,
+                    index: i8,
+                    read_only: bool,
+                ) -> _rt::Vec<Component> {
                     unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 8],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
                         extern "C" {
-                            #[link_name = "[constructor]callback"]
-                            fn wit_import(_: i64) -> i32;
+                            #[link_name = "[method]iter.field-views"]
+                            fn wit_import(_: i32, _: i32, _: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i64) -> i32 {
+                        fn wit_import(_: i32, _: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        let ret = wit_import(_rt::as_i64(&handle));
-                        Callback::from_handle(ret as u32)
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            match &read_only {
+                                true => 1,
+                                false => 0,
+                            },
+                            ptr0,
+                        );
+                        let l1 = *ptr0.add(0).cast::<*mut u8>();
+                        let l2 = *ptr0.add(4).cast::<usize>();
+                        let base4 = l1;
+                        let len4 = l2;
+                        let mut result4 = _rt::Vec::with_capacity(len4);
+                        for i in 0..len4 {
+                            let base = base4.add(i * 4);
+                            let e4 = {
+                                let l3 = *base.add(0).cast::<i32>();
+                                Component::from_handle(l3 as u32)
+                            };
+                            result4.push(e4);
+                        }
+                        _rt::cabi_dealloc(base4, len4 * 4, 4);
+                        result4
                     }
                 }
             }
-            impl Callback {
+            impl Iter {
                 #[allow(unused_unsafe, clippy::all)]
-                pub fn run(&self, iter: Iter) {
+                /// Bulk copies of a field, one call per column instead of one per entity and member.
+                /// A member column holds the packed values of one member, which must be a number or bool.
+                pub fn column(
+                    &self,
+                    index: i8,
+                    member: u32,
+                    member_type: MemberType,
+                ) -> _rt::Vec<u8> {
                     unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 8],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
                         extern "C" {
-                            #[link_name = "[method]callback.run"]
-                            fn wit_import(_: i32, _: i32);
+                            #[link_name = "[method]iter.column"]
+                            fn wit_import(_: i32, _: i32, _: i32, _: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: i32) {
+                        fn wit_import(_: i32, _: i32, _: i32, _: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, (&iter).take_handle() as i32);
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            _rt::as_i32(&member),
+                            member_type.clone() as i32,
+                            ptr0,
+                        );
+                        let l1 = *ptr0.add(0).cast::<*mut u8>();
+                        let l2 = *ptr0.add(4).cast::<usize>();
+                        let len3 = l2;
+                        _rt::Vec::from_raw_parts(l1.cast(), len3, len3)
                     }
                 }
             }
-            impl Callback {
+            impl Iter {
                 #[allow(unused_unsafe, clippy::all)]
-                /// This is prefixed because resources already have a `handle` method.
-                pub fn cb_handle(&self) -> PointerT {
+                pub fn set_column(
+                    &self,
+                    index: i8,
+                    member: u32,
+                    member_type: MemberType,
+                    data: &[u8],
+                ) {
                     unsafe {
+                        let vec0 = data;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
                         #[cfg(target_arch = "wasm32")]
                         #[link(wasm_import_module = "toxoid-component:component/ecs")]
                         extern "C" {
-                            #[link_name = "[method]callback.cb-handle"]
-                            fn wit_import(_: i32) -> i64;
+                            #[link_name = "[method]iter.set-column"]
+                            fn wit_import(
+                                _: i32,
+                                _: i32,
+                                _: i32,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(
+                            _: i32,
+                            _: i32,
+                            _: i32,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                        ) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            _rt::as_i32(&member),
+                            member_type.clone() as i32,
+                            ptr0.cast_mut(),
+                            len0,
+                        );
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                /// The whole component array of a field with the host struct layout, for components with plain data members
+                pub fn field_data(&self, index: i8) -> _rt::Vec<u8> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 8],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.field-data"]
+                            fn wit_import(_: i32, _: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, _rt::as_i32(&index), ptr0);
+                        let l1 = *ptr0.add(0).cast::<*mut u8>();
+                        let l2 = *ptr0.add(4).cast::<usize>();
+                        let len3 = l2;
+                        _rt::Vec::from_raw_parts(l1.cast(), len3, len3)
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn set_field_data(&self, index: i8, data: &[u8]) {
+                    unsafe {
+                        let vec0 = data;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.set-field-data"]
+                            fn wit_import(_: i32, _: i32, _: *mut u8, _: usize);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: *mut u8, _: usize) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            ptr0.cast_mut(),
+                            len0,
+                        );
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Change detection, see the query resource
+                pub fn changed(&self) -> bool {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.changed"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        _rt::bool_lift(ret as u8)
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn skip(&self) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.skip"]
+                            fn wit_import(_: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32);
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn field_changed(&self, index: i8) -> bool {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.field-changed"]
+                            fn wit_import(_: i32, _: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                        );
+                        _rt::bool_lift(ret as u8)
+                    }
+                }
+            }
+            impl Iter {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn field_added(&self, index: i8) -> _rt::Vec<bool> {
+                    unsafe {
+                        #[repr(align(4))]
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 8],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]iter.field-added"]
+                            fn wit_import(_: i32, _: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, _rt::as_i32(&index), ptr0);
+                        let l1 = *ptr0.add(0).cast::<*mut u8>();
+                        let l2 = *ptr0.add(4).cast::<usize>();
+                        let base4 = l1;
+                        let len4 = l2;
+                        let mut result4 = _rt::Vec::with_capacity(len4);
+                        for i in 0..len4 {
+                            let base = base4.add(i * 1);
+                            let e4 = {
+                                let l3 = i32::from(*base.add(0).cast::<u8>());
+                                _rt::bool_lift(l3 as u8)
+                            };
+                            result4.push(e4);
+                        }
+                        _rt::cabi_dealloc(base4, len4 * 1, 1);
+                        result4
+                    }
+                }
+            }
+            impl Callback {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn new(handle: u64) -> Self {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[constructor]callback"]
+                            fn wit_import(_: i64) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i64) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import(_rt::as_i64(&handle));
+                        Callback::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl Callback {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn run(&self, iter: Iter) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]callback.run"]
+                            fn wit_import(_: i32, _: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32, (&iter).take_handle() as i32);
+                    }
+                }
+            }
+            impl Callback {
+                #[allow(unused_unsafe, clippy::all)]
+                /// This is prefixed because resources already have a `handle` method.
+                pub fn cb_handle(&self) -> PointerT {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]callback.cb-handle"]
+                            fn wit_import(_: i32) -> i64;
                         }
                         #[cfg(not(target_arch = "wasm32"))]
                         fn wit_import(_: i32) -> i64 {
@@ -3013,6 +3951,29 @@ pub mod toxoid_component {
                     }
                 }
             }
+            impl System {
+                #[allow(unused_unsafe, clippy::all)]
+                /// See the query resource
+                pub fn monitor(&self, index: i8, kind: MonitorKind) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]system.monitor"]
+                            fn wit_import(_: i32, _: i32, _: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32, _: i32, _: i32) {
+                            unreachable!()
+                        }
+                        wit_import(
+                            (self).handle() as i32,
+                            _rt::as_i32(&index),
+                            kind.clone() as i32,
+                        );
+                    }
+                }
+            }
             impl System {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn order_by(&self, sorting: SortingDesc) {
@@ -3404,156 +4365,1448 @@ pub mod toxoid_component {
                     }
                 }
             }
-            impl Observer {
-                #[allow(unused_unsafe, clippy::all)]
-                pub fn build(&self) {
-                    unsafe {
-                        #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
-                        extern "C" {
-                            #[link_name = "[method]observer.build"]
-                            fn wit_import(_: i32);
+            impl Observer {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn get_id(&self) -> EcsEntityT {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]observer.get-id"]
+                            fn wit_import(_: i32) -> i64;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i64 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        ret as u64
+                    }
+                }
+            }
+            impl Observer {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn build(&self) {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]observer.build"]
+                            fn wit_import(_: i32);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) {
+                            unreachable!()
+                        }
+                        wit_import((self).handle() as i32);
+                    }
+                }
+            }
+            impl Observer {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn callback(&self) -> Callback {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                        extern "C" {
+                            #[link_name = "[method]observer.callback"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        Callback::from_handle(ret as u32)
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn add_singleton(component: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "add-singleton"]
+                        fn wit_import(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(component));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn get_singleton(component: EcsEntityT) -> Component {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "get-singleton"]
+                        fn wit_import(_: i64) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(_rt::as_i64(component));
+                    Component::from_handle(ret as u32)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn remove_singleton(component: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "remove-singleton"]
+                        fn wit_import(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(component));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn add_entity(entity: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "add-entity"]
+                        fn wit_import(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(entity));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn remove_entity(entity: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "remove-entity"]
+                        fn wit_import(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(entity));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// get-entity: func(entity: ecs-entity-t) -> entity;
+            /// get-entity-named: func(name: string) -> entity;
+            pub fn has_entity_named(name: &str) -> bool {
+                unsafe {
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "has-entity-named"]
+                        fn wit_import(_: *mut u8, _: usize) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(ptr0.cast_mut(), len0);
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn get_component_id(component_name: &str) -> EcsEntityT {
+                unsafe {
+                    let vec0 = component_name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "get-component-id"]
+                        fn wit_import(_: *mut u8, _: usize) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(ptr0.cast_mut(), len0);
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn register_enum(desc: &EnumDesc) -> EcsEntityT {
+                unsafe {
+                    let EnumDesc {
+                        name: name0,
+                        constants: constants0,
+                        bitmask: bitmask0,
+                    } = desc;
+                    let vec1 = name0;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let vec4 = constants0;
+                    let len4 = vec4.len();
+                    let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec4.len() * 12,
+                        4,
+                    );
+                    let result4 = if layout4.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout4);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec4.into_iter().enumerate() {
+                        let base = result4.add(i * 12);
+                        {
+                            let EnumConstant { name: name2, value: value2 } = e;
+                            let vec3 = name2;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            *base.add(4).cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            *base.add(8).cast::<i32>() = _rt::as_i32(value2);
+                        }
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "register-enum"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                        ) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                    ) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(
+                        ptr1.cast_mut(),
+                        len1,
+                        result4,
+                        len4,
+                        match bitmask0 {
+                            true => 1,
+                            false => 0,
+                        },
+                    );
+                    if layout4.size() != 0 {
+                        _rt::alloc::dealloc(result4.cast(), layout4);
+                    }
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Entities as JSON, with their children when recursive. Fails for entities that aren't alive.
+            pub fn entity_to_json(
+                entity: EcsEntityT,
+                recursive: bool,
+            ) -> Result<_rt::String, _rt::String> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "entity-to-json"]
+                        fn wit_import(_: i64, _: i32, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64, _: i32, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(
+                        _rt::as_i64(entity),
+                        match &recursive {
+                            true => 1,
+                            false => 0,
+                        },
+                        ptr0,
+                    );
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => {
+                            let e = {
+                                let l2 = *ptr0.add(4).cast::<*mut u8>();
+                                let l3 = *ptr0.add(8).cast::<usize>();
+                                let len4 = l3;
+                                let bytes4 = _rt::Vec::from_raw_parts(
+                                    l2.cast(),
+                                    len4,
+                                    len4,
+                                );
+                                _rt::string_lift(bytes4)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr0.add(4).cast::<*mut u8>();
+                                let l6 = *ptr0.add(8).cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn entity_from_json(
+                json: &str,
+                parent: Option<EcsEntityT>,
+            ) -> Result<EcsEntityT, _rt::String> {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                    let vec0 = json;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let (result1_0, result1_1) = match parent {
+                        Some(e) => (1i32, _rt::as_i64(e)),
+                        None => (0i32, 0i64),
+                    };
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "entity-from-json"]
+                        fn wit_import(_: *mut u8, _: usize, _: i32, _: i64, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: i32, _: i64, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, result1_0, result1_1, ptr2);
+                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
+                    match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr2.add(8).cast::<i64>();
+                                l4 as u64
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr2.add(8).cast::<*mut u8>();
+                                let l6 = *ptr2.add(12).cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Deferred structural changes. They are applied in order at the start of the next phase,
+            /// the ones from the last phase at the start of the next frame.
+            /// A spawned entity is alive right away and gets its components when the commands are applied.
+            pub fn command_spawn() -> EcsEntityT {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "command-spawn"]
+                        fn wit_import() -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import();
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn command_despawn(entity: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "command-despawn"]
+                        fn wit_import(_: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(entity));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn command_add(entity: EcsEntityT, component: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "command-add"]
+                        fn wit_import(_: i64, _: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64, _: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(entity), _rt::as_i64(component));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn command_remove(entity: EcsEntityT, component: EcsEntityT) {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "command-remove"]
+                        fn wit_import(_: i64, _: i64);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64, _: i64) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(entity), _rt::as_i64(component));
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Apply the pending commands now, outside of systems
+            pub fn flush_commands() -> _rt::Vec<CommandError> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "flush-commands"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base6 = l1;
+                    let len6 = l2;
+                    let mut result6 = _rt::Vec::with_capacity(len6);
+                    for i in 0..len6 {
+                        let base = base6.add(i * 24);
+                        let e6 = {
+                            let l3 = i32::from(*base.add(0).cast::<u8>());
+                            let l4 = *base.add(8).cast::<i64>();
+                            let l5 = *base.add(16).cast::<i64>();
+                            CommandError {
+                                kind: CommandKind::_lift(l3 as u8),
+                                entity: l4 as u64,
+                                component: l5 as u64,
+                            }
+                        };
+                        result6.push(e6);
+                    }
+                    _rt::cabi_dealloc(base6, len6 * 24, 8);
+                    result6
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Errors of the commands applied at flush points since the last call
+            pub fn take_command_errors() -> _rt::Vec<CommandError> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    extern "C" {
+                        #[link_name = "take-command-errors"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base6 = l1;
+                    let len6 = l2;
+                    let mut result6 = _rt::Vec::with_capacity(len6);
+                    for i in 0..len6 {
+                        let base = base6.add(i * 24);
+                        let e6 = {
+                            let l3 = i32::from(*base.add(0).cast::<u8>());
+                            let l4 = *base.add(8).cast::<i64>();
+                            let l5 = *base.add(16).cast::<i64>();
+                            CommandError {
+                                kind: CommandKind::_lift(l3 as u8),
+                                entity: l4 as u64,
+                                component: l5 as u64,
+                            }
+                        };
+                        result6.push(e6);
+                    }
+                    _rt::cabi_dealloc(base6, len6 * 24, 8);
+                    result6
+                }
+            }
+        }
+        #[allow(dead_code, clippy::all)]
+        pub mod engine {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// Key codes of Sokol, which are the GLFW key codes
+            pub type KeyCode = u32;
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct TimeInfo {
+                /// Seconds since the last frame
+                pub delta: f32,
+                /// Seconds since the world started
+                pub elapsed: f64,
+                pub frame: u64,
+            }
+            impl ::core::fmt::Debug for TimeInfo {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("TimeInfo")
+                        .field("delta", &self.delta)
+                        .field("elapsed", &self.elapsed)
+                        .field("frame", &self.frame)
+                        .finish()
+                }
+            }
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct WindowInfo {
+                pub width: u32,
+                pub height: u32,
+                /// Size the game is rendered at, the window size clamped to the game's aspect ratio
+                pub render_width: u32,
+                pub render_height: u32,
+            }
+            impl ::core::fmt::Debug for WindowInfo {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("WindowInfo")
+                        .field("width", &self.width)
+                        .field("height", &self.height)
+                        .field("render-width", &self.render_width)
+                        .field("render-height", &self.render_height)
+                        .finish()
+                }
+            }
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct MouseState {
+                pub x: f32,
+                pub y: f32,
+                /// Movement and scrolling since the last frame
+                pub dx: f32,
+                pub dy: f32,
+                pub scroll_x: f32,
+                pub scroll_y: f32,
+                pub left: bool,
+                pub right: bool,
+                pub middle: bool,
+            }
+            impl ::core::fmt::Debug for MouseState {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("MouseState")
+                        .field("x", &self.x)
+                        .field("y", &self.y)
+                        .field("dx", &self.dx)
+                        .field("dy", &self.dy)
+                        .field("scroll-x", &self.scroll_x)
+                        .field("scroll-y", &self.scroll_y)
+                        .field("left", &self.left)
+                        .field("right", &self.right)
+                        .field("middle", &self.middle)
+                        .finish()
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn time() -> TimeInfo {
+                unsafe {
+                    #[repr(align(8))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 24]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 24]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/engine")]
+                    extern "C" {
+                        #[link_name = "time"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<f32>();
+                    let l2 = *ptr0.add(8).cast::<f64>();
+                    let l3 = *ptr0.add(16).cast::<i64>();
+                    TimeInfo {
+                        delta: l1,
+                        elapsed: l2,
+                        frame: l3 as u64,
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn window() -> WindowInfo {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/engine")]
+                    extern "C" {
+                        #[link_name = "window"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<i32>();
+                    let l2 = *ptr0.add(4).cast::<i32>();
+                    let l3 = *ptr0.add(8).cast::<i32>();
+                    let l4 = *ptr0.add(12).cast::<i32>();
+                    WindowInfo {
+                        width: l1 as u32,
+                        height: l2 as u32,
+                        render_width: l3 as u32,
+                        render_height: l4 as u32,
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn mouse() -> MouseState {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 28]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 28]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/engine")]
+                    extern "C" {
+                        #[link_name = "mouse"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<f32>();
+                    let l2 = *ptr0.add(4).cast::<f32>();
+                    let l3 = *ptr0.add(8).cast::<f32>();
+                    let l4 = *ptr0.add(12).cast::<f32>();
+                    let l5 = *ptr0.add(16).cast::<f32>();
+                    let l6 = *ptr0.add(20).cast::<f32>();
+                    let l7 = i32::from(*ptr0.add(24).cast::<u8>());
+                    let l8 = i32::from(*ptr0.add(25).cast::<u8>());
+                    let l9 = i32::from(*ptr0.add(26).cast::<u8>());
+                    MouseState {
+                        x: l1,
+                        y: l2,
+                        dx: l3,
+                        dy: l4,
+                        scroll_x: l5,
+                        scroll_y: l6,
+                        left: _rt::bool_lift(l7 as u8),
+                        right: _rt::bool_lift(l8 as u8),
+                        middle: _rt::bool_lift(l9 as u8),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn key_down(key: KeyCode) -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/engine")]
+                    extern "C" {
+                        #[link_name = "key-down"]
+                        fn wit_import(_: i32) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i32) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(_rt::as_i32(key));
+                    _rt::bool_lift(ret as u8)
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn keys_down() -> _rt::Vec<KeyCode> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/engine")]
+                    extern "C" {
+                        #[link_name = "keys-down"]
+                        fn wit_import(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let len3 = l2;
+                    _rt::Vec::from_raw_parts(l1.cast(), len3, len3)
+                }
+            }
+        }
+        #[allow(dead_code, clippy::all)]
+        pub mod assets {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// The entity an asset is loaded into
+            pub type AssetHandle = u64;
+            #[derive(Clone)]
+            pub enum AssetStatus {
+                Loading,
+                Loaded,
+                Failed(_rt::String),
+            }
+            impl ::core::fmt::Debug for AssetStatus {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        AssetStatus::Loading => {
+                            f.debug_tuple("AssetStatus::Loading").finish()
+                        }
+                        AssetStatus::Loaded => {
+                            f.debug_tuple("AssetStatus::Loaded").finish()
+                        }
+                        AssetStatus::Failed(e) => {
+                            f.debug_tuple("AssetStatus::Failed").field(e).finish()
+                        }
+                    }
+                }
+            }
+            #[derive(Clone)]
+            pub struct AssetEvent {
+                pub handle: AssetHandle,
+                pub status: AssetStatus,
+            }
+            impl ::core::fmt::Debug for AssetEvent {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("AssetEvent")
+                        .field("handle", &self.handle)
+                        .field("status", &self.status)
+                        .finish()
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn load_image(path: &str) -> AssetHandle {
+                unsafe {
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "load-image"]
+                        fn wit_import(_: *mut u8, _: usize) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(ptr0.cast_mut(), len0);
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn load_sprite(path: &str, rendered_on_load: bool) -> AssetHandle {
+                unsafe {
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "load-sprite"]
+                        fn wit_import(_: *mut u8, _: usize, _: i32) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: i32) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        match &rendered_on_load {
+                            true => 1,
+                            false => 0,
+                        },
+                    );
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn load_animation(
+                atlas_path: &str,
+                skeleton_path: &str,
+                rendered_on_load: bool,
+            ) -> AssetHandle {
+                unsafe {
+                    let vec0 = atlas_path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = skeleton_path;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "load-animation"]
+                        fn wit_import(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                        ) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                    ) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        ptr1.cast_mut(),
+                        len1,
+                        match &rendered_on_load {
+                            true => 1,
+                            false => 0,
+                        },
+                    );
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn load_worldmap(path: &str, rendered_on_load: bool) -> AssetHandle {
+                unsafe {
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "load-worldmap"]
+                        fn wit_import(_: *mut u8, _: usize, _: i32) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: i32) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(
+                        ptr0.cast_mut(),
+                        len0,
+                        match &rendered_on_load {
+                            true => 1,
+                            false => 0,
+                        },
+                    );
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Raw file contents, read with `data` once loaded
+            pub fn load_data(path: &str) -> AssetHandle {
+                unsafe {
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "load-data"]
+                        fn wit_import(_: *mut u8, _: usize) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(ptr0.cast_mut(), len0);
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn status(handle: AssetHandle) -> AssetStatus {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "status"]
+                        fn wit_import(_: i64, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(handle), ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let v5 = match l1 {
+                        0 => AssetStatus::Loading,
+                        1 => AssetStatus::Loaded,
+                        n => {
+                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                            let e5 = {
+                                let l2 = *ptr0.add(4).cast::<*mut u8>();
+                                let l3 = *ptr0.add(8).cast::<usize>();
+                                let len4 = l3;
+                                let bytes4 = _rt::Vec::from_raw_parts(
+                                    l2.cast(),
+                                    len4,
+                                    len4,
+                                );
+                                _rt::string_lift(bytes4)
+                            };
+                            AssetStatus::Failed(e5)
                         }
-                        #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32) {
-                            unreachable!()
+                    };
+                    v5
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Width and height of a loaded image or sprite
+            pub fn size(handle: AssetHandle) -> Option<(u32, u32)> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "size"]
+                        fn wit_import(_: i64, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(handle), ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l2 = *ptr0.add(4).cast::<i32>();
+                                let l3 = *ptr0.add(8).cast::<i32>();
+                                (l2 as u32, l3 as u32)
+                            };
+                            Some(e)
                         }
-                        wit_import((self).handle() as i32);
+                        _ => _rt::invalid_enum_discriminant(),
                     }
                 }
             }
-            impl Observer {
-                #[allow(unused_unsafe, clippy::all)]
-                pub fn callback(&self) -> Callback {
-                    unsafe {
-                        #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "toxoid-component:component/ecs")]
-                        extern "C" {
-                            #[link_name = "[method]observer.callback"]
-                            fn wit_import(_: i32) -> i32;
-                        }
-                        #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32) -> i32 {
-                            unreachable!()
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn data(handle: AssetHandle) -> Option<_rt::Vec<u8>> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
+                    extern "C" {
+                        #[link_name = "data"]
+                        fn wit_import(_: i64, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(_rt::as_i64(handle), ptr0);
+                    let l1 = i32::from(*ptr0.add(0).cast::<u8>());
+                    match l1 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l2 = *ptr0.add(4).cast::<*mut u8>();
+                                let l3 = *ptr0.add(8).cast::<usize>();
+                                let len4 = l3;
+                                _rt::Vec::from_raw_parts(l2.cast(), len4, len4)
+                            };
+                            Some(e)
                         }
-                        let ret = wit_import((self).handle() as i32);
-                        Callback::from_handle(ret as u32)
+                        _ => _rt::invalid_enum_discriminant(),
                     }
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn add_singleton(component: EcsEntityT) {
+            /// Assets loaded by this guest that finished loading or failed since the last call
+            pub fn poll_events() -> _rt::Vec<AssetEvent> {
                 unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/assets")]
                     extern "C" {
-                        #[link_name = "add-singleton"]
-                        fn wit_import(_: i64);
+                        #[link_name = "poll-events"]
+                        fn wit_import(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i64) {
+                    fn wit_import(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(_rt::as_i64(component));
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base9 = l1;
+                    let len9 = l2;
+                    let mut result9 = _rt::Vec::with_capacity(len9);
+                    for i in 0..len9 {
+                        let base = base9.add(i * 24);
+                        let e9 = {
+                            let l3 = *base.add(0).cast::<i64>();
+                            let l4 = i32::from(*base.add(8).cast::<u8>());
+                            let v8 = match l4 {
+                                0 => AssetStatus::Loading,
+                                1 => AssetStatus::Loaded,
+                                n => {
+                                    debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                    let e8 = {
+                                        let l5 = *base.add(12).cast::<*mut u8>();
+                                        let l6 = *base.add(16).cast::<usize>();
+                                        let len7 = l6;
+                                        let bytes7 = _rt::Vec::from_raw_parts(
+                                            l5.cast(),
+                                            len7,
+                                            len7,
+                                        );
+                                        _rt::string_lift(bytes7)
+                                    };
+                                    AssetStatus::Failed(e8)
+                                }
+                            };
+                            AssetEvent {
+                                handle: l3 as u64,
+                                status: v8,
+                            }
+                        };
+                        result9.push(e9);
+                    }
+                    _rt::cabi_dealloc(base9, len9 * 24, 8);
+                    result9
+                }
+            }
+        }
+        #[allow(dead_code, clippy::all)]
+        pub mod render {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type AssetHandle = super::super::super::toxoid_component::component::assets::AssetHandle;
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct Rgba {
+                pub r: f32,
+                pub g: f32,
+                pub b: f32,
+                pub a: f32,
+            }
+            impl ::core::fmt::Debug for Rgba {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Rgba")
+                        .field("r", &self.r)
+                        .field("g", &self.g)
+                        .field("b", &self.b)
+                        .field("a", &self.a)
+                        .finish()
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn get_singleton(component: EcsEntityT) -> Component {
+            /// Draw commands are queued and drawn at the end of the frame on top of the render targets,
+            /// in world coordinates like the render targets
+            pub fn draw_sprite(sprite: AssetHandle, x: f32, y: f32) {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/render")]
                     extern "C" {
-                        #[link_name = "get-singleton"]
-                        fn wit_import(_: i64) -> i32;
+                        #[link_name = "draw-sprite"]
+                        fn wit_import(_: i64, _: f32, _: f32);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i64) -> i32 {
+                    fn wit_import(_: i64, _: f32, _: f32) {
                         unreachable!()
                     }
-                    let ret = wit_import(_rt::as_i64(component));
-                    Component::from_handle(ret as u32)
+                    wit_import(_rt::as_i64(sprite), _rt::as_f32(&x), _rt::as_f32(&y));
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn remove_singleton(component: EcsEntityT) {
+            pub fn draw_rect(x: f32, y: f32, width: f32, height: f32, color: Rgba) {
                 unsafe {
+                    let Rgba { r: r0, g: g0, b: b0, a: a0 } = color;
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/render")]
                     extern "C" {
-                        #[link_name = "remove-singleton"]
-                        fn wit_import(_: i64);
+                        #[link_name = "draw-rect"]
+                        fn wit_import(
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                        );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i64) {
+                    fn wit_import(
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                    ) {
                         unreachable!()
                     }
-                    wit_import(_rt::as_i64(component));
+                    wit_import(
+                        _rt::as_f32(&x),
+                        _rt::as_f32(&y),
+                        _rt::as_f32(&width),
+                        _rt::as_f32(&height),
+                        _rt::as_f32(r0),
+                        _rt::as_f32(g0),
+                        _rt::as_f32(b0),
+                        _rt::as_f32(a0),
+                    );
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn add_entity(entity: EcsEntityT) {
+            pub fn draw_line(ax: f32, ay: f32, bx: f32, by: f32, color: Rgba) {
                 unsafe {
+                    let Rgba { r: r0, g: g0, b: b0, a: a0 } = color;
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/render")]
                     extern "C" {
-                        #[link_name = "add-entity"]
-                        fn wit_import(_: i64);
+                        #[link_name = "draw-line"]
+                        fn wit_import(
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                            _: f32,
+                        );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i64) {
+                    fn wit_import(
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                        _: f32,
+                    ) {
                         unreachable!()
                     }
-                    wit_import(_rt::as_i64(entity));
+                    wit_import(
+                        _rt::as_f32(&ax),
+                        _rt::as_f32(&ay),
+                        _rt::as_f32(&bx),
+                        _rt::as_f32(&by),
+                        _rt::as_f32(r0),
+                        _rt::as_f32(g0),
+                        _rt::as_f32(b0),
+                        _rt::as_f32(a0),
+                    );
+                }
+            }
+        }
+        #[allow(dead_code, clippy::all)]
+        pub mod log {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum Level {
+                Trace,
+                Debug,
+                Info,
+                Warn,
+                Error,
+            }
+            impl ::core::fmt::Debug for Level {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        Level::Trace => f.debug_tuple("Level::Trace").finish(),
+                        Level::Debug => f.debug_tuple("Level::Debug").finish(),
+                        Level::Info => f.debug_tuple("Level::Info").finish(),
+                        Level::Warn => f.debug_tuple("Level::Warn").finish(),
+                        Level::Error => f.debug_tuple("Level::Error").finish(),
+                    }
+                }
+            }
+            impl Level {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> Level {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => Level::Trace,
+                        1 => Level::Debug,
+                        2 => Level::Info,
+                        3 => Level::Warn,
+                        4 => Level::Error,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            #[derive(Clone)]
+            pub struct Field {
+                pub key: _rt::String,
+                pub value: _rt::String,
+            }
+            impl ::core::fmt::Debug for Field {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("Field")
+                        .field("key", &self.key)
+                        .field("value", &self.value)
+                        .finish()
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn remove_entity(entity: EcsEntityT) {
+            /// Whether records of this level are logged, to skip building the ones that aren't
+            pub fn enabled(level: Level) -> bool {
                 unsafe {
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/log")]
                     extern "C" {
-                        #[link_name = "remove-entity"]
-                        fn wit_import(_: i64);
+                        #[link_name = "enabled"]
+                        fn wit_import(_: i32) -> i32;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: i64) {
+                    fn wit_import(_: i32) -> i32 {
                         unreachable!()
                     }
-                    wit_import(_rt::as_i64(entity));
+                    let ret = wit_import(level.clone() as i32);
+                    _rt::bool_lift(ret as u8)
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            /// get-entity: func(entity: ecs-entity-t) -> entity;
-            /// get-entity-named: func(name: string) -> entity;
-            pub fn has_entity_named(name: &str) -> bool {
+            /// The host tags records with the guest and the system or observer that emitted them
+            pub fn log(level: Level, target: &str, message: &str, fields: &[Field]) {
                 unsafe {
-                    let vec0 = name;
+                    let vec0 = target;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
+                    let vec1 = message;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let vec5 = fields;
+                    let len5 = vec5.len();
+                    let layout5 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec5.len() * 16,
+                        4,
+                    );
+                    let result5 = if layout5.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout5).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout5);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec5.into_iter().enumerate() {
+                        let base = result5.add(i * 16);
+                        {
+                            let Field { key: key2, value: value2 } = e;
+                            let vec3 = key2;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            *base.add(4).cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            let vec4 = value2;
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            *base.add(12).cast::<usize>() = len4;
+                            *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+                        }
+                    }
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/log")]
                     extern "C" {
-                        #[link_name = "has-entity-named"]
-                        fn wit_import(_: *mut u8, _: usize) -> i32;
+                        #[link_name = "log"]
+                        fn wit_import(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize) -> i32 {
+                    fn wit_import(
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                    ) {
                         unreachable!()
                     }
-                    let ret = wit_import(ptr0.cast_mut(), len0);
-                    _rt::bool_lift(ret as u8)
+                    wit_import(
+                        level.clone() as i32,
+                        ptr0.cast_mut(),
+                        len0,
+                        ptr1.cast_mut(),
+                        len1,
+                        result5,
+                        len5,
+                    );
+                    if layout5.size() != 0 {
+                        _rt::alloc::dealloc(result5.cast(), layout5);
+                    }
                 }
             }
+        }
+        #[allow(dead_code, clippy::all)]
+        pub mod random {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type EcsEntityT = super::super::super::toxoid_component::component::ecs::EcsEntityT;
+            /// Streams are independent sequences derived from the world seed,
+            /// so systems drawing numbers don't change each other's results
+            pub type StreamId = u64;
             #[allow(unused_unsafe, clippy::all)]
-            pub fn get_component_id(component_name: &str) -> EcsEntityT {
+            pub fn seed() -> u64 {
                 unsafe {
-                    let vec0 = component_name;
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/random")]
+                    extern "C" {
+                        #[link_name = "seed"]
+                        fn wit_import() -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import() -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import();
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn named_stream(name: &str) -> StreamId {
+                unsafe {
+                    let vec0 = name;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "toxoid-component:component/ecs")]
+                    #[link(wasm_import_module = "toxoid-component:component/random")]
                     extern "C" {
-                        #[link_name = "get-component-id"]
+                        #[link_name = "named-stream"]
                         fn wit_import(_: *mut u8, _: usize) -> i64;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
@@ -3564,6 +5817,40 @@ pub mod toxoid_component {
                     ret as u64
                 }
             }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn system_stream(system: EcsEntityT) -> StreamId {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/random")]
+                    extern "C" {
+                        #[link_name = "system-stream"]
+                        fn wit_import(_: i64) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(_rt::as_i64(system));
+                    ret as u64
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn next_u64(id: StreamId) -> u64 {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "toxoid-component:component/random")]
+                    extern "C" {
+                        #[link_name = "next-u64"]
+                        fn wit_import(_: i64) -> i64;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: i64) -> i64 {
+                        unreachable!()
+                    }
+                    let ret = wit_import(_rt::as_i64(id));
+                    ret as u64
+                }
+            }
         }
     }
 }
@@ -3580,6 +5867,8 @@ pub mod exports {
                 static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
                 use super::super::super::super::_rt;
                 pub type Iter = super::super::super::super::toxoid_component::component::ecs::Iter;
+                pub type EcsEntityT = super::super::super::super::toxoid_component::component::ecs::EcsEntityT;
+                pub type PointerT = super::super::super::super::toxoid_component::component::ecs::PointerT;
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
                 pub unsafe fn _export_run_cabi<T: Guest>(arg0: i32, arg1: i64) {
@@ -3591,8 +5880,43 @@ pub mod exports {
                         arg1 as u64,
                     );
                 }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_free_cabi<T: Guest>(arg0: i64) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::free(arg0 as u64);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_compare_cabi<T: Guest>(
+                    arg0: i64,
+                    arg1: i64,
+                    arg2: i64,
+                    arg3: i64,
+                    arg4: i64,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::compare(
+                        arg0 as u64,
+                        arg1 as u64,
+                        arg2 as u64,
+                        arg3 as u64,
+                        arg4 as u64,
+                    );
+                    _rt::as_i32(result0)
+                }
                 pub trait Guest {
                     fn run(iter: Iter, handle: u64);
+                    /// Drop the callback of a system or observer the host deleted, along with what it captured
+                    fn free(handle: u64);
+                    /// Comparator of a system's order-by, gets a pointer to the sorted component of both entities
+                    fn compare(
+                        handle: u64,
+                        e1: EcsEntityT,
+                        c1: PointerT,
+                        e2: EcsEntityT,
+                        c2: PointerT,
+                    ) -> i32;
                 }
                 #[doc(hidden)]
                 #[macro_export]
@@ -3601,7 +5925,14 @@ macro_rules! __export_toxoid_component_component_callbacks_cabi {
                         const _ : () = { #[export_name =
                         "toxoid-component:component/callbacks#run"] unsafe extern "C" fn
                         export_run(arg0 : i32, arg1 : i64,) { $($path_to_types)*::
-                        _export_run_cabi::<$ty > (arg0, arg1) } };
+                        _export_run_cabi::<$ty > (arg0, arg1) } #[export_name =
+                        "toxoid-component:component/callbacks#free"] unsafe extern "C" fn
+                        export_free(arg0 : i64,) { $($path_to_types)*::
+                        _export_free_cabi::<$ty > (arg0) } #[export_name =
+                        "toxoid-component:component/callbacks#compare"] unsafe extern "C"
+                        fn export_compare(arg0 : i64, arg1 : i64, arg2 : i64, arg3 : i64,
+                        arg4 : i64,) -> i32 { $($path_to_types)*::
+                        _export_compare_cabi::<$ty > (arg0, arg1, arg2, arg3, arg4) } };
                     };
                 }
                 #[doc(hidden)]
@@ -3822,6 +6153,13 @@ mod _rt {
             String::from_utf8_unchecked(bytes)
         }
     }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
         if size == 0 {
             return;
@@ -3871,9 +6209,9 @@ pub use __export_toxoid_component_world_impl as export;
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.35.0:toxoid-component:component:toxoid-component-world:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 6857] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xbc4\x01A\x02\x01A\x07\
-\x01B\x9d\x02\x01w\x04\0\x0cecs-entity-t\x03\0\0\x01w\x04\0\x09pointer-t\x03\0\x02\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 9995] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xfeL\x01A\x02\x01A\x14\
+\x01B\xe5\x02\x01w\x04\0\x0cecs-entity-t\x03\0\0\x01w\x04\0\x09pointer-t\x03\0\x02\
 \x01q\x03\x04is-a\0\0\x08child-of\0\0\x06custom\x01\x01\0\x04\0\x0crelationship\x03\
 \0\x04\x01q\x0a\x08on-start\0\0\x07on-load\0\0\x09post-load\0\0\x0apre-update\0\0\
 \x09on-update\0\0\x0bon-validate\0\0\x0bpost-update\0\0\x09pre-store\0\0\x08on-s\
@@ -3883,132 +6221,205 @@ t\x08string-t\x06list-t\x08u8list-t\x09u16list-t\x09u32list-t\x09u64list-t\x08i8
 list-t\x09i16list-t\x09i32list-t\x09i64list-t\x09f32list-t\x09f64list-t\x09point\
 er-t\x04\0\x0bmember-type\x03\0\x08\x01m\x07\x06on-set\x06on-add\x09on-remove\x09\
 on-delete\x10on-delete-target\x0fon-table-create\x0fon-table-delete\x04\0\x05eve\
-nt\x03\0\x0a\x01ps\x01p}\x01r\x03\x04names\x0cmember-names\x0c\x0cmember-types\x0d\
-\x04\0\x0ecomponent-desc\x03\0\x0e\x01ks\x01p\x01\x01k\x11\x01r\x03\x04name\x10\x03\
-add\x12\x06prefab\x7f\x04\0\x0bentity-desc\x03\0\x13\x01r\x01\x04exprs\x04\0\x0a\
-query-desc\x03\0\x15\x04\0\x0ecomponent-type\x03\x01\x01r\x03\x04names\x0aquery-\
-desc\x16\x06phases\x11\x04\0\x0dpipeline-desc\x03\0\x18\x04\0\x09component\x03\x01\
-\x04\0\x06entity\x03\x01\x04\0\x05query\x03\x01\x04\0\x04iter\x03\x01\x04\0\x08c\
-allback\x03\x01\x01i\x1e\x01r\x02\x02id\x01\x08callback\x1f\x04\0\x0csorting-des\
-c\x03\0\x20\x01kz\x01r\x05\x04name\x10\x09tick-rate\"\x08callback\x1f\x0aquery-d\
-esc\x16\x08is-guest\x7f\x04\0\x0bsystem-desc\x03\0#\x01p\x0b\x01r\x05\x04name\x10\
-\x0aquery-desc\x16\x06events%\x08callback\x1f\x08is-guest\x7f\x04\0\x0dobserver-\
-desc\x03\0&\x04\0\x06system\x03\x01\x04\0\x05phase\x03\x01\x04\0\x08pipeline\x03\
-\x01\x04\0\x08observer\x03\x01\x01i\x17\x01@\x01\x04init\x0f\0,\x04\0\x1b[constr\
-uctor]component-type\x01-\x01h\x17\x01@\x01\x04self.\0\x01\x04\0\x1d[method]comp\
-onent-type.get-id\x01/\x01i\x1a\x01@\x03\x03ptr\x03\x06entity\x01\x0ecomponent-t\
-ype\x01\00\x04\0\x16[constructor]component\x011\x01@\x01\x03ptr\x03\00\x04\0\x1a\
-[static]component.from-ptr\x012\x01h\x1a\x01@\x03\x04self3\x06offsety\x05value}\x01\
-\0\x04\0\x1f[method]component.set-member-u8\x014\x01@\x02\x04self3\x06offsety\0}\
-\x04\0\x1f[method]component.get-member-u8\x015\x01@\x03\x04self3\x06offsety\x05v\
-alue{\x01\0\x04\0\x20[method]component.set-member-u16\x016\x01@\x02\x04self3\x06\
-offsety\0{\x04\0\x20[method]component.get-member-u16\x017\x01@\x03\x04self3\x06o\
-ffsety\x05valuey\x01\0\x04\0\x20[method]component.set-member-u32\x018\x01@\x02\x04\
-self3\x06offsety\0y\x04\0\x20[method]component.get-member-u32\x019\x01@\x03\x04s\
-elf3\x06offsety\x05valuew\x01\0\x04\0\x20[method]component.set-member-u64\x01:\x01\
-@\x02\x04self3\x06offsety\0w\x04\0\x20[method]component.get-member-u64\x01;\x01@\
-\x03\x04self3\x06offsety\x05value~\x01\0\x04\0\x1f[method]component.set-member-i\
-8\x01<\x01@\x02\x04self3\x06offsety\0~\x04\0\x1f[method]component.get-member-i8\x01\
-=\x01@\x03\x04self3\x06offsety\x05value|\x01\0\x04\0\x20[method]component.set-me\
-mber-i16\x01>\x01@\x02\x04self3\x06offsety\0|\x04\0\x20[method]component.get-mem\
-ber-i16\x01?\x01@\x03\x04self3\x06offsety\x05valuez\x01\0\x04\0\x20[method]compo\
-nent.set-member-i32\x01@\x01@\x02\x04self3\x06offsety\0z\x04\0\x20[method]compon\
-ent.get-member-i32\x01A\x01@\x03\x04self3\x06offsety\x05valuex\x01\0\x04\0\x20[m\
-ethod]component.set-member-i64\x01B\x01@\x02\x04self3\x06offsety\0x\x04\0\x20[me\
-thod]component.get-member-i64\x01C\x01@\x03\x04self3\x06offsety\x05valuev\x01\0\x04\
-\0\x20[method]component.set-member-f32\x01D\x01@\x02\x04self3\x06offsety\0v\x04\0\
-\x20[method]component.get-member-f32\x01E\x01@\x03\x04self3\x06offsety\x05valueu\
-\x01\0\x04\0\x20[method]component.set-member-f64\x01F\x01@\x02\x04self3\x06offse\
-ty\0u\x04\0\x20[method]component.get-member-f64\x01G\x01@\x03\x04self3\x06offset\
-y\x05value\x7f\x01\0\x04\0![method]component.set-member-bool\x01H\x01@\x02\x04se\
-lf3\x06offsety\0\x7f\x04\0![method]component.get-member-bool\x01I\x01@\x03\x04se\
-lf3\x06offsety\x05values\x01\0\x04\0#[method]component.set-member-string\x01J\x01\
-@\x02\x04self3\x06offsety\0s\x04\0#[method]component.get-member-string\x01K\x01@\
-\x03\x04self3\x06offsety\x05value\x0d\x01\0\x04\0#[method]component.set-member-u\
-8list\x01L\x01@\x02\x04self3\x06offsety\0\x0d\x04\0#[method]component.get-member\
--u8list\x01M\x01p{\x01@\x03\x04self3\x06offsety\x05value\xce\0\x01\0\x04\0$[meth\
-od]component.set-member-u16list\x01O\x01@\x02\x04self3\x06offsety\0\xce\0\x04\0$\
-[method]component.get-member-u16list\x01P\x01py\x01@\x03\x04self3\x06offsety\x05\
-value\xd1\0\x01\0\x04\0$[method]component.set-member-u32list\x01R\x01@\x02\x04se\
-lf3\x06offsety\0\xd1\0\x04\0$[method]component.get-member-u32list\x01S\x01pw\x01\
-@\x03\x04self3\x06offsety\x05value\xd4\0\x01\0\x04\0$[method]component.set-membe\
-r-u64list\x01U\x01@\x02\x04self3\x06offsety\0\xd4\0\x04\0$[method]component.get-\
-member-u64list\x01V\x01p~\x01@\x03\x04self3\x06offsety\x05value\xd7\0\x01\0\x04\0\
-#[method]component.set-member-i8list\x01X\x01@\x02\x04self3\x06offsety\0\xd7\0\x04\
-\0#[method]component.get-member-i8list\x01Y\x01p|\x01@\x03\x04self3\x06offsety\x05\
-value\xda\0\x01\0\x04\0$[method]component.set-member-i16list\x01[\x01@\x02\x04se\
-lf3\x06offsety\0\xda\0\x04\0$[method]component.get-member-i16list\x01\\\x01pz\x01\
-@\x03\x04self3\x06offsety\x05value\xdd\0\x01\0\x04\0$[method]component.set-membe\
-r-i32list\x01^\x01@\x02\x04self3\x06offsety\0\xdd\0\x04\0$[method]component.get-\
-member-i32list\x01_\x01px\x01@\x03\x04self3\x06offsety\x05value\xe0\0\x01\0\x04\0\
-$[method]component.set-member-i64list\x01a\x01@\x02\x04self3\x06offsety\0\xe0\0\x04\
-\0$[method]component.get-member-i64list\x01b\x01pv\x01@\x03\x04self3\x06offsety\x05\
-value\xe3\0\x01\0\x04\0$[method]component.set-member-f32list\x01d\x01@\x02\x04se\
-lf3\x06offsety\0\xe3\0\x04\0$[method]component.get-member-f32list\x01e\x01pu\x01\
-@\x03\x04self3\x06offsety\x05value\xe6\0\x01\0\x04\0$[method]component.set-membe\
-r-f64list\x01g\x01@\x02\x04self3\x06offsety\0\xe6\0\x04\0$[method]component.get-\
-member-f64list\x01h\x04\0$[method]component.set-member-pointer\x01:\x04\0$[metho\
-d]component.get-member-pointer\x01;\x01k\x01\x01i\x1b\x01@\x02\x04init\x14\x08in\
-herits\xe9\0\0\xea\0\x04\0\x13[constructor]entity\x01k\x01@\x01\x02idw\0\xea\0\x04\
-\0\x16[static]entity.from-id\x01l\x01h\x1b\x01@\x01\x04self\xed\0\0\x01\x04\0\x15\
-[method]entity.get-id\x01n\x01@\x01\x04self\xed\0\0s\x04\0\x17[method]entity.get\
--name\x01o\x01@\x02\x04self\xed\0\x04names\x01\0\x04\0\x17[method]entity.set-nam\
-e\x01p\x01@\x02\x04self\xed\0\x09component\x01\00\x04\0\x12[method]entity.get\x01\
-q\x01@\x02\x04self\xed\0\x09component\x01\x01\0\x04\0\x12[method]entity.add\x01r\
-\x01@\x02\x04self\xed\0\x09component\x01\0\x7f\x04\0\x12[method]entity.has\x01s\x04\
-\0\x15[method]entity.remove\x01r\x01@\x03\x04self\xed\0\x0crelationship\x05\x06t\
-arget\x01\x01\0\x04\0\x1f[method]entity.add-relationship\x01t\x04\0\"[method]ent\
-ity.remove-relationship\x01t\x01@\x02\x04self\xed\0\x06target\x01\x01\0\x04\0\x18\
-[method]entity.parent-of\x01u\x04\0\x17[method]entity.child-of\x01u\x01@\x01\x04\
-self\xed\0\0\xea\0\x04\0\x15[method]entity.parent\x01v\x01p\xea\0\x01@\x01\x04se\
-lf\xed\0\0\xf7\0\x04\0\x17[method]entity.children\x01x\x01@\x02\x04self\xed\0\x0c\
-relationship\x05\0\xf7\0\x04\0$[method]entity.relationship-entities\x01y\x01@\x01\
-\x04self\xed\0\x01\0\x04\0\x16[method]entity.disable\x01z\x04\0\x15[method]entit\
-y.enable\x01z\x01i\x1c\x01@\x01\x04desc\x16\0\xfb\0\x04\0\x12[constructor]query\x01\
-|\x01h\x1c\x01@\x01\x04self\xfd\0\x01\0\x04\0\x13[method]query.build\x01~\x01@\x02\
-\x04self\xfd\0\x07sorting!\x01\0\x04\0\x16[method]query.order-by\x01\x7f\x01i\x1d\
-\x01@\x01\x04self\xfd\0\0\x80\x01\x04\0\x12[method]query.iter\x01\x81\x01\x01@\x01\
-\x04self\xfd\0\0\x7f\x04\0\x12[method]query.next\x01\x82\x01\x01@\x01\x04self\xfd\
-\0\0z\x04\0\x13[method]query.count\x01\x83\x01\x01@\x01\x04self\xfd\0\0\xf7\0\x04\
-\0\x16[method]query.entities\x01\x84\x01\x01p\x03\x01@\x02\x04self\xfd\0\x05inde\
-x~\0\x85\x01\x04\0\x18[method]query.components\x01\x86\x01\x01@\x01\x03ptrw\0\x80\
-\x01\x04\0\x11[constructor]iter\x01\x87\x01\x01h\x1d\x01@\x01\x04self\x88\x01\0\x7f\
-\x04\0\x11[method]iter.next\x01\x89\x01\x01@\x01\x04self\x88\x01\0z\x04\0\x12[me\
-thod]iter.count\x01\x8a\x01\x01@\x01\x04self\x88\x01\0\xf7\0\x04\0\x15[method]it\
-er.entities\x01\x8b\x01\x01@\x02\x04self\x88\x01\x05index~\0\x85\x01\x04\0\x17[m\
-ethod]iter.components\x01\x8c\x01\x01@\x01\x06handlew\0\x1f\x04\0\x15[constructo\
-r]callback\x01\x8d\x01\x01h\x1e\x01@\x02\x04self\x8e\x01\x04iter\x80\x01\x01\0\x04\
-\0\x14[method]callback.run\x01\x8f\x01\x01@\x01\x04self\x8e\x01\0\x03\x04\0\x1a[\
-method]callback.cb-handle\x01\x90\x01\x01i(\x01@\x01\x04desc$\0\x91\x01\x04\0\x13\
-[constructor]system\x01\x92\x01\x01h(\x01@\x01\x04self\x93\x01\0\x01\x04\0\x15[m\
-ethod]system.get-id\x01\x94\x01\x01@\x01\x04self\x93\x01\x01\0\x04\0\x14[method]\
-system.build\x01\x95\x01\x01@\x02\x04self\x93\x01\x04names\x01\0\x04\0\x14[metho\
-d]system.named\x01\x96\x01\x01@\x02\x04self\x93\x01\x07sorting!\x01\0\x04\0\x17[\
-method]system.order-by\x01\x97\x01\x01@\x01\x04self\x93\x01\0\x1f\x04\0\x17[meth\
-od]system.callback\x01\x98\x01\x04\0\x16[method]system.disable\x01\x95\x01\x04\0\
-\x15[method]system.enable\x01\x95\x01\x01i)\x01@\x01\x04names\0\x99\x01\x04\0\x12\
-[constructor]phase\x01\x9a\x01\x01h)\x01@\x02\x04self\x9b\x01\x05phase\x07\x01\0\
-\x04\0\x18[method]phase.depends-on\x01\x9c\x01\x01@\x01\x04self\x9b\x01\0\x01\x04\
-\0\x14[method]phase.get-id\x01\x9d\x01\x01i*\x01@\x01\x04desc\x19\0\x9e\x01\x04\0\
-\x15[constructor]pipeline\x01\x9f\x01\x01h*\x01@\x01\x04self\xa0\x01\x01\0\x04\0\
-\x16[method]pipeline.build\x01\xa1\x01\x01@\x02\x04self\xa0\x01\x05phase\x01\x01\
-\0\x04\0\x1a[method]pipeline.add-phase\x01\xa2\x01\x01@\x01\x04self\xa0\x01\0\x01\
-\x04\0\x17[method]pipeline.get-id\x01\xa3\x01\x04\0\x18[method]pipeline.disable\x01\
-\xa1\x01\x04\0\x17[method]pipeline.enable\x01\xa1\x01\x01i+\x01@\x01\x04desc'\0\xa4\
-\x01\x04\0\x15[constructor]observer\x01\xa5\x01\x01h+\x01@\x01\x04self\xa6\x01\x01\
-\0\x04\0\x16[method]observer.build\x01\xa7\x01\x01@\x01\x04self\xa6\x01\0\x1f\x04\
-\0\x19[method]observer.callback\x01\xa8\x01\x01@\x01\x09component\x01\x01\0\x04\0\
-\x0dadd-singleton\x01\xa9\x01\x01@\x01\x09component\x01\00\x04\0\x0dget-singleto\
-n\x01\xaa\x01\x04\0\x10remove-singleton\x01\xa9\x01\x01@\x01\x06entity\x01\x01\0\
-\x04\0\x0aadd-entity\x01\xab\x01\x04\0\x0dremove-entity\x01\xab\x01\x01@\x01\x04\
-names\0\x7f\x04\0\x10has-entity-named\x01\xac\x01\x01@\x01\x0ecomponent-names\0\x01\
-\x04\0\x10get-component-id\x01\xad\x01\x03\0\x1etoxoid-component:component/ecs\x05\
-\0\x01@\0\x01\0\x04\0\x04init\x01\x01\x02\x03\0\0\x04iter\x01B\x05\x02\x03\x02\x01\
-\x02\x04\0\x04iter\x03\0\0\x01i\x01\x01@\x02\x04iter\x02\x06handlew\x01\0\x04\0\x03\
-run\x01\x03\x04\0$toxoid-component:component/callbacks\x05\x03\x04\01toxoid-comp\
-onent:component/toxoid-component-world\x04\0\x0b\x1c\x01\0\x16toxoid-component-w\
-orld\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.220.0\
-\x10wit-bindgen-rust\x060.35.0";
+nt\x03\0\x0a\x01m\x02\x07changed\x05added\x04\0\x0cmonitor-kind\x03\0\x0c\x01ps\x01\
+p}\x01p\x01\x01py\x01r\x05\x04names\x0cmember-names\x0e\x0cmember-types\x0f\x0fm\
+ember-type-ids\x10\x0dmember-counts\x11\x04\0\x0ecomponent-desc\x03\0\x12\x01r\x02\
+\x04names\x05valuey\x04\0\x0denum-constant\x03\0\x14\x01p\x15\x01r\x03\x04names\x09\
+constants\x16\x07bitmask\x7f\x04\0\x09enum-desc\x03\0\x17\x01m\x03\x07despawn\x03\
+add\x06remove\x04\0\x0ccommand-kind\x03\0\x19\x01r\x03\x04kind\x1a\x06entity\x01\
+\x09component\x01\x04\0\x0dcommand-error\x03\0\x1b\x01r\x02\x09component\x01\x04\
+data\x0f\x04\0\x0fcomponent-value\x03\0\x1d\x01ks\x01k\x10\x01p\x1e\x01k!\x01r\x04\
+\x04name\x1f\x03add\x20\x06prefab\x7f\x06values\"\x04\0\x0bentity-desc\x03\0#\x01\
+r\x01\x04exprs\x04\0\x0aquery-desc\x03\0%\x04\0\x0ecomponent-type\x03\x01\x01r\x03\
+\x04names\x0aquery-desc&\x06phases\x10\x04\0\x0dpipeline-desc\x03\0(\x04\0\x09co\
+mponent\x03\x01\x04\0\x06entity\x03\x01\x04\0\x05query\x03\x01\x04\0\x04iter\x03\
+\x01\x04\0\x08callback\x03\x01\x01i.\x01r\x02\x02id\x01\x08callback/\x04\0\x0cso\
+rting-desc\x03\00\x01kz\x01r\x05\x04name\x1f\x09tick-rate2\x08callback/\x0aquery\
+-desc&\x08is-guest\x7f\x04\0\x0bsystem-desc\x03\03\x01p\x0b\x01r\x05\x04name\x1f\
+\x0aquery-desc&\x06events5\x08callback/\x08is-guest\x7f\x04\0\x0dobserver-desc\x03\
+\06\x04\0\x06system\x03\x01\x04\0\x05phase\x03\x01\x04\0\x08pipeline\x03\x01\x04\
+\0\x08observer\x03\x01\x01i'\x01@\x01\x04init\x13\0<\x04\0\x1b[constructor]compo\
+nent-type\x01=\x01h'\x01@\x01\x04self>\0\x01\x04\0\x1d[method]component-type.get\
+-id\x01?\x01i*\x01@\x03\x03ptr\x03\x06entity\x01\x0ecomponent-type\x01\0\xc0\0\x04\
+\0\x16[constructor]component\x01A\x01@\x02\x03ptr\x03\x0ecomponent-type\x01\0\xc0\
+\0\x04\0\x1a[static]component.from-ptr\x01B\x01h*\x01@\x03\x04self\xc3\0\x06memb\
+ery\x05value}\x01\0\x04\0\x1f[method]component.set-member-u8\x01D\x01@\x02\x04se\
+lf\xc3\0\x06membery\0}\x04\0\x1f[method]component.get-member-u8\x01E\x01@\x03\x04\
+self\xc3\0\x06membery\x05value{\x01\0\x04\0\x20[method]component.set-member-u16\x01\
+F\x01@\x02\x04self\xc3\0\x06membery\0{\x04\0\x20[method]component.get-member-u16\
+\x01G\x01@\x03\x04self\xc3\0\x06membery\x05valuey\x01\0\x04\0\x20[method]compone\
+nt.set-member-u32\x01H\x01@\x02\x04self\xc3\0\x06membery\0y\x04\0\x20[method]com\
+ponent.get-member-u32\x01I\x01@\x03\x04self\xc3\0\x06membery\x05valuew\x01\0\x04\
+\0\x20[method]component.set-member-u64\x01J\x01@\x02\x04self\xc3\0\x06membery\0w\
+\x04\0\x20[method]component.get-member-u64\x01K\x01@\x03\x04self\xc3\0\x06member\
+y\x05value~\x01\0\x04\0\x1f[method]component.set-member-i8\x01L\x01@\x02\x04self\
+\xc3\0\x06membery\0~\x04\0\x1f[method]component.get-member-i8\x01M\x01@\x03\x04s\
+elf\xc3\0\x06membery\x05value|\x01\0\x04\0\x20[method]component.set-member-i16\x01\
+N\x01@\x02\x04self\xc3\0\x06membery\0|\x04\0\x20[method]component.get-member-i16\
+\x01O\x01@\x03\x04self\xc3\0\x06membery\x05valuez\x01\0\x04\0\x20[method]compone\
+nt.set-member-i32\x01P\x01@\x02\x04self\xc3\0\x06membery\0z\x04\0\x20[method]com\
+ponent.get-member-i32\x01Q\x01@\x03\x04self\xc3\0\x06membery\x05valuex\x01\0\x04\
+\0\x20[method]component.set-member-i64\x01R\x01@\x02\x04self\xc3\0\x06membery\0x\
+\x04\0\x20[method]component.get-member-i64\x01S\x01@\x03\x04self\xc3\0\x06member\
+y\x05valuev\x01\0\x04\0\x20[method]component.set-member-f32\x01T\x01@\x02\x04sel\
+f\xc3\0\x06membery\0v\x04\0\x20[method]component.get-member-f32\x01U\x01@\x03\x04\
+self\xc3\0\x06membery\x05valueu\x01\0\x04\0\x20[method]component.set-member-f64\x01\
+V\x01@\x02\x04self\xc3\0\x06membery\0u\x04\0\x20[method]component.get-member-f64\
+\x01W\x01@\x03\x04self\xc3\0\x06membery\x05value\x7f\x01\0\x04\0![method]compone\
+nt.set-member-bool\x01X\x01@\x02\x04self\xc3\0\x06membery\0\x7f\x04\0![method]co\
+mponent.get-member-bool\x01Y\x01@\x03\x04self\xc3\0\x06membery\x05values\x01\0\x04\
+\0#[method]component.set-member-string\x01Z\x01@\x02\x04self\xc3\0\x06membery\0s\
+\x04\0#[method]component.get-member-string\x01[\x01@\x03\x04self\xc3\0\x06member\
+y\x05value\x0f\x01\0\x04\0#[method]component.set-member-u8list\x01\\\x01@\x02\x04\
+self\xc3\0\x06membery\0\x0f\x04\0#[method]component.get-member-u8list\x01]\x01p{\
+\x01@\x03\x04self\xc3\0\x06membery\x05value\xde\0\x01\0\x04\0$[method]component.\
+set-member-u16list\x01_\x01@\x02\x04self\xc3\0\x06membery\0\xde\0\x04\0$[method]\
+component.get-member-u16list\x01`\x01@\x03\x04self\xc3\0\x06membery\x05value\x11\
+\x01\0\x04\0$[method]component.set-member-u32list\x01a\x01@\x02\x04self\xc3\0\x06\
+membery\0\x11\x04\0$[method]component.get-member-u32list\x01b\x01pw\x01@\x03\x04\
+self\xc3\0\x06membery\x05value\xe3\0\x01\0\x04\0$[method]component.set-member-u6\
+4list\x01d\x01@\x02\x04self\xc3\0\x06membery\0\xe3\0\x04\0$[method]component.get\
+-member-u64list\x01e\x01p~\x01@\x03\x04self\xc3\0\x06membery\x05value\xe6\0\x01\0\
+\x04\0#[method]component.set-member-i8list\x01g\x01@\x02\x04self\xc3\0\x06member\
+y\0\xe6\0\x04\0#[method]component.get-member-i8list\x01h\x01p|\x01@\x03\x04self\xc3\
+\0\x06membery\x05value\xe9\0\x01\0\x04\0$[method]component.set-member-i16list\x01\
+j\x01@\x02\x04self\xc3\0\x06membery\0\xe9\0\x04\0$[method]component.get-member-i\
+16list\x01k\x01pz\x01@\x03\x04self\xc3\0\x06membery\x05value\xec\0\x01\0\x04\0$[\
+method]component.set-member-i32list\x01m\x01@\x02\x04self\xc3\0\x06membery\0\xec\
+\0\x04\0$[method]component.get-member-i32list\x01n\x01px\x01@\x03\x04self\xc3\0\x06\
+membery\x05value\xef\0\x01\0\x04\0$[method]component.set-member-i64list\x01p\x01\
+@\x02\x04self\xc3\0\x06membery\0\xef\0\x04\0$[method]component.get-member-i64lis\
+t\x01q\x01pv\x01@\x03\x04self\xc3\0\x06membery\x05value\xf2\0\x01\0\x04\0$[metho\
+d]component.set-member-f32list\x01s\x01@\x02\x04self\xc3\0\x06membery\0\xf2\0\x04\
+\0$[method]component.get-member-f32list\x01t\x01pu\x01@\x03\x04self\xc3\0\x06mem\
+bery\x05value\xf5\0\x01\0\x04\0$[method]component.set-member-f64list\x01v\x01@\x02\
+\x04self\xc3\0\x06membery\0\xf5\0\x04\0$[method]component.get-member-f64list\x01\
+w\x04\0$[method]component.set-member-pointer\x01J\x04\0$[method]component.get-me\
+mber-pointer\x01K\x01@\x03\x04self\xc3\0\x06membery\x05indexy\0\xc0\0\x04\0&[met\
+hod]component.get-member-component\x01x\x01k\x01\x01i+\x01@\x02\x04init$\x08inhe\
+rits\xf9\0\0\xfa\0\x04\0\x13[constructor]entity\x01{\x01j\x01\x01\x01s\x01@\x02\x04\
+init$\x08inherits\xf9\0\0\xfc\0\x04\0\x15[static]entity.create\x01}\x01@\x01\x02\
+idw\0\xfa\0\x04\0\x16[static]entity.from-id\x01~\x01h+\x01@\x01\x04self\xff\0\0\x01\
+\x04\0\x15[method]entity.get-id\x01\x80\x01\x01@\x01\x04self\xff\0\0s\x04\0\x17[\
+method]entity.get-name\x01\x81\x01\x01@\x02\x04self\xff\0\x04names\x01\0\x04\0\x17\
+[method]entity.set-name\x01\x82\x01\x01@\x02\x04self\xff\0\x09component\x01\0\xc0\
+\0\x04\0\x12[method]entity.get\x01\x83\x01\x01@\x02\x04self\xff\0\x09component\x01\
+\x01\0\x04\0\x12[method]entity.add\x01\x84\x01\x01@\x02\x04self\xff\0\x09compone\
+nt\x01\0\x7f\x04\0\x12[method]entity.has\x01\x85\x01\x04\0\x15[method]entity.rem\
+ove\x01\x84\x01\x01@\x03\x04self\xff\0\x0crelationship\x05\x06target\x01\x01\0\x04\
+\0\x1f[method]entity.add-relationship\x01\x86\x01\x04\0\"[method]entity.remove-r\
+elationship\x01\x86\x01\x01@\x02\x04self\xff\0\x06target\x01\x01\0\x04\0\x18[met\
+hod]entity.parent-of\x01\x87\x01\x04\0\x17[method]entity.child-of\x01\x87\x01\x01\
+@\x01\x04self\xff\0\0\xfa\0\x04\0\x15[method]entity.parent\x01\x88\x01\x01p\xfa\0\
+\x01@\x01\x04self\xff\0\0\x89\x01\x04\0\x17[method]entity.children\x01\x8a\x01\x01\
+@\x02\x04self\xff\0\x0crelationship\x05\0\x89\x01\x04\0$[method]entity.relations\
+hip-entities\x01\x8b\x01\x01@\x01\x04self\xff\0\x01\0\x04\0\x16[method]entity.di\
+sable\x01\x8c\x01\x04\0\x15[method]entity.enable\x01\x8c\x01\x01i,\x01@\x01\x04d\
+esc&\0\x8d\x01\x04\0\x12[constructor]query\x01\x8e\x01\x01h,\x01@\x01\x04self\x8f\
+\x01\x01\0\x04\0\x13[method]query.build\x01\x90\x01\x01i-\x01@\x01\x04self\x8f\x01\
+\0\x91\x01\x04\0\x12[method]query.iter\x01\x92\x01\x01@\x01\x04self\x8f\x01\0\x7f\
+\x04\0\x12[method]query.next\x01\x93\x01\x01@\x01\x04self\x8f\x01\0z\x04\0\x13[m\
+ethod]query.count\x01\x94\x01\x01@\x01\x04self\x8f\x01\0\x89\x01\x04\0\x16[metho\
+d]query.entities\x01\x95\x01\x01p\x03\x01@\x02\x04self\x8f\x01\x05index~\0\x96\x01\
+\x04\0\x18[method]query.components\x01\x97\x01\x01p\xc0\0\x01@\x03\x04self\x8f\x01\
+\x05index~\x09read-only\x7f\0\x98\x01\x04\0\x19[method]query.field-views\x01\x99\
+\x01\x04\0\x13[method]query.cache\x01\x90\x01\x01@\x03\x04self\x8f\x01\x05index~\
+\x04kind\x0d\x01\0\x04\0\x15[method]query.monitor\x01\x9a\x01\x04\0\x15[method]q\
+uery.changed\x01\x93\x01\x04\0\x1a[method]query.iter-changed\x01\x93\x01\x04\0\x12\
+[method]query.skip\x01\x90\x01\x01@\x02\x04self\x8f\x01\x05index~\0\x7f\x04\0\x1b\
+[method]query.field-changed\x01\x9b\x01\x01p\x7f\x01@\x02\x04self\x8f\x01\x05ind\
+ex~\0\x9c\x01\x04\0\x19[method]query.field-added\x01\x9d\x01\x01@\x01\x03ptrw\0\x91\
+\x01\x04\0\x11[constructor]iter\x01\x9e\x01\x01h-\x01@\x01\x04self\x9f\x01\0\x7f\
+\x04\0\x11[method]iter.next\x01\xa0\x01\x01@\x01\x04self\x9f\x01\0z\x04\0\x12[me\
+thod]iter.count\x01\xa1\x01\x01@\x01\x04self\x9f\x01\0\x01\x04\0\x13[method]iter\
+.system\x01\xa2\x01\x01@\x01\x04self\x9f\x01\0\x89\x01\x04\0\x15[method]iter.ent\
+ities\x01\xa3\x01\x01@\x02\x04self\x9f\x01\x05index~\0\x96\x01\x04\0\x17[method]\
+iter.components\x01\xa4\x01\x01@\x03\x04self\x9f\x01\x05index~\x09read-only\x7f\0\
+\x98\x01\x04\0\x18[method]iter.field-views\x01\xa5\x01\x01@\x04\x04self\x9f\x01\x05\
+index~\x06membery\x0bmember-type\x09\0\x0f\x04\0\x13[method]iter.column\x01\xa6\x01\
+\x01@\x05\x04self\x9f\x01\x05index~\x06membery\x0bmember-type\x09\x04data\x0f\x01\
+\0\x04\0\x17[method]iter.set-column\x01\xa7\x01\x01@\x02\x04self\x9f\x01\x05inde\
+x~\0\x0f\x04\0\x17[method]iter.field-data\x01\xa8\x01\x01@\x03\x04self\x9f\x01\x05\
+index~\x04data\x0f\x01\0\x04\0\x1b[method]iter.set-field-data\x01\xa9\x01\x04\0\x14\
+[method]iter.changed\x01\xa0\x01\x01@\x01\x04self\x9f\x01\x01\0\x04\0\x11[method\
+]iter.skip\x01\xaa\x01\x01@\x02\x04self\x9f\x01\x05index~\0\x7f\x04\0\x1a[method\
+]iter.field-changed\x01\xab\x01\x01@\x02\x04self\x9f\x01\x05index~\0\x9c\x01\x04\
+\0\x18[method]iter.field-added\x01\xac\x01\x01@\x01\x06handlew\0/\x04\0\x15[cons\
+tructor]callback\x01\xad\x01\x01h.\x01@\x02\x04self\xae\x01\x04iter\x91\x01\x01\0\
+\x04\0\x14[method]callback.run\x01\xaf\x01\x01@\x01\x04self\xae\x01\0\x03\x04\0\x1a\
+[method]callback.cb-handle\x01\xb0\x01\x01i8\x01@\x01\x04desc4\0\xb1\x01\x04\0\x13\
+[constructor]system\x01\xb2\x01\x01h8\x01@\x01\x04self\xb3\x01\0\x01\x04\0\x15[m\
+ethod]system.get-id\x01\xb4\x01\x01@\x01\x04self\xb3\x01\x01\0\x04\0\x14[method]\
+system.build\x01\xb5\x01\x01@\x02\x04self\xb3\x01\x04names\x01\0\x04\0\x14[metho\
+d]system.named\x01\xb6\x01\x01@\x03\x04self\xb3\x01\x05index~\x04kind\x0d\x01\0\x04\
+\0\x16[method]system.monitor\x01\xb7\x01\x01@\x02\x04self\xb3\x01\x07sorting1\x01\
+\0\x04\0\x17[method]system.order-by\x01\xb8\x01\x01@\x01\x04self\xb3\x01\0/\x04\0\
+\x17[method]system.callback\x01\xb9\x01\x04\0\x16[method]system.disable\x01\xb5\x01\
+\x04\0\x15[method]system.enable\x01\xb5\x01\x01i9\x01@\x01\x04names\0\xba\x01\x04\
+\0\x12[constructor]phase\x01\xbb\x01\x01h9\x01@\x02\x04self\xbc\x01\x05phase\x07\
+\x01\0\x04\0\x18[method]phase.depends-on\x01\xbd\x01\x01@\x01\x04self\xbc\x01\0\x01\
+\x04\0\x14[method]phase.get-id\x01\xbe\x01\x01i:\x01@\x01\x04desc)\0\xbf\x01\x04\
+\0\x15[constructor]pipeline\x01\xc0\x01\x01h:\x01@\x01\x04self\xc1\x01\x01\0\x04\
+\0\x16[method]pipeline.build\x01\xc2\x01\x01@\x02\x04self\xc1\x01\x05phase\x01\x01\
+\0\x04\0\x1a[method]pipeline.add-phase\x01\xc3\x01\x01@\x01\x04self\xc1\x01\0\x01\
+\x04\0\x17[method]pipeline.get-id\x01\xc4\x01\x04\0\x18[method]pipeline.disable\x01\
+\xc2\x01\x04\0\x17[method]pipeline.enable\x01\xc2\x01\x01i;\x01@\x01\x04desc7\0\xc5\
+\x01\x04\0\x15[constructor]observer\x01\xc6\x01\x01h;\x01@\x01\x04self\xc7\x01\0\
+\x01\x04\0\x17[method]observer.get-id\x01\xc8\x01\x01@\x01\x04self\xc7\x01\x01\0\
+\x04\0\x16[method]observer.build\x01\xc9\x01\x01@\x01\x04self\xc7\x01\0/\x04\0\x19\
+[method]observer.callback\x01\xca\x01\x01@\x01\x09component\x01\x01\0\x04\0\x0da\
+dd-singleton\x01\xcb\x01\x01@\x01\x09component\x01\0\xc0\0\x04\0\x0dget-singleto\
+n\x01\xcc\x01\x04\0\x10remove-singleton\x01\xcb\x01\x01@\x01\x06entity\x01\x01\0\
+\x04\0\x0aadd-entity\x01\xcd\x01\x04\0\x0dremove-entity\x01\xcd\x01\x01@\x01\x04\
+names\0\x7f\x04\0\x10has-entity-named\x01\xce\x01\x01@\x01\x0ecomponent-names\0\x01\
+\x04\0\x10get-component-id\x01\xcf\x01\x01@\x01\x04desc\x18\0\x01\x04\0\x0dregis\
+ter-enum\x01\xd0\x01\x01j\x01s\x01s\x01@\x02\x06entity\x01\x09recursive\x7f\0\xd1\
+\x01\x04\0\x0eentity-to-json\x01\xd2\x01\x01@\x02\x04jsons\x06parent\xf9\0\0\xfc\
+\0\x04\0\x10entity-from-json\x01\xd3\x01\x01@\0\0\x01\x04\0\x0dcommand-spawn\x01\
+\xd4\x01\x04\0\x0fcommand-despawn\x01\xcd\x01\x01@\x02\x06entity\x01\x09componen\
+t\x01\x01\0\x04\0\x0bcommand-add\x01\xd5\x01\x04\0\x0ecommand-remove\x01\xd5\x01\
+\x01p\x1c\x01@\0\0\xd6\x01\x04\0\x0eflush-commands\x01\xd7\x01\x04\0\x13take-com\
+mand-errors\x01\xd7\x01\x03\0\x1etoxoid-component:component/ecs\x05\0\x01B\x13\x01\
+y\x04\0\x08key-code\x03\0\0\x01r\x03\x05deltav\x07elapsedu\x05framew\x04\0\x09ti\
+me-info\x03\0\x02\x01r\x04\x05widthy\x06heighty\x0crender-widthy\x0drender-heigh\
+ty\x04\0\x0bwindow-info\x03\0\x04\x01r\x09\x01xv\x01yv\x02dxv\x02dyv\x08scroll-x\
+v\x08scroll-yv\x04left\x7f\x05right\x7f\x06middle\x7f\x04\0\x0bmouse-state\x03\0\
+\x06\x01@\0\0\x03\x04\0\x04time\x01\x08\x01@\0\0\x05\x04\0\x06window\x01\x09\x01\
+@\0\0\x07\x04\0\x05mouse\x01\x0a\x01@\x01\x03key\x01\0\x7f\x04\0\x08key-down\x01\
+\x0b\x01p\x01\x01@\0\0\x0c\x04\0\x09keys-down\x01\x0d\x03\0!toxoid-component:com\
+ponent/engine\x05\x01\x01B\x1b\x01w\x04\0\x0casset-handle\x03\0\0\x01q\x03\x07lo\
+ading\0\0\x06loaded\0\0\x06failed\x01s\0\x04\0\x0casset-status\x03\0\x02\x01r\x02\
+\x06handle\x01\x06status\x03\x04\0\x0basset-event\x03\0\x04\x01@\x01\x04paths\0\x01\
+\x04\0\x0aload-image\x01\x06\x01@\x02\x04paths\x10rendered-on-load\x7f\0\x01\x04\
+\0\x0bload-sprite\x01\x07\x01@\x03\x0aatlas-paths\x0dskeleton-paths\x10rendered-\
+on-load\x7f\0\x01\x04\0\x0eload-animation\x01\x08\x04\0\x0dload-worldmap\x01\x07\
+\x04\0\x09load-data\x01\x06\x01@\x01\x06handle\x01\0\x03\x04\0\x06status\x01\x09\
+\x01o\x02yy\x01k\x0a\x01@\x01\x06handle\x01\0\x0b\x04\0\x04size\x01\x0c\x01p}\x01\
+k\x0d\x01@\x01\x06handle\x01\0\x0e\x04\0\x04data\x01\x0f\x01p\x05\x01@\0\0\x10\x04\
+\0\x0bpoll-events\x01\x11\x03\0!toxoid-component:component/assets\x05\x02\x02\x03\
+\0\x02\x0casset-handle\x01B\x0a\x02\x03\x02\x01\x03\x04\0\x0casset-handle\x03\0\0\
+\x01r\x04\x01rv\x01gv\x01bv\x01av\x04\0\x04rgba\x03\0\x02\x01@\x03\x06sprite\x01\
+\x01xv\x01yv\x01\0\x04\0\x0bdraw-sprite\x01\x04\x01@\x05\x01xv\x01yv\x05widthv\x06\
+heightv\x05color\x03\x01\0\x04\0\x09draw-rect\x01\x05\x01@\x05\x02axv\x02ayv\x02\
+bxv\x02byv\x05color\x03\x01\0\x04\0\x09draw-line\x01\x06\x03\0!toxoid-component:\
+component/render\x05\x04\x01B\x09\x01m\x05\x05trace\x05debug\x04info\x04warn\x05\
+error\x04\0\x05level\x03\0\0\x01r\x02\x03keys\x05values\x04\0\x05field\x03\0\x02\
+\x01@\x01\x05level\x01\0\x7f\x04\0\x07enabled\x01\x04\x01p\x03\x01@\x04\x05level\
+\x01\x06targets\x07messages\x06fields\x05\x01\0\x04\0\x03log\x01\x06\x03\0\x1eto\
+xoid-component:component/log\x05\x05\x02\x03\0\0\x0cecs-entity-t\x01B\x0c\x02\x03\
+\x02\x01\x06\x04\0\x0cecs-entity-t\x03\0\0\x01w\x04\0\x09stream-id\x03\0\x02\x01\
+@\0\0w\x04\0\x04seed\x01\x04\x01@\x01\x04names\0\x03\x04\0\x0cnamed-stream\x01\x05\
+\x01@\x01\x06system\x01\0\x03\x04\0\x0dsystem-stream\x01\x06\x01@\x01\x02id\x03\0\
+w\x04\0\x08next-u64\x01\x07\x03\0!toxoid-component:component/random\x05\x07\x01@\
+\0\x01\0\x04\0\x04init\x01\x08\x02\x03\0\0\x04iter\x02\x03\0\0\x09pointer-t\x01B\
+\x0d\x02\x03\x02\x01\x09\x04\0\x04iter\x03\0\0\x02\x03\x02\x01\x06\x04\0\x0cecs-\
+entity-t\x03\0\x02\x02\x03\x02\x01\x0a\x04\0\x09pointer-t\x03\0\x04\x01i\x01\x01\
+@\x02\x04iter\x06\x06handlew\x01\0\x04\0\x03run\x01\x07\x01@\x01\x06handlew\x01\0\
+\x04\0\x04free\x01\x08\x01@\x05\x06handlew\x02e1\x03\x02c1\x05\x02e2\x03\x02c2\x05\
+\0z\x04\0\x07compare\x01\x09\x04\0$toxoid-component:component/callbacks\x05\x0b\x04\
+\01toxoid-component:component/toxoid-component-world\x04\0\x0b\x1c\x01\0\x16toxo\
+id-component-world\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-compon\
+ent\x070.220.0\x10wit-bindgen-rust\x060.35.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -273,11 +273,54 @@ pub mod exports {
                         }
                     }
                 }
+                /// Filters on a field of a query or system, see monitor
+                #[repr(u8)]
+                #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+                pub enum MonitorKind {
+                    /// The field's component was written since the table was last seen
+                    Changed,
+                    /// Entities got the field's component since they were last seen
+                    Added,
+                }
+                impl ::core::fmt::Debug for MonitorKind {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            MonitorKind::Changed => {
+                                f.debug_tuple("MonitorKind::Changed").finish()
+                            }
+                            MonitorKind::Added => {
+                                f.debug_tuple("MonitorKind::Added").finish()
+                            }
+                        }
+                    }
+                }
+                impl MonitorKind {
+                    #[doc(hidden)]
+                    pub unsafe fn _lift(val: u8) -> MonitorKind {
+                        if !cfg!(debug_assertions) {
+                            return ::core::mem::transmute(val);
+                        }
+                        match val {
+                            0 => MonitorKind::Changed,
+                            1 => MonitorKind::Added,
+                            _ => panic!("invalid enum discriminant"),
+                        }
+                    }
+                }
                 #[derive(Clone)]
                 pub struct ComponentDesc {
                     pub name: _rt::String,
                     pub member_names: _rt::Vec<_rt::String>,
                     pub member_types: _rt::Vec<u8>,
+                    /// Per member, may be left empty. The type of a member with the nested type code,
+                    /// a component type registered before or an enum from register-enum.
+                    pub member_type_ids: _rt::Vec<EcsEntityT>,
+                    /// Per member, may be left empty. Elements of a fixed-size array stored inline,
+                    /// 0 or 1 for a single value.
+                    pub member_counts: _rt::Vec<u32>,
                 }
                 impl ::core::fmt::Debug for ComponentDesc {
                     fn fmt(
@@ -288,6 +331,120 @@ pub mod exports {
                             .field("name", &self.name)
                             .field("member-names", &self.member_names)
                             .field("member-types", &self.member_types)
+                            .field("member-type-ids", &self.member_type_ids)
+                            .field("member-counts", &self.member_counts)
+                            .finish()
+                    }
+                }
+                #[derive(Clone)]
+                pub struct EnumConstant {
+                    pub name: _rt::String,
+                    pub value: u32,
+                }
+                impl ::core::fmt::Debug for EnumConstant {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("EnumConstant")
+                            .field("name", &self.name)
+                            .field("value", &self.value)
+                            .finish()
+                    }
+                }
+                #[derive(Clone)]
+                pub struct EnumDesc {
+                    pub name: _rt::String,
+                    pub constants: _rt::Vec<EnumConstant>,
+                    /// Flags that are combined, stored as a u32 instead of an s32
+                    pub bitmask: bool,
+                }
+                impl ::core::fmt::Debug for EnumDesc {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("EnumDesc")
+                            .field("name", &self.name)
+                            .field("constants", &self.constants)
+                            .field("bitmask", &self.bitmask)
+                            .finish()
+                    }
+                }
+                #[repr(u8)]
+                #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+                pub enum CommandKind {
+                    Despawn,
+                    Add,
+                    Remove,
+                }
+                impl ::core::fmt::Debug for CommandKind {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            CommandKind::Despawn => {
+                                f.debug_tuple("CommandKind::Despawn").finish()
+                            }
+                            CommandKind::Add => {
+                                f.debug_tuple("CommandKind::Add").finish()
+                            }
+                            CommandKind::Remove => {
+                                f.debug_tuple("CommandKind::Remove").finish()
+                            }
+                        }
+                    }
+                }
+                impl CommandKind {
+                    #[doc(hidden)]
+                    pub unsafe fn _lift(val: u8) -> CommandKind {
+                        if !cfg!(debug_assertions) {
+                            return ::core::mem::transmute(val);
+                        }
+                        match val {
+                            0 => CommandKind::Despawn,
+                            1 => CommandKind::Add,
+                            2 => CommandKind::Remove,
+                            _ => panic!("invalid enum discriminant"),
+                        }
+                    }
+                }
+                /// A deferred command whose entity or component was deleted before it was applied
+                #[repr(C)]
+                #[derive(Clone, Copy)]
+                pub struct CommandError {
+                    pub kind: CommandKind,
+                    pub entity: EcsEntityT,
+                    /// 0 for despawn
+                    pub component: EcsEntityT,
+                }
+                impl ::core::fmt::Debug for CommandError {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("CommandError")
+                            .field("kind", &self.kind)
+                            .field("entity", &self.entity)
+                            .field("component", &self.component)
+                            .finish()
+                    }
+                }
+                /// Initial value of a component, its members in layout order encoded like in world snapshots
+                #[derive(Clone)]
+                pub struct ComponentValue {
+                    pub component: EcsEntityT,
+                    pub data: _rt::Vec<u8>,
+                }
+                impl ::core::fmt::Debug for ComponentValue {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("ComponentValue")
+                            .field("component", &self.component)
+                            .field("data", &self.data)
                             .finish()
                     }
                 }
@@ -296,6 +453,7 @@ pub mod exports {
                     pub name: Option<_rt::String>,
                     pub add: Option<_rt::Vec<EcsEntityT>>,
                     pub prefab: bool,
+                    pub values: Option<_rt::Vec<ComponentValue>>,
                 }
                 impl ::core::fmt::Debug for EntityDesc {
                     fn fmt(
@@ -306,6 +464,7 @@ pub mod exports {
                             .field("name", &self.name)
                             .field("add", &self.add)
                             .field("prefab", &self.prefab)
+                            .field("values", &self.values)
                             .finish()
                     }
                 }
@@ -1608,6 +1767,10 @@ pub mod exports {
                     arg3: usize,
                     arg4: *mut u8,
                     arg5: usize,
+                    arg6: *mut u8,
+                    arg7: usize,
+                    arg8: *mut u8,
+                    arg9: usize,
                 ) -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let len0 = arg1;
@@ -1628,7 +1791,9 @@ pub mod exports {
                     }
                     _rt::cabi_dealloc(base4, len4 * 8, 4);
                     let len5 = arg5;
-                    let result6 = ComponentType::new(
+                    let len6 = arg7;
+                    let len7 = arg9;
+                    let result8 = ComponentType::new(
                         T::new(ComponentDesc {
                             name: _rt::string_lift(bytes0),
                             member_names: result4,
@@ -1637,9 +1802,19 @@ pub mod exports {
                                 len5,
                                 len5,
                             ),
+                            member_type_ids: _rt::Vec::from_raw_parts(
+                                arg6.cast(),
+                                len6,
+                                len6,
+                            ),
+                            member_counts: _rt::Vec::from_raw_parts(
+                                arg8.cast(),
+                                len7,
+                                len7,
+                            ),
                         }),
                     );
-                    (result6).take_handle() as i32
+                    (result8).take_handle() as i32
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -1669,9 +1844,10 @@ pub mod exports {
                 #[allow(non_snake_case)]
                 pub unsafe fn _export_static_component_from_ptr_cabi<T: GuestComponent>(
                     arg0: i64,
+                    arg1: i64,
                 ) -> i64 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::from_ptr(arg0 as u64);
+                    let result0 = T::from_ptr(arg0 as u64, arg1 as u64);
                     _rt::as_i64(result0)
                 }
                 #[doc(hidden)]
@@ -2448,10 +2624,13 @@ pub mod exports {
                     arg5: usize,
                     arg6: i32,
                     arg7: i32,
-                    arg8: i64,
+                    arg8: *mut u8,
+                    arg9: usize,
+                    arg10: i32,
+                    arg11: i64,
                 ) -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result2 = Entity::new(
+                    let result7 = Entity::new(
                         T::new(
                             EntityDesc {
                                 name: match arg0 {
@@ -2482,18 +2661,164 @@ pub mod exports {
                                     _ => _rt::invalid_enum_discriminant(),
                                 },
                                 prefab: _rt::bool_lift(arg6 as u8),
+                                values: match arg7 {
+                                    0 => None,
+                                    1 => {
+                                        let e = {
+                                            let base6 = arg8;
+                                            let len6 = arg9;
+                                            let mut result6 = _rt::Vec::with_capacity(len6);
+                                            for i in 0..len6 {
+                                                let base = base6.add(i * 16);
+                                                let e6 = {
+                                                    let l2 = *base.add(0).cast::<i64>();
+                                                    let l3 = *base.add(8).cast::<*mut u8>();
+                                                    let l4 = *base.add(12).cast::<usize>();
+                                                    let len5 = l4;
+                                                    ComponentValue {
+                                                        component: l2 as u64,
+                                                        data: _rt::Vec::from_raw_parts(l3.cast(), len5, len5),
+                                                    }
+                                                };
+                                                result6.push(e6);
+                                            }
+                                            _rt::cabi_dealloc(base6, len6 * 16, 8);
+                                            result6
+                                        };
+                                        Some(e)
+                                    }
+                                    _ => _rt::invalid_enum_discriminant(),
+                                },
                             },
-                            match arg7 {
+                            match arg10 {
                                 0 => None,
                                 1 => {
-                                    let e = arg8 as u64;
+                                    let e = arg11 as u64;
                                     Some(e)
                                 }
                                 _ => _rt::invalid_enum_discriminant(),
                             },
                         ),
                     );
-                    (result2).take_handle() as i32
+                    (result7).take_handle() as i32
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_static_entity_create_cabi<T: GuestEntity>(
+                    arg0: i32,
+                    arg1: *mut u8,
+                    arg2: usize,
+                    arg3: i32,
+                    arg4: *mut u8,
+                    arg5: usize,
+                    arg6: i32,
+                    arg7: i32,
+                    arg8: *mut u8,
+                    arg9: usize,
+                    arg10: i32,
+                    arg11: i64,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result7 = T::create(
+                        EntityDesc {
+                            name: match arg0 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let len0 = arg2;
+                                        let bytes0 = _rt::Vec::from_raw_parts(
+                                            arg1.cast(),
+                                            len0,
+                                            len0,
+                                        );
+                                        _rt::string_lift(bytes0)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            add: match arg3 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let len1 = arg5;
+                                        _rt::Vec::from_raw_parts(arg4.cast(), len1, len1)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            prefab: _rt::bool_lift(arg6 as u8),
+                            values: match arg7 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let base6 = arg8;
+                                        let len6 = arg9;
+                                        let mut result6 = _rt::Vec::with_capacity(len6);
+                                        for i in 0..len6 {
+                                            let base = base6.add(i * 16);
+                                            let e6 = {
+                                                let l2 = *base.add(0).cast::<i64>();
+                                                let l3 = *base.add(8).cast::<*mut u8>();
+                                                let l4 = *base.add(12).cast::<usize>();
+                                                let len5 = l4;
+                                                ComponentValue {
+                                                    component: l2 as u64,
+                                                    data: _rt::Vec::from_raw_parts(l3.cast(), len5, len5),
+                                                }
+                                            };
+                                            result6.push(e6);
+                                        }
+                                        _rt::cabi_dealloc(base6, len6 * 16, 8);
+                                        result6
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                        },
+                        match arg10 {
+                            0 => None,
+                            1 => {
+                                let e = arg11 as u64;
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                    );
+                    let ptr8 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    match result7 {
+                        Ok(e) => {
+                            *ptr8.add(0).cast::<u8>() = (0i32) as u8;
+                            *ptr8.add(8).cast::<i64>() = _rt::as_i64(e);
+                        }
+                        Err(e) => {
+                            *ptr8.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec9 = (e.into_bytes()).into_boxed_slice();
+                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                            let len9 = vec9.len();
+                            ::core::mem::forget(vec9);
+                            *ptr8.add(12).cast::<usize>() = len9;
+                            *ptr8.add(8).cast::<*mut u8>() = ptr9.cast_mut();
+                        }
+                    };
+                    ptr8
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_static_entity_create<T: GuestEntity>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0.add(8).cast::<*mut u8>();
+                            let l2 = *arg0.add(12).cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -2902,20 +3227,35 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_constructor_iter_cabi<T: GuestIter>(
-                    arg0: i64,
-                ) -> i32 {
+                pub unsafe fn _export_method_query_cache_cabi<T: GuestQuery>(
+                    arg0: *mut u8,
+                ) {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = Iter::new(T::new(arg0 as u64));
-                    (result0).take_handle() as i32
+                    T::cache(QueryBorrow::lift(arg0 as u32 as usize).get());
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_iter_next_cabi<T: GuestIter>(
+                pub unsafe fn _export_method_query_monitor_cabi<T: GuestQuery>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                    arg2: i32,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::monitor(
+                        QueryBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                        MonitorKind::_lift(arg2 as u8),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_query_changed_cabi<T: GuestQuery>(
                     arg0: *mut u8,
                 ) -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::next(IterBorrow::lift(arg0 as u32 as usize).get());
+                    let result0 = T::changed(
+                        QueryBorrow::lift(arg0 as u32 as usize).get(),
+                    );
                     match result0 {
                         true => 1,
                         false => 0,
@@ -2923,97 +3263,409 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_iter_count_cabi<T: GuestIter>(
+                pub unsafe fn _export_method_query_iter_changed_cabi<T: GuestQuery>(
                     arg0: *mut u8,
                 ) -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::count(IterBorrow::lift(arg0 as u32 as usize).get());
-                    _rt::as_i32(result0)
+                    let result0 = T::iter_changed(
+                        QueryBorrow::lift(arg0 as u32 as usize).get(),
+                    );
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_iter_entities_cabi<T: GuestIter>(
+                pub unsafe fn _export_method_query_skip_cabi<T: GuestQuery>(
                     arg0: *mut u8,
-                ) -> *mut u8 {
+                ) {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::entities(
-                        IterBorrow::lift(arg0 as u32 as usize).get(),
-                    );
-                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    let vec2 = (result0).into_boxed_slice();
-                    let ptr2 = vec2.as_ptr().cast::<u8>();
-                    let len2 = vec2.len();
-                    ::core::mem::forget(vec2);
-                    *ptr1.add(4).cast::<usize>() = len2;
-                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
-                    ptr1
+                    T::skip(QueryBorrow::lift(arg0 as u32 as usize).get());
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_method_iter_entities<T: GuestIter>(
+                pub unsafe fn _export_method_query_field_changed_cabi<T: GuestQuery>(
                     arg0: *mut u8,
-                ) {
-                    let l0 = *arg0.add(0).cast::<*mut u8>();
-                    let l1 = *arg0.add(4).cast::<usize>();
-                    let base2 = l0;
-                    let len2 = l1;
-                    _rt::cabi_dealloc(base2, len2 * 8, 8);
+                    arg1: i32,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::field_changed(
+                        QueryBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                    );
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_iter_components_cabi<T: GuestIter>(
+                pub unsafe fn _export_method_query_field_added_cabi<T: GuestQuery>(
                     arg0: *mut u8,
                     arg1: i32,
                 ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::components(
-                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                    let result0 = T::field_added(
+                        QueryBorrow::lift(arg0 as u32 as usize).get(),
                         arg1 as i8,
                     );
                     let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    let vec2 = (result0).into_boxed_slice();
-                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let vec2 = result0;
                     let len2 = vec2.len();
-                    ::core::mem::forget(vec2);
+                    let layout2 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec2.len() * 1,
+                        1,
+                    );
+                    let result2 = if layout2.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout2).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout2);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec2.into_iter().enumerate() {
+                        let base = result2.add(i * 1);
+                        {
+                            *base.add(0).cast::<u8>() = (match e {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
+                        }
+                    }
                     *ptr1.add(4).cast::<usize>() = len2;
-                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    *ptr1.add(0).cast::<*mut u8>() = result2;
                     ptr1
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_method_iter_components<T: GuestIter>(
+                pub unsafe fn __post_return_method_query_field_added<T: GuestQuery>(
                     arg0: *mut u8,
                 ) {
                     let l0 = *arg0.add(0).cast::<*mut u8>();
                     let l1 = *arg0.add(4).cast::<usize>();
                     let base2 = l0;
                     let len2 = l1;
-                    _rt::cabi_dealloc(base2, len2 * 8, 8);
+                    _rt::cabi_dealloc(base2, len2 * 1, 1);
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_constructor_callback_cabi<T: GuestCallback>(
+                pub unsafe fn _export_constructor_iter_cabi<T: GuestIter>(
                     arg0: i64,
                 ) -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = Callback::new(T::new(arg0 as u64));
+                    let result0 = Iter::new(T::new(arg0 as u64));
                     (result0).take_handle() as i32
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_callback_run_cabi<T: GuestCallback>(
+                pub unsafe fn _export_method_iter_next_cabi<T: GuestIter>(
                     arg0: *mut u8,
-                    arg1: i32,
-                ) {
+                ) -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::run(
-                        CallbackBorrow::lift(arg0 as u32 as usize).get(),
-                        Iter::from_handle(arg1 as u32),
-                    );
+                    let result0 = T::next(IterBorrow::lift(arg0 as u32 as usize).get());
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_callback_cb_handle_cabi<T: GuestCallback>(
+                pub unsafe fn _export_method_iter_count_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::count(IterBorrow::lift(arg0 as u32 as usize).get());
+                    _rt::as_i32(result0)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_system_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                ) -> i64 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::system(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                    );
+                    _rt::as_i64(result0)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_entities_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::entities(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                    );
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec2 = (result0).into_boxed_slice();
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    ::core::mem::forget(vec2);
+                    *ptr1.add(4).cast::<usize>() = len2;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_iter_entities<T: GuestIter>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 8, 8);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_components_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::components(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                    );
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec2 = (result0).into_boxed_slice();
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    ::core::mem::forget(vec2);
+                    *ptr1.add(4).cast::<usize>() = len2;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_iter_components<T: GuestIter>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 8, 8);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_column_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                    arg2: i32,
+                    arg3: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::column(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                        arg2 as u32,
+                        MemberType::_lift(arg3 as u8),
+                    );
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec2 = (result0).into_boxed_slice();
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    ::core::mem::forget(vec2);
+                    *ptr1.add(4).cast::<usize>() = len2;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_iter_column<T: GuestIter>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 1, 1);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_set_column_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                    arg2: i32,
+                    arg3: i32,
+                    arg4: *mut u8,
+                    arg5: usize,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg5;
+                    T::set_column(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                        arg2 as u32,
+                        MemberType::_lift(arg3 as u8),
+                        _rt::Vec::from_raw_parts(arg4.cast(), len0, len0),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_field_data_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::field_data(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                    );
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec2 = (result0).into_boxed_slice();
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    ::core::mem::forget(vec2);
+                    *ptr1.add(4).cast::<usize>() = len2;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_iter_field_data<T: GuestIter>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 1, 1);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_set_field_data_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg3;
+                    T::set_field_data(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                        _rt::Vec::from_raw_parts(arg2.cast(), len0, len0),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_changed_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::changed(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                    );
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_skip_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::skip(IterBorrow::lift(arg0 as u32 as usize).get());
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_field_changed_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::field_changed(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                    );
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_iter_field_added_cabi<T: GuestIter>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::field_added(
+                        IterBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                    );
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec2 = result0;
+                    let len2 = vec2.len();
+                    let layout2 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec2.len() * 1,
+                        1,
+                    );
+                    let result2 = if layout2.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout2).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout2);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec2.into_iter().enumerate() {
+                        let base = result2.add(i * 1);
+                        {
+                            *base.add(0).cast::<u8>() = (match e {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
+                        }
+                    }
+                    *ptr1.add(4).cast::<usize>() = len2;
+                    *ptr1.add(0).cast::<*mut u8>() = result2;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_iter_field_added<T: GuestIter>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 1, 1);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_constructor_callback_cabi<T: GuestCallback>(
+                    arg0: i64,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = Callback::new(T::new(arg0 as u64));
+                    (result0).take_handle() as i32
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_callback_run_cabi<T: GuestCallback>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::run(
+                        CallbackBorrow::lift(arg0 as u32 as usize).get(),
+                        Iter::from_handle(arg1 as u32),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_callback_cb_handle_cabi<T: GuestCallback>(
                     arg0: *mut u8,
                 ) -> i64 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
@@ -3109,6 +3761,20 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
+                pub unsafe fn _export_method_system_monitor_cabi<T: GuestSystem>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                    arg2: i32,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::monitor(
+                        SystemBorrow::lift(arg0 as u32 as usize).get(),
+                        arg1 as i8,
+                        MonitorKind::_lift(arg2 as u8),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
                 pub unsafe fn _export_method_system_order_by_cabi<T: GuestSystem>(
                     arg0: *mut u8,
                     arg1: i64,
@@ -3125,6 +3791,18 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
+                pub unsafe fn _export_method_system_multi_threaded_cabi<T: GuestSystem>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::multi_threaded(
+                        SystemBorrow::lift(arg0 as u32 as usize).get(),
+                        _rt::bool_lift(arg1 as u8),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
                 pub unsafe fn _export_method_system_callback_cabi<T: GuestSystem>(
                     arg0: *mut u8,
                 ) -> i64 {
@@ -3331,80 +4009,357 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_observer_build_cabi<T: GuestObserver>(
-                    arg0: *mut u8,
-                ) {
+                pub unsafe fn _export_method_observer_get_id_cabi<T: GuestObserver>(
+                    arg0: *mut u8,
+                ) -> i64 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::get_id(
+                        ObserverBorrow::lift(arg0 as u32 as usize).get(),
+                    );
+                    _rt::as_i64(result0)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_observer_build_cabi<T: GuestObserver>(
+                    arg0: *mut u8,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::build(ObserverBorrow::lift(arg0 as u32 as usize).get());
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_observer_callback_cabi<T: GuestObserver>(
+                    arg0: *mut u8,
+                ) -> i64 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::callback(
+                        ObserverBorrow::lift(arg0 as u32 as usize).get(),
+                    );
+                    _rt::as_i64(result0)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_add_singleton_cabi<T: Guest>(arg0: i64) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::add_singleton(arg0 as u64);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_get_singleton_cabi<T: Guest>(arg0: i64) -> i64 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::get_singleton(arg0 as u64);
+                    _rt::as_i64(result0)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_remove_singleton_cabi<T: Guest>(arg0: i64) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::remove_singleton(arg0 as u64);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_add_entity_cabi<T: Guest>(arg0: i64) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::add_entity(arg0 as u64);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_remove_entity_cabi<T: Guest>(arg0: i64) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::remove_entity(arg0 as u64);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_has_entity_named_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                ) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let result1 = T::has_entity_named(_rt::string_lift(bytes0));
+                    match result1 {
+                        true => 1,
+                        false => 0,
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_get_component_id_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                ) -> i64 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let result1 = T::get_component_id(_rt::string_lift(bytes0));
+                    _rt::as_i64(result1)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_register_enum_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                    arg4: i32,
+                ) -> i64 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let base5 = arg2;
+                    let len5 = arg3;
+                    let mut result5 = _rt::Vec::with_capacity(len5);
+                    for i in 0..len5 {
+                        let base = base5.add(i * 12);
+                        let e5 = {
+                            let l1 = *base.add(0).cast::<*mut u8>();
+                            let l2 = *base.add(4).cast::<usize>();
+                            let len3 = l2;
+                            let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
+                            let l4 = *base.add(8).cast::<i32>();
+                            EnumConstant {
+                                name: _rt::string_lift(bytes3),
+                                value: l4 as u32,
+                            }
+                        };
+                        result5.push(e5);
+                    }
+                    _rt::cabi_dealloc(base5, len5 * 12, 4);
+                    let result6 = T::register_enum(EnumDesc {
+                        name: _rt::string_lift(bytes0),
+                        constants: result5,
+                        bitmask: _rt::bool_lift(arg4 as u8),
+                    });
+                    _rt::as_i64(result6)
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_entity_to_json_cabi<T: Guest>(
+                    arg0: i64,
+                    arg1: i32,
+                ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::build(ObserverBorrow::lift(arg0 as u32 as usize).get());
+                    let result0 = T::entity_to_json(
+                        arg0 as u64,
+                        _rt::bool_lift(arg1 as u8),
+                    );
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    match result0 {
+                        Ok(e) => {
+                            *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec2 = (e.into_bytes()).into_boxed_slice();
+                            let ptr2 = vec2.as_ptr().cast::<u8>();
+                            let len2 = vec2.len();
+                            ::core::mem::forget(vec2);
+                            *ptr1.add(8).cast::<usize>() = len2;
+                            *ptr1.add(4).cast::<*mut u8>() = ptr2.cast_mut();
+                        }
+                        Err(e) => {
+                            *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr1.add(8).cast::<usize>() = len3;
+                            *ptr1.add(4).cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
+                    ptr1
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_observer_callback_cabi<T: GuestObserver>(
+                pub unsafe fn __post_return_entity_to_json<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0.add(4).cast::<*mut u8>();
+                            let l2 = *arg0.add(8).cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                        _ => {
+                            let l3 = *arg0.add(4).cast::<*mut u8>();
+                            let l4 = *arg0.add(8).cast::<usize>();
+                            _rt::cabi_dealloc(l3, l4, 1);
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_entity_from_json_cabi<T: Guest>(
                     arg0: *mut u8,
-                ) -> i64 {
+                    arg1: usize,
+                    arg2: i32,
+                    arg3: i64,
+                ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::callback(
-                        ObserverBorrow::lift(arg0 as u32 as usize).get(),
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let result1 = T::entity_from_json(
+                        _rt::string_lift(bytes0),
+                        match arg2 {
+                            0 => None,
+                            1 => {
+                                let e = arg3 as u64;
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
                     );
-                    _rt::as_i64(result0)
+                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            *ptr2.add(8).cast::<i64>() = _rt::as_i64(e);
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2.add(12).cast::<usize>() = len3;
+                            *ptr2.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
+                    ptr2
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_add_singleton_cabi<T: Guest>(arg0: i64) {
-                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::add_singleton(arg0 as u64);
+                pub unsafe fn __post_return_entity_from_json<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0.add(8).cast::<*mut u8>();
+                            let l2 = *arg0.add(12).cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_get_singleton_cabi<T: Guest>(arg0: i64) -> i64 {
+                pub unsafe fn _export_command_spawn_cabi<T: Guest>() -> i64 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::get_singleton(arg0 as u64);
+                    let result0 = T::command_spawn();
                     _rt::as_i64(result0)
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_remove_singleton_cabi<T: Guest>(arg0: i64) {
+                pub unsafe fn _export_command_despawn_cabi<T: Guest>(arg0: i64) {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::remove_singleton(arg0 as u64);
+                    T::command_despawn(arg0 as u64);
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_add_entity_cabi<T: Guest>(arg0: i64) {
+                pub unsafe fn _export_command_add_cabi<T: Guest>(arg0: i64, arg1: i64) {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::add_entity(arg0 as u64);
+                    T::command_add(arg0 as u64, arg1 as u64);
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_remove_entity_cabi<T: Guest>(arg0: i64) {
+                pub unsafe fn _export_command_remove_cabi<T: Guest>(
+                    arg0: i64,
+                    arg1: i64,
+                ) {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::remove_entity(arg0 as u64);
+                    T::command_remove(arg0 as u64, arg1 as u64);
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_has_entity_named_cabi<T: Guest>(
-                    arg0: *mut u8,
-                    arg1: usize,
-                ) -> i32 {
+                pub unsafe fn _export_flush_commands_cabi<T: Guest>() -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let len0 = arg1;
-                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-                    let result1 = T::has_entity_named(_rt::string_lift(bytes0));
-                    match result1 {
-                        true => 1,
-                        false => 0,
+                    let result0 = T::flush_commands();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec3 = result0;
+                    let len3 = vec3.len();
+                    let layout3 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec3.len() * 24,
+                        8,
+                    );
+                    let result3 = if layout3.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout3);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec3.into_iter().enumerate() {
+                        let base = result3.add(i * 24);
+                        {
+                            let CommandError {
+                                kind: kind2,
+                                entity: entity2,
+                                component: component2,
+                            } = e;
+                            *base.add(0).cast::<u8>() = (kind2.clone() as i32) as u8;
+                            *base.add(8).cast::<i64>() = _rt::as_i64(entity2);
+                            *base.add(16).cast::<i64>() = _rt::as_i64(component2);
+                        }
                     }
+                    *ptr1.add(4).cast::<usize>() = len3;
+                    *ptr1.add(0).cast::<*mut u8>() = result3;
+                    ptr1
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_get_component_id_cabi<T: Guest>(
-                    arg0: *mut u8,
-                    arg1: usize,
-                ) -> i64 {
+                pub unsafe fn __post_return_flush_commands<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 24, 8);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_take_command_errors_cabi<T: Guest>() -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let len0 = arg1;
-                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-                    let result1 = T::get_component_id(_rt::string_lift(bytes0));
-                    _rt::as_i64(result1)
+                    let result0 = T::take_command_errors();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec3 = result0;
+                    let len3 = vec3.len();
+                    let layout3 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec3.len() * 24,
+                        8,
+                    );
+                    let result3 = if layout3.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout3);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec3.into_iter().enumerate() {
+                        let base = result3.add(i * 24);
+                        {
+                            let CommandError {
+                                kind: kind2,
+                                entity: entity2,
+                                component: component2,
+                            } = e;
+                            *base.add(0).cast::<u8>() = (kind2.clone() as i32) as u8;
+                            *base.add(8).cast::<i64>() = _rt::as_i64(entity2);
+                            *base.add(16).cast::<i64>() = _rt::as_i64(component2);
+                        }
+                    }
+                    *ptr1.add(4).cast::<usize>() = len3;
+                    *ptr1.add(0).cast::<*mut u8>() = result3;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_take_command_errors<T: Guest>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 24, 8);
                 }
                 pub trait Guest {
                     type ComponentType: GuestComponentType;
@@ -3426,6 +4381,27 @@ pub mod exports {
                     /// get-entity-named: func(name: string) -> u64;
                     fn has_entity_named(name: _rt::String) -> bool;
                     fn get_component_id(component_name: _rt::String) -> EcsEntityT;
+                    fn register_enum(desc: EnumDesc) -> EcsEntityT;
+                    /// Entities as JSON, with their children when recursive. Fails for entities that aren't alive.
+                    fn entity_to_json(
+                        entity: EcsEntityT,
+                        recursive: bool,
+                    ) -> Result<_rt::String, _rt::String>;
+                    fn entity_from_json(
+                        json: _rt::String,
+                        parent: Option<EcsEntityT>,
+                    ) -> Result<EcsEntityT, _rt::String>;
+                    /// Deferred structural changes. They are applied in order at the start of the next phase,
+                    /// the ones from the last phase at the start of the next frame.
+                    /// A spawned entity is alive right away and gets its components when the commands are applied.
+                    fn command_spawn() -> EcsEntityT;
+                    fn command_despawn(entity: EcsEntityT);
+                    fn command_add(entity: EcsEntityT, component: EcsEntityT);
+                    fn command_remove(entity: EcsEntityT, component: EcsEntityT);
+                    /// Apply the pending commands now, outside of systems
+                    fn flush_commands() -> _rt::Vec<CommandError>;
+                    /// Errors of the commands applied at flush points since the last call
+                    fn take_command_errors() -> _rt::Vec<CommandError>;
                 }
                 pub trait GuestComponentType: 'static {
                     #[doc(hidden)]
@@ -3519,53 +4495,53 @@ pub mod exports {
                         entity: EcsEntityT,
                         component_type: EcsEntityT,
                     ) -> Self;
-                    fn from_ptr(ptr: PointerT) -> PointerT;
-                    fn set_member_u8(&self, offset: u32, value: u8);
-                    fn get_member_u8(&self, offset: u32) -> u8;
-                    fn set_member_u16(&self, offset: u32, value: u16);
-                    fn get_member_u16(&self, offset: u32) -> u16;
-                    fn set_member_u32(&self, offset: u32, value: u32);
-                    fn get_member_u32(&self, offset: u32) -> u32;
-                    fn set_member_u64(&self, offset: u32, value: u64);
-                    fn get_member_u64(&self, offset: u32) -> u64;
-                    fn set_member_i8(&self, offset: u32, value: i8);
-                    fn get_member_i8(&self, offset: u32) -> i8;
-                    fn set_member_i16(&self, offset: u32, value: i16);
-                    fn get_member_i16(&self, offset: u32) -> i16;
-                    fn set_member_i32(&self, offset: u32, value: i32);
-                    fn get_member_i32(&self, offset: u32) -> i32;
-                    fn set_member_i64(&self, offset: u32, value: i64);
-                    fn get_member_i64(&self, offset: u32) -> i64;
-                    fn set_member_f32(&self, offset: u32, value: f32);
-                    fn get_member_f32(&self, offset: u32) -> f32;
-                    fn set_member_f64(&self, offset: u32, value: f64);
-                    fn get_member_f64(&self, offset: u32) -> f64;
-                    fn set_member_bool(&self, offset: u32, value: bool);
-                    fn get_member_bool(&self, offset: u32) -> bool;
-                    fn set_member_string(&self, offset: u32, value: _rt::String);
-                    fn get_member_string(&self, offset: u32) -> _rt::String;
-                    fn set_member_u8list(&self, offset: u32, value: _rt::Vec<u8>);
-                    fn get_member_u8list(&self, offset: u32) -> _rt::Vec<u8>;
-                    fn set_member_u16list(&self, offset: u32, value: _rt::Vec<u16>);
-                    fn get_member_u16list(&self, offset: u32) -> _rt::Vec<u16>;
-                    fn set_member_u32list(&self, offset: u32, value: _rt::Vec<u32>);
-                    fn get_member_u32list(&self, offset: u32) -> _rt::Vec<u32>;
-                    fn set_member_u64list(&self, offset: u32, value: _rt::Vec<u64>);
-                    fn get_member_u64list(&self, offset: u32) -> _rt::Vec<u64>;
-                    fn set_member_i8list(&self, offset: u32, value: _rt::Vec<i8>);
-                    fn get_member_i8list(&self, offset: u32) -> _rt::Vec<i8>;
-                    fn set_member_i16list(&self, offset: u32, value: _rt::Vec<i16>);
-                    fn get_member_i16list(&self, offset: u32) -> _rt::Vec<i16>;
-                    fn set_member_i32list(&self, offset: u32, value: _rt::Vec<i32>);
-                    fn get_member_i32list(&self, offset: u32) -> _rt::Vec<i32>;
-                    fn set_member_i64list(&self, offset: u32, value: _rt::Vec<i64>);
-                    fn get_member_i64list(&self, offset: u32) -> _rt::Vec<i64>;
-                    fn set_member_f32list(&self, offset: u32, value: _rt::Vec<f32>);
-                    fn get_member_f32list(&self, offset: u32) -> _rt::Vec<f32>;
-                    fn set_member_f64list(&self, offset: u32, value: _rt::Vec<f64>);
-                    fn get_member_f64list(&self, offset: u32) -> _rt::Vec<f64>;
-                    fn set_member_pointer(&self, offset: u32, value: u64);
-                    fn get_member_pointer(&self, offset: u32) -> u64;
+                    fn from_ptr(ptr: PointerT, component_type: EcsEntityT) -> PointerT;
+                    fn set_member_u8(&self, member: u32, value: u8);
+                    fn get_member_u8(&self, member: u32) -> u8;
+                    fn set_member_u16(&self, member: u32, value: u16);
+                    fn get_member_u16(&self, member: u32) -> u16;
+                    fn set_member_u32(&self, member: u32, value: u32);
+                    fn get_member_u32(&self, member: u32) -> u32;
+                    fn set_member_u64(&self, member: u32, value: u64);
+                    fn get_member_u64(&self, member: u32) -> u64;
+                    fn set_member_i8(&self, member: u32, value: i8);
+                    fn get_member_i8(&self, member: u32) -> i8;
+                    fn set_member_i16(&self, member: u32, value: i16);
+                    fn get_member_i16(&self, member: u32) -> i16;
+                    fn set_member_i32(&self, member: u32, value: i32);
+                    fn get_member_i32(&self, member: u32) -> i32;
+                    fn set_member_i64(&self, member: u32, value: i64);
+                    fn get_member_i64(&self, member: u32) -> i64;
+                    fn set_member_f32(&self, member: u32, value: f32);
+                    fn get_member_f32(&self, member: u32) -> f32;
+                    fn set_member_f64(&self, member: u32, value: f64);
+                    fn get_member_f64(&self, member: u32) -> f64;
+                    fn set_member_bool(&self, member: u32, value: bool);
+                    fn get_member_bool(&self, member: u32) -> bool;
+                    fn set_member_string(&self, member: u32, value: _rt::String);
+                    fn get_member_string(&self, member: u32) -> _rt::String;
+                    fn set_member_u8list(&self, member: u32, value: _rt::Vec<u8>);
+                    fn get_member_u8list(&self, member: u32) -> _rt::Vec<u8>;
+                    fn set_member_u16list(&self, member: u32, value: _rt::Vec<u16>);
+                    fn get_member_u16list(&self, member: u32) -> _rt::Vec<u16>;
+                    fn set_member_u32list(&self, member: u32, value: _rt::Vec<u32>);
+                    fn get_member_u32list(&self, member: u32) -> _rt::Vec<u32>;
+                    fn set_member_u64list(&self, member: u32, value: _rt::Vec<u64>);
+                    fn get_member_u64list(&self, member: u32) -> _rt::Vec<u64>;
+                    fn set_member_i8list(&self, member: u32, value: _rt::Vec<i8>);
+                    fn get_member_i8list(&self, member: u32) -> _rt::Vec<i8>;
+                    fn set_member_i16list(&self, member: u32, value: _rt::Vec<i16>);
+                    fn get_member_i16list(&self, member: u32) -> _rt::Vec<i16>;
+                    fn set_member_i32list(&self, member: u32, value: _rt::Vec<i32>);
+                    fn get_member_i32list(&self, member: u32) -> _rt::Vec<i32>;
+                    fn set_member_i64list(&self, member: u32, value: _rt::Vec<i64>);
+                    fn get_member_i64list(&self, member: u32) -> _rt::Vec<i64>;
+                    fn set_member_f32list(&self, member: u32, value: _rt::Vec<f32>);
+                    fn get_member_f32list(&self, member: u32) -> _rt::Vec<f32>;
+                    fn set_member_f64list(&self, member: u32, value: _rt::Vec<f64>);
+                    fn get_member_f64list(&self, member: u32) -> _rt::Vec<f64>;
+                    fn set_member_pointer(&self, member: u32, value: u64);
+                    fn get_member_pointer(&self, member: u32) -> u64;
                 }
                 pub trait GuestEntity: 'static {
                     #[doc(hidden)]
@@ -3609,6 +4585,11 @@ pub mod exports {
                         }
                     }
                     fn new(desc: EntityDesc, inherits: Option<EcsEntityT>) -> Self;
+                    /// Like the constructor, fails instead of leaving out initial values that can't be set
+                    fn create(
+                        desc: EntityDesc,
+                        inherits: Option<EcsEntityT>,
+                    ) -> Result<EcsEntityT, _rt::String>;
                     fn get_id(&self) -> EcsEntityT;
                     fn get_name(&self) -> _rt::String;
                     fn set_name(&self, name: _rt::String);
@@ -3687,6 +4668,21 @@ pub mod exports {
                     fn count(&self) -> i32;
                     fn entities(&self) -> _rt::Vec<EcsEntityT>;
                     fn components(&self, index: i8) -> _rt::Vec<PointerT>;
+                    /// Keep the tables the query matches in a cache, which whole query change detection needs.
+                    /// Call before build.
+                    fn cache(&self);
+                    /// Track a field for field-changed or field-added, call before build
+                    fn monitor(&self, index: i8, kind: MonitorKind);
+                    /// Change detection. Whether a table the query matches changed since the last iteration
+                    fn changed(&self) -> bool;
+                    /// Whether the current table changed, skip leaves it marked as changed for the next iteration
+                    fn iter_changed(&self) -> bool;
+                    fn skip(&self);
+                    /// Whether the component of a monitored field was written since the query last moved on from
+                    /// the table, and per entity whether it got the component since then. Fields that aren't
+                    /// monitored report whether the table changed.
+                    fn field_changed(&self, index: i8) -> bool;
+                    fn field_added(&self, index: i8) -> _rt::Vec<bool>;
                 }
                 pub trait GuestIter: 'static {
                     #[doc(hidden)]
@@ -3732,8 +4728,33 @@ pub mod exports {
                     fn new(ptr: u64) -> Self;
                     fn next(&self) -> bool;
                     fn count(&self) -> i32;
+                    /// System or observer the iterator belongs to, 0 for queries
+                    fn system(&self) -> EcsEntityT;
                     fn entities(&self) -> _rt::Vec<EcsEntityT>;
                     fn components(&self, index: i8) -> _rt::Vec<PointerT>;
+                    /// Bulk copies of a field, one call per column instead of one per entity and member.
+                    /// A member column holds the packed values of one member, which must be a number or bool.
+                    fn column(
+                        &self,
+                        index: i8,
+                        member: u32,
+                        member_type: MemberType,
+                    ) -> _rt::Vec<u8>;
+                    fn set_column(
+                        &self,
+                        index: i8,
+                        member: u32,
+                        member_type: MemberType,
+                        data: _rt::Vec<u8>,
+                    );
+                    /// The whole component array of a field with the host struct layout, for components with plain data members
+                    fn field_data(&self, index: i8) -> _rt::Vec<u8>;
+                    fn set_field_data(&self, index: i8, data: _rt::Vec<u8>);
+                    /// Change detection, see the query resource
+                    fn changed(&self) -> bool;
+                    fn skip(&self);
+                    fn field_changed(&self, index: i8) -> bool;
+                    fn field_added(&self, index: i8) -> _rt::Vec<bool>;
                 }
                 pub trait GuestCallback: 'static {
                     #[doc(hidden)]
@@ -3826,7 +4847,11 @@ pub mod exports {
                     fn get_id(&self) -> EcsEntityT;
                     fn build(&self);
                     fn named(&self, name: _rt::String);
+                    /// See the query resource
+                    fn monitor(&self, index: i8, kind: MonitorKind);
                     fn order_by(&self, desc: SortingDesc);
+                    /// Run on the worker threads set with toxoid_set_threads, native systems only
+                    fn multi_threaded(&self, enabled: bool);
                     fn callback(&self) -> PointerT;
                     fn disable(&self);
                     fn enable(&self);
@@ -3966,6 +4991,7 @@ pub mod exports {
                         }
                     }
                     fn new(desc: ObserverDesc) -> Self;
+                    fn get_id(&self) -> EcsEntityT;
                     fn build(&self);
                     fn callback(&self) -> PointerT;
                 }
@@ -3976,10 +5002,11 @@ pub mod exports {
                         "toxoid:engine/ecs#[constructor]component-type"] unsafe extern
                         "C" fn export_constructor_component_type(arg0 : * mut u8, arg1 :
                         usize, arg2 : * mut u8, arg3 : usize, arg4 : * mut u8, arg5 :
+                        usize, arg6 : * mut u8, arg7 : usize, arg8 : * mut u8, arg9 :
                         usize,) -> i32 { $($path_to_types)*::
                         _export_constructor_component_type_cabi::<<$ty as
                         $($path_to_types)*:: Guest >::ComponentType > (arg0, arg1, arg2,
-                        arg3, arg4, arg5) } #[export_name =
+                        arg3, arg4, arg5, arg6, arg7, arg8, arg9) } #[export_name =
                         "toxoid:engine/ecs#[method]component-type.get-id"] unsafe extern
                         "C" fn export_method_component_type_get_id(arg0 : * mut u8,) ->
                         i64 { $($path_to_types)*::
@@ -3991,11 +5018,12 @@ pub mod exports {
                         _export_constructor_component_cabi::<<$ty as $($path_to_types)*::
                         Guest >::Component > (arg0, arg1, arg2) } #[export_name =
                         "toxoid:engine/ecs#[static]component.from-ptr"] unsafe extern "C"
-                        fn export_static_component_from_ptr(arg0 : i64,) -> i64 {
-                        $($path_to_types)*::
+                        fn export_static_component_from_ptr(arg0 : i64, arg1 : i64,) ->
+                        i64 { $($path_to_types)*::
                         _export_static_component_from_ptr_cabi::<<$ty as
-                        $($path_to_types)*:: Guest >::Component > (arg0) } #[export_name
-                        = "toxoid:engine/ecs#[method]component.set-member-u8"] unsafe
+                        $($path_to_types)*:: Guest >::Component > (arg0, arg1) }
+                        #[export_name =
+                        "toxoid:engine/ecs#[method]component.set-member-u8"] unsafe
                         extern "C" fn export_method_component_set_member_u8(arg0 : * mut
                         u8, arg1 : i32, arg2 : i32,) { $($path_to_types)*::
                         _export_method_component_set_member_u8_cabi::<<$ty as
@@ -4351,11 +5379,24 @@ pub mod exports {
                         #[export_name = "toxoid:engine/ecs#[constructor]entity"] unsafe
                         extern "C" fn export_constructor_entity(arg0 : i32, arg1 : * mut
                         u8, arg2 : usize, arg3 : i32, arg4 : * mut u8, arg5 : usize, arg6
-                        : i32, arg7 : i32, arg8 : i64,) -> i32 { $($path_to_types)*::
+                        : i32, arg7 : i32, arg8 : * mut u8, arg9 : usize, arg10 : i32,
+                        arg11 : i64,) -> i32 { $($path_to_types)*::
                         _export_constructor_entity_cabi::<<$ty as $($path_to_types)*::
                         Guest >::Entity > (arg0, arg1, arg2, arg3, arg4, arg5, arg6,
-                        arg7, arg8) } #[export_name =
-                        "toxoid:engine/ecs#[method]entity.get-id"] unsafe extern "C" fn
+                        arg7, arg8, arg9, arg10, arg11) } #[export_name =
+                        "toxoid:engine/ecs#[static]entity.create"] unsafe extern "C" fn
+                        export_static_entity_create(arg0 : i32, arg1 : * mut u8, arg2 :
+                        usize, arg3 : i32, arg4 : * mut u8, arg5 : usize, arg6 : i32,
+                        arg7 : i32, arg8 : * mut u8, arg9 : usize, arg10 : i32, arg11 :
+                        i64,) -> * mut u8 { $($path_to_types)*::
+                        _export_static_entity_create_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::Entity > (arg0, arg1, arg2, arg3, arg4, arg5, arg6,
+                        arg7, arg8, arg9, arg10, arg11) } #[export_name =
+                        "cabi_post_toxoid:engine/ecs#[static]entity.create"] unsafe
+                        extern "C" fn _post_return_static_entity_create(arg0 : * mut u8,)
+                        { $($path_to_types)*:: __post_return_static_entity_create::<<$ty
+                        as $($path_to_types)*:: Guest >::Entity > (arg0) } #[export_name
+                        = "toxoid:engine/ecs#[method]entity.get-id"] unsafe extern "C" fn
                         export_method_entity_get_id(arg0 : * mut u8,) -> i64 {
                         $($path_to_types)*:: _export_method_entity_get_id_cabi::<<$ty as
                         $($path_to_types)*:: Guest >::Entity > (arg0) } #[export_name =
@@ -4498,6 +5539,44 @@ pub mod exports {
                         u8,) { $($path_to_types)*::
                         __post_return_method_query_components::<<$ty as
                         $($path_to_types)*:: Guest >::Query > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]query.cache"] unsafe extern "C" fn
+                        export_method_query_cache(arg0 : * mut u8,) {
+                        $($path_to_types)*:: _export_method_query_cache_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Query > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]query.monitor"] unsafe extern "C" fn
+                        export_method_query_monitor(arg0 : * mut u8, arg1 : i32, arg2 :
+                        i32,) { $($path_to_types)*::
+                        _export_method_query_monitor_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::Query > (arg0, arg1, arg2) } #[export_name =
+                        "toxoid:engine/ecs#[method]query.changed"] unsafe extern "C" fn
+                        export_method_query_changed(arg0 : * mut u8,) -> i32 {
+                        $($path_to_types)*:: _export_method_query_changed_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Query > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]query.iter-changed"] unsafe extern "C"
+                        fn export_method_query_iter_changed(arg0 : * mut u8,) -> i32 {
+                        $($path_to_types)*::
+                        _export_method_query_iter_changed_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Query > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]query.skip"] unsafe extern "C" fn
+                        export_method_query_skip(arg0 : * mut u8,) { $($path_to_types)*::
+                        _export_method_query_skip_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::Query > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]query.field-changed"] unsafe extern
+                        "C" fn export_method_query_field_changed(arg0 : * mut u8, arg1 :
+                        i32,) -> i32 { $($path_to_types)*::
+                        _export_method_query_field_changed_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Query > (arg0, arg1) }
+                        #[export_name = "toxoid:engine/ecs#[method]query.field-added"]
+                        unsafe extern "C" fn export_method_query_field_added(arg0 : * mut
+                        u8, arg1 : i32,) -> * mut u8 { $($path_to_types)*::
+                        _export_method_query_field_added_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Query > (arg0, arg1) }
+                        #[export_name =
+                        "cabi_post_toxoid:engine/ecs#[method]query.field-added"] unsafe
+                        extern "C" fn _post_return_method_query_field_added(arg0 : * mut
+                        u8,) { $($path_to_types)*::
+                        __post_return_method_query_field_added::<<$ty as
+                        $($path_to_types)*:: Guest >::Query > (arg0) } #[export_name =
                         "toxoid:engine/ecs#[constructor]iter"] unsafe extern "C" fn
                         export_constructor_iter(arg0 : i64,) -> i32 {
                         $($path_to_types)*:: _export_constructor_iter_cabi::<<$ty as
@@ -4510,6 +5589,10 @@ pub mod exports {
                         export_method_iter_count(arg0 : * mut u8,) -> i32 {
                         $($path_to_types)*:: _export_method_iter_count_cabi::<<$ty as
                         $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.system"] unsafe extern "C" fn
+                        export_method_iter_system(arg0 : * mut u8,) -> i64 {
+                        $($path_to_types)*:: _export_method_iter_system_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
                         "toxoid:engine/ecs#[method]iter.entities"] unsafe extern "C" fn
                         export_method_iter_entities(arg0 : * mut u8,) -> * mut u8 {
                         $($path_to_types)*:: _export_method_iter_entities_cabi::<<$ty as
@@ -4528,6 +5611,59 @@ pub mod exports {
                         u8,) { $($path_to_types)*::
                         __post_return_method_iter_components::<<$ty as
                         $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.column"] unsafe extern "C" fn
+                        export_method_iter_column(arg0 : * mut u8, arg1 : i32, arg2 :
+                        i32, arg3 : i32,) -> * mut u8 { $($path_to_types)*::
+                        _export_method_iter_column_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::Iter > (arg0, arg1, arg2, arg3) } #[export_name =
+                        "cabi_post_toxoid:engine/ecs#[method]iter.column"] unsafe extern
+                        "C" fn _post_return_method_iter_column(arg0 : * mut u8,) {
+                        $($path_to_types)*:: __post_return_method_iter_column::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.set-column"] unsafe extern "C" fn
+                        export_method_iter_set_column(arg0 : * mut u8, arg1 : i32, arg2 :
+                        i32, arg3 : i32, arg4 : * mut u8, arg5 : usize,) {
+                        $($path_to_types)*:: _export_method_iter_set_column_cabi::<<$ty
+                        as $($path_to_types)*:: Guest >::Iter > (arg0, arg1, arg2, arg3,
+                        arg4, arg5) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.field-data"] unsafe extern "C" fn
+                        export_method_iter_field_data(arg0 : * mut u8, arg1 : i32,) -> *
+                        mut u8 { $($path_to_types)*::
+                        _export_method_iter_field_data_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0, arg1) } #[export_name
+                        = "cabi_post_toxoid:engine/ecs#[method]iter.field-data"] unsafe
+                        extern "C" fn _post_return_method_iter_field_data(arg0 : * mut
+                        u8,) { $($path_to_types)*::
+                        __post_return_method_iter_field_data::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.set-field-data"] unsafe extern
+                        "C" fn export_method_iter_set_field_data(arg0 : * mut u8, arg1 :
+                        i32, arg2 : * mut u8, arg3 : usize,) { $($path_to_types)*::
+                        _export_method_iter_set_field_data_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0, arg1, arg2, arg3) }
+                        #[export_name = "toxoid:engine/ecs#[method]iter.changed"] unsafe
+                        extern "C" fn export_method_iter_changed(arg0 : * mut u8,) -> i32
+                        { $($path_to_types)*:: _export_method_iter_changed_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.skip"] unsafe extern "C" fn
+                        export_method_iter_skip(arg0 : * mut u8,) { $($path_to_types)*::
+                        _export_method_iter_skip_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::Iter > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]iter.field-changed"] unsafe extern "C"
+                        fn export_method_iter_field_changed(arg0 : * mut u8, arg1 : i32,)
+                        -> i32 { $($path_to_types)*::
+                        _export_method_iter_field_changed_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0, arg1) } #[export_name
+                        = "toxoid:engine/ecs#[method]iter.field-added"] unsafe extern "C"
+                        fn export_method_iter_field_added(arg0 : * mut u8, arg1 : i32,)
+                        -> * mut u8 { $($path_to_types)*::
+                        _export_method_iter_field_added_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0, arg1) } #[export_name
+                        = "cabi_post_toxoid:engine/ecs#[method]iter.field-added"] unsafe
+                        extern "C" fn _post_return_method_iter_field_added(arg0 : * mut
+                        u8,) { $($path_to_types)*::
+                        __post_return_method_iter_field_added::<<$ty as
+                        $($path_to_types)*:: Guest >::Iter > (arg0) } #[export_name =
                         "toxoid:engine/ecs#[constructor]callback"] unsafe extern "C" fn
                         export_constructor_callback(arg0 : i64,) -> i32 {
                         $($path_to_types)*:: _export_constructor_callback_cabi::<<$ty as
@@ -4561,11 +5697,22 @@ pub mod exports {
                         : usize,) { $($path_to_types)*::
                         _export_method_system_named_cabi::<<$ty as $($path_to_types)*::
                         Guest >::System > (arg0, arg1, arg2) } #[export_name =
+                        "toxoid:engine/ecs#[method]system.monitor"] unsafe extern "C" fn
+                        export_method_system_monitor(arg0 : * mut u8, arg1 : i32, arg2 :
+                        i32,) { $($path_to_types)*::
+                        _export_method_system_monitor_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::System > (arg0, arg1, arg2) } #[export_name =
                         "toxoid:engine/ecs#[method]system.order-by"] unsafe extern "C" fn
                         export_method_system_order_by(arg0 : * mut u8, arg1 : i64, arg2 :
                         i64,) { $($path_to_types)*::
                         _export_method_system_order_by_cabi::<<$ty as
                         $($path_to_types)*:: Guest >::System > (arg0, arg1, arg2) }
+                        #[export_name =
+                        "toxoid:engine/ecs#[method]system.multi-threaded"] unsafe extern
+                        "C" fn export_method_system_multi_threaded(arg0 : * mut u8, arg1
+                        : i32,) { $($path_to_types)*::
+                        _export_method_system_multi_threaded_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::System > (arg0, arg1) }
                         #[export_name = "toxoid:engine/ecs#[method]system.callback"]
                         unsafe extern "C" fn export_method_system_callback(arg0 : * mut
                         u8,) -> i64 { $($path_to_types)*::
@@ -4628,10 +5775,15 @@ pub mod exports {
                         _export_constructor_observer_cabi::<<$ty as $($path_to_types)*::
                         Guest >::Observer > (arg0, arg1, arg2, arg3, arg4, arg5, arg6,
                         arg7, arg8) } #[export_name =
-                        "toxoid:engine/ecs#[method]observer.build"] unsafe extern "C" fn
-                        export_method_observer_build(arg0 : * mut u8,) {
-                        $($path_to_types)*:: _export_method_observer_build_cabi::<<$ty as
-                        $($path_to_types)*:: Guest >::Observer > (arg0) } #[export_name =
+                        "toxoid:engine/ecs#[method]observer.get-id"] unsafe extern "C" fn
+                        export_method_observer_get_id(arg0 : * mut u8,) -> i64 {
+                        $($path_to_types)*:: _export_method_observer_get_id_cabi::<<$ty
+                        as $($path_to_types)*:: Guest >::Observer > (arg0) }
+                        #[export_name = "toxoid:engine/ecs#[method]observer.build"]
+                        unsafe extern "C" fn export_method_observer_build(arg0 : * mut
+                        u8,) { $($path_to_types)*::
+                        _export_method_observer_build_cabi::<<$ty as $($path_to_types)*::
+                        Guest >::Observer > (arg0) } #[export_name =
                         "toxoid:engine/ecs#[method]observer.callback"] unsafe extern "C"
                         fn export_method_observer_callback(arg0 : * mut u8,) -> i64 {
                         $($path_to_types)*:: _export_method_observer_callback_cabi::<<$ty
@@ -4657,8 +5809,54 @@ pub mod exports {
                         arg1) } #[export_name = "toxoid:engine/ecs#get-component-id"]
                         unsafe extern "C" fn export_get_component_id(arg0 : * mut u8,
                         arg1 : usize,) -> i64 { $($path_to_types)*::
-                        _export_get_component_id_cabi::<$ty > (arg0, arg1) } const _ : ()
-                        = { #[doc(hidden)] #[export_name =
+                        _export_get_component_id_cabi::<$ty > (arg0, arg1) }
+                        #[export_name = "toxoid:engine/ecs#register-enum"] unsafe extern
+                        "C" fn export_register_enum(arg0 : * mut u8, arg1 : usize, arg2 :
+                        * mut u8, arg3 : usize, arg4 : i32,) -> i64 {
+                        $($path_to_types)*:: _export_register_enum_cabi::<$ty > (arg0,
+                        arg1, arg2, arg3, arg4) } #[export_name =
+                        "toxoid:engine/ecs#entity-to-json"] unsafe extern "C" fn
+                        export_entity_to_json(arg0 : i64, arg1 : i32,) -> * mut u8 {
+                        $($path_to_types)*:: _export_entity_to_json_cabi::<$ty > (arg0,
+                        arg1) } #[export_name =
+                        "cabi_post_toxoid:engine/ecs#entity-to-json"] unsafe extern "C"
+                        fn _post_return_entity_to_json(arg0 : * mut u8,) {
+                        $($path_to_types)*:: __post_return_entity_to_json::<$ty > (arg0)
+                        } #[export_name = "toxoid:engine/ecs#entity-from-json"] unsafe
+                        extern "C" fn export_entity_from_json(arg0 : * mut u8, arg1 :
+                        usize, arg2 : i32, arg3 : i64,) -> * mut u8 {
+                        $($path_to_types)*:: _export_entity_from_json_cabi::<$ty > (arg0,
+                        arg1, arg2, arg3) } #[export_name =
+                        "cabi_post_toxoid:engine/ecs#entity-from-json"] unsafe extern "C"
+                        fn _post_return_entity_from_json(arg0 : * mut u8,) {
+                        $($path_to_types)*:: __post_return_entity_from_json::<$ty >
+                        (arg0) } #[export_name = "toxoid:engine/ecs#command-spawn"]
+                        unsafe extern "C" fn export_command_spawn() -> i64 {
+                        $($path_to_types)*:: _export_command_spawn_cabi::<$ty > () }
+                        #[export_name = "toxoid:engine/ecs#command-despawn"] unsafe
+                        extern "C" fn export_command_despawn(arg0 : i64,) {
+                        $($path_to_types)*:: _export_command_despawn_cabi::<$ty > (arg0)
+                        } #[export_name = "toxoid:engine/ecs#command-add"] unsafe extern
+                        "C" fn export_command_add(arg0 : i64, arg1 : i64,) {
+                        $($path_to_types)*:: _export_command_add_cabi::<$ty > (arg0,
+                        arg1) } #[export_name = "toxoid:engine/ecs#command-remove"]
+                        unsafe extern "C" fn export_command_remove(arg0 : i64, arg1 :
+                        i64,) { $($path_to_types)*:: _export_command_remove_cabi::<$ty >
+                        (arg0, arg1) } #[export_name =
+                        "toxoid:engine/ecs#flush-commands"] unsafe extern "C" fn
+                        export_flush_commands() -> * mut u8 { $($path_to_types)*::
+                        _export_flush_commands_cabi::<$ty > () } #[export_name =
+                        "cabi_post_toxoid:engine/ecs#flush-commands"] unsafe extern "C"
+                        fn _post_return_flush_commands(arg0 : * mut u8,) {
+                        $($path_to_types)*:: __post_return_flush_commands::<$ty > (arg0)
+                        } #[export_name = "toxoid:engine/ecs#take-command-errors"] unsafe
+                        extern "C" fn export_take_command_errors() -> * mut u8 {
+                        $($path_to_types)*:: _export_take_command_errors_cabi::<$ty > ()
+                        } #[export_name =
+                        "cabi_post_toxoid:engine/ecs#take-command-errors"] unsafe extern
+                        "C" fn _post_return_take_command_errors(arg0 : * mut u8,) {
+                        $($path_to_types)*:: __post_return_take_command_errors::<$ty >
+                        (arg0) } const _ : () = { #[doc(hidden)] #[export_name =
                         "toxoid:engine/ecs#[dtor]component-type"]
                         #[allow(non_snake_case)] unsafe extern "C" fn dtor(rep : * mut
                         u8) { $($path_to_types)*:: ComponentType::dtor::< <$ty as
@@ -4705,10 +5903,230 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 pub(crate) use __export_toxoid_engine_ecs_cabi;
-                #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                #[repr(align(8))]
+                struct _RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                static mut _RET_AREA: _RetArea = _RetArea(
+                    [::core::mem::MaybeUninit::uninit(); 16],
+                );
+            }
+            #[allow(dead_code, clippy::all)]
+            pub mod engine {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                /// Key codes of Sokol, which are the GLFW key codes
+                pub type KeyCode = u32;
+                #[repr(C)]
+                #[derive(Clone, Copy)]
+                pub struct TimeInfo {
+                    /// Seconds since the last frame
+                    pub delta: f32,
+                    /// Seconds since the world started
+                    pub elapsed: f64,
+                    pub frame: u64,
+                }
+                impl ::core::fmt::Debug for TimeInfo {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("TimeInfo")
+                            .field("delta", &self.delta)
+                            .field("elapsed", &self.elapsed)
+                            .field("frame", &self.frame)
+                            .finish()
+                    }
+                }
+                #[repr(C)]
+                #[derive(Clone, Copy)]
+                pub struct WindowInfo {
+                    pub width: u32,
+                    pub height: u32,
+                    /// Size the game is rendered at, the window size clamped to the game's aspect ratio
+                    pub render_width: u32,
+                    pub render_height: u32,
+                }
+                impl ::core::fmt::Debug for WindowInfo {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("WindowInfo")
+                            .field("width", &self.width)
+                            .field("height", &self.height)
+                            .field("render-width", &self.render_width)
+                            .field("render-height", &self.render_height)
+                            .finish()
+                    }
+                }
+                #[repr(C)]
+                #[derive(Clone, Copy)]
+                pub struct MouseState {
+                    pub x: f32,
+                    pub y: f32,
+                    /// Movement and scrolling since the last frame
+                    pub dx: f32,
+                    pub dy: f32,
+                    pub scroll_x: f32,
+                    pub scroll_y: f32,
+                    pub left: bool,
+                    pub right: bool,
+                    pub middle: bool,
+                }
+                impl ::core::fmt::Debug for MouseState {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("MouseState")
+                            .field("x", &self.x)
+                            .field("y", &self.y)
+                            .field("dx", &self.dx)
+                            .field("dy", &self.dy)
+                            .field("scroll-x", &self.scroll_x)
+                            .field("scroll-y", &self.scroll_y)
+                            .field("left", &self.left)
+                            .field("right", &self.right)
+                            .field("middle", &self.middle)
+                            .finish()
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_time_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::time();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let TimeInfo { delta: delta2, elapsed: elapsed2, frame: frame2 } = result0;
+                    *ptr1.add(0).cast::<f32>() = _rt::as_f32(delta2);
+                    *ptr1.add(8).cast::<f64>() = _rt::as_f64(elapsed2);
+                    *ptr1.add(16).cast::<i64>() = _rt::as_i64(frame2);
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_window_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::window();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let WindowInfo {
+                        width: width2,
+                        height: height2,
+                        render_width: render_width2,
+                        render_height: render_height2,
+                    } = result0;
+                    *ptr1.add(0).cast::<i32>() = _rt::as_i32(width2);
+                    *ptr1.add(4).cast::<i32>() = _rt::as_i32(height2);
+                    *ptr1.add(8).cast::<i32>() = _rt::as_i32(render_width2);
+                    *ptr1.add(12).cast::<i32>() = _rt::as_i32(render_height2);
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_mouse_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::mouse();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let MouseState {
+                        x: x2,
+                        y: y2,
+                        dx: dx2,
+                        dy: dy2,
+                        scroll_x: scroll_x2,
+                        scroll_y: scroll_y2,
+                        left: left2,
+                        right: right2,
+                        middle: middle2,
+                    } = result0;
+                    *ptr1.add(0).cast::<f32>() = _rt::as_f32(x2);
+                    *ptr1.add(4).cast::<f32>() = _rt::as_f32(y2);
+                    *ptr1.add(8).cast::<f32>() = _rt::as_f32(dx2);
+                    *ptr1.add(12).cast::<f32>() = _rt::as_f32(dy2);
+                    *ptr1.add(16).cast::<f32>() = _rt::as_f32(scroll_x2);
+                    *ptr1.add(20).cast::<f32>() = _rt::as_f32(scroll_y2);
+                    *ptr1.add(24).cast::<u8>() = (match left2 {
+                        true => 1,
+                        false => 0,
+                    }) as u8;
+                    *ptr1.add(25).cast::<u8>() = (match right2 {
+                        true => 1,
+                        false => 0,
+                    }) as u8;
+                    *ptr1.add(26).cast::<u8>() = (match middle2 {
+                        true => 1,
+                        false => 0,
+                    }) as u8;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_key_down_cabi<T: Guest>(arg0: i32) -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::key_down(arg0 as u32);
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_keys_down_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::keys_down();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec2 = (result0).into_boxed_slice();
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    ::core::mem::forget(vec2);
+                    *ptr1.add(4).cast::<usize>() = len2;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_keys_down<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0.add(4).cast::<usize>();
+                    let base2 = l0;
+                    let len2 = l1;
+                    _rt::cabi_dealloc(base2, len2 * 4, 4);
+                }
+                pub trait Guest {
+                    fn time() -> TimeInfo;
+                    fn window() -> WindowInfo;
+                    fn mouse() -> MouseState;
+                    fn key_down(key: KeyCode) -> bool;
+                    fn keys_down() -> _rt::Vec<KeyCode>;
+                }
+                #[doc(hidden)]
+                macro_rules! __export_toxoid_engine_engine_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = { #[export_name = "toxoid:engine/engine#time"]
+                        unsafe extern "C" fn export_time() -> * mut u8 {
+                        $($path_to_types)*:: _export_time_cabi::<$ty > () } #[export_name
+                        = "toxoid:engine/engine#window"] unsafe extern "C" fn
+                        export_window() -> * mut u8 { $($path_to_types)*::
+                        _export_window_cabi::<$ty > () } #[export_name =
+                        "toxoid:engine/engine#mouse"] unsafe extern "C" fn export_mouse()
+                        -> * mut u8 { $($path_to_types)*:: _export_mouse_cabi::<$ty > ()
+                        } #[export_name = "toxoid:engine/engine#key-down"] unsafe extern
+                        "C" fn export_key_down(arg0 : i32,) -> i32 { $($path_to_types)*::
+                        _export_key_down_cabi::<$ty > (arg0) } #[export_name =
+                        "toxoid:engine/engine#keys-down"] unsafe extern "C" fn
+                        export_keys_down() -> * mut u8 { $($path_to_types)*::
+                        _export_keys_down_cabi::<$ty > () } #[export_name =
+                        "cabi_post_toxoid:engine/engine#keys-down"] unsafe extern "C" fn
+                        _post_return_keys_down(arg0 : * mut u8,) { $($path_to_types)*::
+                        __post_return_keys_down::<$ty > (arg0) } };
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_toxoid_engine_engine_cabi;
+                #[repr(align(8))]
+                struct _RetArea([::core::mem::MaybeUninit<u8>; 28]);
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 8],
+                    [::core::mem::MaybeUninit::uninit(); 28],
                 );
             }
         }
@@ -4945,8 +6363,8 @@ wit_bindgen_rt::run_ctors_once();
             core::hint::unreachable_unchecked()
         }
     }
-    extern crate alloc as alloc_crate;
     pub use alloc_crate::alloc;
+    extern crate alloc as alloc_crate;
 }
 /// Generates `#[no_mangle]` functions to export the specified type as the
 /// root implementation of all generated traits.
@@ -4974,6 +6392,9 @@ macro_rules! __export_toxoid_engine_world_impl {
         $($path_to_types_root)*::
         exports::toxoid::engine::ecs::__export_toxoid_engine_ecs_cabi!($ty with_types_in
         $($path_to_types_root)*:: exports::toxoid::engine::ecs);
+        $($path_to_types_root)*::
+        exports::toxoid::engine::engine::__export_toxoid_engine_engine_cabi!($ty
+        with_types_in $($path_to_types_root)*:: exports::toxoid::engine::engine);
     };
 }
 #[doc(inline)]
@@ -4981,9 +6402,9 @@ pub(crate) use __export_toxoid_engine_world_impl as export;
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.35.0:toxoid:engine:toxoid-engine-world:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 6691] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x993\x01A\x02\x01A\x02\
-\x01B\x9b\x02\x01w\x04\0\x0cecs-entity-t\x03\0\0\x01w\x04\0\x09pointer-t\x03\0\x02\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 8586] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x80B\x01A\x02\x01A\x04\
+\x01B\xe1\x02\x01w\x04\0\x0cecs-entity-t\x03\0\0\x01w\x04\0\x09pointer-t\x03\0\x02\
 \x01q\x03\x04is-a\0\0\x08child-of\0\0\x06custom\x01\x01\0\x04\0\x0crelationship\x03\
 \0\x04\x01q\x0a\x08on-start\0\0\x07on-load\0\0\x09post-load\0\0\x0apre-update\0\0\
 \x09on-update\0\0\x0bon-validate\0\0\x0bpost-update\0\0\x09pre-store\0\0\x08on-s\
@@ -4993,128 +6414,172 @@ t\x08string-t\x06list-t\x08u8list-t\x09u16list-t\x09u32list-t\x09u64list-t\x08i8
 list-t\x09i16list-t\x09i32list-t\x09i64list-t\x09f32list-t\x09f64list-t\x09point\
 er-t\x04\0\x0bmember-type\x03\0\x08\x01m\x07\x06on-set\x06on-add\x09on-remove\x09\
 on-delete\x10on-delete-target\x0fon-table-create\x0fon-table-delete\x04\0\x05eve\
-nt\x03\0\x0a\x01ps\x01p}\x01r\x03\x04names\x0cmember-names\x0c\x0cmember-types\x0d\
-\x04\0\x0ecomponent-desc\x03\0\x0e\x01ks\x01p\x01\x01k\x11\x01r\x03\x04name\x10\x03\
-add\x12\x06prefab\x7f\x04\0\x0bentity-desc\x03\0\x13\x01r\x01\x04exprs\x04\0\x0a\
-query-desc\x03\0\x15\x01r\x02\x02id\x01\x08callback\x03\x04\0\x0csorting-desc\x03\
-\0\x17\x01kz\x01r\x05\x04name\x10\x09tick-rate\x19\x08callback\x03\x0aquery-desc\
-\x16\x08is-guest\x7f\x04\0\x0bsystem-desc\x03\0\x1a\x01r\x03\x04names\x0aquery-d\
-esc\x16\x06phases\x11\x04\0\x0dpipeline-desc\x03\0\x1c\x01p\x0b\x01r\x05\x04name\
-\x10\x0aquery-desc\x16\x06events\x1e\x08callback\x03\x08is-guest\x7f\x04\0\x0dob\
-server-desc\x03\0\x1f\x04\0\x0ecomponent-type\x03\x01\x04\0\x09component\x03\x01\
-\x04\0\x06entity\x03\x01\x04\0\x05query\x03\x01\x04\0\x04iter\x03\x01\x04\0\x08c\
-allback\x03\x01\x04\0\x06system\x03\x01\x04\0\x05phase\x03\x01\x04\0\x08pipeline\
-\x03\x01\x04\0\x08observer\x03\x01\x01i!\x01@\x01\x04desc\x0f\0+\x04\0\x1b[const\
-ructor]component-type\x01,\x01h!\x01@\x01\x04self-\0\x01\x04\0\x1d[method]compon\
-ent-type.get-id\x01.\x01i\"\x01@\x03\x03ptr\x03\x06entity\x01\x0ecomponent-type\x01\
-\0/\x04\0\x16[constructor]component\x010\x01@\x01\x03ptr\x03\0\x03\x04\0\x1a[sta\
-tic]component.from-ptr\x011\x01h\"\x01@\x03\x04self2\x06offsety\x05value}\x01\0\x04\
-\0\x1f[method]component.set-member-u8\x013\x01@\x02\x04self2\x06offsety\0}\x04\0\
-\x1f[method]component.get-member-u8\x014\x01@\x03\x04self2\x06offsety\x05value{\x01\
-\0\x04\0\x20[method]component.set-member-u16\x015\x01@\x02\x04self2\x06offsety\0\
-{\x04\0\x20[method]component.get-member-u16\x016\x01@\x03\x04self2\x06offsety\x05\
-valuey\x01\0\x04\0\x20[method]component.set-member-u32\x017\x01@\x02\x04self2\x06\
-offsety\0y\x04\0\x20[method]component.get-member-u32\x018\x01@\x03\x04self2\x06o\
-ffsety\x05valuew\x01\0\x04\0\x20[method]component.set-member-u64\x019\x01@\x02\x04\
-self2\x06offsety\0w\x04\0\x20[method]component.get-member-u64\x01:\x01@\x03\x04s\
-elf2\x06offsety\x05value~\x01\0\x04\0\x1f[method]component.set-member-i8\x01;\x01\
-@\x02\x04self2\x06offsety\0~\x04\0\x1f[method]component.get-member-i8\x01<\x01@\x03\
-\x04self2\x06offsety\x05value|\x01\0\x04\0\x20[method]component.set-member-i16\x01\
-=\x01@\x02\x04self2\x06offsety\0|\x04\0\x20[method]component.get-member-i16\x01>\
-\x01@\x03\x04self2\x06offsety\x05valuez\x01\0\x04\0\x20[method]component.set-mem\
-ber-i32\x01?\x01@\x02\x04self2\x06offsety\0z\x04\0\x20[method]component.get-memb\
-er-i32\x01@\x01@\x03\x04self2\x06offsety\x05valuex\x01\0\x04\0\x20[method]compon\
-ent.set-member-i64\x01A\x01@\x02\x04self2\x06offsety\0x\x04\0\x20[method]compone\
-nt.get-member-i64\x01B\x01@\x03\x04self2\x06offsety\x05valuev\x01\0\x04\0\x20[me\
-thod]component.set-member-f32\x01C\x01@\x02\x04self2\x06offsety\0v\x04\0\x20[met\
-hod]component.get-member-f32\x01D\x01@\x03\x04self2\x06offsety\x05valueu\x01\0\x04\
-\0\x20[method]component.set-member-f64\x01E\x01@\x02\x04self2\x06offsety\0u\x04\0\
-\x20[method]component.get-member-f64\x01F\x01@\x03\x04self2\x06offsety\x05value\x7f\
-\x01\0\x04\0![method]component.set-member-bool\x01G\x01@\x02\x04self2\x06offsety\
-\0\x7f\x04\0![method]component.get-member-bool\x01H\x01@\x03\x04self2\x06offsety\
-\x05values\x01\0\x04\0#[method]component.set-member-string\x01I\x01@\x02\x04self\
-2\x06offsety\0s\x04\0#[method]component.get-member-string\x01J\x01@\x03\x04self2\
-\x06offsety\x05value\x0d\x01\0\x04\0#[method]component.set-member-u8list\x01K\x01\
-@\x02\x04self2\x06offsety\0\x0d\x04\0#[method]component.get-member-u8list\x01L\x01\
-p{\x01@\x03\x04self2\x06offsety\x05value\xcd\0\x01\0\x04\0$[method]component.set\
--member-u16list\x01N\x01@\x02\x04self2\x06offsety\0\xcd\0\x04\0$[method]componen\
-t.get-member-u16list\x01O\x01py\x01@\x03\x04self2\x06offsety\x05value\xd0\0\x01\0\
-\x04\0$[method]component.set-member-u32list\x01Q\x01@\x02\x04self2\x06offsety\0\xd0\
-\0\x04\0$[method]component.get-member-u32list\x01R\x01pw\x01@\x03\x04self2\x06of\
-fsety\x05value\xd3\0\x01\0\x04\0$[method]component.set-member-u64list\x01T\x01@\x02\
-\x04self2\x06offsety\0\xd3\0\x04\0$[method]component.get-member-u64list\x01U\x01\
-p~\x01@\x03\x04self2\x06offsety\x05value\xd6\0\x01\0\x04\0#[method]component.set\
--member-i8list\x01W\x01@\x02\x04self2\x06offsety\0\xd6\0\x04\0#[method]component\
-.get-member-i8list\x01X\x01p|\x01@\x03\x04self2\x06offsety\x05value\xd9\0\x01\0\x04\
-\0$[method]component.set-member-i16list\x01Z\x01@\x02\x04self2\x06offsety\0\xd9\0\
-\x04\0$[method]component.get-member-i16list\x01[\x01pz\x01@\x03\x04self2\x06offs\
-ety\x05value\xdc\0\x01\0\x04\0$[method]component.set-member-i32list\x01]\x01@\x02\
-\x04self2\x06offsety\0\xdc\0\x04\0$[method]component.get-member-i32list\x01^\x01\
-px\x01@\x03\x04self2\x06offsety\x05value\xdf\0\x01\0\x04\0$[method]component.set\
--member-i64list\x01`\x01@\x02\x04self2\x06offsety\0\xdf\0\x04\0$[method]componen\
-t.get-member-i64list\x01a\x01pv\x01@\x03\x04self2\x06offsety\x05value\xe2\0\x01\0\
-\x04\0$[method]component.set-member-f32list\x01c\x01@\x02\x04self2\x06offsety\0\xe2\
-\0\x04\0$[method]component.get-member-f32list\x01d\x01pu\x01@\x03\x04self2\x06of\
-fsety\x05value\xe5\0\x01\0\x04\0$[method]component.set-member-f64list\x01f\x01@\x02\
-\x04self2\x06offsety\0\xe5\0\x04\0$[method]component.get-member-f64list\x01g\x04\
-\0$[method]component.set-member-pointer\x019\x04\0$[method]component.get-member-\
-pointer\x01:\x01k\x01\x01i#\x01@\x02\x04desc\x14\x08inherits\xe8\0\0\xe9\0\x04\0\
-\x13[constructor]entity\x01j\x01h#\x01@\x01\x04self\xeb\0\0\x01\x04\0\x15[method\
-]entity.get-id\x01l\x01@\x01\x04self\xeb\0\0s\x04\0\x17[method]entity.get-name\x01\
-m\x01@\x02\x04self\xeb\0\x04names\x01\0\x04\0\x17[method]entity.set-name\x01n\x01\
-@\x01\x02idw\0\x03\x04\0\x16[static]entity.from-id\x01o\x01@\x02\x04self\xeb\0\x09\
-component\x01\0\x03\x04\0\x12[method]entity.get\x01p\x01@\x02\x04self\xeb\0\x09c\
-omponent\x01\x01\0\x04\0\x12[method]entity.add\x01q\x01@\x02\x04self\xeb\0\x09co\
-mponent\x01\0\x7f\x04\0\x12[method]entity.has\x01r\x04\0\x15[method]entity.remov\
-e\x01q\x01@\x03\x04self\xeb\0\x0crelationship\x05\x06target\x01\x01\0\x04\0\x1f[\
-method]entity.add-relationship\x01s\x04\0\"[method]entity.remove-relationship\x01\
-s\x01@\x02\x04self\xeb\0\x06target\x01\x01\0\x04\0\x18[method]entity.parent-of\x01\
-t\x04\0\x17[method]entity.child-of\x01t\x04\0\x15[method]entity.parent\x01l\x01@\
-\x01\x04self\xeb\0\0\x11\x04\0\x17[method]entity.children\x01u\x01@\x02\x04self\xeb\
-\0\x0crelationship\x05\0\x11\x04\0$[method]entity.relationship-entities\x01v\x01\
-@\x01\x04self\xeb\0\x01\0\x04\0\x16[method]entity.disable\x01w\x04\0\x15[method]\
-entity.enable\x01w\x01i$\x01@\x01\x04desc\x16\0\xf8\0\x04\0\x12[constructor]quer\
-y\x01y\x01h$\x01@\x01\x04self\xfa\0\x01\0\x04\0\x13[method]query.build\x01{\x01@\
-\x02\x04self\xfa\0\x04desc\x18\x01\0\x04\0\x16[method]query.order-by\x01|\x01@\x01\
-\x04self\xfa\0\0\x03\x04\0\x12[method]query.iter\x01}\x01@\x01\x04self\xfa\0\0\x7f\
-\x04\0\x12[method]query.next\x01~\x01@\x01\x04self\xfa\0\0z\x04\0\x13[method]que\
-ry.count\x01\x7f\x01@\x01\x04self\xfa\0\0\x11\x04\0\x16[method]query.entities\x01\
-\x80\x01\x01p\x03\x01@\x02\x04self\xfa\0\x05index~\0\x81\x01\x04\0\x18[method]qu\
-ery.components\x01\x82\x01\x01i%\x01@\x01\x03ptrw\0\x83\x01\x04\0\x11[constructo\
-r]iter\x01\x84\x01\x01h%\x01@\x01\x04self\x85\x01\0\x7f\x04\0\x11[method]iter.ne\
-xt\x01\x86\x01\x01@\x01\x04self\x85\x01\0z\x04\0\x12[method]iter.count\x01\x87\x01\
-\x01@\x01\x04self\x85\x01\0\x11\x04\0\x15[method]iter.entities\x01\x88\x01\x01@\x02\
-\x04self\x85\x01\x05index~\0\x81\x01\x04\0\x17[method]iter.components\x01\x89\x01\
-\x01i&\x01@\x01\x06handlew\0\x8a\x01\x04\0\x15[constructor]callback\x01\x8b\x01\x01\
-h&\x01@\x02\x04self\x8c\x01\x04iter\x83\x01\x01\0\x04\0\x14[method]callback.run\x01\
-\x8d\x01\x01@\x01\x04self\x8c\x01\0\x03\x04\0\x1a[method]callback.cb-handle\x01\x8e\
-\x01\x01i'\x01@\x01\x04desc\x1b\0\x8f\x01\x04\0\x13[constructor]system\x01\x90\x01\
-\x01h'\x01@\x01\x04self\x91\x01\0\x01\x04\0\x15[method]system.get-id\x01\x92\x01\
-\x01@\x01\x04self\x91\x01\x01\0\x04\0\x14[method]system.build\x01\x93\x01\x01@\x02\
-\x04self\x91\x01\x04names\x01\0\x04\0\x14[method]system.named\x01\x94\x01\x01@\x02\
-\x04self\x91\x01\x04desc\x18\x01\0\x04\0\x17[method]system.order-by\x01\x95\x01\x01\
-@\x01\x04self\x91\x01\0\x03\x04\0\x17[method]system.callback\x01\x96\x01\x04\0\x16\
-[method]system.disable\x01\x93\x01\x04\0\x15[method]system.enable\x01\x93\x01\x01\
-i(\x01@\x01\x04names\0\x97\x01\x04\0\x12[constructor]phase\x01\x98\x01\x01h(\x01\
-@\x02\x04self\x99\x01\x05phase\x07\x01\0\x04\0\x18[method]phase.depends-on\x01\x9a\
-\x01\x01@\x01\x04self\x99\x01\0\x01\x04\0\x14[method]phase.get-id\x01\x9b\x01\x01\
-i)\x01@\x01\x04desc\x1d\0\x9c\x01\x04\0\x15[constructor]pipeline\x01\x9d\x01\x01\
-h)\x01@\x01\x04self\x9e\x01\x01\0\x04\0\x16[method]pipeline.build\x01\x9f\x01\x01\
-@\x02\x04self\x9e\x01\x05phase\x01\x01\0\x04\0\x1a[method]pipeline.add-phase\x01\
-\xa0\x01\x01@\x01\x04self\x9e\x01\0\x01\x04\0\x17[method]pipeline.get-id\x01\xa1\
-\x01\x04\0\x18[method]pipeline.disable\x01\x9f\x01\x04\0\x17[method]pipeline.ena\
-ble\x01\x9f\x01\x01i*\x01@\x01\x04desc\x20\0\xa2\x01\x04\0\x15[constructor]obser\
-ver\x01\xa3\x01\x01h*\x01@\x01\x04self\xa4\x01\x01\0\x04\0\x16[method]observer.b\
-uild\x01\xa5\x01\x01@\x01\x04self\xa4\x01\0\x03\x04\0\x19[method]observer.callba\
-ck\x01\xa6\x01\x01@\x01\x0ccomponent-id\x01\x01\0\x04\0\x0dadd-singleton\x01\xa7\
-\x01\x01@\x01\x0ccomponent-id\x01\0w\x04\0\x0dget-singleton\x01\xa8\x01\x04\0\x10\
-remove-singleton\x01\xa7\x01\x01@\x01\x09entity-id\x01\x01\0\x04\0\x0aadd-entity\
-\x01\xa9\x01\x04\0\x0dremove-entity\x01\xa9\x01\x01@\x01\x04names\0\x7f\x04\0\x10\
-has-entity-named\x01\xaa\x01\x01@\x01\x0ecomponent-names\0\x01\x04\0\x10get-comp\
-onent-id\x01\xab\x01\x04\0\x11toxoid:engine/ecs\x05\0\x04\0!toxoid:engine/toxoid\
--engine-world\x04\0\x0b\x19\x01\0\x13toxoid-engine-world\x03\0\0\0G\x09producers\
-\x01\x0cprocessed-by\x02\x0dwit-component\x070.220.0\x10wit-bindgen-rust\x060.35\
-.0";
+nt\x03\0\x0a\x01m\x02\x07changed\x05added\x04\0\x0cmonitor-kind\x03\0\x0c\x01ps\x01\
+p}\x01p\x01\x01py\x01r\x05\x04names\x0cmember-names\x0e\x0cmember-types\x0f\x0fm\
+ember-type-ids\x10\x0dmember-counts\x11\x04\0\x0ecomponent-desc\x03\0\x12\x01r\x02\
+\x04names\x05valuey\x04\0\x0denum-constant\x03\0\x14\x01p\x15\x01r\x03\x04names\x09\
+constants\x16\x07bitmask\x7f\x04\0\x09enum-desc\x03\0\x17\x01m\x03\x07despawn\x03\
+add\x06remove\x04\0\x0ccommand-kind\x03\0\x19\x01r\x03\x04kind\x1a\x06entity\x01\
+\x09component\x01\x04\0\x0dcommand-error\x03\0\x1b\x01r\x02\x09component\x01\x04\
+data\x0f\x04\0\x0fcomponent-value\x03\0\x1d\x01ks\x01k\x10\x01p\x1e\x01k!\x01r\x04\
+\x04name\x1f\x03add\x20\x06prefab\x7f\x06values\"\x04\0\x0bentity-desc\x03\0#\x01\
+r\x01\x04exprs\x04\0\x0aquery-desc\x03\0%\x01r\x02\x02id\x01\x08callback\x03\x04\
+\0\x0csorting-desc\x03\0'\x01kz\x01r\x05\x04name\x1f\x09tick-rate)\x08callback\x03\
+\x0aquery-desc&\x08is-guest\x7f\x04\0\x0bsystem-desc\x03\0*\x01r\x03\x04names\x0a\
+query-desc&\x06phases\x10\x04\0\x0dpipeline-desc\x03\0,\x01p\x0b\x01r\x05\x04nam\
+e\x1f\x0aquery-desc&\x06events.\x08callback\x03\x08is-guest\x7f\x04\0\x0dobserve\
+r-desc\x03\0/\x04\0\x0ecomponent-type\x03\x01\x04\0\x09component\x03\x01\x04\0\x06\
+entity\x03\x01\x04\0\x05query\x03\x01\x04\0\x04iter\x03\x01\x04\0\x08callback\x03\
+\x01\x04\0\x06system\x03\x01\x04\0\x05phase\x03\x01\x04\0\x08pipeline\x03\x01\x04\
+\0\x08observer\x03\x01\x01i1\x01@\x01\x04desc\x13\0;\x04\0\x1b[constructor]compo\
+nent-type\x01<\x01h1\x01@\x01\x04self=\0\x01\x04\0\x1d[method]component-type.get\
+-id\x01>\x01i2\x01@\x03\x03ptr\x03\x06entity\x01\x0ecomponent-type\x01\0?\x04\0\x16\
+[constructor]component\x01@\x01@\x02\x03ptr\x03\x0ecomponent-type\x01\0\x03\x04\0\
+\x1a[static]component.from-ptr\x01A\x01h2\x01@\x03\x04self\xc2\0\x06membery\x05v\
+alue}\x01\0\x04\0\x1f[method]component.set-member-u8\x01C\x01@\x02\x04self\xc2\0\
+\x06membery\0}\x04\0\x1f[method]component.get-member-u8\x01D\x01@\x03\x04self\xc2\
+\0\x06membery\x05value{\x01\0\x04\0\x20[method]component.set-member-u16\x01E\x01\
+@\x02\x04self\xc2\0\x06membery\0{\x04\0\x20[method]component.get-member-u16\x01F\
+\x01@\x03\x04self\xc2\0\x06membery\x05valuey\x01\0\x04\0\x20[method]component.se\
+t-member-u32\x01G\x01@\x02\x04self\xc2\0\x06membery\0y\x04\0\x20[method]componen\
+t.get-member-u32\x01H\x01@\x03\x04self\xc2\0\x06membery\x05valuew\x01\0\x04\0\x20\
+[method]component.set-member-u64\x01I\x01@\x02\x04self\xc2\0\x06membery\0w\x04\0\
+\x20[method]component.get-member-u64\x01J\x01@\x03\x04self\xc2\0\x06membery\x05v\
+alue~\x01\0\x04\0\x1f[method]component.set-member-i8\x01K\x01@\x02\x04self\xc2\0\
+\x06membery\0~\x04\0\x1f[method]component.get-member-i8\x01L\x01@\x03\x04self\xc2\
+\0\x06membery\x05value|\x01\0\x04\0\x20[method]component.set-member-i16\x01M\x01\
+@\x02\x04self\xc2\0\x06membery\0|\x04\0\x20[method]component.get-member-i16\x01N\
+\x01@\x03\x04self\xc2\0\x06membery\x05valuez\x01\0\x04\0\x20[method]component.se\
+t-member-i32\x01O\x01@\x02\x04self\xc2\0\x06membery\0z\x04\0\x20[method]componen\
+t.get-member-i32\x01P\x01@\x03\x04self\xc2\0\x06membery\x05valuex\x01\0\x04\0\x20\
+[method]component.set-member-i64\x01Q\x01@\x02\x04self\xc2\0\x06membery\0x\x04\0\
+\x20[method]component.get-member-i64\x01R\x01@\x03\x04self\xc2\0\x06membery\x05v\
+aluev\x01\0\x04\0\x20[method]component.set-member-f32\x01S\x01@\x02\x04self\xc2\0\
+\x06membery\0v\x04\0\x20[method]component.get-member-f32\x01T\x01@\x03\x04self\xc2\
+\0\x06membery\x05valueu\x01\0\x04\0\x20[method]component.set-member-f64\x01U\x01\
+@\x02\x04self\xc2\0\x06membery\0u\x04\0\x20[method]component.get-member-f64\x01V\
+\x01@\x03\x04self\xc2\0\x06membery\x05value\x7f\x01\0\x04\0![method]component.se\
+t-member-bool\x01W\x01@\x02\x04self\xc2\0\x06membery\0\x7f\x04\0![method]compone\
+nt.get-member-bool\x01X\x01@\x03\x04self\xc2\0\x06membery\x05values\x01\0\x04\0#\
+[method]component.set-member-string\x01Y\x01@\x02\x04self\xc2\0\x06membery\0s\x04\
+\0#[method]component.get-member-string\x01Z\x01@\x03\x04self\xc2\0\x06membery\x05\
+value\x0f\x01\0\x04\0#[method]component.set-member-u8list\x01[\x01@\x02\x04self\xc2\
+\0\x06membery\0\x0f\x04\0#[method]component.get-member-u8list\x01\\\x01p{\x01@\x03\
+\x04self\xc2\0\x06membery\x05value\xdd\0\x01\0\x04\0$[method]component.set-membe\
+r-u16list\x01^\x01@\x02\x04self\xc2\0\x06membery\0\xdd\0\x04\0$[method]component\
+.get-member-u16list\x01_\x01@\x03\x04self\xc2\0\x06membery\x05value\x11\x01\0\x04\
+\0$[method]component.set-member-u32list\x01`\x01@\x02\x04self\xc2\0\x06membery\0\
+\x11\x04\0$[method]component.get-member-u32list\x01a\x01pw\x01@\x03\x04self\xc2\0\
+\x06membery\x05value\xe2\0\x01\0\x04\0$[method]component.set-member-u64list\x01c\
+\x01@\x02\x04self\xc2\0\x06membery\0\xe2\0\x04\0$[method]component.get-member-u6\
+4list\x01d\x01p~\x01@\x03\x04self\xc2\0\x06membery\x05value\xe5\0\x01\0\x04\0#[m\
+ethod]component.set-member-i8list\x01f\x01@\x02\x04self\xc2\0\x06membery\0\xe5\0\
+\x04\0#[method]component.get-member-i8list\x01g\x01p|\x01@\x03\x04self\xc2\0\x06\
+membery\x05value\xe8\0\x01\0\x04\0$[method]component.set-member-i16list\x01i\x01\
+@\x02\x04self\xc2\0\x06membery\0\xe8\0\x04\0$[method]component.get-member-i16lis\
+t\x01j\x01pz\x01@\x03\x04self\xc2\0\x06membery\x05value\xeb\0\x01\0\x04\0$[metho\
+d]component.set-member-i32list\x01l\x01@\x02\x04self\xc2\0\x06membery\0\xeb\0\x04\
+\0$[method]component.get-member-i32list\x01m\x01px\x01@\x03\x04self\xc2\0\x06mem\
+bery\x05value\xee\0\x01\0\x04\0$[method]component.set-member-i64list\x01o\x01@\x02\
+\x04self\xc2\0\x06membery\0\xee\0\x04\0$[method]component.get-member-i64list\x01\
+p\x01pv\x01@\x03\x04self\xc2\0\x06membery\x05value\xf1\0\x01\0\x04\0$[method]com\
+ponent.set-member-f32list\x01r\x01@\x02\x04self\xc2\0\x06membery\0\xf1\0\x04\0$[\
+method]component.get-member-f32list\x01s\x01pu\x01@\x03\x04self\xc2\0\x06membery\
+\x05value\xf4\0\x01\0\x04\0$[method]component.set-member-f64list\x01u\x01@\x02\x04\
+self\xc2\0\x06membery\0\xf4\0\x04\0$[method]component.get-member-f64list\x01v\x04\
+\0$[method]component.set-member-pointer\x01I\x04\0$[method]component.get-member-\
+pointer\x01J\x01k\x01\x01i3\x01@\x02\x04desc$\x08inherits\xf7\0\0\xf8\0\x04\0\x13\
+[constructor]entity\x01y\x01j\x01\x01\x01s\x01@\x02\x04desc$\x08inherits\xf7\0\0\
+\xfa\0\x04\0\x15[static]entity.create\x01{\x01h3\x01@\x01\x04self\xfc\0\0\x01\x04\
+\0\x15[method]entity.get-id\x01}\x01@\x01\x04self\xfc\0\0s\x04\0\x17[method]enti\
+ty.get-name\x01~\x01@\x02\x04self\xfc\0\x04names\x01\0\x04\0\x17[method]entity.s\
+et-name\x01\x7f\x01@\x01\x02idw\0\x03\x04\0\x16[static]entity.from-id\x01\x80\x01\
+\x01@\x02\x04self\xfc\0\x09component\x01\0\x03\x04\0\x12[method]entity.get\x01\x81\
+\x01\x01@\x02\x04self\xfc\0\x09component\x01\x01\0\x04\0\x12[method]entity.add\x01\
+\x82\x01\x01@\x02\x04self\xfc\0\x09component\x01\0\x7f\x04\0\x12[method]entity.h\
+as\x01\x83\x01\x04\0\x15[method]entity.remove\x01\x82\x01\x01@\x03\x04self\xfc\0\
+\x0crelationship\x05\x06target\x01\x01\0\x04\0\x1f[method]entity.add-relationshi\
+p\x01\x84\x01\x04\0\"[method]entity.remove-relationship\x01\x84\x01\x01@\x02\x04\
+self\xfc\0\x06target\x01\x01\0\x04\0\x18[method]entity.parent-of\x01\x85\x01\x04\
+\0\x17[method]entity.child-of\x01\x85\x01\x04\0\x15[method]entity.parent\x01}\x01\
+@\x01\x04self\xfc\0\0\x10\x04\0\x17[method]entity.children\x01\x86\x01\x01@\x02\x04\
+self\xfc\0\x0crelationship\x05\0\x10\x04\0$[method]entity.relationship-entities\x01\
+\x87\x01\x01@\x01\x04self\xfc\0\x01\0\x04\0\x16[method]entity.disable\x01\x88\x01\
+\x04\0\x15[method]entity.enable\x01\x88\x01\x01i4\x01@\x01\x04desc&\0\x89\x01\x04\
+\0\x12[constructor]query\x01\x8a\x01\x01h4\x01@\x01\x04self\x8b\x01\x01\0\x04\0\x13\
+[method]query.build\x01\x8c\x01\x01@\x02\x04self\x8b\x01\x04desc(\x01\0\x04\0\x16\
+[method]query.order-by\x01\x8d\x01\x01@\x01\x04self\x8b\x01\0\x03\x04\0\x12[meth\
+od]query.iter\x01\x8e\x01\x01@\x01\x04self\x8b\x01\0\x7f\x04\0\x12[method]query.\
+next\x01\x8f\x01\x01@\x01\x04self\x8b\x01\0z\x04\0\x13[method]query.count\x01\x90\
+\x01\x01@\x01\x04self\x8b\x01\0\x10\x04\0\x16[method]query.entities\x01\x91\x01\x01\
+p\x03\x01@\x02\x04self\x8b\x01\x05index~\0\x92\x01\x04\0\x18[method]query.compon\
+ents\x01\x93\x01\x04\0\x13[method]query.cache\x01\x8c\x01\x01@\x03\x04self\x8b\x01\
+\x05index~\x04kind\x0d\x01\0\x04\0\x15[method]query.monitor\x01\x94\x01\x04\0\x15\
+[method]query.changed\x01\x8f\x01\x04\0\x1a[method]query.iter-changed\x01\x8f\x01\
+\x04\0\x12[method]query.skip\x01\x8c\x01\x01@\x02\x04self\x8b\x01\x05index~\0\x7f\
+\x04\0\x1b[method]query.field-changed\x01\x95\x01\x01p\x7f\x01@\x02\x04self\x8b\x01\
+\x05index~\0\x96\x01\x04\0\x19[method]query.field-added\x01\x97\x01\x01i5\x01@\x01\
+\x03ptrw\0\x98\x01\x04\0\x11[constructor]iter\x01\x99\x01\x01h5\x01@\x01\x04self\
+\x9a\x01\0\x7f\x04\0\x11[method]iter.next\x01\x9b\x01\x01@\x01\x04self\x9a\x01\0\
+z\x04\0\x12[method]iter.count\x01\x9c\x01\x01@\x01\x04self\x9a\x01\0\x01\x04\0\x13\
+[method]iter.system\x01\x9d\x01\x01@\x01\x04self\x9a\x01\0\x10\x04\0\x15[method]\
+iter.entities\x01\x9e\x01\x01@\x02\x04self\x9a\x01\x05index~\0\x92\x01\x04\0\x17\
+[method]iter.components\x01\x9f\x01\x01@\x04\x04self\x9a\x01\x05index~\x06member\
+y\x0bmember-type\x09\0\x0f\x04\0\x13[method]iter.column\x01\xa0\x01\x01@\x05\x04\
+self\x9a\x01\x05index~\x06membery\x0bmember-type\x09\x04data\x0f\x01\0\x04\0\x17\
+[method]iter.set-column\x01\xa1\x01\x01@\x02\x04self\x9a\x01\x05index~\0\x0f\x04\
+\0\x17[method]iter.field-data\x01\xa2\x01\x01@\x03\x04self\x9a\x01\x05index~\x04\
+data\x0f\x01\0\x04\0\x1b[method]iter.set-field-data\x01\xa3\x01\x04\0\x14[method\
+]iter.changed\x01\x9b\x01\x01@\x01\x04self\x9a\x01\x01\0\x04\0\x11[method]iter.s\
+kip\x01\xa4\x01\x01@\x02\x04self\x9a\x01\x05index~\0\x7f\x04\0\x1a[method]iter.f\
+ield-changed\x01\xa5\x01\x01@\x02\x04self\x9a\x01\x05index~\0\x96\x01\x04\0\x18[\
+method]iter.field-added\x01\xa6\x01\x01i6\x01@\x01\x06handlew\0\xa7\x01\x04\0\x15\
+[constructor]callback\x01\xa8\x01\x01h6\x01@\x02\x04self\xa9\x01\x04iter\x98\x01\
+\x01\0\x04\0\x14[method]callback.run\x01\xaa\x01\x01@\x01\x04self\xa9\x01\0\x03\x04\
+\0\x1a[method]callback.cb-handle\x01\xab\x01\x01i7\x01@\x01\x04desc+\0\xac\x01\x04\
+\0\x13[constructor]system\x01\xad\x01\x01h7\x01@\x01\x04self\xae\x01\0\x01\x04\0\
+\x15[method]system.get-id\x01\xaf\x01\x01@\x01\x04self\xae\x01\x01\0\x04\0\x14[m\
+ethod]system.build\x01\xb0\x01\x01@\x02\x04self\xae\x01\x04names\x01\0\x04\0\x14\
+[method]system.named\x01\xb1\x01\x01@\x03\x04self\xae\x01\x05index~\x04kind\x0d\x01\
+\0\x04\0\x16[method]system.monitor\x01\xb2\x01\x01@\x02\x04self\xae\x01\x04desc(\
+\x01\0\x04\0\x17[method]system.order-by\x01\xb3\x01\x01@\x02\x04self\xae\x01\x07\
+enabled\x7f\x01\0\x04\0\x1d[method]system.multi-threaded\x01\xb4\x01\x01@\x01\x04\
+self\xae\x01\0\x03\x04\0\x17[method]system.callback\x01\xb5\x01\x04\0\x16[method\
+]system.disable\x01\xb0\x01\x04\0\x15[method]system.enable\x01\xb0\x01\x01i8\x01\
+@\x01\x04names\0\xb6\x01\x04\0\x12[constructor]phase\x01\xb7\x01\x01h8\x01@\x02\x04\
+self\xb8\x01\x05phase\x07\x01\0\x04\0\x18[method]phase.depends-on\x01\xb9\x01\x01\
+@\x01\x04self\xb8\x01\0\x01\x04\0\x14[method]phase.get-id\x01\xba\x01\x01i9\x01@\
+\x01\x04desc-\0\xbb\x01\x04\0\x15[constructor]pipeline\x01\xbc\x01\x01h9\x01@\x01\
+\x04self\xbd\x01\x01\0\x04\0\x16[method]pipeline.build\x01\xbe\x01\x01@\x02\x04s\
+elf\xbd\x01\x05phase\x01\x01\0\x04\0\x1a[method]pipeline.add-phase\x01\xbf\x01\x01\
+@\x01\x04self\xbd\x01\0\x01\x04\0\x17[method]pipeline.get-id\x01\xc0\x01\x04\0\x18\
+[method]pipeline.disable\x01\xbe\x01\x04\0\x17[method]pipeline.enable\x01\xbe\x01\
+\x01i:\x01@\x01\x04desc0\0\xc1\x01\x04\0\x15[constructor]observer\x01\xc2\x01\x01\
+h:\x01@\x01\x04self\xc3\x01\0\x01\x04\0\x17[method]observer.get-id\x01\xc4\x01\x01\
+@\x01\x04self\xc3\x01\x01\0\x04\0\x16[method]observer.build\x01\xc5\x01\x01@\x01\
+\x04self\xc3\x01\0\x03\x04\0\x19[method]observer.callback\x01\xc6\x01\x01@\x01\x0c\
+component-id\x01\x01\0\x04\0\x0dadd-singleton\x01\xc7\x01\x01@\x01\x0ccomponent-\
+id\x01\0w\x04\0\x0dget-singleton\x01\xc8\x01\x04\0\x10remove-singleton\x01\xc7\x01\
+\x01@\x01\x09entity-id\x01\x01\0\x04\0\x0aadd-entity\x01\xc9\x01\x04\0\x0dremove\
+-entity\x01\xc9\x01\x01@\x01\x04names\0\x7f\x04\0\x10has-entity-named\x01\xca\x01\
+\x01@\x01\x0ecomponent-names\0\x01\x04\0\x10get-component-id\x01\xcb\x01\x01@\x01\
+\x04desc\x18\0\x01\x04\0\x0dregister-enum\x01\xcc\x01\x01j\x01s\x01s\x01@\x02\x06\
+entity\x01\x09recursive\x7f\0\xcd\x01\x04\0\x0eentity-to-json\x01\xce\x01\x01@\x02\
+\x04jsons\x06parent\xf7\0\0\xfa\0\x04\0\x10entity-from-json\x01\xcf\x01\x01@\0\0\
+\x01\x04\0\x0dcommand-spawn\x01\xd0\x01\x01@\x01\x06entity\x01\x01\0\x04\0\x0fco\
+mmand-despawn\x01\xd1\x01\x01@\x02\x06entity\x01\x09component\x01\x01\0\x04\0\x0b\
+command-add\x01\xd2\x01\x04\0\x0ecommand-remove\x01\xd2\x01\x01p\x1c\x01@\0\0\xd3\
+\x01\x04\0\x0eflush-commands\x01\xd4\x01\x04\0\x13take-command-errors\x01\xd4\x01\
+\x04\0\x11toxoid:engine/ecs\x05\0\x01B\x13\x01y\x04\0\x08key-code\x03\0\0\x01r\x03\
+\x05deltav\x07elapsedu\x05framew\x04\0\x09time-info\x03\0\x02\x01r\x04\x05widthy\
+\x06heighty\x0crender-widthy\x0drender-heighty\x04\0\x0bwindow-info\x03\0\x04\x01\
+r\x09\x01xv\x01yv\x02dxv\x02dyv\x08scroll-xv\x08scroll-yv\x04left\x7f\x05right\x7f\
+\x06middle\x7f\x04\0\x0bmouse-state\x03\0\x06\x01@\0\0\x03\x04\0\x04time\x01\x08\
+\x01@\0\0\x05\x04\0\x06window\x01\x09\x01@\0\0\x07\x04\0\x05mouse\x01\x0a\x01@\x01\
+\x03key\x01\0\x7f\x04\0\x08key-down\x01\x0b\x01p\x01\x01@\0\0\x0c\x04\0\x09keys-\
+down\x01\x0d\x04\0\x14toxoid:engine/engine\x05\x01\x04\0!toxoid:engine/toxoid-en\
+gine-world\x04\0\x0b\x19\x01\0\x13toxoid-engine-world\x03\0\0\0G\x09producers\x01\
+\x0cprocessed-by\x02\x0dwit-component\x070.220.0\x10wit-bindgen-rust\x060.35.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -0,0 +1,296 @@
+use crate::*;
+use bindings::exports::toxoid::engine::ecs::MonitorKind;
+use std::collections::HashSet;
+use toxoid_flecs::bindings::{ecs_get_table, ecs_inout_kind_t_EcsIn, ecs_is_fini, ecs_iter_changed, ecs_iter_fini, ecs_iter_set_var_as_table, ecs_iter_skip, ecs_query_cache_kind_t_EcsQueryCacheAuto, ecs_query_cache_kind_t_EcsQueryCacheNone, ecs_query_changed, ecs_query_fini, ecs_table_t};
+use toxoid_flecs::{EcsOnAdd, EcsOnRemove};
+
+// Change detection, built on the monitors Flecs keeps for cached queries. Flecs counts per table
+// how often entities entered or left it and how often each component column was written, either
+// with ecs_modified or by a query that doesn't read it as [in]. A cached query takes note of the
+// counters of a table when it moves on to the next one, unless the table was skipped.
+//
+// Fields with a `Changed` or `Added` filter are monitored separately, so the other fields of the
+// query don't count. A changed field is checked with a cached query of its component alone and the
+// entities that got a component are collected by an observer. What a query saw of a table is only
+// noted once it moves on, so reading a filter twice gives the same result. In multithreaded systems
+// the thread that finishes a table first does that for all of them. The monitors are the binding
+// context of the query and are freed with it.
+
+struct FieldMonitor {
+    field: i8,
+    kind: MonitorKind,
+    // Cached query of the field's component, for `Changed`
+    query: *mut ecs_query_t,
+    // Observer filling `added`, for `Added`
+    observer: ecs_entity_t,
+    // Entities that got the component and weren't seen yet
+    added: Arc<Mutex<HashSet<ecs_entity_t>>>
+}
+
+// What a query read of a table it didn't move on from yet
+#[derive(Default)]
+struct Visit {
+    // Whether a field changed and the table its component was checked in
+    changed: HashMap<i8, (bool, usize)>,
+    // Added entities the query was given, by field
+    seen: Vec<(i8, ecs_entity_t)>
+}
+
+pub struct QueryMonitors {
+    world: *mut ecs_world_t,
+    fields: Vec<FieldMonitor>,
+    // Keyed by the iterated table
+    visits: Mutex<HashMap<usize, Visit>>
+}
+
+impl QueryMonitors {
+    fn field(&self, field: i8, kind: MonitorKind) -> Option<&FieldMonitor> {
+        self.fields.iter().find(|monitor| monitor.field == field && monitor.kind == kind)
+    }
+}
+
+impl Drop for QueryMonitors {
+    fn drop(&mut self) {
+        // A world being finished deletes the queries and observers itself
+        if self.world.is_null() || unsafe { ecs_is_fini(self.world) } {
+            return;
+        }
+        for monitor in &self.fields {
+            unsafe {
+                if !monitor.query.is_null() {
+                    ecs_query_fini(monitor.query);
+                }
+                if monitor.observer != 0 {
+                    ecs_delete(self.world, monitor.observer);
+                }
+            }
+        }
+    }
+}
+
+// Binding context of a query with monitors for `fields`, null if there are none.
+// The monitors start with `start_monitors` once the query is built.
+pub(crate) fn monitors_ctx(fields: &[(i8, MonitorKind)]) -> *mut c_void {
+    if fields.is_empty() {
+        return std::ptr::null_mut();
+    }
+    let fields = fields
+        .iter()
+        .map(|&(field, kind)| FieldMonitor {
+            field,
+            kind,
+            query: std::ptr::null_mut(),
+            observer: 0,
+            added: Arc::new(Mutex::new(HashSet::new()))
+        })
+        .collect();
+    let monitors = QueryMonitors { world: std::ptr::null_mut(), fields, visits: Mutex::new(HashMap::new()) };
+    Box::into_raw(Box::new(monitors)) as *mut c_void
+}
+
+pub(crate) unsafe extern "C" fn free_monitors(ctx: *mut c_void) {
+    drop(Box::from_raw(ctx as *mut QueryMonitors));
+}
+
+// Create the query or observer of every monitored field of a query built with `monitors_ctx`
+pub(crate) unsafe fn start_monitors(query: *mut ecs_query_t) {
+    let Some(monitors) = ((*query).binding_ctx as *mut QueryMonitors).as_mut() else {
+        return;
+    };
+    let world = (*query).real_world;
+    monitors.world = world;
+    for monitor in monitors.fields.iter_mut() {
+        let terms = &(&(*query).terms)[..(*query).term_count as usize];
+        let Some(term) = terms
+            .iter()
+            .find(|term| term.field_index == monitor.field)
+        else {
+            continue;
+        };
+        match monitor.kind {
+            MonitorKind::Changed => {
+                let mut desc: ecs_query_desc_t = MaybeUninit::zeroed().assume_init();
+                desc.terms[0].id = term.id;
+                desc.terms[0].inout = ecs_inout_kind_t_EcsIn as i16;
+                desc.cache_kind = ecs_query_cache_kind_t_EcsQueryCacheAuto;
+                monitor.query = ecs_query_init(world, &desc);
+            }
+            MonitorKind::Added => {
+                let mut desc: ecs_observer_desc_t = MaybeUninit::zeroed().assume_init();
+                desc.query.terms[0].id = term.id;
+                desc.events[0] = EcsOnAdd;
+                desc.events[1] = EcsOnRemove;
+                desc.callback = Some(track_added);
+                desc.ctx = Arc::into_raw(monitor.added.clone()) as *mut c_void;
+                desc.ctx_free = Some(release_added);
+                monitor.observer = ecs_observer_init(world, &desc);
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn track_added(iter: *mut ecs_iter_t) {
+    let added = &*((*iter).ctx as *const Mutex<HashSet<ecs_entity_t>>);
+    let entities = std::slice::from_raw_parts((*iter).entities, (*iter).count as usize);
+    let mut added = added.lock().unwrap();
+    for entity in entities {
+        if (*iter).event == EcsOnAdd {
+            added.insert(*entity);
+        } else {
+            added.remove(entity);
+        }
+    }
+}
+
+unsafe extern "C" fn release_added(ctx: *mut c_void) {
+    drop(Arc::from_raw(ctx as *const Mutex<HashSet<ecs_entity_t>>));
+}
+
+unsafe fn monitors<'a>(iter: *mut ecs_iter_t) -> Option<&'a QueryMonitors> {
+    if iter.is_null() || (*iter).query.is_null() {
+        return None;
+    }
+    ((*(*iter).query).binding_ctx as *const QueryMonitors).as_ref()
+}
+
+// Only cached queries track changes, uncached queries always report changes
+unsafe fn is_cached(query: *const ecs_query_t) -> bool {
+    !query.is_null() && (*query).cache_kind != ecs_query_cache_kind_t_EcsQueryCacheNone
+}
+
+pub unsafe fn query_changed(query: *mut ecs_query_t) -> bool {
+    !is_cached(query) || ecs_query_changed(query)
+}
+
+pub unsafe fn iter_changed(iter: *mut ecs_iter_t) -> bool {
+    if iter.is_null() || (*iter).count == 0 {
+        return false;
+    }
+    !is_cached((*iter).query) || ecs_iter_changed(iter)
+}
+
+// Leave the current table as it is for the next iteration, the filters of the query included
+pub unsafe fn iter_skip(iter: *mut ecs_iter_t) {
+    if iter.is_null() {
+        return;
+    }
+    if let Some(monitors) = monitors(iter) {
+        monitors.visits.lock().unwrap().remove(&((*iter).table as usize));
+    }
+    if is_cached((*iter).query) {
+        ecs_iter_skip(iter);
+    }
+}
+
+pub unsafe fn field_changed(iter: *mut ecs_iter_t, index: i8) -> bool {
+    let Some(monitors) = monitors(iter) else {
+        return iter_changed(iter);
+    };
+    let Some(monitor) = monitors.field(index, MonitorKind::Changed) else {
+        return iter_changed(iter);
+    };
+    let Some(table) = field_table(iter, index) else {
+        return false;
+    };
+    let mut visits = monitors.visits.lock().unwrap();
+    let visit = visits.entry((*iter).table as usize).or_default();
+    if let Some(&(changed, _)) = visit.changed.get(&index) {
+        return changed;
+    }
+    let changed = table_changed((*iter).world, monitor.query, table);
+    visit.changed.insert(index, (changed, table as usize));
+    changed
+}
+
+pub unsafe fn field_added(iter: *mut ecs_iter_t, index: i8) -> Vec<bool> {
+    if iter.is_null() || (*iter).count == 0 {
+        return vec![];
+    }
+    let count = (*iter).count as usize;
+    let Some(monitors) = monitors(iter) else {
+        return vec![iter_changed(iter); count];
+    };
+    let Some(monitor) = monitors.field(index, MonitorKind::Added) else {
+        return vec![iter_changed(iter); count];
+    };
+    if !ecs_field_is_set(iter, index) {
+        return vec![false; count];
+    }
+    // A component of another entity such as a parent is added for every row
+    let entities = if ecs_field_is_self(iter, index) {
+        std::slice::from_raw_parts((*iter).entities, count).to_vec()
+    } else {
+        vec![ecs_field_src(iter, index); count]
+    };
+    let added = monitor.added.lock().unwrap();
+    let rows: Vec<bool> = entities.iter().map(|entity| added.contains(entity)).collect();
+    let mut visits = monitors.visits.lock().unwrap();
+    let visit = visits.entry((*iter).table as usize).or_default();
+    visit.seen.extend(entities.iter().zip(&rows).filter(|(_, added)| **added).map(|(entity, _)| (index, *entity)));
+    rows
+}
+
+// The query moved on from the current table, note what its filters saw of it
+pub unsafe fn visit_done(iter: *mut ecs_iter_t) {
+    let Some(monitors) = monitors(iter) else {
+        return;
+    };
+    let Some(visit) = monitors.visits.lock().unwrap().remove(&((*iter).table as usize)) else {
+        return;
+    };
+    for (field, (_, table)) in visit.changed {
+        if let Some(monitor) = monitors.field(field, MonitorKind::Changed) {
+            sync_table((*iter).world, monitor.query, table as *mut ecs_table_t);
+        }
+    }
+    for (field, entity) in visit.seen {
+        if let Some(monitor) = monitors.field(field, MonitorKind::Added) {
+            monitor.added.lock().unwrap().remove(&entity);
+        }
+    }
+}
+
+// Table the component of a field is stored in, that of another entity such as a parent
+// for fields that aren't matched on the iterated entity
+unsafe fn field_table(iter: *mut ecs_iter_t, index: i8) -> Option<*mut ecs_table_t> {
+    if iter.is_null() || (*iter).count == 0 || !ecs_field_is_set(iter, index) {
+        return None;
+    }
+    let table = if ecs_field_is_self(iter, index) {
+        (*iter).table
+    } else {
+        ecs_get_table((*iter).real_world, ecs_field_src(iter, index))
+    };
+    (!table.is_null()).then_some(table)
+}
+
+// Iterate a monitor query over one table, its $this variable is the first one
+unsafe fn table_iter(world: *mut ecs_world_t, query: *mut ecs_query_t, table: *mut ecs_table_t) -> ecs_iter_t {
+    let mut iter = ecs_query_iter(world, query);
+    ecs_iter_set_var_as_table(&mut iter, 0, table);
+    iter
+}
+
+// Whether the table changed since the monitor query last moved on from it.
+// A table the query hasn't seen before counts as changed.
+unsafe fn table_changed(world: *mut ecs_world_t, query: *mut ecs_query_t, table: *mut ecs_table_t) -> bool {
+    if query.is_null() {
+        return true;
+    }
+    let mut iter = table_iter(world, query, table);
+    if !ecs_query_next(&mut iter) {
+        return false;
+    }
+    let changed = ecs_iter_changed(&mut iter);
+    ecs_iter_fini(&mut iter);
+    changed
+}
+
+// Moving on from the table makes Flecs note its counters
+unsafe fn sync_table(world: *mut ecs_world_t, query: *mut ecs_query_t, table: *mut ecs_table_t) {
+    if query.is_null() {
+        return;
+    }
+    let mut iter = table_iter(world, query, table);
+    while ecs_query_next(&mut iter) {}
+}
@@ -0,0 +1,142 @@
+use crate::*;
+use crate::bindings::exports::toxoid::engine::ecs::{CommandError, CommandKind};
+use crate::log::{LogLevel, LogRecord};
+use std::collections::VecDeque;
+use toxoid_flecs::bindings::{ecs_get_alive, ecs_id_is_pair, ecs_is_alive, ecs_new, ECS_COMPONENT_MASK, ECS_ENTITY_MASK};
+
+// Structural changes that systems record while they iterate. Every world has a flush system
+// at the start of each built-in phase that applies the commands recorded before it, so commands
+// of one phase are seen by the systems of the next one. Commands from the last phase and from
+// outside of systems are applied at the start of the next frame, or with `flush`.
+
+#[derive(Clone, Copy)]
+pub enum Command {
+    Despawn(ecs_entity_t),
+    Add(ecs_entity_t, ecs_entity_t),
+    Remove(ecs_entity_t, ecs_entity_t)
+}
+
+// Errors are kept until they're taken, the oldest are dropped beyond this
+const MAX_ERRORS: usize = 256;
+
+#[derive(Default)]
+struct CommandBuffer {
+    commands: Vec<Command>,
+    errors: VecDeque<CommandError>
+}
+
+// Buffers by world
+static BUFFERS: Lazy<Mutex<HashMap<u64, CommandBuffer>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            CommandKind::Despawn => write!(f, "Can't despawn entity {}, it's already deleted", self.entity),
+            CommandKind::Add => write!(f, "Can't add {} to entity {}, one of them is deleted", self.component, self.entity),
+            CommandKind::Remove => write!(f, "Can't remove {} from entity {}, one of them is deleted", self.component, self.entity)
+        }
+    }
+}
+
+// Entity for a spawn command. It's created right away so later commands can refer to it,
+// its components are added when the commands are applied.
+pub fn spawn() -> ecs_entity_t {
+    unsafe { ecs_new(stage()) }
+}
+
+// Record a command for the active world, safe to call from worker threads
+pub fn push(command: Command) {
+    BUFFERS.lock().unwrap().entry(unsafe { WORLD.0 } as u64).or_default().commands.push(command);
+}
+
+// Apply the commands of the active world now and return the ones that failed.
+// Inside of systems the changes are deferred by Flecs like any other.
+pub fn flush() -> Vec<CommandError> {
+    unsafe { apply(WORLD.0) }
+}
+
+// Errors of the commands applied at flush points of the active world
+pub fn take_errors() -> Vec<CommandError> {
+    BUFFERS
+        .lock()
+        .unwrap()
+        .get_mut(&(unsafe { WORLD.0 } as u64))
+        .map(|buffer| buffer.errors.drain(..).collect())
+        .unwrap_or_default()
+}
+
+unsafe fn apply(world: *mut ecs_world_t) -> Vec<CommandError> {
+    // Taken out of the lock, applying can run observers that record more commands
+    let commands = match BUFFERS.lock().unwrap().get_mut(&(world as u64)) {
+        Some(buffer) => std::mem::take(&mut buffer.commands),
+        None => return vec![]
+    };
+    let mut errors = Vec::new();
+    for command in commands {
+        let (kind, entity, component) = match command {
+            Command::Despawn(entity) => (CommandKind::Despawn, entity, 0),
+            Command::Add(entity, component) => (CommandKind::Add, entity, component),
+            Command::Remove(entity, component) => (CommandKind::Remove, entity, component)
+        };
+        if !ecs_is_alive(world, entity) || (component != 0 && !id_is_alive(world, component)) {
+            errors.push(CommandError { kind, entity, component });
+            continue;
+        }
+        match kind {
+            CommandKind::Despawn => ecs_delete(world, entity),
+            CommandKind::Add => ecs_add_id(world, entity, component),
+            CommandKind::Remove => ecs_remove_id(world, entity, component)
+        }
+    }
+    errors
+}
+
+// Pairs hold the entities without their generation, their parts are looked up by index
+unsafe fn id_is_alive(world: *mut ecs_world_t, id: ecs_entity_t) -> bool {
+    if ecs_id_is_pair(id) {
+        let first = (id & ECS_COMPONENT_MASK) >> 32;
+        let second = id & ECS_ENTITY_MASK as u64;
+        ecs_get_alive(world, first) != 0 && ecs_get_alive(world, second) != 0
+    } else {
+        ecs_is_alive(world, id)
+    }
+}
+
+// Flush system, immediate so it runs outside of Flecs deferred mode
+unsafe extern "C" fn flush_system(iter: *mut ecs_iter_t) {
+    let world = (*iter).world;
+    let errors = apply(world);
+    if errors.is_empty() {
+        return;
+    }
+    for error in &errors {
+        crate::log::log(LogRecord::new(LogLevel::Warn, "commands", &error.to_string()));
+    }
+    let mut buffers = BUFFERS.lock().unwrap();
+    let buffer = buffers.entry(world as u64).or_default();
+    buffer.errors.extend(errors);
+    while buffer.errors.len() > MAX_ERRORS {
+        buffer.errors.pop_front();
+    }
+}
+
+// Drop the commands and errors of a world that is finished
+pub(crate) fn forget(world: *mut ecs_world_t) {
+    BUFFERS.lock().unwrap().remove(&(world as u64));
+}
+
+// Add the flush systems to a new world, before any other system so they run first in their phase
+pub(crate) unsafe fn init(world: *mut ecs_world_t) {
+    let phases = [EcsOnLoad, EcsPostLoad, EcsPreUpdate, EcsOnUpdate, EcsOnValidate, EcsPostUpdate, EcsPreStore, EcsOnStore];
+    for phase in phases {
+        let mut entity_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
+        let add = [ecs_make_pair(EcsDependsOn, phase), 0];
+        entity_desc.add = add.as_ptr();
+        let entity = ecs_entity_init(world, &entity_desc);
+        let mut system_desc: ecs_system_desc_t = MaybeUninit::zeroed().assume_init();
+        system_desc.entity = entity;
+        system_desc.immediate = true;
+        system_desc.callback = Some(flush_system);
+        ecs_system_init(world, &system_desc);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::bindings::exports::toxoid::engine::engine::{Guest, KeyCode, MouseState, TimeInfo, WindowInfo};
+use crate::{ToxoidApi, WORLD};
+use toxoid_flecs::bindings::ecs_get_world_info;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle
+}
+
+// Window and input state, written by the platform layer as its events come in
+struct EngineState {
+    window_width: u32,
+    window_height: u32,
+    render_width: u32,
+    render_height: u32,
+    mouse: MouseState,
+    keys: HashSet<KeyCode>
+}
+
+static ENGINE_STATE: Lazy<Mutex<EngineState>> = Lazy::new(|| Mutex::new(EngineState {
+    window_width: 0,
+    window_height: 0,
+    render_width: 0,
+    render_height: 0,
+    mouse: MouseState { x: 0., y: 0., dx: 0., dy: 0., scroll_x: 0., scroll_y: 0., left: false, right: false, middle: false },
+    keys: HashSet::new()
+}));
+
+pub fn set_window_size(width: u32, height: u32) {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    state.window_width = width;
+    state.window_height = height;
+}
+
+pub fn set_render_size(width: u32, height: u32) {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    state.render_width = width;
+    state.render_height = height;
+}
+
+pub fn set_key(key: KeyCode, down: bool) {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    if down {
+        state.keys.insert(key);
+    } else {
+        state.keys.remove(&key);
+    }
+}
+
+pub fn set_mouse_position(x: f32, y: f32, dx: f32, dy: f32) {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    state.mouse.x = x;
+    state.mouse.y = y;
+    // Several move events can arrive between two frames
+    state.mouse.dx += dx;
+    state.mouse.dy += dy;
+}
+
+pub fn set_mouse_button(button: MouseButton, down: bool) {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    match button {
+        MouseButton::Left => state.mouse.left = down,
+        MouseButton::Right => state.mouse.right = down,
+        MouseButton::Middle => state.mouse.middle = down
+    }
+}
+
+pub fn add_mouse_scroll(x: f32, y: f32) {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    state.mouse.scroll_x += x;
+    state.mouse.scroll_y += y;
+}
+
+// Work handed to the main loop by other threads. Loading guests and switching worlds change the
+// active world, which must not happen while a frame is running, so they are done between frames.
+static FRAME_TASKS: Lazy<Mutex<Vec<Box<dyn FnOnce() + Send>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// Run a task on the main loop after the current frame, tasks run in the order they were queued
+pub fn run_between_frames(task: impl FnOnce() + Send + 'static) {
+    FRAME_TASKS.lock().unwrap().push(Box::new(task));
+}
+
+// Run the tasks queued for after the frame, for hosts that progress their worlds themselves
+pub fn run_frame_tasks() {
+    // Taken out of the lock, tasks can queue more tasks for the next frame
+    let tasks = std::mem::take(&mut *FRAME_TASKS.lock().unwrap());
+    tasks.into_iter().for_each(|task| task());
+}
+
+// Reset per frame input, called once the world has progressed
+pub(crate) fn end_frame() {
+    let mut state = ENGINE_STATE.lock().unwrap();
+    state.mouse.dx = 0.;
+    state.mouse.dy = 0.;
+    state.mouse.scroll_x = 0.;
+    state.mouse.scroll_y = 0.;
+}
+
+impl Guest for ToxoidApi {
+    fn time() -> TimeInfo {
+        // Flecs keeps track of the frame time when progressing the world
+        let info = unsafe { &*ecs_get_world_info(WORLD.0) };
+        TimeInfo {
+            delta: info.delta_time,
+            elapsed: info.world_time_total,
+            frame: info.frame_count_total as u64
+        }
+    }
+
+    fn window() -> WindowInfo {
+        let state = ENGINE_STATE.lock().unwrap();
+        WindowInfo {
+            width: state.window_width,
+            height: state.window_height,
+            render_width: state.render_width,
+            render_height: state.render_height
+        }
+    }
+
+    fn mouse() -> MouseState {
+        ENGINE_STATE.lock().unwrap().mouse
+    }
+
+    fn key_down(key: KeyCode) -> bool {
+        ENGINE_STATE.lock().unwrap().keys.contains(&key)
+    }
+
+    fn keys_down() -> Vec<KeyCode> {
+        ENGINE_STATE.lock().unwrap().keys.iter().copied().collect()
+    }
+}
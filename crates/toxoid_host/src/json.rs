@@ -0,0 +1,316 @@
+use crate::bindings::exports::toxoid::engine::ecs::GuestEntity;
+use crate::snapshot::{component_layout, entity_ids, entity_path, lookup, pair_first, pair_second, read_value, set_values, Value};
+use crate::{Entity, FieldType, WORLD};
+use toxoid_flecs::bindings::{
+    ecs_add_id, ecs_get_id, ecs_get_name, ecs_get_typeid, ecs_id_is_pair, ecs_is_alive, ecs_make_pair, ecs_new, ecs_set_name,
+    EcsChildOf, ECS_COMPONENT_MASK, FLECS_IDEcsIdentifierID_
+};
+use serde_json::{Map, Number};
+use core::ffi::c_void;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+
+type ecs_entity_t = u64;
+
+// Entities as JSON, so level designers can author them in Tiled properties or in standalone files.
+// Components and tags are referred to by the names they were registered with, every key is optional:
+//
+// {
+//   "name": "Chest",
+//   "tags": ["Interactable"],
+//   "components": { "Position": { "x": 32, "y": 64 }, "Loot": { "items": [3, 7] } },
+//   "pairs": [["Likes", "Player"]],
+//   "children": [{ "name": "Lid", "components": { "Size": { "width": 16, "height": 4 } } }]
+// }
+//
+// Other keys are rejected. Members missing from a component keep their default value. Pointer members and data on pairs
+// are host memory and are left out.
+// Members of nested structs are keyed by their path like "bounds.min.x", enums are their
+// integer value and fixed-size arrays are lists.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    Parse(String),
+    // An entity or one of its components isn't a JSON object, or `tags`, `pairs` or `children` isn't an array
+    Malformed(String),
+    // A key other than the ones above
+    UnknownKey(String),
+    // The entity to write doesn't exist
+    NotAlive(ecs_entity_t),
+    // A component, tag or pair element that isn't registered in this world
+    UnknownEntity(String),
+    UnknownMember { component: String, member: String },
+    // The value doesn't fit the type of the member
+    InvalidValue { component: String, member: String }
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::Parse(error) => write!(f, "Invalid JSON: {}", error),
+            JsonError::Malformed(error) => write!(f, "Malformed entity: {}", error),
+            JsonError::UnknownKey(key) => write!(f, "Unknown key {}", key),
+            JsonError::NotAlive(entity) => write!(f, "Entity {} is not alive", entity),
+            JsonError::UnknownEntity(path) => write!(f, "{} doesn't exist in this world", path),
+            JsonError::UnknownMember { component, member } => write!(f, "Component {} has no member {}", component, member),
+            JsonError::InvalidValue { component, member } => write!(f, "Invalid value for member {} of component {}", member, component)
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+// An entity of the active world as JSON, with its children when recursive
+pub fn entity_to_json(entity: ecs_entity_t, recursive: bool) -> Result<String, JsonError> {
+    if entity == 0 || !unsafe { ecs_is_alive(WORLD.0, entity) } {
+        return Err(JsonError::NotAlive(entity));
+    }
+    let value = unsafe { entity_to_value(entity, recursive) };
+    Ok(serde_json::to_string_pretty(&value).unwrap_or_default())
+}
+
+// Create an entity from JSON in the active world, under a parent if given
+pub fn entity_from_json(json: &str, parent: Option<ecs_entity_t>) -> Result<ecs_entity_t, JsonError> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|e| JsonError::Parse(e.to_string()))?;
+    // Everything is resolved before the first entity is created, so a bad document doesn't leave half an entity
+    let entity = unsafe { resolve_entity(&value)? };
+    Ok(unsafe { create_entity(&entity, parent) })
+}
+
+unsafe fn entity_to_value(entity: ecs_entity_t, recursive: bool) -> serde_json::Value {
+    let mut object = Map::new();
+    let name = ecs_get_name(WORLD.0, entity);
+    if !name.is_null() {
+        object.insert("name".to_string(), CStr::from_ptr(name).to_string_lossy().into_owned().into());
+    }
+
+    let mut tags = Vec::new();
+    let mut components = Map::new();
+    let mut pairs = Vec::new();
+    for id in entity_ids(entity) {
+        let type_id = ecs_get_typeid(WORLD.0, id);
+        if ecs_id_is_pair(id) {
+            let first = pair_first(id);
+            // Names are written above and the hierarchy through nesting
+            if first == FLECS_IDEcsIdentifierID_ || first == EcsChildOf || type_id != 0 {
+                continue;
+            }
+            pairs.push(serde_json::Value::from(vec![entity_path(first), entity_path(pair_second(id))]));
+        } else if id & !ECS_COMPONENT_MASK != 0 {
+            continue;
+        } else if type_id == 0 {
+            tags.push(serde_json::Value::from(entity_path(id)));
+        } else if let Some(layout) = component_layout(type_id) {
+            let ptr = ecs_get_id(WORLD.0, entity, id);
+            let mut members = Map::new();
+            for member in layout.iter() {
+                if member.field_type != FieldType::Pointer {
+                    let struct_ptr = (ptr as *const u8).add(member.struct_offset as usize) as *const c_void;
+                    let value = read_value(struct_ptr, member.offset, member.field_type, member.count);
+                    members.insert(member.name.clone(), value_to_json(value));
+                }
+            }
+            components.insert(entity_path(type_id), members.into());
+        }
+    }
+    if !tags.is_empty() {
+        object.insert("tags".to_string(), tags.into());
+    }
+    if !components.is_empty() {
+        object.insert("components".to_string(), components.into());
+    }
+    if !pairs.is_empty() {
+        object.insert("pairs".to_string(), pairs.into());
+    }
+
+    if recursive {
+        let children: Vec<serde_json::Value> = Entity { id: entity }
+            .children()
+            .into_iter()
+            .map(|child| entity_to_value(child, true))
+            .collect();
+        if !children.is_empty() {
+            object.insert("children".to_string(), children.into());
+        }
+    }
+    object.into()
+}
+
+fn value_to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::U8(value) => value.into(),
+        Value::U16(value) => value.into(),
+        Value::U32(value) => value.into(),
+        Value::U64(value) => value.into(),
+        Value::I8(value) => value.into(),
+        Value::I16(value) => value.into(),
+        Value::I32(value) => value.into(),
+        Value::I64(value) => value.into(),
+        Value::F32(value) => float_to_json(value as f64),
+        Value::F64(value) => float_to_json(value),
+        Value::Bool(value) => value.into(),
+        Value::String(value) => value.into(),
+        Value::U8List(values) => values.into(),
+        Value::U16List(values) => values.into(),
+        Value::U32List(values) => values.into(),
+        Value::U64List(values) => values.into(),
+        Value::I8List(values) => values.into(),
+        Value::I16List(values) => values.into(),
+        Value::I32List(values) => values.into(),
+        Value::I64List(values) => values.into(),
+        Value::F32List(values) => values.into_iter().map(|value| float_to_json(value as f64)).collect(),
+        Value::F64List(values) => values.into_iter().map(float_to_json).collect(),
+        Value::Pointer(value) => value.into(),
+        Value::Entity(value) => value.into()
+    }
+}
+
+// JSON has no NaN or infinity
+fn float_to_json(value: f64) -> serde_json::Value {
+    Number::from_f64(value).map(serde_json::Value::Number).unwrap_or(serde_json::Value::Null)
+}
+
+const ENTITY_KEYS: [&str; 5] = ["name", "tags", "components", "pairs", "children"];
+
+// An entity of a JSON document with its ids looked up
+struct ResolvedEntity {
+    name: Option<String>,
+    ids: Vec<ecs_entity_t>,
+    components: Vec<(ecs_entity_t, Vec<(String, FieldType)>, Vec<Value>)>,
+    children: Vec<ResolvedEntity>
+}
+
+unsafe fn resolve_entity(value: &serde_json::Value) -> Result<ResolvedEntity, JsonError> {
+    let object = value.as_object().ok_or_else(|| JsonError::Malformed("entity is not an object".to_string()))?;
+    // A misspelled key would otherwise drop its contents without a word
+    if let Some(key) = object.keys().find(|key| !ENTITY_KEYS.contains(&key.as_str())) {
+        return Err(JsonError::UnknownKey(key.clone()));
+    }
+    let name = match object.get("name") {
+        Some(name) => Some(name.as_str().ok_or_else(|| JsonError::Malformed("name is not a string".to_string()))?.to_string()),
+        None => None
+    };
+
+    let mut ids = Vec::new();
+    for tag in array(object, "tags")? {
+        ids.push(resolve_path(tag)?);
+    }
+    for pair in array(object, "pairs")? {
+        match pair.as_array().map(|pair| pair.as_slice()) {
+            Some([first, second]) => ids.push(ecs_make_pair(resolve_path(first)?, resolve_path(second)?)),
+            _ => return Err(JsonError::Malformed("a pair is not an array of two names".to_string()))
+        }
+    }
+
+    let mut components = Vec::new();
+    if let Some(entries) = object.get("components") {
+        let entries = entries.as_object().ok_or_else(|| JsonError::Malformed("components is not an object".to_string()))?;
+        for (component_name, members) in entries.iter() {
+            components.push(resolve_component(component_name, members)?);
+        }
+    }
+
+    let mut children = Vec::new();
+    for child in array(object, "children")? {
+        children.push(resolve_entity(child)?);
+    }
+    Ok(ResolvedEntity { name, ids, components, children })
+}
+
+fn array<'a>(object: &'a Map<String, serde_json::Value>, key: &str) -> Result<&'a [serde_json::Value], JsonError> {
+    match object.get(key) {
+        Some(value) => value
+            .as_array()
+            .map(|values| values.as_slice())
+            .ok_or_else(|| JsonError::Malformed(format!("{} is not an array", key))),
+        None => Ok(&[])
+    }
+}
+
+unsafe fn resolve_path(value: &serde_json::Value) -> Result<ecs_entity_t, JsonError> {
+    let path = value.as_str().ok_or_else(|| JsonError::Malformed(format!("{} is not a name", value)))?;
+    lookup(path).ok_or_else(|| JsonError::UnknownEntity(path.to_string()))
+}
+
+unsafe fn resolve_component(component_name: &str, members: &serde_json::Value) -> Result<(ecs_entity_t, Vec<(String, FieldType)>, Vec<Value>), JsonError> {
+    let component = lookup(component_name).ok_or_else(|| JsonError::UnknownEntity(component_name.to_string()))?;
+    let layout = component_layout(component).ok_or_else(|| JsonError::UnknownEntity(component_name.to_string()))?;
+    let members = members.as_object().ok_or_else(|| JsonError::Malformed(format!("{} is not an object", component_name)))?;
+    let mut member_layout = Vec::new();
+    let mut values = Vec::new();
+    for (member, value) in members.iter() {
+        let field_type = layout
+            .iter()
+            .find(|layout_member| &layout_member.name == member)
+            .map(|layout_member| layout_member.field_type)
+            .ok_or_else(|| JsonError::UnknownMember { component: component_name.to_string(), member: member.clone() })?;
+        let value = json_to_value(value, field_type)
+            .ok_or_else(|| JsonError::InvalidValue { component: component_name.to_string(), member: member.clone() })?;
+        member_layout.push((member.clone(), field_type));
+        values.push(value);
+    }
+    Ok((component, member_layout, values))
+}
+
+fn json_to_value(value: &serde_json::Value, field_type: FieldType) -> Option<Value> {
+    Some(match field_type {
+        FieldType::U8 => Value::U8(u8::try_from(value.as_u64()?).ok()?),
+        FieldType::U16 => Value::U16(u16::try_from(value.as_u64()?).ok()?),
+        FieldType::U32 => Value::U32(u32::try_from(value.as_u64()?).ok()?),
+        FieldType::U64 => Value::U64(value.as_u64()?),
+        FieldType::I8 => Value::I8(i8::try_from(value.as_i64()?).ok()?),
+        FieldType::I16 => Value::I16(i16::try_from(value.as_i64()?).ok()?),
+        FieldType::I32 => Value::I32(i32::try_from(value.as_i64()?).ok()?),
+        FieldType::I64 => Value::I64(value.as_i64()?),
+        FieldType::F32 => Value::F32(value.as_f64()? as f32),
+        FieldType::F64 => Value::F64(value.as_f64()?),
+        FieldType::Bool => Value::Bool(value.as_bool()?),
+        FieldType::String => match value {
+            serde_json::Value::Null => Value::String(None),
+            value => Value::String(Some(value.as_str()?.to_string()))
+        },
+        FieldType::U8List => Value::U8List(list(value, FieldType::U8, |value| match value { Value::U8(value) => Some(value), _ => None })?),
+        FieldType::U16List => Value::U16List(list(value, FieldType::U16, |value| match value { Value::U16(value) => Some(value), _ => None })?),
+        FieldType::U32List => Value::U32List(list(value, FieldType::U32, |value| match value { Value::U32(value) => Some(value), _ => None })?),
+        FieldType::U64List => Value::U64List(list(value, FieldType::U64, |value| match value { Value::U64(value) => Some(value), _ => None })?),
+        FieldType::I8List => Value::I8List(list(value, FieldType::I8, |value| match value { Value::I8(value) => Some(value), _ => None })?),
+        FieldType::I16List => Value::I16List(list(value, FieldType::I16, |value| match value { Value::I16(value) => Some(value), _ => None })?),
+        FieldType::I32List => Value::I32List(list(value, FieldType::I32, |value| match value { Value::I32(value) => Some(value), _ => None })?),
+        FieldType::I64List => Value::I64List(list(value, FieldType::I64, |value| match value { Value::I64(value) => Some(value), _ => None })?),
+        FieldType::F32List => Value::F32List(list(value, FieldType::F32, |value| match value { Value::F32(value) => Some(value), _ => None })?),
+        FieldType::F64List => Value::F64List(list(value, FieldType::F64, |value| match value { Value::F64(value) => Some(value), _ => None })?),
+        FieldType::Entity => Value::Entity(value.as_u64()?),
+        // Host addresses can't come from a file
+        FieldType::Pointer => return None
+    })
+}
+
+fn list<T>(value: &serde_json::Value, element_type: FieldType, element: fn(Value) -> Option<T>) -> Option<Vec<T>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|value| json_to_value(value, element_type).and_then(element))
+        .collect()
+}
+
+unsafe fn create_entity(resolved: &ResolvedEntity, parent: Option<ecs_entity_t>) -> ecs_entity_t {
+    let entity = ecs_new(WORLD.0);
+    if let Some(parent) = parent {
+        ecs_add_id(WORLD.0, entity, ecs_make_pair(EcsChildOf, parent));
+    }
+    // Names are unique among siblings, so the name is set once the entity is in its parent
+    if let Some(name) = resolved.name.as_ref() {
+        let name = CString::new(name.as_str()).unwrap_or_default();
+        ecs_set_name(WORLD.0, entity, name.as_ptr());
+    }
+    for id in resolved.ids.iter() {
+        ecs_add_id(WORLD.0, entity, *id);
+    }
+    for (component, layout, values) in resolved.components.iter() {
+        set_values(entity, *component, *component, layout, values, &HashMap::new());
+    }
+    for child in resolved.children.iter() {
+        create_entity(child, Some(entity));
+    }
+    entity
+}
@@ -2,10 +2,24 @@
 #![allow(warnings)]
 
 pub mod bindings;
-use bindings::exports::toxoid::engine::ecs::{EcsEntityT, GuestIter, GuestObserver, ObserverDesc, Phases, PointerT, Relationship};
-use bindings::exports::toxoid::engine::ecs::{self, ComponentDesc, EntityDesc, Guest, GuestCallback, GuestComponent, GuestComponentType, GuestEntity, GuestQuery, GuestSystem, GuestPhase, GuestPipeline, QueryDesc, SystemDesc, PipelineDesc, Event, SortingDesc};
-pub use toxoid_flecs::bindings::{ecs_add_id, ecs_entity_desc_t, ecs_entity_init, ecs_fini, ecs_get_mut_id, ecs_init, ecs_iter_t, ecs_lookup, ecs_make_pair, ecs_member_t, ecs_progress, ecs_query_desc_t, ecs_query_init, ecs_query_iter, ecs_query_next, ecs_iter_next, ecs_query_t, ecs_struct_desc_t, ecs_struct_init, ecs_system_desc_t, ecs_system_init, ecs_system_t, ecs_world_t, EcsDependsOn, EcsOnUpdate, ecs_set_rate, ecs_get_id, ecs_remove_id};
-use toxoid_flecs::{ecs_children, ecs_children_next, ecs_delete, ecs_enable, ecs_ensure_id, ecs_field_size, ecs_field_w_size, ecs_get_name, ecs_get_parent, ecs_get_path_w_sep, ecs_get_target, ecs_has_id, ecs_modified_id, ecs_new_w_id, ecs_observer_desc_t, ecs_observer_init, ecs_observer_t, ecs_pipeline_desc_t, ecs_pipeline_init, ecs_set_name, EcsChildOf, EcsInherit, EcsIsA, EcsOnInstantiate, EcsOnLoad, EcsOnStart, EcsOnStore, EcsOnValidate, EcsPhase, EcsPostLoad, EcsPostUpdate, EcsPreStore, EcsPreUpdate, EcsPrefab};
+pub mod engine;
+pub mod log;
+pub mod random;
+pub mod worlds;
+pub mod snapshot;
+pub mod json;
+pub mod changes;
+pub mod commands;
+#[cfg(feature = "rest")]
+pub mod rest;
+#[cfg(test)]
+mod tests;
+use bindings::exports::toxoid::engine::ecs::{EcsEntityT, GuestIter, GuestObserver, MemberType, MonitorKind, ObserverDesc, Phases, PointerT, Relationship};
+use bindings::exports::toxoid::engine::ecs::{self, ComponentDesc, ComponentValue, EntityDesc, EnumDesc, Guest, GuestCallback, GuestComponent, GuestComponentType, GuestEntity, GuestQuery, GuestSystem, GuestPhase, GuestPipeline, QueryDesc, SystemDesc, PipelineDesc, Event, SortingDesc};
+pub use toxoid_flecs::bindings::{ecs_add_id, ecs_entity_desc_t, ecs_entity_init, ecs_fini, ecs_get_mut_id, ecs_init, ecs_iter_t, ecs_lookup, ecs_make_pair, ecs_member_t, ecs_progress, ecs_query_desc_t, ecs_query_init, ecs_query_iter, ecs_query_next, ecs_iter_next, ecs_query_t, ecs_struct_desc_t, ecs_struct_init, ecs_system_desc_t, ecs_system_init, ecs_system_t, ecs_world_t, EcsDependsOn, EcsOnUpdate, ecs_set_rate, ecs_get_id, ecs_remove_id, EcsStruct};
+use toxoid_flecs::bindings::ecs_query_fini;
+use toxoid_flecs::{ecs_children, ecs_children_next, ecs_delete, ecs_enable, ecs_ensure_id, ecs_field_id, ecs_field_is_self, ecs_field_is_set, ecs_field_size, ecs_field_src, ecs_field_w_size, ecs_get_typeid, ecs_get_name, ecs_get_parent, ecs_get_path_w_sep, ecs_get_target, ecs_has_id, ecs_modified_id, ecs_new_w_id, ecs_observer_desc_t, ecs_observer_init, ecs_observer_t, ecs_pipeline_desc_t, ecs_pipeline_init, ecs_set_name, ecs_set_id, ecs_get_type_info, EcsChildOf, EcsConstant, EcsMember, EcsInherit, EcsIsA, EcsOnInstantiate, EcsOnLoad, EcsOnStart, EcsOnStore, EcsOnValidate, EcsPhase, EcsPostLoad, EcsPostUpdate, EcsPreStore, EcsPreUpdate, EcsPrefab};
+use log::{LogLevel, LogRecord};
 use std::ffi::CStr;
 use std::{borrow::BorrowMut, mem::MaybeUninit};
 use core::ffi::c_void;
@@ -13,7 +27,7 @@ use core::ffi::c_char;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, RwLock};
 type ecs_entity_t = u64;
 
 pub struct ToxoidApi;
@@ -26,7 +40,11 @@ pub struct Component {
     pub ptr: *const c_void,
     pub field_offsets: Vec<u8>,
     pub entity_added: ecs_entity_t,
-    pub component_type_id: ecs_entity_t
+    pub component_type_id: ecs_entity_t,
+    // Component the data belongs to, the type of the outer component for a nested struct member
+    pub owner_type_id: ecs_entity_t,
+    // View of a field the query only reads, writes through it fail
+    pub read_only: bool
 }
 
 pub struct Entity { 
@@ -35,15 +53,21 @@ pub struct Entity {
 
 pub struct Query {
     pub desc: RefCell<ecs_query_desc_t>,
-    pub query: RefCell<ecs_query_t>,
-    pub iter: RefCell<ecs_iter_t>
+    // Null until built, finished when the query is dropped
+    pub query: RefCell<*mut ecs_query_t>,
+    pub iter: RefCell<ecs_iter_t>,
+    // Fields tracked for change detection, see changes.rs
+    pub monitors: RefCell<Vec<(i8, MonitorKind)>>,
+    // Number of the build in BUILT_QUERIES
+    build: std::cell::Cell<u64>
 }
 
 pub struct System {
     pub desc: RefCell<ecs_system_desc_t>,
     pub entity: RefCell<ecs_entity_t>,
     pub system: RefCell<ecs_system_t>,
-    pub callback: RefCell<Callback>
+    pub callback: RefCell<Callback>,
+    pub monitors: RefCell<Vec<(i8, MonitorKind)>>
 }
 
 pub struct Observer {
@@ -76,7 +100,8 @@ unsafe impl Send for EcsWorldPtr {}
 unsafe impl Sync for EcsWorldPtr {}
 
 #[repr(u8)]
-enum FieldType {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
     U8,
     U16,
     U32,
@@ -99,17 +124,53 @@ enum FieldType {
     I64List,
     F32List,
     F64List,
-    Pointer
+    Pointer,
+    // Entity ID, after `MEMBER_TYPE_NESTED`
+    Entity = 24
 }
 
+// The active world, host calls operate on this one. Switched with `worlds::set_active_world`.
 pub static mut WORLD: Lazy<EcsWorldPtr> = Lazy::new(|| 
     EcsWorldPtr(unsafe { 
         let world = ecs_init();
-        // toxoid_flecs::bindings::ecs_set_threads(world, 4);
+        commands::init(world);
+        // Single threaded until `toxoid_set_threads` is called
         world
     })
 );
 
+thread_local! {
+    // Stage of the system running on this thread, Flecs worker threads can't
+    // write to the world directly and queue their commands on a stage instead
+    static STAGE: std::cell::Cell<*mut ecs_world_t> = const { std::cell::Cell::new(std::ptr::null_mut()) };
+}
+
+// World or stage that entity and component operations go to on this thread
+pub fn stage() -> *mut ecs_world_t {
+    let stage = STAGE.with(|stage| stage.get());
+    if stage.is_null() {
+        unsafe { WORLD.0 }
+    } else {
+        stage
+    }
+}
+
+// Set the stage of this thread for the duration of a callback, returns the previous one to restore.
+// Null goes back to the active world.
+pub fn set_stage(stage: *mut ecs_world_t) -> *mut ecs_world_t {
+    STAGE.with(|current| current.replace(stage))
+}
+
+// Run systems marked as multithreaded on this many Flecs worker threads, 1 turns them off.
+// Guest systems always run on the main thread, since each guest has a single store.
+// Emscripten builds don't have threads and stay single threaded.
+pub fn toxoid_set_threads(threads: u32) {
+    #[cfg(not(target_os = "emscripten"))]
+    unsafe {
+        toxoid_flecs::bindings::ecs_set_threads(WORLD.0, threads.max(1) as i32);
+    }
+}
+
 // Create a wrapper type for the pointer that implements Send
 #[derive(Hash, Eq, PartialEq, Clone, Copy)]
 struct ThreadSafePtr(*const c_void);
@@ -120,17 +181,18 @@ unsafe impl Sync for ThreadSafePtr {}
 static ARRAY_LENGTH_CACHE: Lazy<Mutex<HashMap<(ThreadSafePtr, u32), usize>>> = 
     Lazy::new(|| Mutex::new(HashMap::new()));
 
-// Progress the world - game loop tick
+// Progress the default world - game loop tick.
+// Other worlds are progressed with `worlds::progress_world` before this, which ends the frame.
 pub fn toxoid_progress(delta_time: f32) -> bool {
-    unsafe { ecs_progress(WORLD.0, delta_time) }
+    let running = worlds::progress_world(worlds::DEFAULT_WORLD, delta_time);
+    engine::end_frame();
+    engine::run_frame_tasks();
+    running
 }
 
-// Reset the world - delete all entities
+// Reset the active world - delete all entities
 pub fn toxoid_reset() {
-    unsafe {
-        ecs_fini(WORLD.0);
-        WORLD = Lazy::new(|| EcsWorldPtr(unsafe { ecs_init() }));
-    }
+    worlds::reset_active_world();
 }
 
 fn map_event(event: Event) -> ecs_entity_t {
@@ -162,15 +224,49 @@ unsafe fn map_member_type(member_type: u8) -> ecs_entity_t {
         9 => toxoid_flecs::bindings::FLECS_IDecs_f64_tID_,
         10 => toxoid_flecs::bindings::FLECS_IDecs_bool_tID_,
         11 => toxoid_flecs::bindings::FLECS_IDecs_string_tID_,
+        24 => toxoid_flecs::bindings::FLECS_IDecs_entity_tID_,
         _ => toxoid_flecs::bindings::FLECS_IDecs_uptr_tID_,
     }
 }
 
+// Member type code of a member whose type is given in `ComponentDesc::member_type_ids`,
+// a nested struct, enum or bitmask
+pub const MEMBER_TYPE_NESTED: u8 = 23;
+
+// Members that fit in a Flecs struct descriptor, the rest are added to the struct afterwards
+const STRUCT_DESC_MEMBERS: usize = 32;
+
+// Member types of the registered component types by name. Flecs only knows lists and pointers
+// as uptr members, this keeps their element types for snapshots. Enums are accessed as i32,
+// bitmasks as u32 and fixed-size arrays through the list type of their elements.
+static COMPONENT_FIELDS: Lazy<Mutex<HashMap<String, Vec<FieldType>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+unsafe fn member_field_type(code: u8, type_id: ecs_entity_t, count: u32) -> FieldType {
+    if code == MEMBER_TYPE_NESTED {
+        if ecs_has_id(WORLD.0, type_id, toxoid_flecs::bindings::FLECS_IDEcsEnumID_) {
+            return FieldType::I32;
+        }
+        if ecs_has_id(WORLD.0, type_id, toxoid_flecs::bindings::FLECS_IDEcsBitmaskID_) {
+            return FieldType::U32;
+        }
+        return FieldType::Pointer;
+    }
+    let field_type = FieldType::from_code(code);
+    if count > 1 {
+        return field_type.list().unwrap_or(FieldType::Pointer);
+    }
+    field_type
+}
+
 impl GuestComponentType for ComponentType {
     fn new(desc: ComponentDesc) -> ComponentType {
+        let member_type_id = |index: usize| desc.member_type_ids.get(index).copied().unwrap_or(0);
+        let member_count = |index: usize| desc.member_counts.get(index).copied().unwrap_or(0);
         unsafe {
             let is_tag = desc.member_names.len() == 0 || desc.member_types.len() == 0;
             if is_tag {
+                COMPONENT_FIELDS.lock().unwrap().insert(desc.name.clone(), vec![]);
                 let mut ent_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
                 ent_desc.name = c_string(&desc.name);
                 let mut tag_entity = ecs_entity_init(WORLD.0, &ent_desc);
@@ -186,6 +282,13 @@ impl GuestComponentType for ComponentType {
                     id: tag_entity
                 }
             } else {
+                let field_types = desc.member_types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, code)| member_field_type(*code, member_type_id(index), member_count(index)))
+                    .collect();
+                COMPONENT_FIELDS.lock().unwrap().insert(desc.name.clone(), field_types);
+
                 // Create component entity
                 let mut ent_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
                 ent_desc.name = c_string(&desc.name);
@@ -203,20 +306,46 @@ impl GuestComponentType for ComponentType {
                 let mut struct_desc: ecs_struct_desc_t = MaybeUninit::zeroed().assume_init();
                 struct_desc.entity = component_entity;
                 let member: ecs_member_t = MaybeUninit::zeroed().assume_init();
-                struct_desc.members = [member; 32usize];
+                struct_desc.members = [member; STRUCT_DESC_MEMBERS];
                 
                 // Iterate through member names
+                let mut members = Vec::with_capacity(desc.member_names.len());
                 for (index, member_name) in desc.member_names.iter().enumerate() {
                     // Create component member
                     let mut member: ecs_member_t = MaybeUninit::zeroed().assume_init();
-                    member.name = member_name.as_ptr() as *const i8;
-                    member.type_ = map_member_type(desc.member_types[index]);
-                    struct_desc.members[index] = member;
+                    member.name = c_string(member_name);
+                    member.type_ = if desc.member_types[index] == MEMBER_TYPE_NESTED {
+                        member_type_id(index)
+                    } else {
+                        map_member_type(desc.member_types[index])
+                    };
+                    // Lists are stored behind a pointer, fixed-size arrays inline
+                    member.count = member_count(index) as i32;
+                    members.push(member);
+                }
+                for (index, member) in members.iter().take(STRUCT_DESC_MEMBERS).enumerate() {
+                    struct_desc.members[index] = *member;
                 }
 
                 // Initialize component
                 if lookup == 0 {
                     ecs_struct_init(WORLD.0, &struct_desc);
+                    // Members past the descriptor are added like Flecs adds the others,
+                    // as child entities of the struct with an EcsMember component
+                    for member in members.iter().skip(STRUCT_DESC_MEMBERS) {
+                        let mut member_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
+                        member_desc.name = member.name;
+                        member_desc.parent = component_entity;
+                        let member_entity = ecs_entity_init(WORLD.0, &member_desc);
+                        let value = EcsMember { type_: member.type_, count: member.count, unit: 0, offset: 0, use_offset: false };
+                        ecs_set_id(
+                            WORLD.0,
+                            member_entity,
+                            toxoid_flecs::bindings::FLECS_IDEcsMemberID_,
+                            std::mem::size_of::<EcsMember>(),
+                            &value as *const EcsMember as *const c_void
+                        );
+                    }
                 }
 
                 // Return component 
@@ -240,578 +369,651 @@ impl Component {
     // But it's a better than leaking memory with boxed
     // allocations on the heap and managing their lifetimes
     // manually.
-    pub fn from_ptr_host(ptr: u64) -> Component {
+    pub fn from_ptr_host(ptr: u64, component_type_id: ecs_entity_t) -> Component {
         Component {
             ptr: ptr as *const c_void,
             field_offsets: vec![],
             entity_added: 0,
-            component_type_id: 0
+            component_type_id,
+            owner_type_id: component_type_id,
+            read_only: false
         }
     }
-}
 
-impl GuestComponent for Component {
-    // This is a component instance so it will need a the entity it belongs to and the component type
-    // This is required for observers / events to work
-    fn new(ptr: u64, entity_added: ecs_entity_t, component_type_id: ecs_entity_t) -> Component {
-        Component { 
-            ptr: ptr as *const c_void, 
-            field_offsets: vec![], 
-            entity_added, 
-            component_type_id
+    // View of a nested struct member, writes through it mark the outer component as modified
+    pub fn member_component(&self, member: u32, index: u32) -> Result<Component, MemberError> {
+        if self.ptr.is_null() {
+            return Err(MemberError::NullComponent);
+        }
+        let layout = member_layout(self.component_type_id, member)?;
+        if !layout.is_struct {
+            return Err(MemberError::NotStruct { component: self.component_type_id, member });
         }
+        if index >= layout.count {
+            return Err(MemberError::IndexOutOfRange { component: self.component_type_id, member, index, count: layout.count });
+        }
+        Ok(Component {
+            ptr: unsafe { self.ptr.add((layout.offset + index * layout.size) as usize) },
+            field_offsets: vec![],
+            entity_added: self.entity_added,
+            component_type_id: layout.type_,
+            owner_type_id: self.owner_type_id,
+            read_only: self.read_only
+        })
     }
 
-    fn from_ptr(ptr: u64) -> PointerT {
-        unimplemented!("Use from_ptr_host instead");
-        // unsafe {
-        //     Box::into_raw(Box::new(Component {
-        //         ptr: ptr as *const c_void,
-        //         field_offsets: vec![],
-        //         entity_added: 0,
-        //         component_type_id: 0
-        //     })) as PointerT
-        // }
+    fn modified(&self) {
+        // Views without an entity, like detached nested members, have nothing to mark
+        if self.entity_added == 0 {
+            return;
+        }
+        unsafe { ecs_modified_id(stage(), self.entity_added, self.owner_type_id) };
     }
 
-    fn set_member_u8(&self, offset: u32, value: u8) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u8;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
+    // Pointer to a member, checking the index and the accessed type against the member metadata
+    fn member_ptr(&self, member: u32, field_type: FieldType, access: MemberAccess) -> Result<(*mut u8, MemberLayout), MemberError> {
+        if self.ptr.is_null() {
+            return Err(MemberError::NullComponent);
         }
+        let layout = checked_member(self.component_type_id, member, field_type, access)?;
+        Ok((unsafe { self.ptr.add(layout.offset as usize) as *mut u8 }, layout))
     }
 
-    fn get_member_u8(&self, offset: u32) -> u8 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u8;
-            let member_value: u8 = *member_ptr;
-            member_value
+    fn writable_ptr(&self, member: u32, field_type: FieldType, access: MemberAccess) -> Result<(*mut u8, MemberLayout), MemberError> {
+        if self.read_only {
+            return Err(MemberError::ReadOnly(self.owner_type_id));
         }
+        self.member_ptr(member, field_type, access)
     }
 
-    fn set_member_u16(&self, offset: u32, value: u16) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u16;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    // Byte offset of a member, checked like an access from the host
+    pub fn member_offset(&self, member: u32, field_type: FieldType) -> Result<u32, MemberError> {
+        self.member_ptr(member, field_type, MemberAccess::Host).map(|(_, layout)| layout.offset)
     }
 
-    fn get_member_u16(&self, offset: u32) -> u16 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u16;
-            let member_value: u16 = *member_ptr;
-            member_value
-        }
+    // Value of a number, bool (as u8) or pointer (as usize) member
+    pub fn read_member<T: Copy>(&self, member: u32, field_type: FieldType, access: MemberAccess) -> Result<T, MemberError> {
+        let (member_ptr, _) = self.member_ptr(member, field_type, access)?;
+        Ok(unsafe { std::ptr::read_unaligned(member_ptr as *const T) })
     }
 
-    fn set_member_u32(&self, offset: u32, value: u32) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u32;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    pub fn write_member<T: Copy>(&self, member: u32, field_type: FieldType, access: MemberAccess, value: T) -> Result<(), MemberError> {
+        let (member_ptr, _) = self.writable_ptr(member, field_type, access)?;
+        unsafe { std::ptr::write_unaligned(member_ptr as *mut T, value) };
+        self.modified();
+        Ok(())
     }
 
-    fn get_member_u32(&self, offset: u32) -> u32 {
+    pub fn read_string(&self, member: u32) -> Result<String, MemberError> {
+        let (member_ptr, _) = self.member_ptr(member, FieldType::String, MemberAccess::Guest)?;
         unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u32;
-            let member_value: u32 = *member_ptr;
-            member_value
+            let string = *(member_ptr as *const *const c_char);
+            if string.is_null() {
+                return Ok(String::new());
+            }
+            Ok(CStr::from_ptr(string).to_string_lossy().into_owned())
         }
     }
 
-    fn set_member_u64(&self, offset: u32, value: u64) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u64;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    pub fn write_string(&self, member: u32, value: String) -> Result<(), MemberError> {
+        let (member_ptr, _) = self.writable_ptr(member, FieldType::String, MemberAccess::Guest)?;
+        let c_string = std::ffi::CString::new(value).map_err(|_| MemberError::InteriorNul)?;
+        unsafe { *(member_ptr as *mut *const c_char) = c_string.into_raw() };
+        self.modified();
+        Ok(())
     }
 
-    fn get_member_u64(&self, offset: u32) -> u64 {
+    // Elements of a list member, or of a fixed-size array member stored inline
+    pub fn read_list<T: Copy>(&self, member: u32, list_type: FieldType) -> Result<Vec<T>, MemberError> {
+        let (member_ptr, layout) = self.member_ptr(member, list_type, MemberAccess::Guest)?;
         unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut u64;
-            let member_value: u64 = *member_ptr;
-            member_value
+            if layout.count > 1 {
+                return Ok(std::slice::from_raw_parts(member_ptr as *const T, layout.count as usize).to_vec());
+            }
+            let elements = *(member_ptr as *const *const T);
+            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
+                .get(&(ThreadSafePtr(elements as *const c_void), layout.offset))
+                .copied()
+                .unwrap_or(0);
+            if elements.is_null() || length == 0 {
+                return Ok(Vec::new());
+            }
+            Ok(std::slice::from_raw_parts(elements, length).to_vec())
         }
     }
 
-    fn set_member_i8(&self, offset: u32, value: i8) {
+    // Fixed-size arrays cut off longer lists and zero the elements past the end of shorter ones
+    pub fn write_list<T: Copy>(&self, member: u32, list_type: FieldType, value: Vec<T>) -> Result<(), MemberError> {
+        let (member_ptr, layout) = self.writable_ptr(member, list_type, MemberAccess::Guest)?;
         unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i8;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
+            if layout.count > 1 {
+                let elements = member_ptr as *mut T;
+                let length = value.len().min(layout.count as usize);
+                std::ptr::write_bytes(elements, 0, layout.count as usize);
+                std::ptr::copy_nonoverlapping(value.as_ptr(), elements, length);
+            } else if value.is_empty() {
+                // Nothing to allocate, an empty list is a null pointer
+                *(member_ptr as *mut *mut T) = std::ptr::null_mut();
+            } else {
+                let layout_array = std::alloc::Layout::array::<T>(value.len()).map_err(|_| MemberError::LengthMismatch { expected: 0, found: value.len() })?;
+                let elements = std::alloc::alloc(layout_array) as *mut T;
+                std::ptr::copy_nonoverlapping(value.as_ptr(), elements, value.len());
+                *(member_ptr as *mut *mut T) = elements;
+                // Keyed by the list's pointer, the member itself only stores that
+                ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(elements as *const c_void), layout.offset), value.len());
+            }
         }
+        self.modified();
+        Ok(())
     }
+}
 
-    fn get_member_i8(&self, offset: u32) -> i8 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i8;
-            let member_value: i8 = *member_ptr;
-            member_value
-        }
+// Native member access can't return errors through the component interface,
+// they are logged and a bad read gives the default value
+fn reported<T: Default>(result: Result<T, MemberError>) -> T {
+    result.unwrap_or_else(|e| {
+        log::log(LogRecord::new(LogLevel::Error, "component", &e.to_string()));
+        T::default()
+    })
+}
+
+// Who accesses a member. Pointer and list members hold host addresses, guests only get them
+// through the type they were declared with, never as a plain u64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberAccess {
+    Host,
+    Guest
+}
+
+// Metadata of a component member, read from Flecs once per component type
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MemberLayout {
+    pub type_: ecs_entity_t,
+    pub offset: u32,
+    // Elements of a fixed-size array, 1 for other members
+    pub count: u32,
+    // Size of the member type, of one element for arrays
+    pub size: u32,
+    // Type the member was registered with in `ComponentType::new`, None for other structs
+    pub field_type: Option<FieldType>,
+    pub is_enum: bool,
+    pub is_bitmask: bool,
+    pub is_struct: bool,
+}
+
+// Member layouts by world and component type. A world's entry is dropped when it is finished,
+// since a new world can get the same address and reuse the IDs.
+static MEMBER_LAYOUTS: Lazy<RwLock<HashMap<(usize, ecs_entity_t), Arc<[MemberLayout]>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub(crate) fn forget_member_layouts(world: *mut ecs_world_t) {
+    MEMBER_LAYOUTS.write().unwrap().retain(|(layout_world, _), _| *layout_world != world as usize);
+    snapshot::forget_component_layouts(world);
+}
+
+fn member_layouts(component_type: ecs_entity_t) -> Result<Arc<[MemberLayout]>, MemberError> {
+    let key = (unsafe { WORLD.0 } as usize, component_type);
+    if let Some(layouts) = MEMBER_LAYOUTS.read().unwrap().get(&key) {
+        return Ok(layouts.clone());
     }
+    let layouts: Arc<[MemberLayout]> = unsafe {
+        let members = struct_members(component_type)?;
+        let field_types = COMPONENT_FIELDS.lock().unwrap().get(&snapshot::entity_path(component_type)).cloned();
+        members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| MemberLayout {
+                type_: member.type_,
+                offset: member.offset as u32,
+                count: member.count.max(1) as u32,
+                size: (*ecs_get_type_info(WORLD.0, member.type_)).size as u32,
+                field_type: field_types.as_ref().and_then(|field_types| field_types.get(index).copied()),
+                is_enum: ecs_has_id(WORLD.0, member.type_, toxoid_flecs::bindings::FLECS_IDEcsEnumID_),
+                is_bitmask: ecs_has_id(WORLD.0, member.type_, toxoid_flecs::bindings::FLECS_IDEcsBitmaskID_),
+                is_struct: ecs_has_id(WORLD.0, member.type_, toxoid_flecs::bindings::FLECS_IDEcsStructID_),
+            })
+            .collect()
+    };
+    MEMBER_LAYOUTS.write().unwrap().insert(key, layouts.clone());
+    Ok(layouts)
+}
 
-    fn set_member_i16(&self, offset: u32, value: i16) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i16;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+fn member_layout(component_type: ecs_entity_t, member: u32) -> Result<MemberLayout, MemberError> {
+    let layouts = member_layouts(component_type)?;
+    layouts.get(member as usize)
+        .copied()
+        .ok_or(MemberError::OutOfRange { component: component_type, member, count: layouts.len() as u32 })
+}
+
+// Members of a component type registered through `ComponentType::new`
+unsafe fn struct_members(component_type: ecs_entity_t) -> Result<&'static [ecs_member_t], MemberError> {
+    let struct_ptr = ecs_get_id(WORLD.0, component_type, toxoid_flecs::bindings::FLECS_IDEcsStructID_) as *const EcsStruct;
+    if struct_ptr.is_null() {
+        return Err(MemberError::NoMetadata(component_type));
+    }
+    let members = &(*struct_ptr).members;
+    if members.count == 0 {
+        return Ok(&[]);
     }
+    Ok(std::slice::from_raw_parts(members.array as *const ecs_member_t, members.count as usize))
+}
 
-    fn get_member_i16(&self, offset: u32) -> i16 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i16;
-            let member_value: i16 = *member_ptr;
-            member_value
+// Layout of a member, checking it can be accessed as `field_type`
+fn checked_member(component_type: ecs_entity_t, member: u32, field_type: FieldType, access: MemberAccess) -> Result<MemberLayout, MemberError> {
+    let layout = member_layout(component_type, member)?;
+    let wrong_type = MemberError::WrongType { component: component_type, member, field_type };
+    let uptr = unsafe { toxoid_flecs::bindings::FLECS_IDecs_uptr_tID_ };
+    // Lists and pointers are both uptr members, guests only access them as declared
+    if access == MemberAccess::Guest && layout.type_ == uptr && layout.field_type != Some(field_type) {
+        return Err(wrong_type);
+    }
+    // Fixed-size arrays are accessed as lists of their elements
+    let is_array = layout.count > 1 && field_type.element()
+        .map_or(false, |element| layout.type_ == unsafe { map_member_type(element as u8) });
+    if is_array {
+        return Ok(layout);
+    }
+    // The host reads pointers registered as uptr as u64, entities are IDs and read as u64 by anyone
+    let is_u64_alias = field_type == FieldType::U64 && (
+        (access == MemberAccess::Host && layout.type_ == uptr) ||
+        layout.type_ == unsafe { toxoid_flecs::bindings::FLECS_IDecs_entity_tID_ }
+    );
+    // Enums are stored as i32 and bitmasks as u32
+    let is_enum = field_type == FieldType::I32 && layout.is_enum;
+    let is_bitmask = field_type == FieldType::U32 && layout.is_bitmask;
+    let expected = unsafe { map_member_type(field_type as u8) };
+    if layout.count > 1 || (layout.type_ != expected && !is_u64_alias && !is_enum && !is_bitmask) {
+        return Err(wrong_type);
+    }
+    Ok(layout)
+}
+
+impl FieldType {
+    // Member type codes of `ComponentDesc::member_types`, unknown codes are pointers like in Flecs
+    pub fn from_code(code: u8) -> FieldType {
+        match code {
+            0 => FieldType::U8,
+            1 => FieldType::U16,
+            2 => FieldType::U32,
+            3 => FieldType::U64,
+            4 => FieldType::I8,
+            5 => FieldType::I16,
+            6 => FieldType::I32,
+            7 => FieldType::I64,
+            8 => FieldType::F32,
+            9 => FieldType::F64,
+            10 => FieldType::Bool,
+            11 => FieldType::String,
+            12 => FieldType::U8List,
+            13 => FieldType::U16List,
+            14 => FieldType::U32List,
+            15 => FieldType::U64List,
+            16 => FieldType::I8List,
+            17 => FieldType::I16List,
+            18 => FieldType::I32List,
+            19 => FieldType::I64List,
+            20 => FieldType::F32List,
+            21 => FieldType::F64List,
+            24 => FieldType::Entity,
+            _ => FieldType::Pointer
+        }
+    }
+
+    // `MemberType` by its position in the WIT enum, which also numbers the runtime's copy of it.
+    // `list-t` comes before the typed lists and is a pointer.
+    pub fn from_member_type(index: u8) -> FieldType {
+        match index {
+            0..=11 => FieldType::from_code(index),
+            13..=22 => FieldType::from_code(index - 1),
+            _ => FieldType::Pointer
+        }
+    }
+
+    // List of elements of this type, None for strings, lists and pointers
+    pub fn list(&self) -> Option<FieldType> {
+        match self {
+            FieldType::U8 => Some(FieldType::U8List),
+            FieldType::U16 => Some(FieldType::U16List),
+            FieldType::U32 => Some(FieldType::U32List),
+            FieldType::U64 => Some(FieldType::U64List),
+            FieldType::I8 => Some(FieldType::I8List),
+            FieldType::I16 => Some(FieldType::I16List),
+            FieldType::I32 => Some(FieldType::I32List),
+            FieldType::I64 => Some(FieldType::I64List),
+            FieldType::F32 => Some(FieldType::F32List),
+            FieldType::F64 => Some(FieldType::F64List),
+            _ => None
+        }
+    }
+
+    // Element type of a list type
+    pub fn element(&self) -> Option<FieldType> {
+        match self {
+            FieldType::U8List => Some(FieldType::U8),
+            FieldType::U16List => Some(FieldType::U16),
+            FieldType::U32List => Some(FieldType::U32),
+            FieldType::U64List => Some(FieldType::U64),
+            FieldType::I8List => Some(FieldType::I8),
+            FieldType::I16List => Some(FieldType::I16),
+            FieldType::I32List => Some(FieldType::I32),
+            FieldType::I64List => Some(FieldType::I64),
+            FieldType::F32List => Some(FieldType::F32),
+            FieldType::F64List => Some(FieldType::F64),
+            _ => None
+        }
+    }
+
+    // Size of a member of this type that can be copied by value, None for strings, lists and pointers
+    pub fn plain_size(&self) -> Option<usize> {
+        match self {
+            FieldType::U8 | FieldType::I8 | FieldType::Bool => Some(1),
+            FieldType::U16 | FieldType::I16 => Some(2),
+            FieldType::U32 | FieldType::I32 | FieldType::F32 => Some(4),
+            FieldType::U64 | FieldType::I64 | FieldType::F64 | FieldType::Entity => Some(8),
+            _ => None
         }
     }
+}
 
-    fn set_member_i32(&self, offset: u32, value: i32) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i32;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+impl From<MemberType> for FieldType {
+    fn from(member_type: MemberType) -> FieldType {
+        FieldType::from_member_type(member_type as u8)
     }
+}
 
-    fn get_member_i32(&self, offset: u32) -> i32 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i32;
-            let member_value: i32 = *member_ptr;
-            member_value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberError {
+    // The component has no data, e.g. a tag
+    NullComponent,
+    // The component type was not registered with members
+    NoMetadata(ecs_entity_t),
+    OutOfRange { component: ecs_entity_t, member: u32, count: u32 },
+    WrongType { component: ecs_entity_t, member: u32, field_type: FieldType },
+    // The member isn't a nested struct
+    NotStruct { component: ecs_entity_t, member: u32 },
+    IndexOutOfRange { component: ecs_entity_t, member: u32, index: u32, count: u32 },
+    // Strings, lists and pointers point into host memory and can't be copied in bulk
+    NotPlainData(ecs_entity_t),
+    // Written data doesn't match the size of the column
+    LengthMismatch { expected: usize, found: usize },
+    // Strings are stored NUL-terminated
+    InteriorNul,
+    // Component of a field the query only reads
+    ReadOnly(ecs_entity_t)
+}
+
+impl std::fmt::Display for MemberError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemberError::NullComponent => write!(f, "Component has no data"),
+            MemberError::NoMetadata(component) => write!(f, "Component type {} has no member metadata", component),
+            MemberError::OutOfRange { component, member, count } => write!(f, "Member {} is out of range for component type {} with {} members", member, component, count),
+            MemberError::WrongType { component, member, field_type } => write!(f, "Member {} of component type {} is not of type {:?}", member, component, field_type),
+            MemberError::NotStruct { component, member } => write!(f, "Member {} of component type {} is not a struct", member, component),
+            MemberError::IndexOutOfRange { component, member, index, count } => write!(f, "Element {} is out of range for member {} of component type {} with {} elements", index, member, component, count),
+            MemberError::NotPlainData(component) => write!(f, "Component type {} has members that can't be copied by value", component),
+            MemberError::LengthMismatch { expected, found } => write!(f, "Expected {} bytes of column data, got {}", expected, found),
+            MemberError::InteriorNul => write!(f, "String contains a NUL byte"),
+            MemberError::ReadOnly(component) => write!(f, "Component type {} is read-only in this query", component)
         }
     }
+}
 
-    fn set_member_i64(&self, offset: u32, value: i64) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i64;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
+impl std::error::Error for MemberError {}
+
+impl GuestComponent for Component {
+    // This is a component instance so it will need a the entity it belongs to and the component type
+    // This is required for observers / events to work
+    fn new(ptr: u64, entity_added: ecs_entity_t, component_type_id: ecs_entity_t) -> Component {
+        Component { 
+            ptr: ptr as *const c_void, 
+            field_offsets: vec![], 
+            entity_added, 
+            component_type_id,
+            owner_type_id: component_type_id,
+            read_only: false
         }
     }
 
-    fn get_member_i64(&self, offset: u32) -> i64 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut i64;
-            let member_value: i64 = *member_ptr;
-            member_value
-        }
-    }   
+    fn from_ptr(ptr: u64, component_type: ecs_entity_t) -> PointerT {
+        Box::into_raw(Box::new(Component::from_ptr_host(ptr, component_type))) as PointerT
+    }
 
-    fn set_member_f32(&self, offset: u32, value: f32) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut f32;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn set_member_u8(&self, member: u32, value: u8) {
+        reported(self.write_member(member, FieldType::U8, MemberAccess::Host, value))
     }
 
-    fn get_member_f32(&self, offset: u32) -> f32 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut f32;
-            let member_value: f32 = *member_ptr;
-            member_value
-        }
+    fn get_member_u8(&self, member: u32) -> u8 {
+        reported(self.read_member(member, FieldType::U8, MemberAccess::Host))
     }
 
-    fn set_member_f64(&self, offset: u32, value: f64) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut f64;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn set_member_u16(&self, member: u32, value: u16) {
+        reported(self.write_member(member, FieldType::U16, MemberAccess::Host, value))
     }
 
-    fn get_member_f64(&self, offset: u32) -> f64 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut f64;
-            let member_value: f64 = *member_ptr;
-            member_value
-        }
+    fn get_member_u16(&self, member: u32) -> u16 {
+        reported(self.read_member(member, FieldType::U16, MemberAccess::Host))
     }
 
-    fn set_member_bool(&self, offset: u32, value: bool) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut bool;
-            *member_ptr = value;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn set_member_u32(&self, member: u32, value: u32) {
+        reported(self.write_member(member, FieldType::U32, MemberAccess::Host, value))
     }
 
-    fn get_member_bool(&self, offset: u32) -> bool {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut bool;
-            let member_value: bool = *member_ptr;
-            member_value
-        }
+    fn get_member_u32(&self, member: u32) -> u32 {
+        reported(self.read_member(member, FieldType::U32, MemberAccess::Host))
     }
 
-    fn set_member_string(&self, offset: u32, value: String) {
-        unsafe {
-            // Convert to c_string
-            let c_string = std::ffi::CString::new(value).expect("CString::new failed");
-            let c_ptr = c_string.as_ptr();
-            std::mem::forget(c_string); // Prevent CString from being deallocated
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *const i8;
-            *member_ptr = c_ptr;
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn set_member_u64(&self, member: u32, value: u64) {
+        reported(self.write_member(member, FieldType::U64, MemberAccess::Host, value))
     }
 
-    fn get_member_string(&self, offset: u32) -> String {
-        unsafe {        
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *const i8;
-            let member_value: String = unsafe { CStr::from_ptr(*member_ptr).to_string_lossy().into_owned() };
-            member_value
-        }
+    fn get_member_u64(&self, member: u32) -> u64 {
+        reported(self.read_member(member, FieldType::U64, MemberAccess::Host))
     }
 
-    fn set_member_u8list(&self, offset: u32, value: Vec<u8>) {
-        unsafe {
-            // Get pointer to member
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u8;
-            // Allocate memory for array data
-            let layout = std::alloc::Layout::array::<u8>(value.len()).unwrap();
-            // Allocate memory for length (usize) + array data
-            let ptr = std::alloc::alloc(layout) as *mut u8;
-            // Copy array data to allocated memory
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            // Store pointer to allocated memory
-            *member_ptr = ptr;
-            // Store length of array
-            // Use the actual array pointer as the key
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            // Mark component as modified
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn set_member_i8(&self, member: u32, value: i8) {
+        reported(self.write_member(member, FieldType::I8, MemberAccess::Host, value))
     }
 
-    fn get_member_u8list(&self, offset: u32) -> Vec<u8> {
-        unsafe {
-            // Get pointer to member
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u8;
-            // Get pointer to allocated memory
-            let ptr = *member_ptr;
-            
-            // Use the actual array pointer as the key
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
+    fn get_member_i8(&self, member: u32) -> i8 {
+        reported(self.read_member(member, FieldType::I8, MemberAccess::Host))
+    }
 
-            // Create slice from allocated memory
-            let slice = std::slice::from_raw_parts(ptr, length);
-            // Return slice as vector
-            slice.to_vec()
-        }
+    fn set_member_i16(&self, member: u32, value: i16) {
+        reported(self.write_member(member, FieldType::I16, MemberAccess::Host, value))
     }
 
-    fn set_member_u16list(&self, offset: u32, value: Vec<u16>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u16;
-            let layout = std::alloc::Layout::array::<u16>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut u16;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            // Use the actual array pointer as the key
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_i16(&self, member: u32) -> i16 {
+        reported(self.read_member(member, FieldType::I16, MemberAccess::Host))
     }
 
-    fn get_member_u16list(&self, offset: u32) -> Vec<u16> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u16;
-            let ptr = *member_ptr;
-            
-            // Use the actual array pointer as the key
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_i32(&self, member: u32, value: i32) {
+        reported(self.write_member(member, FieldType::I32, MemberAccess::Host, value))
     }
 
-    fn set_member_u32list(&self, offset: u32, value: Vec<u32>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u32;
-            let layout = std::alloc::Layout::array::<u32>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut u32;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_i32(&self, member: u32) -> i32 {
+        reported(self.read_member(member, FieldType::I32, MemberAccess::Host))
     }
 
-    fn get_member_u32list(&self, offset: u32) -> Vec<u32> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u32;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_i64(&self, member: u32, value: i64) {
+        reported(self.write_member(member, FieldType::I64, MemberAccess::Host, value))
     }
 
-    fn set_member_u64list(&self, offset: u32, value: Vec<u64>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u64;
-            let layout = std::alloc::Layout::array::<u64>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut u64;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_i64(&self, member: u32) -> i64 {
+        reported(self.read_member(member, FieldType::I64, MemberAccess::Host))
     }
 
-    fn get_member_u64list(&self, offset: u32) -> Vec<u64> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u64;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_f32(&self, member: u32, value: f32) {
+        reported(self.write_member(member, FieldType::F32, MemberAccess::Host, value))
     }
 
-    fn set_member_i8list(&self, offset: u32, value: Vec<i8>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i8;
-            let layout = std::alloc::Layout::array::<i8>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut i8;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_f32(&self, member: u32) -> f32 {
+        reported(self.read_member(member, FieldType::F32, MemberAccess::Host))
     }
 
-    fn get_member_i8list(&self, offset: u32) -> Vec<i8> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i8;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_f64(&self, member: u32, value: f64) {
+        reported(self.write_member(member, FieldType::F64, MemberAccess::Host, value))
     }
 
-    fn set_member_i16list(&self, offset: u32, value: Vec<i16>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i16;
-            let layout = std::alloc::Layout::array::<i16>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut i16;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_f64(&self, member: u32) -> f64 {
+        reported(self.read_member(member, FieldType::F64, MemberAccess::Host))
     }
 
-    fn get_member_i16list(&self, offset: u32) -> Vec<i16> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i16;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_bool(&self, member: u32, value: bool) {
+        reported(self.write_member(member, FieldType::Bool, MemberAccess::Host, value as u8))
     }
 
-    fn set_member_i32list(&self, offset: u32, value: Vec<i32>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i32;
-            let layout = std::alloc::Layout::array::<i32>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut i32;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_bool(&self, member: u32) -> bool {
+        reported(self.read_member::<u8>(member, FieldType::Bool, MemberAccess::Host)) != 0
     }
 
-    fn get_member_i32list(&self, offset: u32) -> Vec<i32> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i32;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_string(&self, member: u32, value: String) {
+        reported(self.write_string(member, value))
     }
 
-    fn set_member_i64list(&self, offset: u32, value: Vec<i64>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i64;
-            let layout = std::alloc::Layout::array::<i64>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut i64;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_string(&self, member: u32) -> String {
+        reported(self.read_string(member))
     }
 
-    fn get_member_i64list(&self, offset: u32) -> Vec<i64> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut i64;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_u8list(&self, member: u32, value: Vec<u8>) {
+        reported(self.write_list(member, FieldType::U8List, value))
     }
 
-    fn set_member_f32list(&self, offset: u32, value: Vec<f32>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut f32;
-            let layout = std::alloc::Layout::array::<f32>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut f32;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_u8list(&self, member: u32) -> Vec<u8> {
+        reported(self.read_list(member, FieldType::U8List))
     }
 
-    fn get_member_f32list(&self, offset: u32) -> Vec<f32> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut f32;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_u16list(&self, member: u32, value: Vec<u16>) {
+        reported(self.write_list(member, FieldType::U16List, value))
     }
 
-    fn set_member_f64list(&self, offset: u32, value: Vec<f64>) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut f64;
-            let layout = std::alloc::Layout::array::<f64>(value.len()).unwrap();
-            let ptr = std::alloc::alloc(layout) as *mut f64;
-            
-            std::ptr::copy_nonoverlapping(value.as_ptr(), ptr, value.len());
-            
-            *member_ptr = ptr;
-            ARRAY_LENGTH_CACHE.lock().unwrap().insert((ThreadSafePtr(ptr as *const c_void), offset), value.len());
-            
-            ecs_modified_id(WORLD.0, self.entity_added, self.component_type_id);
-        }
+    fn get_member_u16list(&self, member: u32) -> Vec<u16> {
+        reported(self.read_list(member, FieldType::U16List))
     }
 
-    fn get_member_f64list(&self, offset: u32) -> Vec<f64> {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut f64;
-            let ptr = *member_ptr;
-            
-            let length = ARRAY_LENGTH_CACHE.lock().unwrap()
-                .get(&(ThreadSafePtr(ptr as *const c_void), offset))
-                .copied()
-                .unwrap_or(0);
-            let slice = std::slice::from_raw_parts(ptr, length);
-            slice.to_vec()
-        }
+    fn set_member_u32list(&self, member: u32, value: Vec<u32>) {
+        reported(self.write_list(member, FieldType::U32List, value))
     }
 
-    fn set_member_pointer(&self, offset: u32, value: u64) {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u64;
-            *member_ptr = value as *mut u64;
-        }
+    fn get_member_u32list(&self, member: u32) -> Vec<u32> {
+        reported(self.read_list(member, FieldType::U32List))
     }
 
-    fn get_member_pointer(&self, offset: u32) -> u64 {
-        unsafe {
-            let member_ptr = self.ptr.offset(offset as isize) as *mut *mut u64;
-            *member_ptr as u64
+    fn set_member_u64list(&self, member: u32, value: Vec<u64>) {
+        reported(self.write_list(member, FieldType::U64List, value))
+    }
+
+    fn get_member_u64list(&self, member: u32) -> Vec<u64> {
+        reported(self.read_list(member, FieldType::U64List))
+    }
+
+    fn set_member_i8list(&self, member: u32, value: Vec<i8>) {
+        reported(self.write_list(member, FieldType::I8List, value))
+    }
+
+    fn get_member_i8list(&self, member: u32) -> Vec<i8> {
+        reported(self.read_list(member, FieldType::I8List))
+    }
+
+    fn set_member_i16list(&self, member: u32, value: Vec<i16>) {
+        reported(self.write_list(member, FieldType::I16List, value))
+    }
+
+    fn get_member_i16list(&self, member: u32) -> Vec<i16> {
+        reported(self.read_list(member, FieldType::I16List))
+    }
+
+    fn set_member_i32list(&self, member: u32, value: Vec<i32>) {
+        reported(self.write_list(member, FieldType::I32List, value))
+    }
+
+    fn get_member_i32list(&self, member: u32) -> Vec<i32> {
+        reported(self.read_list(member, FieldType::I32List))
+    }
+
+    fn set_member_i64list(&self, member: u32, value: Vec<i64>) {
+        reported(self.write_list(member, FieldType::I64List, value))
+    }
+
+    fn get_member_i64list(&self, member: u32) -> Vec<i64> {
+        reported(self.read_list(member, FieldType::I64List))
+    }
+
+    fn set_member_f32list(&self, member: u32, value: Vec<f32>) {
+        reported(self.write_list(member, FieldType::F32List, value))
+    }
+
+    fn get_member_f32list(&self, member: u32) -> Vec<f32> {
+        reported(self.read_list(member, FieldType::F32List))
+    }
+
+    fn set_member_f64list(&self, member: u32, value: Vec<f64>) {
+        reported(self.write_list(member, FieldType::F64List, value))
+    }
+
+    fn get_member_f64list(&self, member: u32) -> Vec<f64> {
+        reported(self.read_list(member, FieldType::F64List))
+    }
+
+    fn set_member_pointer(&self, member: u32, value: u64) {
+        reported(self.write_member(member, FieldType::Pointer, MemberAccess::Host, value as usize))
+    }
+
+    fn get_member_pointer(&self, member: u32) -> u64 {
+        reported(self.read_member::<usize>(member, FieldType::Pointer, MemberAccess::Host)) as u64
+    }
+}
+
+impl Entity {
+    // Decode the initial values of a new entity, none are set if one of them is bad
+    unsafe fn decode_values(values: Option<Vec<ComponentValue>>) -> Result<Vec<snapshot::EncodedValues>, String> {
+        values
+            .unwrap_or_default()
+            .iter()
+            .map(|value| snapshot::decode_encoded(value.component, &value.data)
+                .map_err(|error| format!("Can't set initial value of {}: {}", value.component, error)))
+            .collect()
+    }
+
+    unsafe fn init(desc: EntityDesc, inherits: Option<ecs_entity_t>, values: &[snapshot::EncodedValues]) -> Entity {
+        let mut ent_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
+        if let Some(name) = desc.name {
+            ent_desc.name = c_string(&name);
         }
+        let mut add = desc.add.unwrap_or_default();
+        if desc.prefab {
+            add.push(EcsPrefab);
+        }
+        if let Some(inherits) = inherits {
+            add.push(ecs_make_pair(EcsIsA, inherits));
+        }
+        // Flecs reads the ids up to a 0
+        add.push(0);
+        ent_desc.add = add.as_ptr();
+        let entity = ecs_entity_init(stage(), &ent_desc);
+        for value in values {
+            snapshot::set_encoded(entity, value);
+        }
+        Entity { id: entity }
     }
 }
 
 impl GuestEntity for Entity {
-    fn new(desc: EntityDesc, inherits: Option<ecs_entity_t>) -> Entity {
+    // Bad initial values are logged and none of them are set, `create` returns the error
+    fn new(mut desc: EntityDesc, inherits: Option<ecs_entity_t>) -> Entity {
         unsafe {
-            let mut ent_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
-            if let Some(name) = desc.name {
-                ent_desc.name = c_string(&name);
-            }
-            if let Some(mut add) = desc.add {
-                if desc.prefab {
-                    add.push(EcsPrefab);
-                }
-                let add_array = add.iter().map(|&x| x).collect::<Vec<_>>();
-                ent_desc.add = add_array.as_ptr();
-                std::mem::forget(add_array); // Prevent deallocation
-            }
-            let entity = if let Some(inherits) = inherits {
-                let pair = ecs_make_pair(EcsIsA, inherits);
-                ecs_new_w_id(WORLD.0, pair)
-            } else {
-                ecs_entity_init(WORLD.0, &ent_desc)
-            };
-            Entity { id: entity }
+            let values = Self::decode_values(desc.values.take()).unwrap_or_else(|error| {
+                log::log(LogRecord::new(LogLevel::Warn, "entity", &error));
+                vec![]
+            });
+            Self::init(desc, inherits, &values)
+        }
+    }
+
+    fn create(mut desc: EntityDesc, inherits: Option<ecs_entity_t>) -> Result<ecs_entity_t, String> {
+        unsafe {
+            let values = Self::decode_values(desc.values.take())?;
+            Ok(Self::init(desc, inherits, &values).id)
         }
     }
     
@@ -821,7 +1023,7 @@ impl GuestEntity for Entity {
 
     fn get_name(&self) -> String {
         unsafe {
-            let name = ecs_get_name(WORLD.0, self.id);
+            let name = ecs_get_name(stage(), self.id);
             if name.is_null() {
                 return String::new();
             }
@@ -831,24 +1033,24 @@ impl GuestEntity for Entity {
     }
 
     fn set_name(&self, name: String) {
-        unsafe { ecs_set_name(WORLD.0, self.id, c_string(&name)) };
+        unsafe { ecs_set_name(stage(), self.id, c_string(&name)) };
     }
 
     fn add(&self, component: ecs_entity_t) {
         unsafe {
-            ecs_add_id(WORLD.0, self.id, component);
+            ecs_add_id(stage(), self.id, component);
         }
     }
 
     fn has(&self, component: ecs_entity_t) -> bool {
         unsafe {
-            ecs_has_id(WORLD.0, self.id, component)
+            ecs_has_id(stage(), self.id, component)
         }
     }
 
     fn remove(&self, component: ecs_entity_t) {
         unsafe {
-            ecs_remove_id(WORLD.0, self.id, component);
+            ecs_remove_id(stage(), self.id, component);
         }
     }
 
@@ -859,7 +1061,7 @@ impl GuestEntity for Entity {
     fn get(&self, component: ecs_entity_t) -> PointerT {
         unsafe {
             // println!("Getting component {:?} from entity {:?}", component, self.id);
-            ecs_ensure_id(WORLD.0, self.id, component) as PointerT
+            ecs_ensure_id(stage(), self.id, component) as PointerT
         }
     }
 
@@ -870,18 +1072,18 @@ impl GuestEntity for Entity {
     fn parent_of(&self, target: ecs_entity_t) {
         unsafe {
             let pair = ecs_make_pair(EcsChildOf, self.id);
-            ecs_add_id(WORLD.0, target, pair); 
+            ecs_add_id(stage(), target, pair); 
         }
     }
 
     fn parent(&self) -> EcsEntityT {
-        let id = unsafe { ecs_get_parent(WORLD.0, self.id) };
+        let id = unsafe { ecs_get_parent(stage(), self.id) };
         id
     }
 
     fn children(&self) -> Vec<EcsEntityT> {
         unsafe {
-            let mut iter = ecs_children(WORLD.0, self.id);
+            let mut iter = ecs_children(stage(), self.id);
             let mut children = Vec::new();
             while ecs_children_next(&mut iter) {
                 if !iter.entities.is_null() && iter.count > 0 {
@@ -903,8 +1105,8 @@ impl GuestEntity for Entity {
                 Relationship::ChildOf => EcsChildOf,
                 Relationship::Custom(entity) => entity
             };
-            while ecs_get_target(WORLD.0, self.id, relationship_entity, index) > 0 {
-                relationships.push(ecs_get_target(WORLD.0, self.id, relationship_entity, index));
+            while ecs_get_target(stage(), self.id, relationship_entity, index) > 0 {
+                relationships.push(ecs_get_target(stage(), self.id, relationship_entity, index));
                 index += 1;
             }
         }
@@ -919,7 +1121,7 @@ impl GuestEntity for Entity {
                 Relationship::Custom(entity) => entity
             };
             let pair = ecs_make_pair(relationship_entity, target);
-            ecs_add_id(WORLD.0, self.id, pair); 
+            ecs_add_id(stage(), self.id, pair); 
         };
     }
 
@@ -931,16 +1133,37 @@ impl GuestEntity for Entity {
                 Relationship::Custom(entity) => entity
             };
             let pair = ecs_make_pair(relationship_entity, target);
-            ecs_remove_id(WORLD.0, self.id, pair);
+            ecs_remove_id(stage(), self.id, pair);
         }
     }
 
     fn disable(&self) {
-        unsafe { ecs_enable(WORLD.0, self.id, false) };
+        unsafe { ecs_enable(stage(), self.id, false) };
     }
 
     fn enable(&self) {
-        unsafe { ecs_enable(WORLD.0, self.id, true) };
+        unsafe { ecs_enable(stage(), self.id, true) };
+    }
+}
+
+// Queries built by `Query` with their world and build number. Finishing a world deletes its
+// queries, so a dropped `Query` only finishes its query if it's still listed here.
+static BUILT_QUERIES: Lazy<Mutex<HashMap<usize, (usize, u64)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static QUERY_BUILDS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+pub(crate) fn forget_queries(world: *mut ecs_world_t) {
+    BUILT_QUERIES.lock().unwrap().retain(|_, (query_world, _)| *query_world != world as usize);
+}
+
+impl Drop for Query {
+    fn drop(&mut self) {
+        let query = *self.query.borrow();
+        let mut built = BUILT_QUERIES.lock().unwrap();
+        if built.get(&(query as usize)).is_some_and(|(_, build)| *build == self.build.get()) {
+            built.remove(&(query as usize));
+            drop(built);
+            unsafe { ecs_query_fini(query) };
+        }
     }
 }
 
@@ -950,14 +1173,36 @@ impl GuestQuery for Query {
         desc.expr = c_string(&query_desc.expr);
         Query { 
             desc: RefCell::new(desc), 
-            query: RefCell::new(unsafe { MaybeUninit::zeroed().assume_init() }), 
-            iter: RefCell::new(unsafe { MaybeUninit::zeroed().assume_init() }) 
+            query: RefCell::new(std::ptr::null_mut()), 
+            iter: RefCell::new(unsafe { MaybeUninit::zeroed().assume_init() }),
+            monitors: RefCell::new(Vec::new()),
+            build: std::cell::Cell::new(0)
         }
     }
 
     fn build(&self) { 
-        let query = unsafe { ecs_query_init(WORLD.0, self.desc.as_ptr()) };
-        *self.query.borrow_mut() = unsafe { *query };
+        let mut desc = self.desc.borrow_mut();
+        desc.binding_ctx = changes::monitors_ctx(&self.monitors.borrow());
+        if !desc.binding_ctx.is_null() {
+            desc.binding_ctx_free = Some(changes::free_monitors);
+        }
+        let query = unsafe { ecs_query_init(WORLD.0, &*desc) };
+        if query.is_null() {
+            return;
+        }
+        unsafe { changes::start_monitors(query) };
+        *self.query.borrow_mut() = query;
+        let build = QUERY_BUILDS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.build.set(build);
+        BUILT_QUERIES.lock().unwrap().insert(query as usize, (unsafe { WORLD.0 } as usize, build));
+    }
+
+    fn cache(&self) {
+        self.desc.borrow_mut().cache_kind = toxoid_flecs::bindings::ecs_query_cache_kind_t_EcsQueryCacheAuto;
+    }
+
+    fn monitor(&self, index: i8, kind: MonitorKind) {
+        self.monitors.borrow_mut().push((index, kind));
     }
 
     #[cfg(not(target_os = "emscripten"))]
@@ -979,9 +1224,9 @@ impl GuestQuery for Query {
     }
 
     fn iter(&self) -> PointerT {
-        let query = unsafe { *self.query.as_ptr() };
         // Create new iterator
-        let iter = unsafe { ecs_query_iter(WORLD.0, self.query.as_ptr()) };
+        // Iterated on the stage of this thread, in multithreaded systems the world can't be read directly
+        let iter = unsafe { ecs_query_iter(stage(), *self.query.borrow()) };
         
         // Store it in our RefCell
         *self.iter.borrow_mut() = iter;
@@ -998,7 +1243,10 @@ impl GuestQuery for Query {
         if iter_ptr.is_null() {
             return false;
         }
-        unsafe { ecs_iter_next(iter_ptr) }
+        unsafe {
+            changes::visit_done(iter_ptr);
+            ecs_iter_next(iter_ptr)
+        }
     }
 
     fn count(&self) -> i32 {
@@ -1012,28 +1260,221 @@ impl GuestQuery for Query {
     }
 
     fn components(&self, index: i8) -> Vec<PointerT> {
-        // Get iter as raw pointer
-        let iter = self.iter.as_ptr();
-        // Get count of components
-        let count = unsafe { (*iter).count };
-        if count != 0 {
-            // Get size of field (list of components of type T mapped by index)
-            let size = unsafe { ecs_field_size(iter, index) };
-            // Get field at index (list of components of type T mapped by index)
-            let field = unsafe { ecs_field_w_size(iter, size, index) };
-            // Create a slice of the field data and convert directly to Vec
-            unsafe {
-                // Create a vector to store component pointers
-                let mut components = Vec::with_capacity(count as usize);
-                for i in 0..count {
-                    // Calculate pointer to each component using size
-                    let component_ptr = (field as *const u8).add(i as usize * size as usize);
-                    components.push(component_ptr as PointerT);
-                }
-                components
+        unsafe { field_components(self.iter.as_ptr(), index) }
+    }
+
+    fn changed(&self) -> bool {
+        unsafe { changes::query_changed(*self.query.borrow()) }
+    }
+
+    fn iter_changed(&self) -> bool {
+        unsafe { changes::iter_changed(self.iter.as_ptr()) }
+    }
+
+    fn skip(&self) {
+        unsafe { changes::iter_skip(self.iter.as_ptr()) }
+    }
+
+    fn field_changed(&self, index: i8) -> bool {
+        unsafe { changes::field_changed(self.iter.as_ptr(), index) }
+    }
+
+    fn field_added(&self, index: i8) -> Vec<bool> {
+        unsafe { changes::field_added(self.iter.as_ptr(), index) }
+    }
+}
+
+// Pointers to the component of every entity in field `index`. An optional field that isn't
+// set has none, a field matched on another entity such as a parent repeats its one component.
+unsafe fn field_components(iter: *mut ecs_iter_t, index: i8) -> Vec<PointerT> {
+    // Get count of components
+    let count = (*iter).count;
+    if count == 0 || !ecs_field_is_set(iter, index) {
+        return vec![];
+    }
+    // Get size of field (list of components of type T mapped by index)
+    let size = ecs_field_size(iter, index);
+    // Get field at index (list of components of type T mapped by index)
+    let field = ecs_field_w_size(iter, size, index);
+    let is_self = ecs_field_is_self(iter, index);
+    (0..count)
+        .map(|i| {
+            // Calculate pointer to each component using size
+            let offset = if is_self { i as usize * size as usize } else { 0 };
+            (field as *const u8).add(offset) as PointerT
+        })
+        .collect()
+}
+
+// Views of the components of field `index`, tied to the entity each one belongs to
+// so writes through them mark the component as modified on it
+unsafe fn field_views(iter: *mut ecs_iter_t, index: i8, read_only: bool) -> Vec<Component> {
+    let components = field_components(iter, index);
+    if components.is_empty() {
+        return vec![];
+    }
+    let component_type_id = ecs_get_typeid((*iter).world, ecs_field_id(iter, index));
+    let is_self = ecs_field_is_self(iter, index);
+    let source = ecs_field_src(iter, index);
+    components
+        .into_iter()
+        .enumerate()
+        .map(|(i, ptr)| Component {
+            read_only,
+            ..Component::new(ptr, if is_self { *(*iter).entities.add(i) } else { source }, component_type_id)
+        })
+        .collect()
+}
+
+// A field of the current table as it's laid out in Flecs storage
+struct FieldColumn {
+    component_type: ecs_entity_t,
+    data: *mut u8,
+    size: usize,
+    // Number of components, 1 when the field is matched on another entity such as a parent
+    count: usize
+}
+
+fn is_plain_member(member: &ecs_member_t) -> bool {
+    unsafe {
+        member.type_ != toxoid_flecs::bindings::FLECS_IDecs_string_tID_ &&
+        member.type_ != toxoid_flecs::bindings::FLECS_IDecs_uptr_tID_
+    }
+}
+
+// Raw writes can put any byte in a bool, only 0 and 1 are valid
+unsafe fn normalize_bools(component_type: ecs_entity_t, data: *mut u8) -> Result<(), MemberError> {
+    for layout in member_layouts(component_type)?.iter() {
+        for i in 0..layout.count {
+            let member_ptr = data.add((layout.offset + i * layout.size) as usize);
+            if layout.type_ == toxoid_flecs::bindings::FLECS_IDecs_bool_tID_ {
+                *member_ptr = (*member_ptr != 0) as u8;
+            } else if layout.is_struct {
+                normalize_bools(layout.type_, member_ptr)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Query {
+    // Views of the components of field `index` in the current table, see `Iter::field_views`
+    pub fn field_views(&self, index: i8, read_only: bool) -> Vec<Component> {
+        unsafe { field_views(self.iter.as_ptr(), index, read_only) }
+    }
+}
+
+impl Iter {
+    // Views of the components of field `index`, `read_only` for fields the query only reads
+    pub fn field_views(&self, index: i8, read_only: bool) -> Vec<Component> {
+        unsafe { field_views(self.ptr as *mut ecs_iter_t, index, read_only) }
+    }
+
+    unsafe fn field_column(&self, index: i8) -> Option<FieldColumn> {
+        let iter = self.ptr as *mut ecs_iter_t;
+        if iter.is_null() || (*iter).count == 0 || !ecs_field_is_set(iter, index) {
+            return None;
+        }
+        let size = ecs_field_size(iter, index);
+        let data = ecs_field_w_size(iter, size, index) as *mut u8;
+        // Tags have no data
+        if data.is_null() || size == 0 {
+            return None;
+        }
+        let count = if ecs_field_is_self(iter, index) { (*iter).count as usize } else { 1 };
+        Some(FieldColumn {
+            component_type: ecs_get_typeid((*iter).world, ecs_field_id(iter, index)),
+            data,
+            size,
+            count
+        })
+    }
+
+    // Bulk writes bypass the member setters, so mark the components as modified here.
+    // In a system the iterator's world is the stage of the thread running it.
+    unsafe fn field_modified(&self, index: i8) {
+        let iter = self.ptr as *mut ecs_iter_t;
+        let id = ecs_field_id(iter, index);
+        if ecs_field_is_self(iter, index) {
+            for entity in self.entities() {
+                ecs_modified_id((*iter).world, entity, id);
             }
         } else {
-            vec![]
+            ecs_modified_id((*iter).world, ecs_field_src(iter, index), id);
+        }
+    }
+
+    // Copy one member of every component of a field into a packed buffer
+    pub fn read_column(&self, index: i8, member: u32, field_type: FieldType, access: MemberAccess) -> Result<Vec<u8>, MemberError> {
+        unsafe {
+            let Some(column) = self.field_column(index) else {
+                return Ok(Vec::new());
+            };
+            let member_desc = checked_member(column.component_type, member, field_type, access)?;
+            let member_size = field_type.plain_size().ok_or(MemberError::NotPlainData(column.component_type))?;
+            let mut data = Vec::with_capacity(column.count * member_size);
+            for i in 0..column.count {
+                let member_ptr = column.data.add(i * column.size + member_desc.offset as usize);
+                data.extend_from_slice(std::slice::from_raw_parts(member_ptr, member_size));
+            }
+            Ok(data)
+        }
+    }
+
+    // Write a packed buffer from `read_column` back into the components of a field
+    pub fn write_column(&self, index: i8, member: u32, field_type: FieldType, access: MemberAccess, data: &[u8]) -> Result<(), MemberError> {
+        unsafe {
+            let Some(column) = self.field_column(index) else {
+                return if data.is_empty() { Ok(()) } else { Err(MemberError::LengthMismatch { expected: 0, found: data.len() }) };
+            };
+            let member_desc = checked_member(column.component_type, member, field_type, access)?;
+            let member_size = field_type.plain_size().ok_or(MemberError::NotPlainData(column.component_type))?;
+            if data.len() != column.count * member_size {
+                return Err(MemberError::LengthMismatch { expected: column.count * member_size, found: data.len() });
+            }
+            for (i, value) in data.chunks_exact(member_size).enumerate() {
+                let member_ptr = column.data.add(i * column.size + member_desc.offset as usize);
+                std::ptr::copy_nonoverlapping(value.as_ptr(), member_ptr, member_size);
+                if field_type == FieldType::Bool {
+                    *member_ptr = (*member_ptr != 0) as u8;
+                }
+            }
+            self.field_modified(index);
+            Ok(())
+        }
+    }
+
+    // Copy the whole component array of a field
+    pub fn read_field_data(&self, index: i8) -> Result<Vec<u8>, MemberError> {
+        unsafe {
+            let Some(column) = self.field_column(index) else {
+                return Ok(Vec::new());
+            };
+            if !struct_members(column.component_type)?.iter().all(is_plain_member) {
+                return Err(MemberError::NotPlainData(column.component_type));
+            }
+            Ok(std::slice::from_raw_parts(column.data, column.count * column.size).to_vec())
+        }
+    }
+
+    // Overwrite the whole component array of a field
+    pub fn write_field_data(&self, index: i8, data: &[u8]) -> Result<(), MemberError> {
+        unsafe {
+            let Some(column) = self.field_column(index) else {
+                return if data.is_empty() { Ok(()) } else { Err(MemberError::LengthMismatch { expected: 0, found: data.len() }) };
+            };
+            if !struct_members(column.component_type)?.iter().all(is_plain_member) {
+                return Err(MemberError::NotPlainData(column.component_type));
+            }
+            if data.len() != column.count * column.size {
+                return Err(MemberError::LengthMismatch { expected: column.count * column.size, found: data.len() });
+            }
+            std::ptr::copy_nonoverlapping(data.as_ptr(), column.data, data.len());
+            for i in 0..column.count {
+                normalize_bools(column.component_type, column.data.add(i * column.size))?;
+            }
+            self.field_modified(index);
+            Ok(())
         }
     }
 }
@@ -1055,6 +1496,11 @@ impl GuestIter for Iter {
         iter.count
     }
 
+    fn system(&self) -> EcsEntityT {
+        let iter = unsafe { *(self.ptr as *mut ecs_iter_t) };
+        iter.system
+    }
+
     fn entities(&self) -> Vec<EcsEntityT> {
         let iter = unsafe { *(self.ptr as *mut ecs_iter_t) };
         let entities = iter.entities;
@@ -1063,34 +1509,56 @@ impl GuestIter for Iter {
     }
 
     fn components(&self, index: i8) -> Vec<PointerT> {
-        // Get iter as raw pointer
-        let iter = self.ptr as *mut ecs_iter_t;
-        // Get count of components
-        let count = unsafe { (*iter).count };
-        if count != 0 {
-            // Get size of field (list of components of type T mapped by index)
-            let size = unsafe { ecs_field_size(iter, index) };
-            // Get field at index (list of components of type T mapped by index)
-            let field = unsafe { ecs_field_w_size(iter, size, index) };
-            // Create a slice of the field data and convert directly to Vec
-            unsafe {
-                // Create a vector to store component pointers
-                let mut components = Vec::with_capacity(count as usize);
-                for i in 0..count {
-                    // Calculate pointer to each component using size
-                    let component_ptr = (field as *const u8).add(i as usize * size as usize);
-                    components.push(component_ptr as PointerT);
-                }
-                components
-            }
-        } else {
-            vec![]
-        }
+        unsafe { field_components(self.ptr as *mut ecs_iter_t, index) }
+    }
+
+    fn column(&self, index: i8, member: u32, member_type: MemberType) -> Vec<u8> {
+        reported(self.read_column(index, member, member_type.into(), MemberAccess::Host))
+    }
+
+    fn set_column(&self, index: i8, member: u32, member_type: MemberType, data: Vec<u8>) {
+        reported(self.write_column(index, member, member_type.into(), MemberAccess::Host, &data))
+    }
+
+    fn field_data(&self, index: i8) -> Vec<u8> {
+        reported(self.read_field_data(index))
+    }
+
+    fn set_field_data(&self, index: i8, data: Vec<u8>) {
+        reported(self.write_field_data(index, &data))
+    }
+
+    fn changed(&self) -> bool {
+        unsafe { changes::iter_changed(self.ptr as *mut ecs_iter_t) }
+    }
+
+    fn skip(&self) {
+        unsafe { changes::iter_skip(self.ptr as *mut ecs_iter_t) }
+    }
+
+    fn field_changed(&self, index: i8) -> bool {
+        unsafe { changes::field_changed(self.ptr as *mut ecs_iter_t, index) }
+    }
+
+    fn field_added(&self, index: i8) -> Vec<bool> {
+        unsafe { changes::field_added(self.ptr as *mut ecs_iter_t, index) }
     }
 }
 
 pub static mut QUERY_TRAMPOLINE: Option<unsafe extern "C" fn(*mut ecs_iter_t)> = None;
 
+// Called by Flecs with the callback handle of a native system or observer when it's deleted,
+// so the closure behind the handle can be dropped
+pub static mut CALLBACK_FREE: Option<unsafe extern "C" fn(*mut c_void)> = None;
+
+// Systems run the trampoline for every table and then move their filters on from it
+unsafe extern "C" fn system_callback(iter: *mut ecs_iter_t) {
+    if let Some(trampoline) = QUERY_TRAMPOLINE {
+        trampoline(iter);
+    }
+    changes::visit_done(iter);
+}
+
 impl GuestSystem for System {
     fn new(desc: SystemDesc) -> System {
         // Create system entity
@@ -1113,16 +1581,18 @@ impl GuestSystem for System {
             system_desc.ctx = 1 as *mut c_void;
         } else {
             system_desc.ctx = std::ptr::null_mut();
+            system_desc.callback_ctx_free = unsafe { CALLBACK_FREE };
         }
         system_desc.callback_ctx = desc.callback as *mut c_void;
-        system_desc.callback = Some(unsafe { QUERY_TRAMPOLINE.unwrap() });
+        system_desc.callback = Some(system_callback);
         System { 
             desc: RefCell::new(system_desc),
             entity: RefCell::new(entity), 
             callback: RefCell::new(Callback::new(desc.callback)),
             system: RefCell::new(
                 unsafe { MaybeUninit::zeroed().assume_init() }
-            )
+            ),
+            monitors: RefCell::new(Vec::new())
         }
     }
 
@@ -1130,6 +1600,10 @@ impl GuestSystem for System {
         let entity = self.entity.borrow();
         unsafe { ecs_set_name(WORLD.0, *entity, c_string(&name)) };
     }
+
+    fn monitor(&self, index: i8, kind: MonitorKind) {
+        self.monitors.borrow_mut().push((index, kind));
+    }
     
     #[cfg(not(target_os = "emscripten"))]
     fn order_by(&self, sorting: SortingDesc) {
@@ -1151,6 +1625,15 @@ impl GuestSystem for System {
         self.desc.borrow_mut().query = query;
     }
 
+    // Let Flecs split the matched entities over the worker threads.
+    // Ignored for guest systems, a guest's store can only be entered from one thread at a time.
+    fn multi_threaded(&self, enabled: bool) {
+        let mut desc = self.desc.borrow_mut();
+        if desc.ctx.is_null() {
+            desc.multi_threaded = enabled;
+        }
+    }
+
     fn get_id(&self) -> ecs_entity_t {
         *self.entity.borrow()
     }
@@ -1160,7 +1643,20 @@ impl GuestSystem for System {
     }
 
     fn build(&self) {
+        {
+            let mut desc = self.desc.borrow_mut();
+            desc.query.binding_ctx = changes::monitors_ctx(&self.monitors.borrow());
+            if !desc.query.binding_ctx.is_null() {
+                desc.query.binding_ctx_free = Some(changes::free_monitors);
+            }
+        }
         *self.entity.borrow_mut() = unsafe { ecs_system_init(WORLD.0, self.desc.as_ptr()) };
+        unsafe {
+            let system = toxoid_flecs::bindings::ecs_system_get(WORLD.0, *self.entity.borrow());
+            if !system.is_null() {
+                changes::start_monitors((*system).query);
+            }
+        }
         // Set tickrate using world, system id, description, and 0 (to use frames as a source)
         unsafe { ecs_set_rate(WORLD.0, *self.entity.borrow(), self.desc.borrow().rate, 0) };
     }
@@ -1191,6 +1687,7 @@ impl GuestObserver for Observer {
             observer_desc.ctx = 1 as *mut c_void;
         } else {
             observer_desc.ctx = std::ptr::null_mut();
+            observer_desc.callback_ctx_free = unsafe { CALLBACK_FREE };
         }
         observer_desc.callback_ctx = desc.callback as *mut c_void;
         observer_desc.callback = Some(unsafe { QUERY_TRAMPOLINE.unwrap() });
@@ -1218,6 +1715,10 @@ impl GuestObserver for Observer {
         self.callback.borrow().handle
     }
 
+    fn get_id(&self) -> ecs_entity_t {
+        *self.entity.borrow()
+    }
+
     fn build(&self) {
         *self.entity.borrow_mut() = unsafe { 
             ecs_observer_init(WORLD.0, self.desc.as_ptr()) 
@@ -1317,24 +1818,24 @@ impl Guest for ToxoidApi {
     type Pipeline = Pipeline;
     
     fn add_singleton(component: ecs_entity_t) {
-        unsafe { ecs_add_id(WORLD.0, component, component) };   
+        unsafe { ecs_add_id(stage(), component, component) };   
     }
 
     fn get_singleton(component: ecs_entity_t) -> u64 {
         // unsafe { ecs_get_mut_id(WORLD.0, component, component) as u64 }
-        unsafe { ecs_ensure_id(WORLD.0, component, component) as u64 }  
+        unsafe { ecs_ensure_id(stage(), component, component) as u64 }  
     }
 
     fn remove_singleton(component: ecs_entity_t) {
-        unsafe { ecs_remove_id(WORLD.0, component, component) };
+        unsafe { ecs_remove_id(stage(), component, component) };
     }
 
     fn add_entity(entity: ecs_entity_t) {
-        unsafe { ecs_add_id(WORLD.0, entity, entity) };
+        unsafe { ecs_add_id(stage(), entity, entity) };
     }
 
     fn remove_entity(entity: ecs_entity_t) {
-        unsafe { ecs_delete(WORLD.0, entity); }
+        unsafe { ecs_delete(stage(), entity); }
     }
 
     fn has_entity_named(name: String) -> bool {
@@ -1347,6 +1848,73 @@ impl Guest for ToxoidApi {
         let c_name = c_string(&component_name);
         unsafe { ecs_lookup(WORLD.0, c_name) }
     }
+
+    fn register_enum(desc: EnumDesc) -> ecs_entity_t {
+        unsafe {
+            let name = c_string(&desc.name);
+            let lookup = ecs_lookup(WORLD.0, name);
+            if lookup != 0 {
+                return lookup;
+            }
+            let mut ent_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
+            ent_desc.name = name;
+            let enum_entity = ecs_entity_init(WORLD.0, &ent_desc);
+            let (kind, underlying_type) = if desc.bitmask {
+                (toxoid_flecs::bindings::FLECS_IDEcsBitmaskID_, toxoid_flecs::bindings::FLECS_IDecs_u32_tID_)
+            } else {
+                (toxoid_flecs::bindings::FLECS_IDEcsEnumID_, toxoid_flecs::bindings::FLECS_IDecs_i32_tID_)
+            };
+            ecs_add_id(WORLD.0, enum_entity, kind);
+            // Constants are children with their value in a (Constant, type) pair, which Flecs
+            // picks up for the enum. Unlike the enum descriptor this isn't limited to 32 constants.
+            for constant in desc.constants.iter() {
+                let mut constant_desc: ecs_entity_desc_t = MaybeUninit::zeroed().assume_init();
+                constant_desc.name = c_string(&constant.name);
+                constant_desc.parent = enum_entity;
+                let constant_entity = ecs_entity_init(WORLD.0, &constant_desc);
+                ecs_set_id(
+                    WORLD.0,
+                    constant_entity,
+                    ecs_make_pair(EcsConstant, underlying_type),
+                    std::mem::size_of::<u32>(),
+                    &constant.value as *const u32 as *const c_void
+                );
+            }
+            enum_entity
+        }
+    }
+
+    fn entity_to_json(entity: ecs_entity_t, recursive: bool) -> Result<String, String> {
+        json::entity_to_json(entity, recursive).map_err(|e| e.to_string())
+    }
+
+    fn entity_from_json(json: String, parent: Option<ecs_entity_t>) -> Result<ecs_entity_t, String> {
+        json::entity_from_json(&json, parent).map_err(|e| e.to_string())
+    }
+
+    fn command_spawn() -> ecs_entity_t {
+        commands::spawn()
+    }
+
+    fn command_despawn(entity: ecs_entity_t) {
+        commands::push(commands::Command::Despawn(entity));
+    }
+
+    fn command_add(entity: ecs_entity_t, component: ecs_entity_t) {
+        commands::push(commands::Command::Add(entity, component));
+    }
+
+    fn command_remove(entity: ecs_entity_t, component: ecs_entity_t) {
+        commands::push(commands::Command::Remove(entity, component));
+    }
+
+    fn flush_commands() -> Vec<ecs::CommandError> {
+        commands::flush()
+    }
+
+    fn take_command_errors() -> Vec<ecs::CommandError> {
+        commands::take_errors()
+    }
 }
 
 // TODO: Don't forget to clean up the cache when components are deleted!
@@ -1356,22 +1924,15 @@ fn cleanup_array_lengths(ptr: *const c_void) {
 }
 
 fn c_string(rust_str: &str) -> *const i8 {
-    use std::collections::HashMap;
-    use std::sync::Once;
-
-    static mut STRING_CACHE: Option<HashMap<String, std::ffi::CString>> = None;
-    static INIT: Once = Once::new();
-
-    // Initialize the static HashMap if it hasn't been initialized
-    unsafe {
-        INIT.call_once(|| {
-            STRING_CACHE = Some(HashMap::new());
-        });
-
-        // Get or insert the CString
-        let cache = STRING_CACHE.as_mut().unwrap();
-        cache.entry(rust_str.to_string())
-            .or_insert_with(|| std::ffi::CString::new(rust_str).expect("CString::new failed"))
-            .as_ptr()
-    }
-}
\ No newline at end of file
+    // Strings are kept for the lifetime of the program since Flecs holds on to some of them,
+    // the CString buffers don't move when the map grows
+    static STRING_CACHE: Lazy<Mutex<HashMap<String, std::ffi::CString>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+    // Get or insert the CString
+    STRING_CACHE
+        .lock()
+        .unwrap()
+        .entry(rust_str.to_string())
+        .or_insert_with(|| std::ffi::CString::new(rust_str).expect("CString::new failed"))
+        .as_ptr()
+}
@@ -0,0 +1,193 @@
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR"
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    pub level: LogLevel,
+    // Module or subsystem the record is about, chosen by the emitter
+    pub target: String,
+    pub message: String,
+    pub fields: Vec<(String, String)>,
+    // Guest component that emitted the record, None for the host
+    pub guest: Option<String>,
+    // System or observer that was running when the record was emitted
+    pub system: Option<String>,
+}
+
+impl LogRecord {
+    pub fn new(level: LogLevel, target: &str, message: &str) -> Self {
+        LogRecord {
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+            fields: Vec::new(),
+            guest: None,
+            system: None,
+        }
+    }
+}
+
+// Formatted as `LEVEL [guest system] target: message key=value ...`
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<5}", self.level)?;
+        match (&self.guest, &self.system) {
+            (Some(guest), Some(system)) => write!(f, " [{} {}]", guest, system)?,
+            (Some(guest), None) => write!(f, " [{}]", guest)?,
+            (None, Some(system)) => write!(f, " [{}]", system)?,
+            (None, None) => {}
+        }
+        if !self.target.is_empty() {
+            write!(f, " {}:", self.target)?;
+        }
+        write!(f, " {}", self.message)?;
+        for (key, value) in self.fields.iter() {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+// Destination of log records, see `set_log_sink` and `add_log_sink`
+pub trait LogSink: Send {
+    fn log(&mut self, record: &LogRecord);
+    fn flush(&mut self) {}
+}
+
+// Warnings and errors go to stderr, the rest to stdout
+pub struct TerminalSink;
+
+impl LogSink for TerminalSink {
+    fn log(&mut self, record: &LogRecord) {
+        if record.level >= LogLevel::Warn {
+            eprintln!("{}", record);
+        } else {
+            println!("{}", record);
+        }
+    }
+}
+
+// Appends records to a file, one per line
+pub struct FileSink {
+    file: File
+}
+
+impl FileSink {
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(FileSink { file })
+    }
+}
+
+impl LogSink for FileSink {
+    fn log(&mut self, record: &LogRecord) {
+        // A failing log file shouldn't take down the engine, the record is dropped
+        let _ = writeln!(self.file, "{}", record);
+    }
+
+    fn flush(&mut self) {
+        let _ = self.file.flush();
+    }
+}
+
+// Keeps the latest records in memory for an in-game console to display
+pub struct ConsoleSink {
+    records: Arc<Mutex<VecDeque<LogRecord>>>,
+    capacity: usize
+}
+
+impl ConsoleSink {
+    pub fn new(capacity: usize) -> Self {
+        ConsoleSink { records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))), capacity }
+    }
+
+    // Shared buffer of the records, oldest first, the console reads from this
+    pub fn records(&self) -> Arc<Mutex<VecDeque<LogRecord>>> {
+        self.records.clone()
+    }
+}
+
+impl LogSink for ConsoleSink {
+    fn log(&mut self, record: &LogRecord) {
+        let mut records = self.records.lock().unwrap();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record.clone());
+    }
+}
+
+struct Logger {
+    sinks: Vec<Box<dyn LogSink>>,
+    min_level: LogLevel
+}
+
+static LOGGER: Lazy<Mutex<Logger>> = Lazy::new(|| Mutex::new(Logger {
+    sinks: vec![Box::new(TerminalSink)],
+    min_level: LogLevel::Info
+}));
+
+// Replace the sinks with a single one
+pub fn set_log_sink(sink: Box<dyn LogSink>) {
+    let mut logger = LOGGER.lock().unwrap();
+    logger.sinks.iter_mut().for_each(|sink| sink.flush());
+    logger.sinks = vec![sink];
+}
+
+pub fn add_log_sink(sink: Box<dyn LogSink>) {
+    LOGGER.lock().unwrap().sinks.push(sink);
+}
+
+// Records below this level are dropped, Info by default
+pub fn set_log_level(level: LogLevel) {
+    LOGGER.lock().unwrap().min_level = level;
+}
+
+pub fn log_enabled(level: LogLevel) -> bool {
+    level >= LOGGER.lock().unwrap().min_level
+}
+
+// Sinks must not log themselves, the logger is locked while they run
+pub fn log(record: LogRecord) {
+    let mut logger = LOGGER.lock().unwrap();
+    if record.level < logger.min_level {
+        return;
+    }
+    logger.sinks.iter_mut().for_each(|sink| sink.log(&record));
+}
+
+pub fn flush_log() {
+    LOGGER.lock().unwrap().sinks.iter_mut().for_each(|sink| sink.flush());
+}
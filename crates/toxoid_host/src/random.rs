@@ -0,0 +1,93 @@
+use crate::bindings::exports::toxoid::engine::ecs::{EcsEntityT, GuestEntity};
+use crate::{Entity, WORLD};
+use toxoid_flecs::bindings::ecs_world_t;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Stream of the world, for code that isn't tied to a system
+pub const WORLD_STREAM: u64 = 0;
+
+// SplitMix64, small and fully specified so native, WASM and emscripten builds produce
+// the same sequences for the same seed
+#[derive(Clone, Copy, Debug)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix(self.state)
+    }
+}
+
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// Independent streams derived from the world seed, created on first use
+#[derive(Default)]
+struct Random {
+    seed: u64,
+    streams: HashMap<u64, Rng>
+}
+
+// Seeds and streams by world, every world starts with seed 0
+static RANDOM: Lazy<Mutex<HashMap<u64, Random>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Run with the random state of the active world
+fn with_random<R>(f: impl FnOnce(&mut Random) -> R) -> R {
+    f(RANDOM.lock().unwrap().entry(unsafe { WORLD.0 } as u64).or_default())
+}
+
+// Reseed the active world, every stream starts over
+pub fn set_seed(seed: u64) {
+    with_random(|random| {
+        random.seed = seed;
+        random.streams.clear();
+    });
+}
+
+pub fn seed() -> u64 {
+    with_random(|random| random.seed)
+}
+
+// Drop the seed and streams of a world that is finished
+pub(crate) fn forget(world: *mut ecs_world_t) {
+    RANDOM.lock().unwrap().remove(&(world as u64));
+}
+
+// Stream ID of a name, FNV-1a so it doesn't depend on the platform's hasher
+pub fn named_stream(name: &str) -> u64 {
+    name.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3))
+}
+
+// Stream ID of a system or observer, by name when it has one since entity IDs
+// depend on registration order
+pub fn system_stream(system: EcsEntityT) -> u64 {
+    let name = Entity { id: system }.get_name();
+    if name.is_empty() {
+        system
+    } else {
+        named_stream(&name)
+    }
+}
+
+pub fn next_u64(stream: u64) -> u64 {
+    with_random(|random| {
+        let seed = random.seed;
+        random
+            .streams
+            .entry(stream)
+            .or_insert_with(|| Rng::new(mix(seed ^ mix(stream))))
+            .next_u64()
+    })
+}
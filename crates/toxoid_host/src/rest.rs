@@ -0,0 +1,45 @@
+use crate::WORLD;
+use std::ffi::CString;
+use toxoid_flecs::bindings::{
+    ecs_import_c, ecs_measure_frame_time, ecs_measure_system_time, ecs_remove_id, ecs_set_id, EcsRest,
+    FlecsRestImport, FlecsStatsImport, FLECS_IDEcsRestID_
+};
+
+// Port the Flecs explorer connects to by default, https://www.flecs.dev/explorer
+pub const REST_DEFAULT_PORT: u16 = 27750;
+
+// Only the machine running the game can connect unless another address is given
+pub const REST_DEFAULT_ADDRESS: &str = "127.0.0.1";
+
+// Serve the active world over HTTP for the Flecs explorer or any other client of the Flecs REST API:
+// entity trees, component values, system timings and queries. Requests are handled while the
+// world progresses, so the server only answers while the game loop runs.
+// The server binds to `bind_address`, or to the loopback address when it's `None`. Anyone who can
+// reach the address can read and change the world, so "0.0.0.0" should only be used on trusted networks.
+pub fn enable_rest(port: u16, bind_address: Option<&str>) {
+    let address = CString::new(bind_address.unwrap_or(REST_DEFAULT_ADDRESS))
+        .unwrap_or_else(|_| CString::new(REST_DEFAULT_ADDRESS).unwrap());
+    unsafe {
+        // Statistics of the world, pipelines and systems for the explorer's performance views
+        ecs_import_c(WORLD.0, Some(FlecsStatsImport), c"FlecsStats".as_ptr());
+        ecs_import_c(WORLD.0, Some(FlecsRestImport), c"FlecsRest".as_ptr());
+        ecs_measure_frame_time(WORLD.0, true);
+        ecs_measure_system_time(WORLD.0, true);
+        // Flecs keeps a copy of the address
+        let rest = EcsRest { port, ipaddr: address.as_ptr() as *mut _, impl_: std::ptr::null_mut() };
+        ecs_set_id(
+            WORLD.0,
+            FLECS_IDEcsRestID_,
+            FLECS_IDEcsRestID_,
+            std::mem::size_of::<EcsRest>(),
+            &rest as *const EcsRest as *const core::ffi::c_void
+        );
+    }
+}
+
+// Stop the server of the active world, the statistics keep being collected
+pub fn disable_rest() {
+    unsafe {
+        ecs_remove_id(WORLD.0, FLECS_IDEcsRestID_, FLECS_IDEcsRestID_);
+    }
+}
@@ -0,0 +1,909 @@
+use crate::bindings::exports::toxoid::engine::ecs::GuestComponent;
+use crate::{stage, struct_members, Component, FieldType, ThreadSafePtr, ARRAY_LENGTH_CACHE, COMPONENT_FIELDS, WORLD};
+use toxoid_flecs::bindings::{
+    ecs_add_id, ecs_get_type_info, ecs_delete, ecs_ensure_id, ecs_get_alive, ecs_get_entities, ecs_get_id, ecs_get_name,
+    ecs_get_parent, ecs_get_path_w_sep, ecs_get_type, ecs_get_typeid, ecs_has_id, ecs_id_is_pair, ecs_is_alive,
+    ecs_lookup_path_w_sep, ecs_make_alive, ecs_make_pair, ecs_modified_id, ecs_new, ecs_os_api, ecs_set_name,
+    EcsModule, EcsPhase, EcsWildcard, ECS_COMPONENT_MASK, ECS_ENTITY_MASK, FLECS_IDEcsComponentID_, FLECS_IDEcsStructID_,
+    FLECS_IDEcsIdentifierID_, FLECS_IDEcsPipelineID_, FLECS_IDEcsPolyID_, ecs_world_t, ecs_is_deferred,
+    ecs_world_get_flags, EcsWorldFrameInProgress
+};
+use core::ffi::{c_char, c_void};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::sync::{Arc, RwLock};
+
+type ecs_entity_t = u64;
+
+// Snapshots of the active world, to save games, rewind or hand a world state to a test.
+// Only what games create is saved: entities with their names, hierarchies, relationships and
+// component values. Components, systems, observers, pipelines and modules are registered by code
+// and must already exist in the world a snapshot is restored into, they are referred to by path.
+//
+// Layout, little-endian:
+//   magic "TOXSNAP\0", u32 version
+//   references: u32 count, (u64 id, string path)
+//   component layouts: u32 count, (u64 component, u32 count, (string member, u8 field type))
+//   entities: u32 count, (u64 id, optional string name)
+//   ids of each entity in the same order: u32 count, (u8 kind, u64 id or u64 first + u64 second,
+//     u8 has data, [u64 component, member values])
+//   singletons: u32 count, (u64 component, member values)
+//
+// Strings are a u32 byte length and UTF-8, optional strings have a u8 presence flag in front.
+// Lists are a u32 count and the elements. Entity members are IDs that are mapped to the restored
+// entities like the entities themselves, pointers are host addresses and saved as null.
+const MAGIC: &[u8; 8] = b"TOXSNAP\0";
+// Version 2 flattens nested structs into their components and saves entity members
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+const ID_KIND_ID: u8 = 0;
+const ID_KIND_PAIR: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    // Not a snapshot
+    BadMagic,
+    // Written by another version of the engine
+    UnsupportedVersion(u32),
+    // The data ends in the middle of the snapshot
+    Truncated,
+    InvalidUtf8,
+    // A component, tag or other entity the snapshot refers to isn't registered in this world
+    UnknownEntity(String),
+    UnknownFieldType(u8),
+    UnknownIdKind(u8),
+    // Fixed-size array member with another number of elements, by member name
+    ArrayLength(String, u32),
+    // Bytes left over after the values of a component
+    TrailingBytes(usize),
+    // Restoring while the world progresses, entities can only be deleted and created between frames
+    WorldInUse
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::BadMagic => write!(f, "Data is not a world snapshot"),
+            SnapshotError::UnsupportedVersion(version) => write!(f, "Unsupported snapshot version {}, expected {}", version, SNAPSHOT_VERSION),
+            SnapshotError::Truncated => write!(f, "Snapshot data is truncated"),
+            SnapshotError::InvalidUtf8 => write!(f, "Snapshot contains a string that is not valid UTF-8"),
+            SnapshotError::UnknownEntity(path) => write!(f, "Snapshot refers to {} which doesn't exist in this world", path),
+            SnapshotError::UnknownFieldType(code) => write!(f, "Snapshot contains unknown member type {}", code),
+            SnapshotError::UnknownIdKind(code) => write!(f, "Snapshot contains unknown ID kind {}", code),
+            SnapshotError::ArrayLength(member, count) => write!(f, "Member {} is an array of {} elements", member, count),
+            SnapshotError::TrailingBytes(count) => write!(f, "Values are followed by {} unexpected bytes", count),
+            SnapshotError::WorldInUse => write!(f, "Snapshots can't be restored while the world progresses")
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+// A member value, lists and strings are copied out of the component
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    String(Option<String>),
+    U8List(Vec<u8>),
+    U16List(Vec<u16>),
+    U32List(Vec<u32>),
+    U64List(Vec<u64>),
+    I8List(Vec<i8>),
+    I16List(Vec<i16>),
+    I32List(Vec<i32>),
+    I64List(Vec<i64>),
+    F32List(Vec<f32>),
+    F64List(Vec<f64>),
+    Pointer(u64),
+    Entity(ecs_entity_t)
+}
+
+impl Value {
+    // Elements of a list, None for other values
+    fn list_len(&self) -> Option<usize> {
+        Some(match self {
+            Value::U8List(values) => values.len(),
+            Value::U16List(values) => values.len(),
+            Value::U32List(values) => values.len(),
+            Value::U64List(values) => values.len(),
+            Value::I8List(values) => values.len(),
+            Value::I16List(values) => values.len(),
+            Value::I32List(values) => values.len(),
+            Value::I64List(values) => values.len(),
+            Value::F32List(values) => values.len(),
+            Value::F64List(values) => values.len(),
+            _ => return None
+        })
+    }
+}
+
+enum SavedId {
+    Id(ecs_entity_t),
+    Pair(ecs_entity_t, ecs_entity_t)
+}
+
+struct SavedEntity {
+    id: ecs_entity_t,
+    name: Option<String>,
+    ids: Vec<(SavedId, Option<(ecs_entity_t, Vec<Value>)>)>
+}
+
+struct Snapshot {
+    refs: Vec<(ecs_entity_t, String)>,
+    layouts: HashMap<ecs_entity_t, Vec<(String, FieldType)>>,
+    entities: Vec<SavedEntity>,
+    singletons: Vec<(ecs_entity_t, Vec<Value>)>
+}
+
+// Serialize the active world
+pub fn snapshot() -> Vec<u8> {
+    unsafe {
+        let user = user_entities();
+        let user_set: HashSet<ecs_entity_t> = user.iter().copied().collect();
+        let mut layouts: HashMap<ecs_entity_t, Arc<[LayoutMember]>> = HashMap::new();
+        let mut refs: Vec<ecs_entity_t> = Vec::new();
+        let add_ref = |id: ecs_entity_t, refs: &mut Vec<ecs_entity_t>| {
+            if !user_set.contains(&id) && !refs.contains(&id) {
+                refs.push(id);
+            }
+        };
+
+        // Collect the ids first, references and layouts are written before the entities
+        let mut entities = Vec::new();
+        for entity in user.iter().copied() {
+            let mut ids = Vec::new();
+            for id in entity_ids(entity) {
+                let saved = if ecs_id_is_pair(id) {
+                    if pair_first(id) == FLECS_IDEcsIdentifierID_ {
+                        // Names are saved with the entity
+                        continue;
+                    }
+                    SavedId::Pair(pair_first(id), pair_second(id))
+                } else if id & !ECS_COMPONENT_MASK != 0 {
+                    // Id flags such as overrides and toggles aren't supported
+                    continue;
+                } else {
+                    SavedId::Id(id)
+                };
+                let type_id = ecs_get_typeid(WORLD.0, id);
+                if type_id != 0 && !layouts.contains_key(&type_id) {
+                    match component_layout(type_id) {
+                        Some(layout) => { layouts.insert(type_id, layout); }
+                        // Data the engine doesn't have a layout for, e.g. Flecs internals
+                        None => continue
+                    }
+                }
+                match saved {
+                    SavedId::Id(id) => add_ref(id, &mut refs),
+                    SavedId::Pair(first, second) => {
+                        add_ref(first, &mut refs);
+                        add_ref(second, &mut refs);
+                    }
+                }
+                ids.push((saved, id, type_id));
+            }
+            entities.push((entity, ids));
+        }
+
+        // Singletons are components set on their own entity
+        let mut singletons = Vec::new();
+        let mut component_ids: Vec<ecs_entity_t> = COMPONENT_FIELDS.lock().unwrap().keys()
+            .filter_map(|path| lookup(path))
+            .collect();
+        component_ids.sort();
+        for component in component_ids {
+            if !ecs_has_id(WORLD.0, component, component) {
+                continue;
+            }
+            if let Some(layout) = component_layout(component) {
+                layouts.entry(component).or_insert(layout);
+                add_ref(component, &mut refs);
+                singletons.push(component);
+            }
+        }
+        let mut layout_ids: Vec<ecs_entity_t> = layouts.keys().copied().collect();
+        layout_ids.sort();
+        for type_id in layout_ids.iter() {
+            add_ref(*type_id, &mut refs);
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        put_u32(&mut out, SNAPSHOT_VERSION);
+
+        put_u32(&mut out, refs.len() as u32);
+        for id in refs.iter() {
+            put_u64(&mut out, *id);
+            put_string(&mut out, &entity_path(*id));
+        }
+
+        put_u32(&mut out, layout_ids.len() as u32);
+        for type_id in layout_ids.iter() {
+            let layout = &layouts[type_id];
+            put_u64(&mut out, *type_id);
+            put_u32(&mut out, layout.len() as u32);
+            for member in layout.iter() {
+                put_string(&mut out, &member.name);
+                out.push(member.field_type as u8);
+            }
+        }
+
+        put_u32(&mut out, entities.len() as u32);
+        for (entity, _) in entities.iter() {
+            put_u64(&mut out, *entity);
+            let name = ecs_get_name(WORLD.0, *entity);
+            if name.is_null() {
+                out.push(0);
+            } else {
+                out.push(1);
+                put_string(&mut out, &CStr::from_ptr(name).to_string_lossy());
+            }
+        }
+
+        for (entity, ids) in entities.iter() {
+            put_u32(&mut out, ids.len() as u32);
+            for (saved, id, type_id) in ids.iter() {
+                match saved {
+                    SavedId::Id(id) => {
+                        out.push(ID_KIND_ID);
+                        put_u64(&mut out, *id);
+                    }
+                    SavedId::Pair(first, second) => {
+                        out.push(ID_KIND_PAIR);
+                        put_u64(&mut out, *first);
+                        put_u64(&mut out, *second);
+                    }
+                }
+                if *type_id == 0 {
+                    out.push(0);
+                    continue;
+                }
+                out.push(1);
+                put_u64(&mut out, *type_id);
+                let ptr = ecs_get_id(WORLD.0, *entity, *id);
+                put_values(&mut out, ptr, &layouts[type_id]);
+            }
+        }
+
+        put_u32(&mut out, singletons.len() as u32);
+        for component in singletons {
+            put_u64(&mut out, component);
+            let ptr = ecs_get_id(WORLD.0, component, component);
+            put_values(&mut out, ptr, &layouts[&component]);
+        }
+        out
+    }
+}
+
+// Replace the entities of the active world with the ones in a snapshot.
+// The snapshot is checked completely before anything in the world is changed.
+// Systems and observers can't restore, queue it with `engine::run_between_frames` instead.
+pub fn restore(bytes: &[u8]) -> Result<(), SnapshotError> {
+    let snapshot = parse(bytes)?;
+    unsafe {
+        if ecs_world_get_flags(WORLD.0) & EcsWorldFrameInProgress != 0 || ecs_is_deferred(WORLD.0) {
+            return Err(SnapshotError::WorldInUse);
+        }
+        let mut ids: HashMap<ecs_entity_t, ecs_entity_t> = HashMap::new();
+        for (id, path) in snapshot.refs.iter() {
+            match lookup(path) {
+                Some(found) => { ids.insert(*id, found); }
+                None => return Err(SnapshotError::UnknownEntity(path.clone()))
+            }
+        }
+
+        for entity in user_entities() {
+            // Children are deleted with their parents
+            if ecs_is_alive(WORLD.0, entity) {
+                ecs_delete(WORLD.0, entity);
+            }
+        }
+
+        // Entities keep their IDs where the index is free, so IDs stored in components stay valid
+        for saved in snapshot.entities.iter() {
+            let entity = if ecs_get_alive(WORLD.0, saved.id & ECS_ENTITY_MASK as u64) == 0 {
+                ecs_make_alive(WORLD.0, saved.id);
+                saved.id
+            } else {
+                ecs_new(WORLD.0)
+            };
+            ids.insert(saved.id, entity);
+        }
+
+        let resolve = |id: &ecs_entity_t| ids.get(id).copied().unwrap_or(*id);
+        for saved in snapshot.entities.iter() {
+            let entity = resolve(&saved.id);
+            for (saved_id, data) in saved.ids.iter() {
+                let id = match saved_id {
+                    SavedId::Id(id) => resolve(id),
+                    SavedId::Pair(first, second) => ecs_make_pair(resolve(first), resolve(second))
+                };
+                match data {
+                    Some((type_id, values)) => {
+                        let layout = &snapshot.layouts[type_id];
+                        set_values(entity, id, resolve(type_id), layout, values, &ids);
+                    }
+                    None => ecs_add_id(WORLD.0, entity, id)
+                }
+            }
+        }
+
+        // Names are unique among siblings, so they're set once the hierarchy is in place
+        for saved in snapshot.entities.iter() {
+            if let Some(name) = saved.name.as_ref() {
+                let name = CString::new(name.as_str()).unwrap_or_default();
+                ecs_set_name(WORLD.0, resolve(&saved.id), name.as_ptr());
+            }
+        }
+
+        for (component, values) in snapshot.singletons.iter() {
+            let component_id = resolve(component);
+            set_values(component_id, component_id, component_id, &snapshot.layouts[component], values, &ids);
+        }
+    }
+    Ok(())
+}
+
+// Entities created by the game, as opposed to registered components, systems,
+// observers, pipelines, phases and modules and the entities they live under
+unsafe fn user_entities() -> Vec<ecs_entity_t> {
+    let entities = ecs_get_entities(WORLD.0);
+    if entities.ids.is_null() || entities.alive_count <= 0 {
+        return Vec::new();
+    }
+    let alive = std::slice::from_raw_parts(entities.ids, entities.alive_count as usize);
+    let registered: HashSet<String> = COMPONENT_FIELDS.lock().unwrap().keys().cloned().collect();
+    let poly = ecs_make_pair(FLECS_IDEcsPolyID_, EcsWildcard);
+
+    let is_engine = |entity: ecs_entity_t| {
+        ecs_has_id(WORLD.0, entity, FLECS_IDEcsComponentID_)
+            || ecs_has_id(WORLD.0, entity, EcsModule)
+            || ecs_has_id(WORLD.0, entity, poly)
+            || ecs_has_id(WORLD.0, entity, FLECS_IDEcsPipelineID_)
+            || ecs_has_id(WORLD.0, entity, EcsPhase)
+            || (ecs_get_parent(WORLD.0, entity) == 0 && registered.contains(&entity_path(entity)))
+    };
+
+    let mut engine: HashSet<ecs_entity_t> = HashSet::new();
+    for entity in alive.iter().copied() {
+        // Everything under an engine entity belongs to it, such as the members of a component
+        let mut current = entity;
+        let mut owned = false;
+        while current != 0 {
+            if is_engine(current) {
+                owned = true;
+                break;
+            }
+            current = ecs_get_parent(WORLD.0, current);
+        }
+        if !owned {
+            continue;
+        }
+        // Groups that systems are created under are kept with them
+        let mut current = entity;
+        while current != 0 && engine.insert(current) {
+            current = ecs_get_parent(WORLD.0, current);
+        }
+    }
+
+    alive.iter().copied().filter(|entity| !engine.contains(entity)).collect()
+}
+
+pub(crate) unsafe fn entity_ids(entity: ecs_entity_t) -> Vec<ecs_entity_t> {
+    let entity_type = ecs_get_type(WORLD.0, entity);
+    if entity_type.is_null() || (*entity_type).count <= 0 {
+        return Vec::new();
+    }
+    std::slice::from_raw_parts((*entity_type).array, (*entity_type).count as usize).to_vec()
+}
+
+pub(crate) unsafe fn pair_first(pair: ecs_entity_t) -> ecs_entity_t {
+    ecs_get_alive(WORLD.0, (pair & ECS_COMPONENT_MASK) >> 32)
+}
+
+pub(crate) unsafe fn pair_second(pair: ecs_entity_t) -> ecs_entity_t {
+    ecs_get_alive(WORLD.0, pair & ECS_ENTITY_MASK as u64)
+}
+
+pub(crate) unsafe fn entity_path(entity: ecs_entity_t) -> String {
+    let path = ecs_get_path_w_sep(WORLD.0, 0, entity, c".".as_ptr(), std::ptr::null());
+    if path.is_null() {
+        return String::new();
+    }
+    let result = CStr::from_ptr(path).to_string_lossy().into_owned();
+    if let Some(free) = ecs_os_api.free_ {
+        free(path as *mut c_void);
+    }
+    result
+}
+
+pub(crate) unsafe fn lookup(path: &str) -> Option<ecs_entity_t> {
+    let c_path = CString::new(path).ok()?;
+    let entity = ecs_lookup_path_w_sep(WORLD.0, 0, c_path.as_ptr(), c".".as_ptr(), std::ptr::null(), true);
+    if entity == 0 { None } else { Some(entity) }
+}
+
+// A member of a component registered through `ComponentType::new`. Members of nested structs
+// are flattened into the component with their path as name, e.g. `transform.position.x`,
+// and `items[2].count` for an element of an array of structs.
+#[derive(Clone)]
+pub(crate) struct LayoutMember {
+    pub name: String,
+    pub field_type: FieldType,
+    // Struct the member is declared in and its offset in the component
+    pub struct_type: ecs_entity_t,
+    pub struct_offset: u32,
+    // Index and offset of the member in that struct
+    pub index: u32,
+    pub offset: u32,
+    // Elements of a fixed-size array of numbers, 0 for other members
+    pub count: u32
+}
+
+// Flattened layouts by world and component type, dropped with the member layouts of the world
+static COMPONENT_LAYOUTS: Lazy<RwLock<HashMap<(usize, ecs_entity_t), Arc<[LayoutMember]>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub(crate) fn forget_component_layouts(world: *mut ecs_world_t) {
+    COMPONENT_LAYOUTS.write().unwrap().retain(|(layout_world, _), _| *layout_world != world as usize);
+}
+
+pub(crate) unsafe fn component_layout(type_id: ecs_entity_t) -> Option<Arc<[LayoutMember]>> {
+    let key = (WORLD.0 as usize, type_id);
+    if let Some(layout) = COMPONENT_LAYOUTS.read().unwrap().get(&key) {
+        return Some(layout.clone());
+    }
+    let mut layout = Vec::new();
+    flatten_layout(type_id, "", 0, &mut layout)?;
+    let layout: Arc<[LayoutMember]> = layout.into();
+    COMPONENT_LAYOUTS.write().unwrap().insert(key, layout.clone());
+    Some(layout)
+}
+
+unsafe fn flatten_layout(type_id: ecs_entity_t, prefix: &str, struct_offset: u32, layout: &mut Vec<LayoutMember>) -> Option<()> {
+    let field_types = COMPONENT_FIELDS.lock().unwrap().get(&entity_path(type_id))?.clone();
+    let members = struct_members(type_id).ok()?;
+    if members.len() != field_types.len() {
+        return None;
+    }
+    for (index, (member, field_type)) in members.iter().zip(field_types).enumerate() {
+        let name = format!("{}{}", prefix, CStr::from_ptr(member.name).to_string_lossy());
+        let count = if member.count > 1 { member.count as u32 } else { 0 };
+        if ecs_has_id(WORLD.0, member.type_, FLECS_IDEcsStructID_) {
+            let size = (*ecs_get_type_info(WORLD.0, member.type_)).size as u32;
+            if count == 0 {
+                flatten_layout(member.type_, &format!("{}.", name), struct_offset + member.offset as u32, layout)?;
+            }
+            for element in 0..count {
+                let element_offset = struct_offset + member.offset as u32 + element * size;
+                flatten_layout(member.type_, &format!("{}[{}].", name, element), element_offset, layout)?;
+            }
+            continue;
+        }
+        // Only arrays of numbers can be accessed, as lists of their elements
+        if count > 0 && field_type.element().is_none() {
+            continue;
+        }
+        layout.push(LayoutMember {
+            name,
+            field_type,
+            struct_type: type_id,
+            struct_offset,
+            index: index as u32,
+            offset: member.offset as u32,
+            count
+        });
+    }
+    Some(())
+}
+
+unsafe fn put_values(out: &mut Vec<u8>, ptr: *const c_void, layout: &[LayoutMember]) {
+    for member in layout.iter() {
+        let struct_ptr = (ptr as *const u8).add(member.struct_offset as usize) as *const c_void;
+        put_value(out, &read_value(struct_ptr, member.offset, member.field_type, member.count));
+    }
+}
+
+// Fixed-size arrays are read from the struct, lists through the pointer to them.
+// Layouts only have arrays of numbers, see `flatten_layout`.
+pub(crate) unsafe fn read_value(ptr: *const c_void, offset: u32, field_type: FieldType, count: u32) -> Value {
+    let member = (ptr as *const u8).add(offset as usize);
+    if count > 0 {
+        return match field_type {
+            FieldType::U8List => Value::U8List(read_array(member, count)),
+            FieldType::U16List => Value::U16List(read_array(member, count)),
+            FieldType::U32List => Value::U32List(read_array(member, count)),
+            FieldType::U64List => Value::U64List(read_array(member, count)),
+            FieldType::I8List => Value::I8List(read_array(member, count)),
+            FieldType::I16List => Value::I16List(read_array(member, count)),
+            FieldType::I32List => Value::I32List(read_array(member, count)),
+            FieldType::I64List => Value::I64List(read_array(member, count)),
+            FieldType::F32List => Value::F32List(read_array(member, count)),
+            FieldType::F64List => Value::F64List(read_array(member, count)),
+            _ => unreachable!("array of {:?} in a component layout", field_type)
+        };
+    }
+    match field_type {
+        FieldType::U8 => Value::U8(*member),
+        FieldType::U16 => Value::U16((member as *const u16).read_unaligned()),
+        FieldType::U32 => Value::U32((member as *const u32).read_unaligned()),
+        FieldType::U64 => Value::U64((member as *const u64).read_unaligned()),
+        FieldType::I8 => Value::I8(*(member as *const i8)),
+        FieldType::I16 => Value::I16((member as *const i16).read_unaligned()),
+        FieldType::I32 => Value::I32((member as *const i32).read_unaligned()),
+        FieldType::I64 => Value::I64((member as *const i64).read_unaligned()),
+        FieldType::F32 => Value::F32((member as *const f32).read_unaligned()),
+        FieldType::F64 => Value::F64((member as *const f64).read_unaligned()),
+        FieldType::Bool => Value::Bool(*member != 0),
+        FieldType::String => {
+            let string = (member as *const *const c_char).read_unaligned();
+            if string.is_null() {
+                Value::String(None)
+            } else {
+                Value::String(Some(CStr::from_ptr(string).to_string_lossy().into_owned()))
+            }
+        }
+        FieldType::U8List => Value::U8List(read_list(member, offset)),
+        FieldType::U16List => Value::U16List(read_list(member, offset)),
+        FieldType::U32List => Value::U32List(read_list(member, offset)),
+        FieldType::U64List => Value::U64List(read_list(member, offset)),
+        FieldType::I8List => Value::I8List(read_list(member, offset)),
+        FieldType::I16List => Value::I16List(read_list(member, offset)),
+        FieldType::I32List => Value::I32List(read_list(member, offset)),
+        FieldType::I64List => Value::I64List(read_list(member, offset)),
+        FieldType::F32List => Value::F32List(read_list(member, offset)),
+        FieldType::F64List => Value::F64List(read_list(member, offset)),
+        FieldType::Pointer => Value::Pointer((member as *const u64).read_unaligned()),
+        FieldType::Entity => Value::Entity((member as *const u64).read_unaligned())
+    }
+}
+
+unsafe fn read_array<T: Copy>(member: *const u8, count: u32) -> Vec<T> {
+    (0..count as usize).map(|index| (member as *const T).add(index).read_unaligned()).collect()
+}
+
+// Lists are allocated by the `set_member_*list` functions which track their lengths
+unsafe fn read_list<T: Copy>(member: *const u8, offset: u32) -> Vec<T> {
+    let list = (member as *const *const T).read_unaligned();
+    if list.is_null() {
+        return Vec::new();
+    }
+    let length = ARRAY_LENGTH_CACHE.lock().unwrap()
+        .get(&(ThreadSafePtr(list as *const c_void), offset))
+        .copied()
+        .unwrap_or(0);
+    std::slice::from_raw_parts(list, length).to_vec()
+}
+
+// Write saved values into a component, matching members by name and type so
+// snapshots survive members being added, removed or reordered.
+// Entity members are mapped through `ids`, IDs that aren't in it are kept while they're alive.
+pub(crate) unsafe fn set_values(entity: ecs_entity_t, id: ecs_entity_t, type_id: ecs_entity_t, layout: &[(String, FieldType)], values: &[Value], ids: &HashMap<ecs_entity_t, ecs_entity_t>) {
+    let ptr = ecs_ensure_id(WORLD.0, entity, id);
+    write_values(ptr, id, type_id, layout, values, Some(ids));
+    ecs_modified_id(WORLD.0, entity, id);
+}
+
+// Initial values of a component of a new entity, decoded before the entity is created
+pub(crate) struct EncodedValues {
+    id: ecs_entity_t,
+    // 0 for tags, which have no values
+    type_id: ecs_entity_t,
+    layout: Vec<(String, FieldType)>,
+    values: Vec<Value>
+}
+
+// Decode the values of a component, its members in the order of its current layout encoded
+// like in snapshots. Fixed-size arrays have exactly the elements of the member.
+pub(crate) unsafe fn decode_encoded(id: ecs_entity_t, data: &[u8]) -> Result<EncodedValues, SnapshotError> {
+    if !ecs_id_is_pair(id) && !ecs_is_alive(WORLD.0, id) {
+        return Err(SnapshotError::UnknownEntity(format!("#{}", id)));
+    }
+    let type_id = ecs_get_typeid(WORLD.0, id);
+    let mut reader = Reader { bytes: data, pos: 0 };
+    let mut layout = Vec::new();
+    let mut values = Vec::new();
+    if type_id != 0 {
+        let members = component_layout(type_id).ok_or_else(|| SnapshotError::UnknownEntity(entity_path(id)))?;
+        for member in members.iter() {
+            let value = reader.value(member.field_type)?;
+            if member.count > 0 && value.list_len() != Some(member.count as usize) {
+                return Err(SnapshotError::ArrayLength(member.name.clone(), member.count));
+            }
+            layout.push((member.name.clone(), member.field_type));
+            values.push(value);
+        }
+    }
+    if reader.pos != data.len() {
+        return Err(SnapshotError::TrailingBytes(data.len() - reader.pos));
+    }
+    Ok(EncodedValues { id, type_id, layout, values })
+}
+
+// Written through the active stage so systems can spawn entities. The values are of this run,
+// pointers and entities are written as they are.
+pub(crate) unsafe fn set_encoded(entity: ecs_entity_t, encoded: &EncodedValues) {
+    if encoded.type_id == 0 {
+        ecs_add_id(stage(), entity, encoded.id);
+        return;
+    }
+    let ptr = ecs_ensure_id(stage(), entity, encoded.id);
+    write_values(ptr, encoded.id, encoded.type_id, &encoded.layout, &encoded.values, None);
+    ecs_modified_id(stage(), entity, encoded.id);
+}
+
+// Values of a snapshot have their entities mapped through `ids` and their pointers cleared,
+// values without `ids` are written as they are
+unsafe fn write_values(ptr: *mut c_void, id: ecs_entity_t, type_id: ecs_entity_t, layout: &[(String, FieldType)], values: &[Value], ids: Option<&HashMap<ecs_entity_t, ecs_entity_t>>) {
+    let current = component_layout(type_id);
+    let current = current.as_deref().unwrap_or(&[]);
+    for ((name, field_type), value) in layout.iter().zip(values) {
+        let current_member = match current.iter().find(|member| &member.name == name && member.field_type == *field_type) {
+            Some(member) => member,
+            None => continue
+        };
+        // Nested members are written through the struct they are declared in,
+        // the component is marked as modified once all of them are set
+        let component = Component {
+            ptr: (ptr as *const u8).add(current_member.struct_offset as usize) as *const c_void,
+            field_offsets: vec![],
+            entity_added: 0,
+            component_type_id: current_member.struct_type,
+            owner_type_id: id,
+            read_only: false
+        };
+        let member = current_member.index;
+        match value.clone() {
+            Value::U8(value) => component.set_member_u8(member, value),
+            Value::U16(value) => component.set_member_u16(member, value),
+            Value::U32(value) => component.set_member_u32(member, value),
+            Value::U64(value) => component.set_member_u64(member, value),
+            Value::I8(value) => component.set_member_i8(member, value),
+            Value::I16(value) => component.set_member_i16(member, value),
+            Value::I32(value) => component.set_member_i32(member, value),
+            Value::I64(value) => component.set_member_i64(member, value),
+            Value::F32(value) => component.set_member_f32(member, value),
+            Value::F64(value) => component.set_member_f64(member, value),
+            Value::Bool(value) => component.set_member_bool(member, value),
+            Value::String(Some(value)) => component.set_member_string(member, value),
+            Value::String(None) => {}
+            Value::U8List(value) => component.set_member_u8list(member, value),
+            Value::U16List(value) => component.set_member_u16list(member, value),
+            Value::U32List(value) => component.set_member_u32list(member, value),
+            Value::U64List(value) => component.set_member_u64list(member, value),
+            Value::I8List(value) => component.set_member_i8list(member, value),
+            Value::I16List(value) => component.set_member_i16list(member, value),
+            Value::I32List(value) => component.set_member_i32list(member, value),
+            Value::I64List(value) => component.set_member_i64list(member, value),
+            Value::F32List(value) => component.set_member_f32list(member, value),
+            Value::F64List(value) => component.set_member_f64list(member, value),
+            Value::Pointer(value) if ids.is_none() => component.set_member_pointer(member, value),
+            // Addresses of another run of the engine would be dangling
+            Value::Pointer(_) => component.set_member_pointer(member, 0),
+            Value::Entity(value) => match ids {
+                Some(ids) => component.set_member_u64(member, map_entity(value, ids)),
+                None => component.set_member_u64(member, value)
+            }
+        }
+    }
+}
+
+unsafe fn map_entity(entity: ecs_entity_t, ids: &HashMap<ecs_entity_t, ecs_entity_t>) -> ecs_entity_t {
+    match ids.get(&entity) {
+        Some(mapped) => *mapped,
+        None if entity != 0 && ecs_is_alive(WORLD.0, entity) => entity,
+        None => 0
+    }
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_string(out: &mut Vec<u8>, value: &str) {
+    put_u32(out, value.len() as u32);
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn put_list<T: Copy>(out: &mut Vec<u8>, values: &[T], put: impl Fn(&mut Vec<u8>, T)) {
+    put_u32(out, values.len() as u32);
+    values.iter().for_each(|value| put(out, *value));
+}
+
+fn put_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::U8(value) => out.push(*value),
+        Value::U16(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::U32(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::U64(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::I8(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::I16(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::I32(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::I64(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::F32(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::F64(value) => out.extend_from_slice(&value.to_le_bytes()),
+        Value::Bool(value) => out.push(*value as u8),
+        Value::String(None) => out.push(0),
+        Value::String(Some(value)) => {
+            out.push(1);
+            put_string(out, value);
+        }
+        Value::U8List(values) => put_list(out, values, |out, value| out.push(value)),
+        Value::U16List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::U32List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::U64List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::I8List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::I16List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::I32List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::I64List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::F32List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::F64List(values) => put_list(out, values, |out, value| out.extend_from_slice(&value.to_le_bytes())),
+        Value::Pointer(_) => put_u64(out, 0),
+        Value::Entity(value) => put_u64(out, *value)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let end = self.pos.checked_add(N).ok_or(SnapshotError::Truncated)?;
+        let bytes = self.bytes.get(self.pos..end).ok_or(SnapshotError::Truncated)?;
+        self.pos = end;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    // Counts are checked against the remaining data so a corrupt count can't allocate gigabytes
+    fn count(&mut self, min_size: usize) -> Result<usize, SnapshotError> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(min_size) > self.bytes.len() - self.pos {
+            return Err(SnapshotError::Truncated);
+        }
+        Ok(count)
+    }
+
+    fn string(&mut self) -> Result<String, SnapshotError> {
+        let length = self.count(1)?;
+        let bytes = &self.bytes[self.pos..self.pos + length];
+        self.pos += length;
+        String::from_utf8(bytes.to_vec()).map_err(|_| SnapshotError::InvalidUtf8)
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.string()?))
+        }
+    }
+
+    fn list<T, const N: usize>(&mut self, from_bytes: fn([u8; N]) -> T) -> Result<Vec<T>, SnapshotError> {
+        let count = self.count(N)?;
+        (0..count).map(|_| Ok(from_bytes(self.take()?))).collect()
+    }
+
+    fn value(&mut self, field_type: FieldType) -> Result<Value, SnapshotError> {
+        Ok(match field_type {
+            FieldType::U8 => Value::U8(self.u8()?),
+            FieldType::U16 => Value::U16(u16::from_le_bytes(self.take()?)),
+            FieldType::U32 => Value::U32(self.u32()?),
+            FieldType::U64 => Value::U64(self.u64()?),
+            FieldType::I8 => Value::I8(i8::from_le_bytes(self.take()?)),
+            FieldType::I16 => Value::I16(i16::from_le_bytes(self.take()?)),
+            FieldType::I32 => Value::I32(i32::from_le_bytes(self.take()?)),
+            FieldType::I64 => Value::I64(i64::from_le_bytes(self.take()?)),
+            FieldType::F32 => Value::F32(f32::from_le_bytes(self.take()?)),
+            FieldType::F64 => Value::F64(f64::from_le_bytes(self.take()?)),
+            FieldType::Bool => Value::Bool(self.u8()? != 0),
+            FieldType::String => Value::String(self.optional_string()?),
+            FieldType::U8List => Value::U8List(self.list(u8::from_le_bytes)?),
+            FieldType::U16List => Value::U16List(self.list(u16::from_le_bytes)?),
+            FieldType::U32List => Value::U32List(self.list(u32::from_le_bytes)?),
+            FieldType::U64List => Value::U64List(self.list(u64::from_le_bytes)?),
+            FieldType::I8List => Value::I8List(self.list(i8::from_le_bytes)?),
+            FieldType::I16List => Value::I16List(self.list(i16::from_le_bytes)?),
+            FieldType::I32List => Value::I32List(self.list(i32::from_le_bytes)?),
+            FieldType::I64List => Value::I64List(self.list(i64::from_le_bytes)?),
+            FieldType::F32List => Value::F32List(self.list(f32::from_le_bytes)?),
+            FieldType::F64List => Value::F64List(self.list(f64::from_le_bytes)?),
+            FieldType::Pointer => Value::Pointer(self.u64()?),
+            FieldType::Entity => Value::Entity(self.u64()?)
+        })
+    }
+
+    fn values(&mut self, layouts: &HashMap<ecs_entity_t, Vec<(String, FieldType)>>, type_id: ecs_entity_t) -> Result<Vec<Value>, SnapshotError> {
+        let layout = layouts.get(&type_id).ok_or_else(|| SnapshotError::UnknownEntity(format!("#{}", type_id)))?;
+        layout.iter().map(|(_, field_type)| self.value(*field_type)).collect()
+    }
+}
+
+fn parse(bytes: &[u8]) -> Result<Snapshot, SnapshotError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.take::<8>().map_err(|_| SnapshotError::BadMagic)? != *MAGIC {
+        return Err(SnapshotError::BadMagic);
+    }
+    let version = reader.u32()?;
+    if version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+
+    let mut refs = Vec::new();
+    for _ in 0..reader.count(12)? {
+        refs.push((reader.u64()?, reader.string()?));
+    }
+
+    let mut layouts = HashMap::new();
+    for _ in 0..reader.count(12)? {
+        let type_id = reader.u64()?;
+        let mut layout = Vec::new();
+        for _ in 0..reader.count(5)? {
+            let name = reader.string()?;
+            let code = reader.u8()?;
+            // Unknown codes are pointers when registering, but here they mean a newer format
+            if code > FieldType::Pointer as u8 && code != FieldType::Entity as u8 {
+                return Err(SnapshotError::UnknownFieldType(code));
+            }
+            layout.push((name, FieldType::from_code(code)));
+        }
+        layouts.insert(type_id, layout);
+    }
+
+    let mut entities = Vec::new();
+    for _ in 0..reader.count(9)? {
+        entities.push(SavedEntity { id: reader.u64()?, name: reader.optional_string()?, ids: Vec::new() });
+    }
+    for entity in entities.iter_mut() {
+        for _ in 0..reader.count(10)? {
+            let id = match reader.u8()? {
+                ID_KIND_ID => SavedId::Id(reader.u64()?),
+                ID_KIND_PAIR => SavedId::Pair(reader.u64()?, reader.u64()?),
+                kind => return Err(SnapshotError::UnknownIdKind(kind))
+            };
+            let data = match reader.u8()? {
+                0 => None,
+                _ => {
+                    let type_id = reader.u64()?;
+                    Some((type_id, reader.values(&layouts, type_id)?))
+                }
+            };
+            entity.ids.push((id, data));
+        }
+    }
+
+    let mut singletons = Vec::new();
+    for _ in 0..reader.count(8)? {
+        let component = reader.u64()?;
+        singletons.push((component, reader.values(&layouts, component)?));
+    }
+
+    Ok(Snapshot { refs, layouts, entities, singletons })
+}
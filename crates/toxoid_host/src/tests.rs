@@ -0,0 +1,354 @@
+use super::*;
+use crate::worlds::*;
+use std::sync::{MutexGuard, PoisonError};
+
+// The active world is global, tests using it take turns
+static WORLD_LOCK: Mutex<()> = Mutex::new(());
+
+// Runs systems like the engine's trampoline, their callback handle is the function to call
+unsafe extern "C" fn test_trampoline(iter: *mut ecs_iter_t) {
+    let callback: fn(*mut ecs_iter_t) = std::mem::transmute((*iter).callback_ctx);
+    let previous = set_stage((*iter).world);
+    callback(iter);
+    set_stage(previous);
+}
+
+fn lock_world() -> MutexGuard<'static, ()> {
+    let guard = WORLD_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    unsafe { QUERY_TRAMPOLINE = Some(test_trampoline) };
+    guard
+}
+
+fn system(expr: &str, callback: fn(*mut ecs_iter_t)) -> System {
+    System::new(SystemDesc {
+        name: None,
+        tick_rate: None,
+        callback: callback as usize as PointerT,
+        query_desc: QueryDesc { expr: expr.to_string() },
+        is_guest: false
+    })
+}
+
+fn component(name: &str, members: &[(&str, FieldType)]) -> ecs_entity_t {
+    ComponentType::new(ComponentDesc {
+        name: name.to_string(),
+        member_names: members.iter().map(|(member, _)| member.to_string()).collect(),
+        member_types: members.iter().map(|(_, field_type)| *field_type as u8).collect(),
+        member_type_ids: vec![],
+        member_counts: vec![]
+    }).get_id()
+}
+
+fn view(entity: ecs_entity_t, component: ecs_entity_t) -> Component {
+    Component::new(Entity { id: entity }.get(component), entity, component)
+}
+
+fn lookup(name: &CStr) -> ecs_entity_t {
+    unsafe { ecs_lookup(WORLD.0, name.as_ptr()) }
+}
+
+fn named_entity(name: &str) -> ecs_entity_t {
+    Entity::new(EntityDesc { name: Some(name.to_string()), add: None, prefab: false, values: None }, None).id
+}
+
+#[test]
+fn worlds_are_created_switched_and_destroyed() {
+    let _world = lock_world();
+    let world = create_world();
+    assert!(world_exists(world));
+    let entity = with_world(world, || {
+        assert_eq!(active_world(), world);
+        named_entity("OnlyInCreatedWorld")
+    });
+    // The previous world is active again and doesn't see the entity
+    assert_eq!(active_world(), DEFAULT_WORLD);
+    assert_eq!(lookup(c"OnlyInCreatedWorld"), 0);
+    assert_eq!(with_world(world, || lookup(c"OnlyInCreatedWorld")), entity);
+
+    // Neither the active nor the default world can be destroyed
+    assert!(set_active_world(world));
+    assert!(!destroy_world(world));
+    assert!(set_active_world(DEFAULT_WORLD));
+    assert!(!destroy_world(DEFAULT_WORLD));
+
+    assert!(destroy_world(world));
+    assert!(!world_exists(world));
+    assert!(!set_active_world(world));
+    assert!(!destroy_world(world));
+    assert_eq!(active_world(), DEFAULT_WORLD);
+}
+
+fn draw(stream: &str, count: usize) -> Vec<u64> {
+    let stream = random::named_stream(stream);
+    (0..count).map(|_| random::next_u64(stream)).collect()
+}
+
+#[test]
+fn same_seed_gives_same_numbers() {
+    let _world = lock_world();
+    random::set_seed(42);
+    let first = draw("spawn", 16);
+    random::set_seed(42);
+    assert_eq!(draw("spawn", 16), first);
+    random::set_seed(43);
+    assert_ne!(draw("spawn", 16), first);
+}
+
+#[test]
+fn named_streams_are_independent() {
+    let _world = lock_world();
+    random::set_seed(7);
+    let alone = draw("food", 8);
+    random::set_seed(7);
+    let interleaved: Vec<u64> = (0..8)
+        .map(|_| {
+            draw("enemies", 3);
+            draw("food", 1)[0]
+        })
+        .collect();
+    assert_eq!(interleaved, alone);
+    assert_ne!(draw("enemies", 8), draw("food", 8));
+}
+
+#[test]
+fn worlds_have_their_own_random_streams() {
+    let _world = lock_world();
+    random::set_seed(9);
+    let world = create_world();
+    with_world(world, || {
+        assert_eq!(random::seed(), 0);
+        random::set_seed(9);
+    });
+    // Drawing from one world doesn't move the streams of the other
+    let drawn = draw("loot", 4);
+    assert_eq!(with_world(world, || draw("loot", 4)), drawn);
+    assert_eq!(random::seed(), 9);
+
+    // A world created in place of a destroyed one starts over
+    assert!(destroy_world(world));
+    let world = create_world();
+    assert_eq!(with_world(world, random::seed), 0);
+    assert!(destroy_world(world));
+}
+
+fn holder() -> ecs_entity_t {
+    component("Holder", &[
+        ("target", FieldType::Entity),
+        ("label", FieldType::String),
+        ("items", FieldType::U64List),
+        ("address", FieldType::Pointer)
+    ])
+}
+
+#[test]
+fn snapshot_restores_values_and_entity_references() {
+    let _world = lock_world();
+    let world = create_world();
+    with_world(world, || {
+        let holder = holder();
+        let target = named_entity("SnapshotTarget");
+        let value = view(named_entity("SnapshotHolder"), holder);
+        value.set_member_u64(0, target);
+        value.set_member_string(1, "chest".to_string());
+        value.set_member_u64list(2, vec![3, 7]);
+        value.set_member_pointer(3, 0xDEAD_BEEF);
+        let bytes = snapshot::snapshot();
+
+        // Taking the target's index makes it come back under another ID
+        unsafe { ecs_delete(WORLD.0, target) };
+        component("Filler", &[("value", FieldType::U32)]);
+        snapshot::restore(&bytes).unwrap();
+
+        let restored_target = lookup(c"SnapshotTarget");
+        assert_ne!(restored_target, 0);
+        assert_ne!(restored_target, target);
+        let value = view(lookup(c"SnapshotHolder"), holder);
+        assert_eq!(value.get_member_u64(0), restored_target);
+        assert_eq!(value.get_member_string(1), "chest");
+        assert_eq!(value.get_member_u64list(2), vec![3, 7]);
+        // Host addresses of the saved run aren't restored
+        assert_eq!(value.get_member_pointer(3), 0);
+    });
+    assert!(destroy_world(world));
+}
+
+#[test]
+fn snapshot_of_another_version_is_rejected() {
+    let _world = lock_world();
+    let mut bytes = snapshot::snapshot();
+    for version in [0, snapshot::SNAPSHOT_VERSION + 1] {
+        bytes[8..12].copy_from_slice(&version.to_le_bytes());
+        assert_eq!(snapshot::restore(&bytes), Err(snapshot::SnapshotError::UnsupportedVersion(version)));
+    }
+}
+
+#[test]
+fn snapshot_with_an_unknown_id_kind_is_rejected() {
+    let _world = lock_world();
+    let world = create_world();
+    with_world(world, || {
+        let tag = component("SnapshotTag", &[]);
+        Entity { id: named_entity("Tagged") }.add(tag);
+        let mut bytes = snapshot::snapshot();
+        // The entity's only ID is the tag, saved as a plain ID
+        let mut saved_id = vec![0];
+        saved_id.extend_from_slice(&tag.to_le_bytes());
+        let kind = bytes.windows(saved_id.len()).rposition(|window| window == saved_id.as_slice()).unwrap();
+        bytes[kind] = 2;
+        assert_eq!(snapshot::restore(&bytes), Err(snapshot::SnapshotError::UnknownIdKind(2)));
+        assert_ne!(lookup(c"Tagged"), 0);
+    });
+    assert!(destroy_world(world));
+}
+
+static SNAPSHOT: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+static RESTORED: Mutex<Option<Result<(), snapshot::SnapshotError>>> = Mutex::new(None);
+
+fn restore_snapshot(_iter: *mut ecs_iter_t) {
+    let result = snapshot::restore(&SNAPSHOT.lock().unwrap());
+    *RESTORED.lock().unwrap() = Some(result);
+}
+
+#[test]
+fn snapshot_is_not_restored_while_the_world_progresses() {
+    let _world = lock_world();
+    let world = create_world();
+    with_world(world, || {
+        *SNAPSHOT.lock().unwrap() = snapshot::snapshot();
+        let entity = named_entity("KeptWhileProgressing");
+        system("", restore_snapshot).build();
+        unsafe { ecs_progress(WORLD.0, 0.0) };
+        assert_eq!(RESTORED.lock().unwrap().take(), Some(Err(snapshot::SnapshotError::WorldInUse)));
+        assert_eq!(lookup(c"KeptWhileProgressing"), entity);
+    });
+    assert!(destroy_world(world));
+}
+
+#[test]
+fn entity_json_round_trips() {
+    let _world = lock_world();
+    let world = create_world();
+    with_world(world, || {
+        let (position, holder) = (component("Position", &[("x", FieldType::I32), ("y", FieldType::I32)]), holder());
+        let chest = named_entity("JsonChest");
+        let value = view(chest, position);
+        value.set_member_i32(0, 32);
+        value.set_member_i32(1, -64);
+        let lid = named_entity("JsonLid");
+        view(lid, holder).set_member_string(1, "hinged".to_string());
+        Entity { id: lid }.child_of(chest);
+        let json = json::entity_to_json(chest, true).unwrap();
+
+        unsafe { ecs_delete(WORLD.0, chest) };
+        let restored = json::entity_from_json(&json, None).unwrap();
+        assert_eq!(json::entity_to_json(restored, true).unwrap(), json);
+        assert_eq!(lookup(c"JsonChest"), restored);
+        assert_eq!(view(restored, position).get_member_i32(1), -64);
+        assert_eq!(view(lookup(c"JsonChest.JsonLid"), holder).get_member_string(1), "hinged");
+    });
+    assert!(destroy_world(world));
+}
+
+#[test]
+fn entity_json_of_a_dead_entity_is_an_error() {
+    let _world = lock_world();
+    let entity = named_entity("JsonDeleted");
+    unsafe { ecs_delete(WORLD.0, entity) };
+    assert_eq!(json::entity_to_json(entity, false), Err(json::JsonError::NotAlive(entity)));
+    assert_eq!(json::entity_to_json(0, false), Err(json::JsonError::NotAlive(0)));
+}
+
+#[test]
+fn entity_json_with_unknown_keys_is_rejected() {
+    let _world = lock_world();
+    let world = create_world();
+    with_world(world, || {
+        component("Position", &[("x", FieldType::I32), ("y", FieldType::I32)]);
+        let error = json::entity_from_json(r#"{ "name": "JsonTypo", "component": { "Position": { "x": 1 } } }"#, None);
+        assert_eq!(error, Err(json::JsonError::UnknownKey("component".to_string())));
+        assert_eq!(lookup(c"JsonTypo"), 0);
+    });
+    assert!(destroy_world(world));
+}
+
+// Request a page from the REST server, progressing the world until it's answered
+#[cfg(feature = "rest")]
+fn rest_request(port: u16, path: &str) -> String {
+    use std::io::{Read, Write};
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path);
+    let client = std::thread::spawn(move || {
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+        response
+    });
+    while !client.is_finished() {
+        unsafe { ecs_progress(WORLD.0, 0.0) };
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    client.join().unwrap()
+}
+
+#[cfg(feature = "rest")]
+#[test]
+fn rest_server_answers_until_disabled() {
+    let _world = lock_world();
+    let world = create_world();
+    let port = rest::REST_DEFAULT_PORT + 1;
+    with_world(world, || {
+        named_entity("Inspected");
+        rest::enable_rest(port, None);
+        unsafe { ecs_progress(WORLD.0, 0.0) };
+        let response = rest_request(port, "/entity/Inspected");
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+        rest::disable_rest();
+        unsafe { ecs_progress(WORLD.0, 0.0) };
+        assert!(std::net::TcpStream::connect(("127.0.0.1", port)).is_err());
+    });
+    assert!(destroy_world(world));
+}
+
+static DONE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+// Thread of each callback, and whether host calls went to the stage it was given
+static THREADS: Mutex<Vec<(std::thread::ThreadId, bool)>> = Mutex::new(Vec::new());
+
+fn mark_done(iter: *mut ecs_iter_t) {
+    let on_stage = unsafe { stage() == (*iter).world && stage() != WORLD.0 };
+    THREADS.lock().unwrap().push((std::thread::current().id(), on_stage));
+    let done = DONE.load(std::sync::atomic::Ordering::SeqCst);
+    let entities = unsafe { std::slice::from_raw_parts((*iter).entities, (*iter).count as usize) };
+    // Goes to the stage of this thread, writing to the world would assert
+    entities.iter().for_each(|entity| Entity { id: *entity }.add(done));
+}
+
+#[test]
+fn multithreaded_systems_write_to_their_stage() {
+    let _world = lock_world();
+    let world = create_world();
+    with_world(world, || {
+        let (work, done) = (component("ThreadedWork", &[]), component("ThreadedDone", &[]));
+        DONE.store(done, std::sync::atomic::Ordering::SeqCst);
+        let entities: Vec<ecs_entity_t> = (0..64)
+            .map(|_| Entity::new(EntityDesc { name: None, add: Some(vec![work]), prefab: false, values: None }, None).id)
+            .collect();
+        toxoid_set_threads(4);
+        let system = system("ThreadedWork", mark_done);
+        system.multi_threaded(true);
+        system.build();
+        unsafe { ecs_progress(WORLD.0, 0.0) };
+
+        // Commands of the stages are merged at the end of the frame
+        assert!(entities.iter().all(|entity| Entity { id: *entity }.has(done)));
+        let calls = std::mem::take(&mut *THREADS.lock().unwrap());
+        assert!(calls.iter().all(|(_, on_stage)| *on_stage));
+        let mut threads: Vec<_> = calls.iter().map(|(thread, _)| format!("{:?}", thread)).collect();
+        threads.sort();
+        threads.dedup();
+        // The main thread runs one of the stages, the worker threads the others
+        assert!(threads.len() > 1);
+    });
+    assert!(destroy_world(world));
+}
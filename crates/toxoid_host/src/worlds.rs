@@ -0,0 +1,118 @@
+use crate::{EcsWorldPtr, WORLD};
+use toxoid_flecs::bindings::{ecs_fini, ecs_init, ecs_progress};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// ECS worlds side by side, such as a simulation, a UI and an editor preview world.
+// Host calls operate on the active world, so entities, systems and singletons are created
+// in whichever world is active at the time and their IDs are only meaningful in that world.
+pub type WorldId = u32;
+
+// The world the engine starts with
+pub const DEFAULT_WORLD: WorldId = 0;
+
+struct Worlds {
+    worlds: HashMap<WorldId, EcsWorldPtr>,
+    next_id: WorldId,
+    active: WorldId
+}
+
+static WORLDS: Lazy<Mutex<Worlds>> = Lazy::new(|| {
+    let mut worlds = HashMap::new();
+    // The default world is created on first use of WORLD
+    worlds.insert(DEFAULT_WORLD, EcsWorldPtr(unsafe { WORLD.0 }));
+    Mutex::new(Worlds { worlds, next_id: DEFAULT_WORLD + 1, active: DEFAULT_WORLD })
+});
+
+pub fn create_world() -> WorldId {
+    let world = unsafe { ecs_init() };
+    unsafe { crate::commands::init(world) };
+    let mut worlds = WORLDS.lock().unwrap();
+    let id = worlds.next_id;
+    worlds.next_id += 1;
+    worlds.worlds.insert(id, EcsWorldPtr(world));
+    id
+}
+
+// Delete a world and everything in it, the default and the active world can't be destroyed
+pub fn destroy_world(id: WorldId) -> bool {
+    let world = {
+        let mut worlds = WORLDS.lock().unwrap();
+        if id == DEFAULT_WORLD || id == worlds.active {
+            return false;
+        }
+        match worlds.worlds.remove(&id) {
+            Some(world) => world,
+            None => return false
+        }
+    };
+    crate::forget_member_layouts(world.0);
+    crate::forget_queries(world.0);
+    unsafe { ecs_fini(world.0) };
+    crate::commands::forget(world.0);
+    crate::random::forget(world.0);
+    true
+}
+
+pub fn world_exists(id: WorldId) -> bool {
+    WORLDS.lock().unwrap().worlds.contains_key(&id)
+}
+
+pub fn active_world() -> WorldId {
+    WORLDS.lock().unwrap().active
+}
+
+// Make a world the target of host calls, false if it doesn't exist
+pub fn set_active_world(id: WorldId) -> bool {
+    let mut worlds = WORLDS.lock().unwrap();
+    let world = match worlds.worlds.get(&id) {
+        Some(world) => world.0,
+        None => return false
+    };
+    worlds.active = id;
+    unsafe { WORLD.0 = world };
+    true
+}
+
+// Run with a world active, the previously active world is restored afterwards.
+// Panics if the world doesn't exist.
+pub fn with_world<R>(id: WorldId, f: impl FnOnce() -> R) -> R {
+    let _restore = RestoreWorld(active_world());
+    if !set_active_world(id) {
+        panic!("World {} doesn't exist", id);
+    }
+    f()
+}
+
+// Restores the active world when dropped, also when a callback panics
+struct RestoreWorld(WorldId);
+
+impl Drop for RestoreWorld {
+    fn drop(&mut self) {
+        set_active_world(self.0);
+    }
+}
+
+// Progress a world, running its systems with it active
+pub fn progress_world(id: WorldId, delta_time: f32) -> bool {
+    with_world(id, || unsafe { ecs_progress(WORLD.0, delta_time) })
+}
+
+// Delete everything in the active world and start it over empty.
+// Finishing the world runs hooks and observers, which can look up worlds, so it runs unlocked.
+pub(crate) fn reset_active_world() {
+    let active = active_world();
+    unsafe {
+        crate::forget_member_layouts(WORLD.0);
+        crate::forget_queries(WORLD.0);
+        ecs_fini(WORLD.0);
+        crate::commands::forget(WORLD.0);
+        crate::random::forget(WORLD.0);
+        let world = ecs_init();
+        crate::commands::init(world);
+        let mut worlds = WORLDS.lock().unwrap();
+        worlds.worlds.insert(active, EcsWorldPtr(world));
+        WORLD.0 = world;
+    }
+}
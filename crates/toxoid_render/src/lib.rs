@@ -49,10 +49,10 @@ pub trait Renderer2D {
     fn draw_sprite(sprite: &Box<dyn Sprite>, x: f32, y: f32);
     // Draw render target
     fn draw_render_target(source: &Box<dyn RenderTarget>, sx: f32, sy: f32, sw: f32, sh: f32, dx: f32, dy: f32, dw: f32, dh: f32, blend_mode: u8);
-    // Draw a filled rect
-    fn draw_filled_rect(pos: &Position, size: &Size, color: &Color);
-    // Draw a line
-    fn draw_line(ax: f32, ay: f32, bx: f32, by: f32);
+    // Draw a filled rect, color is RGBA
+    fn draw_filled_rect(x: f32, y: f32, width: f32, height: f32, color: [f32; 4]);
+    // Draw a line, color is RGBA
+    fn draw_line(ax: f32, ay: f32, bx: f32, by: f32, color: [f32; 4]);
     // Clear sprite
     fn clear_sprite(render_target: &Box<dyn RenderTarget>, x: i32, y: i32, width: i32, height: i32);
     // Clear entire canvas
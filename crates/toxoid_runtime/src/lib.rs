@@ -2,6 +2,38 @@
 bindgen!({
     world: "toxoid-component-world",
     path: "../toxoid_guest/wit",
+    // Host functions that can fail on a bad guest request, the error traps the calling guest
+    trappable_imports: [
+        "get-singleton", "[method]callback.run", "[method]system.order-by",
+        "[method]iter.column", "[method]iter.set-column", "[method]iter.field-data", "[method]iter.set-field-data",
+        // Assets, a guest can only use the handles of assets it loaded itself
+        "status", "size", "data", "draw-sprite",
+        // Component members, checked against the Flecs struct metadata of the component type
+        "[method]component.set-member-bool", "[method]component.get-member-bool",
+        "[method]component.set-member-f32", "[method]component.get-member-f32",
+        "[method]component.set-member-f64", "[method]component.get-member-f64",
+        "[method]component.set-member-i16", "[method]component.get-member-i16",
+        "[method]component.set-member-i32", "[method]component.get-member-i32",
+        "[method]component.set-member-i64", "[method]component.get-member-i64",
+        "[method]component.set-member-i8", "[method]component.get-member-i8",
+        "[method]component.set-member-pointer", "[method]component.get-member-pointer",
+        "[method]component.get-member-component",
+        "[method]component.set-member-string", "[method]component.get-member-string",
+        "[method]component.set-member-u16", "[method]component.get-member-u16",
+        "[method]component.set-member-u32", "[method]component.get-member-u32",
+        "[method]component.set-member-u64", "[method]component.get-member-u64",
+        "[method]component.set-member-u8", "[method]component.get-member-u8",
+        "[method]component.set-member-f32list", "[method]component.get-member-f32list",
+        "[method]component.set-member-f64list", "[method]component.get-member-f64list",
+        "[method]component.set-member-i16list", "[method]component.get-member-i16list",
+        "[method]component.set-member-i32list", "[method]component.get-member-i32list",
+        "[method]component.set-member-i64list", "[method]component.get-member-i64list",
+        "[method]component.set-member-i8list", "[method]component.get-member-i8list",
+        "[method]component.set-member-u16list", "[method]component.get-member-u16list",
+        "[method]component.set-member-u32list", "[method]component.get-member-u32list",
+        "[method]component.set-member-u64list", "[method]component.get-member-u64list",
+        "[method]component.set-member-u8list", "[method]component.get-member-u8list",
+    ],
     with: {
         // Specify that our host resource is going to point to the `ComponentTypeProxy`, `ComponentProxy`, etc.
         "toxoid-component:component/ecs/component-type": ComponentTypeProxy,
@@ -17,62 +49,219 @@ bindgen!({
     },
 });
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use core::ffi::c_void;
 use toxoid_api::{EcsEntityT, GuestObserver};
 use toxoid_component::component::ecs::PointerT;
 use toxoid_host::bindings::exports::toxoid::engine::ecs::{Guest, GuestCallback, GuestComponent, GuestComponentType, GuestEntity, GuestIter, GuestPhase, GuestPipeline, GuestQuery, GuestSystem};
-use toxoid_host::ToxoidApi;
+use toxoid_host::{FieldType, MemberAccess, ToxoidApi};
+use toxoid_host::log::{LogLevel, LogRecord};
+use toxoid_host::worlds::{with_world, world_exists, WorldId};
+use toxoid_host::bindings::exports::toxoid::engine::engine::Guest as EngineGuest;
 use wasmtime::component::{bindgen, Component, Linker, Resource, ResourceTable};
-use wasmtime::{Config, Engine, OptLevel, Result, Store};
-use wasmtime_wasi::{WasiCtx, WasiView, WasiCtxBuilder};
+use wasmtime::{Config, Engine, ResourceLimiter, Result, Store, Trap};
+// Re-exported for `EngineSettings`
+pub use wasmtime::OptLevel;
+use wasmtime_wasi::{WasiCtx, WasiView};
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
-
-pub struct ComponentTypeProxy {
-    ptr: *mut toxoid_host::ComponentType
+use std::sync::{Arc, Mutex, MutexGuard, Once, PoisonError, TryLockError};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::hash::{Hash, Hasher};
+use sha2::{Digest, Sha256};
+
+mod manifest;
+#[cfg(test)]
+mod tests;
+pub use manifest::*;
+
+// Host objects handed to guests as resources. A proxy owns the boxed host object it points to,
+// which is freed when the guest drops the resource or when the guest's store is dropped.
+macro_rules! proxy {
+    ($proxy:ident, $host:ty) => {
+        pub struct $proxy {
+            pub ptr: *mut $host
+        }
+        unsafe impl Send for $proxy {}
+        impl $proxy {
+            // Takes ownership of a pointer returned by `Box::into_raw`
+            fn new(ptr: *mut $host) -> Self {
+                LIVE_PROXIES.fetch_add(1, Ordering::SeqCst);
+                $proxy { ptr }
+            }
+        }
+        impl Drop for $proxy {
+            fn drop(&mut self) {
+                LIVE_PROXIES.fetch_sub(1, Ordering::SeqCst);
+                if !self.ptr.is_null() {
+                    drop(unsafe { Box::from_raw(self.ptr) });
+                }
+            }
+        }
+    };
 }
-unsafe impl Send for ComponentTypeProxy {}
-pub struct ComponentProxy {
-    ptr: *mut toxoid_host::Component
+
+proxy!(ComponentTypeProxy, toxoid_host::ComponentType);
+proxy!(ComponentProxy, toxoid_host::Component);
+proxy!(EntityProxy, toxoid_host::Entity);
+proxy!(QueryProxy, toxoid_host::Query);
+proxy!(SystemProxy, toxoid_host::System);
+proxy!(CallbackProxy, toxoid_host::Callback);
+proxy!(IterProxy, toxoid_host::Iter);
+proxy!(ObserverProxy, toxoid_host::Observer);
+proxy!(PipelineProxy, toxoid_host::Pipeline);
+proxy!(PhaseProxy, toxoid_host::Phase);
+
+// Number of proxies alive across all guests, a guest that was unloaded leaves none behind
+static LIVE_PROXIES: AtomicUsize = AtomicUsize::new(0);
+
+pub fn live_proxies() -> usize {
+    LIVE_PROXIES.load(Ordering::SeqCst)
 }
-unsafe impl Send for ComponentProxy {}
-pub struct EntityProxy {
-    ptr: *mut toxoid_host::Entity
+
+// StoreState is the state of the WASM store.
+// Every guest component gets its own store, so resources and
+// singletons are never shared between guests.
+pub struct StoreState {
+    pub ctx: WasiCtx,
+    pub table: ResourceTable,
+    pub guest_id: u32,
+    // Instance of the guest the store belongs to, reloads and restarts keep the guest ID
+    pub instance: u64,
+    pub name: String,
+    // World the guest is bound to, see `GuestManifest::world`
+    pub world: WorldId,
+    pub singletons: HashSet<toxoid_component::component::ecs::EcsEntityT>,
+    pub registered: GuestRegistrations,
+    pub limits: GuestLimits,
+    // Systems and observers disabled after their callback failed, they aren't dispatched to anymore
+    pub quarantined: HashSet<EcsEntityT>,
+    // Systems and observers quarantined in the instance this one replaced after a failure
+    pub restart_quarantine: Quarantine,
+    // Assets loaded by the guest, and those of them not reported by `poll-events` yet
+    pub assets: HashSet<u64>,
+    pub pending_assets: Vec<u64>,
+    // System or observer whose callback the guest is running, log records are tagged with it
+    pub current_system: Option<EcsEntityT>,
+    // Iterators handed to callbacks that the guest hasn't dropped yet, by resource rep
+    pub callback_iters: HashSet<u32>,
 }
-unsafe impl Send for EntityProxy {}
-pub struct QueryProxy {
-    ptr: *mut toxoid_host::Query
+
+// Resource limits of a guest component
+#[derive(Clone, Debug)]
+pub struct GuestLimits {
+    // Maximum size of the guest's linear memory in bytes
+    pub max_memory: usize,
+    // Maximum number of elements in a guest table
+    pub max_table_elements: usize,
+    // Wall-clock time a single system or observer callback may take. It's not CPU time, time spent
+    // in host calls or with the thread preempted counts too, so leave headroom.
+    pub callback_budget: Duration,
+    // Wall-clock time the guest's init may take
+    pub init_budget: Duration,
 }
-unsafe impl Send for QueryProxy {}
-pub struct SystemProxy {
-    ptr: *mut toxoid_host::System
+
+impl Default for GuestLimits {
+    fn default() -> Self {
+        GuestLimits {
+            max_memory: 256 * 1024 * 1024,
+            max_table_elements: 100_000,
+            callback_budget: Duration::from_millis(250),
+            init_budget: Duration::from_secs(10),
+        }
+    }
 }
-unsafe impl Send for SystemProxy {}
-pub struct CallbackProxy {
-    ptr: *mut toxoid_host::Callback
+
+// Error raised when a guest tries to grow its memory past its limit
+#[derive(Debug)]
+pub struct MemoryLimitExceeded {
+    pub desired: usize,
+    pub limit: usize,
 }
-unsafe impl Send for CallbackProxy {}
-pub struct IterProxy {
-    pub ptr: *mut toxoid_host::Iter
+
+impl std::fmt::Display for MemoryLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "guest memory limit exceeded, requested {} bytes with a limit of {} bytes", self.desired, self.limit)
+    }
 }
-unsafe impl Send for IterProxy {}
-pub struct ObserverProxy {
-    pub ptr: *mut toxoid_host::Observer
+
+impl std::error::Error for MemoryLimitExceeded {}
+
+impl ResourceLimiter for StoreState {
+    fn memory_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> Result<bool> {
+        if desired > self.limits.max_memory {
+            // Trap instead of failing the grow, so the guest is stopped and the failure reported
+            return Err(MemoryLimitExceeded { desired, limit: self.limits.max_memory }.into());
+        }
+        Ok(true)
+    }
+
+    fn table_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> Result<bool> {
+        Ok(desired <= self.limits.max_table_elements)
+    }
 }
-unsafe impl Send for ObserverProxy {}
-pub struct PipelineProxy {
-    pub ptr: *mut toxoid_host::Pipeline
+
+// Systems and observers to quarantine when a guest is restarted, by the order they are registered in
+// since the new instance creates their entities again
+#[derive(Clone, Default)]
+pub struct Quarantine {
+    pub systems: HashSet<usize>,
+    pub observers: HashSet<usize>,
 }
-unsafe impl Send for PipelineProxy {}
-pub struct PhaseProxy {
-    pub ptr: *mut toxoid_host::Phase
+
+// Everything a guest has registered with the ECS world, in creation order,
+// so that it can be torn down when the guest is reloaded or unloaded.
+#[derive(Default)]
+pub struct GuestRegistrations {
+    pub systems: Vec<EcsEntityT>,
+    pub observers: Vec<EcsEntityT>,
+    pub component_types: Vec<EcsEntityT>,
+    pub pipelines: Vec<EcsEntityT>,
+    pub phases: Vec<EcsEntityT>,
+    // Comparator slots of systems sorted with order_by
+    pub comparators: Vec<usize>,
 }
-unsafe impl Send for PhaseProxy {}
 
-// StoreState is the state of the WASM store.
-pub struct StoreState {
-    pub ctx: WasiCtx,
-    pub table: ResourceTable,
+impl StoreState {
+    fn quarantine(&mut self, entity: EcsEntityT) {
+        toxoid_host::Entity { id: entity }.disable();
+        self.quarantined.insert(entity);
+    }
+
+    // Quarantined systems and observers by registration order, for the instance restarting this one
+    fn quarantine_by_order(&self) -> Quarantine {
+        let positions = |entities: &Vec<EcsEntityT>| entities
+            .iter()
+            .enumerate()
+            .filter(|(_, entity)| self.quarantined.contains(entity))
+            .map(|(index, _)| index)
+            .collect();
+        Quarantine {
+            systems: positions(&self.registered.systems),
+            observers: positions(&self.registered.observers),
+        }
+    }
+
+    // Tear down everything the guest registered, while keeping entity and component data alive.
+    // Observers go first so deleting systems, pipelines and phases doesn't trigger guest callbacks,
+    // and each kind is deleted in reverse creation order. Runs on the main loop, it switches the active world.
+    pub fn teardown(&mut self) {
+        let registered = std::mem::take(&mut self.registered);
+        // Nothing is left to delete when the guest's world was destroyed
+        if world_exists(self.world) {
+            with_world(self.world, || {
+                registered.observers.iter().rev().for_each(|observer| ToxoidApi::remove_entity(*observer));
+                registered.systems.iter().rev().for_each(|system| ToxoidApi::remove_entity(*system));
+                registered.pipelines.iter().rev().for_each(|pipeline| ToxoidApi::remove_entity(*pipeline));
+                registered.phases.iter().rev().for_each(|phase| ToxoidApi::remove_entity(*phase));
+            });
+        }
+        registered.comparators.iter().for_each(|slot| release_comparator(*slot));
+        // Component types and singletons are left in the world since entities still hold their data,
+        // the next guest instance looks them up again by name when it registers them.
+        self.singletons.clear();
+    }
 }
 
 // A trait which provides access to internal WASI state.
@@ -82,44 +271,31 @@ impl WasiView for StoreState {
     fn table(&mut self) -> &mut ResourceTable { &mut self.table }
 }
 
-static mut SINGLETON_MAP: Lazy<HashMap<toxoid_component::component::ecs::EcsEntityT, u32>> = Lazy::new(|| HashMap::new());
-
 impl toxoid_component::component::ecs::Host for StoreState {
     fn add_singleton(&mut self, component: toxoid_component::component::ecs::EcsEntityT) {
         ToxoidApi::add_singleton(component);
-        // TODO: Check if !ptr.is_null()
-        let component_ptr = ToxoidApi::get_singleton(component);
-        let host_component = toxoid_host::Component::new(component_ptr, component, component);
-        let boxed_component_ptr = Box::into_raw(Box::new(host_component));
-        let resource = self.table.push::<ComponentProxy>(ComponentProxy {
-            ptr: boxed_component_ptr 
-        })
-            .expect("Failed to push component to table");
-        unsafe {
-            SINGLETON_MAP.insert(component, resource.rep());
-        }
+        self.singletons.insert(component);
     }
 
-    fn get_singleton(&mut self, component: toxoid_component::component::ecs::EcsEntityT) -> Resource<ComponentProxy> {
-        unsafe {
-            if SINGLETON_MAP.contains_key(&component) {    
-                let resource_rep = SINGLETON_MAP.get(&component).unwrap().clone();
-                let resource = Resource::<ComponentProxy>::new_own(resource_rep);
-                resource
-            } else {
-                panic!("Failed to get singleton, component ID: {:?}", component);
-                // let ptr = ToxoidApi::get_singleton(component) as *mut toxoid_host::Component;
-                // let resource = self.table.push::<ComponentProxy>(ComponentProxy { ptr: ptr }).expect("Failed to push component to table");
-                // resource
+    fn get_singleton(&mut self, component: toxoid_component::component::ecs::EcsEntityT) -> Result<Resource<ComponentProxy>> {
+        if self.singletons.contains(&component) {
+            // Every call hands out its own resource, since the guest drops each one it receives
+            let component_ptr = ToxoidApi::get_singleton(component);
+            // Singletons are added when missing, only tags have no data to point to
+            if component_ptr == 0 {
+                return Err(wasmtime::Error::msg(format!("Singleton has no data, component ID: {:?}", component)));
             }
+            let host_component = toxoid_host::Component::new(component_ptr, component, component);
+            let boxed_component_ptr = Box::into_raw(Box::new(host_component));
+            Ok(self.table.push::<ComponentProxy>(ComponentProxy::new(boxed_component_ptr))?)
+        } else {
+            Err(wasmtime::Error::msg(format!("Failed to get singleton, component ID: {:?}", component)))
         }
     }
 
     fn remove_singleton(&mut self, component: toxoid_component::component::ecs::EcsEntityT) {
         ToxoidApi::remove_singleton(component);
-        unsafe {
-            SINGLETON_MAP.remove(&component);
-        }
+        self.singletons.remove(&component);
     }
 
     fn add_entity(&mut self, entity: toxoid_component::component::ecs::EcsEntityT) {
@@ -146,15 +322,271 @@ impl toxoid_component::component::ecs::Host for StoreState {
     fn get_component_id(&mut self, component_name: String) -> toxoid_component::component::ecs::EcsEntityT {
         ToxoidApi::get_component_id(component_name)
     }
+
+    fn register_enum(&mut self, desc: toxoid_component::component::ecs::EnumDesc) -> toxoid_component::component::ecs::EcsEntityT {
+        ToxoidApi::register_enum(toxoid_host::bindings::exports::toxoid::engine::ecs::EnumDesc {
+            name: desc.name,
+            constants: desc.constants
+                .into_iter()
+                .map(|constant| toxoid_host::bindings::exports::toxoid::engine::ecs::EnumConstant { name: constant.name, value: constant.value })
+                .collect(),
+            bitmask: desc.bitmask
+        })
+    }
+
+    fn entity_to_json(&mut self, entity: toxoid_component::component::ecs::EcsEntityT, recursive: bool) -> Result<String, String> {
+        ToxoidApi::entity_to_json(entity, recursive)
+    }
+
+    fn entity_from_json(&mut self, json: String, parent: Option<toxoid_component::component::ecs::EcsEntityT>) -> Result<toxoid_component::component::ecs::EcsEntityT, String> {
+        ToxoidApi::entity_from_json(json, parent)
+    }
+
+    fn command_spawn(&mut self) -> toxoid_component::component::ecs::EcsEntityT {
+        ToxoidApi::command_spawn()
+    }
+
+    fn command_despawn(&mut self, entity: toxoid_component::component::ecs::EcsEntityT) {
+        ToxoidApi::command_despawn(entity)
+    }
+
+    fn command_add(&mut self, entity: toxoid_component::component::ecs::EcsEntityT, component: toxoid_component::component::ecs::EcsEntityT) {
+        ToxoidApi::command_add(entity, component)
+    }
+
+    fn command_remove(&mut self, entity: toxoid_component::component::ecs::EcsEntityT, component: toxoid_component::component::ecs::EcsEntityT) {
+        ToxoidApi::command_remove(entity, component)
+    }
+
+    fn flush_commands(&mut self) -> Vec<toxoid_component::component::ecs::CommandError> {
+        ToxoidApi::flush_commands().into_iter().map(map_command_error).collect()
+    }
+
+    fn take_command_errors(&mut self) -> Vec<toxoid_component::component::ecs::CommandError> {
+        ToxoidApi::take_command_errors().into_iter().map(map_command_error).collect()
+    }
+}
+
+fn map_command_error(error: toxoid_host::bindings::exports::toxoid::engine::ecs::CommandError) -> toxoid_component::component::ecs::CommandError {
+    use toxoid_host::bindings::exports::toxoid::engine::ecs::CommandKind;
+    toxoid_component::component::ecs::CommandError {
+        kind: match error.kind {
+            CommandKind::Despawn => toxoid_component::component::ecs::CommandKind::Despawn,
+            CommandKind::Add => toxoid_component::component::ecs::CommandKind::Add,
+            CommandKind::Remove => toxoid_component::component::ecs::CommandKind::Remove
+        },
+        entity: error.entity,
+        component: error.component
+    }
+}
+
+impl toxoid_component::component::engine::Host for StoreState {
+    fn time(&mut self) -> toxoid_component::component::engine::TimeInfo {
+        let time = <ToxoidApi as EngineGuest>::time();
+        toxoid_component::component::engine::TimeInfo { delta: time.delta, elapsed: time.elapsed, frame: time.frame }
+    }
+
+    fn window(&mut self) -> toxoid_component::component::engine::WindowInfo {
+        let window = <ToxoidApi as EngineGuest>::window();
+        toxoid_component::component::engine::WindowInfo {
+            width: window.width,
+            height: window.height,
+            render_width: window.render_width,
+            render_height: window.render_height
+        }
+    }
+
+    fn mouse(&mut self) -> toxoid_component::component::engine::MouseState {
+        let mouse = <ToxoidApi as EngineGuest>::mouse();
+        toxoid_component::component::engine::MouseState {
+            x: mouse.x,
+            y: mouse.y,
+            dx: mouse.dx,
+            dy: mouse.dy,
+            scroll_x: mouse.scroll_x,
+            scroll_y: mouse.scroll_y,
+            left: mouse.left,
+            right: mouse.right,
+            middle: mouse.middle
+        }
+    }
+
+    fn key_down(&mut self, key: toxoid_component::component::engine::KeyCode) -> bool {
+        <ToxoidApi as EngineGuest>::key_down(key)
+    }
+
+    fn keys_down(&mut self) -> Vec<toxoid_component::component::engine::KeyCode> {
+        <ToxoidApi as EngineGuest>::keys_down()
+    }
+}
+
+impl StoreState {
+    fn track_asset(&mut self, entity: toxoid_api::Entity) -> u64 {
+        let handle = entity.get_id();
+        self.assets.insert(handle);
+        self.pending_assets.push(handle);
+        handle
+    }
+
+    fn check_asset(&self, handle: u64) -> Result<()> {
+        if self.assets.contains(&handle) {
+            Ok(())
+        } else {
+            Err(wasmtime::Error::msg(format!("Unknown asset handle: {:?}", handle)))
+        }
+    }
+}
+
+fn map_asset_status(status: toxoid_api::AssetStatus) -> toxoid_component::component::assets::AssetStatus {
+    match status {
+        toxoid_api::AssetStatus::Loading => toxoid_component::component::assets::AssetStatus::Loading,
+        toxoid_api::AssetStatus::Loaded => toxoid_component::component::assets::AssetStatus::Loaded,
+        toxoid_api::AssetStatus::Failed(error) => toxoid_component::component::assets::AssetStatus::Failed(error)
+    }
+}
+
+fn map_entity_desc(desc: toxoid_component::component::ecs::EntityDesc) -> toxoid_host::bindings::exports::toxoid::engine::ecs::EntityDesc {
+    toxoid_host::bindings::exports::toxoid::engine::ecs::EntityDesc {
+        name: desc.name,
+        add: desc.add,
+        prefab: desc.prefab,
+        values: desc.values.map(|values| values
+            .into_iter()
+            .map(|value| toxoid_host::bindings::exports::toxoid::engine::ecs::ComponentValue {
+                component: value.component,
+                data: value.data
+            })
+            .collect())
+    }
+}
+
+fn map_monitor_kind(kind: toxoid_component::component::ecs::MonitorKind) -> toxoid_host::bindings::exports::toxoid::engine::ecs::MonitorKind {
+    match kind {
+        toxoid_component::component::ecs::MonitorKind::Changed => toxoid_host::bindings::exports::toxoid::engine::ecs::MonitorKind::Changed,
+        toxoid_component::component::ecs::MonitorKind::Added => toxoid_host::bindings::exports::toxoid::engine::ecs::MonitorKind::Added
+    }
+}
+
+impl toxoid_component::component::assets::Host for StoreState {
+    fn load_image(&mut self, path: String) -> toxoid_component::component::assets::AssetHandle {
+        self.track_asset(toxoid_api::load_image(&path))
+    }
+
+    fn load_sprite(&mut self, path: String, rendered_on_load: bool) -> toxoid_component::component::assets::AssetHandle {
+        self.track_asset(toxoid_api::load_sprite(&path, rendered_on_load))
+    }
+
+    fn load_animation(&mut self, atlas_path: String, skeleton_path: String, rendered_on_load: bool) -> toxoid_component::component::assets::AssetHandle {
+        self.track_asset(toxoid_api::load_animation(&atlas_path, &skeleton_path, rendered_on_load))
+    }
+
+    fn load_worldmap(&mut self, path: String, rendered_on_load: bool) -> toxoid_component::component::assets::AssetHandle {
+        self.track_asset(toxoid_api::load_worldmap(&path, rendered_on_load))
+    }
+
+    fn load_data(&mut self, path: String) -> toxoid_component::component::assets::AssetHandle {
+        self.track_asset(toxoid_api::fetch(&path, toxoid_api::DataType::Raw, None))
+    }
+
+    fn status(&mut self, handle: toxoid_component::component::assets::AssetHandle) -> Result<toxoid_component::component::assets::AssetStatus> {
+        self.check_asset(handle)?;
+        Ok(map_asset_status(toxoid_api::asset_status(handle)))
+    }
+
+    fn size(&mut self, handle: toxoid_component::component::assets::AssetHandle) -> Result<Option<(u32, u32)>> {
+        self.check_asset(handle)?;
+        Ok(toxoid_api::asset_size(handle))
+    }
+
+    fn data(&mut self, handle: toxoid_component::component::assets::AssetHandle) -> Result<Option<Vec<u8>>> {
+        self.check_asset(handle)?;
+        Ok(toxoid_api::asset_data(handle))
+    }
+
+    fn poll_events(&mut self) -> Vec<toxoid_component::component::assets::AssetEvent> {
+        toxoid_api::poll_asset_events(&mut self.pending_assets)
+            .into_iter()
+            .map(|event| toxoid_component::component::assets::AssetEvent { handle: event.handle, status: map_asset_status(event.status) })
+            .collect()
+    }
+}
+
+fn map_rgba(color: toxoid_component::component::render::Rgba) -> toxoid_api::Rgba {
+    toxoid_api::Rgba { r: color.r, g: color.g, b: color.b, a: color.a }
+}
+
+impl toxoid_component::component::render::Host for StoreState {
+    fn draw_sprite(&mut self, sprite: toxoid_component::component::assets::AssetHandle, x: f32, y: f32) -> Result<()> {
+        self.check_asset(sprite)?;
+        toxoid_api::queue_draw(toxoid_api::DrawCommand::Sprite { sprite, x, y });
+        Ok(())
+    }
+
+    fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: toxoid_component::component::render::Rgba) {
+        toxoid_api::queue_draw(toxoid_api::DrawCommand::Rect { x, y, width, height, color: map_rgba(color) });
+    }
+
+    fn draw_line(&mut self, ax: f32, ay: f32, bx: f32, by: f32, color: toxoid_component::component::render::Rgba) {
+        toxoid_api::queue_draw(toxoid_api::DrawCommand::Line { ax, ay, bx, by, color: map_rgba(color) });
+    }
+}
+
+fn map_log_level(level: toxoid_component::component::log::Level) -> LogLevel {
+    match level {
+        toxoid_component::component::log::Level::Trace => LogLevel::Trace,
+        toxoid_component::component::log::Level::Debug => LogLevel::Debug,
+        toxoid_component::component::log::Level::Info => LogLevel::Info,
+        toxoid_component::component::log::Level::Warn => LogLevel::Warn,
+        toxoid_component::component::log::Level::Error => LogLevel::Error
+    }
+}
+
+impl toxoid_component::component::log::Host for StoreState {
+    fn enabled(&mut self, level: toxoid_component::component::log::Level) -> bool {
+        toxoid_host::log::log_enabled(map_log_level(level))
+    }
+
+    fn log(&mut self, level: toxoid_component::component::log::Level, target: String, message: String, fields: Vec<toxoid_component::component::log::Field>) {
+        toxoid_host::log::log(LogRecord {
+            level: map_log_level(level),
+            target,
+            message,
+            fields: fields.into_iter().map(|field| (field.key, field.value)).collect(),
+            guest: Some(self.name.clone()),
+            system: self.current_system.map(system_name),
+        });
+    }
+}
+
+impl toxoid_component::component::random::Host for StoreState {
+    fn seed(&mut self) -> u64 {
+        toxoid_host::random::seed()
+    }
+
+    fn named_stream(&mut self, name: String) -> toxoid_component::component::random::StreamId {
+        toxoid_host::random::named_stream(&name)
+    }
+
+    fn system_stream(&mut self, system: EcsEntityT) -> toxoid_component::component::random::StreamId {
+        toxoid_host::random::system_stream(system)
+    }
+
+    fn next_u64(&mut self, id: toxoid_component::component::random::StreamId) -> u64 {
+        toxoid_host::random::next_u64(id)
+    }
 }
 
 impl toxoid_component::component::ecs::HostIter for StoreState {
     fn new(&mut self, ptr: u64) -> Resource<IterProxy> {
-        self.table.push::<IterProxy>(IterProxy { ptr: ptr as *mut toxoid_host::Iter }).unwrap()
+        let iter = Box::into_raw(Box::new(toxoid_host::Iter::new(ptr)));
+        self.table.push::<IterProxy>(IterProxy::new(iter)).unwrap()
     }   
 
     fn next(&mut self, iter: Resource<IterProxy>) -> bool {
         let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return false;
+        }
         let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
         let result = iter.next();
         Box::into_raw(iter);
@@ -163,14 +595,31 @@ impl toxoid_component::component::ecs::HostIter for StoreState {
 
     fn count(&mut self, iter: Resource<IterProxy>) -> i32 {
         let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return 0;
+        }
         let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
         let result = iter.count();
         Box::into_raw(iter);
         result
     }
 
+    fn system(&mut self, iter: Resource<IterProxy>) -> EcsEntityT {
+        let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return 0;
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.system();
+        Box::into_raw(iter);
+        result
+    }
+
     fn entities(&mut self, iter: Resource<IterProxy>) -> Vec<Resource<EntityProxy>>{
         let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Vec::new();
+        }
         let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
         let entity_ids = iter.entities();
         let ids = entity_ids.iter().map(|entity_id| {
@@ -180,9 +629,7 @@ impl toxoid_component::component::ecs::HostIter for StoreState {
             // Push component to resource table
             let id = self
                 .table
-                .push::<EntityProxy>(EntityProxy {
-                    ptr: entity
-                })
+                .push::<EntityProxy>(EntityProxy::new(entity))
                 .expect("Failed to push component to table");
             id
         }).collect();
@@ -192,13 +639,117 @@ impl toxoid_component::component::ecs::HostIter for StoreState {
 
     fn components(&mut self, iter: Resource<IterProxy>, index: i8) -> Vec<PointerT> {
         let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Vec::new();
+        }
         let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
         let result = iter.components(index);
         Box::into_raw(iter);
         result
     }
 
-    fn drop(&mut self, _iter: Resource<IterProxy>) -> Result<(), wasmtime::Error> {
+    fn field_views(&mut self, iter: Resource<IterProxy>, index: i8, read_only: bool) -> Vec<Resource<ComponentProxy>> {
+        let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Vec::new();
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let views = iter.field_views(index, read_only);
+        Box::into_raw(iter);
+        push_components(self, views)
+    }
+
+    fn column(&mut self, iter: Resource<IterProxy>, index: i8, member: u32, member_type: toxoid_component::component::ecs::MemberType) -> Result<Vec<u8>> {
+        let iter_proxy = self.table.get(&iter)? as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Ok(Vec::new());
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.read_column(index, member, FieldType::from_member_type(member_type as u8), MemberAccess::Guest);
+        Box::into_raw(iter);
+        Ok(result?)
+    }
+
+    fn set_column(&mut self, iter: Resource<IterProxy>, index: i8, member: u32, member_type: toxoid_component::component::ecs::MemberType, data: Vec<u8>) -> Result<()> {
+        let iter_proxy = self.table.get(&iter)? as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Ok(());
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.write_column(index, member, FieldType::from_member_type(member_type as u8), MemberAccess::Guest, &data);
+        Box::into_raw(iter);
+        Ok(result?)
+    }
+
+    fn field_data(&mut self, iter: Resource<IterProxy>, index: i8) -> Result<Vec<u8>> {
+        let iter_proxy = self.table.get(&iter)? as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Ok(Vec::new());
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.read_field_data(index);
+        Box::into_raw(iter);
+        Ok(result?)
+    }
+
+    fn set_field_data(&mut self, iter: Resource<IterProxy>, index: i8, data: Vec<u8>) -> Result<()> {
+        let iter_proxy = self.table.get(&iter)? as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return Ok(());
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.write_field_data(index, &data);
+        Box::into_raw(iter);
+        Ok(result?)
+    }
+
+    fn changed(&mut self, iter: Resource<IterProxy>) -> bool {
+        let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return false;
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.changed();
+        Box::into_raw(iter);
+        result
+    }
+
+    fn skip(&mut self, iter: Resource<IterProxy>) {
+        let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return;
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        iter.skip();
+        Box::into_raw(iter);
+    }
+
+    fn field_changed(&mut self, iter: Resource<IterProxy>, index: i8) -> bool {
+        let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return false;
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.field_changed(index);
+        Box::into_raw(iter);
+        result
+    }
+
+    fn field_added(&mut self, iter: Resource<IterProxy>, index: i8) -> Vec<bool> {
+        let iter_proxy = self.table.get(&iter).unwrap() as &IterProxy;
+        if iter_proxy.ptr.is_null() {
+            return vec![];
+        }
+        let iter = unsafe { Box::from_raw(iter_proxy.ptr) };
+        let result = iter.field_added(index);
+        Box::into_raw(iter);
+        result
+    }
+
+    fn drop(&mut self, iter: Resource<IterProxy>) -> Result<(), wasmtime::Error> {
+        // The rep can be reused by the next iterator, which mustn't be expired with the callback
+        self.callback_iters.remove(&iter.rep());
+        self.table.delete(iter)?;
         Ok(())
     }
 }
@@ -208,28 +759,38 @@ impl toxoid_component::component::ecs::HostCallback for StoreState {
         let callback = <toxoid_host::Callback as toxoid_host::bindings::exports::toxoid::engine::ecs::GuestCallback>::new(handle);
         let boxed_callback = Box::new(callback);
         let boxed_callback_ptr = Box::into_raw(boxed_callback);
-        self.table.push::<CallbackProxy>(CallbackProxy { ptr: boxed_callback_ptr }).unwrap()
+        self.table.push::<CallbackProxy>(CallbackProxy::new(boxed_callback_ptr)).unwrap()
     }
 
-    fn run(&mut self, _callback: wasmtime::component::Resource<CallbackProxy>, iter: wasmtime::component::Resource<IterProxy>) -> () {
-        let callback_proxy = self.table.get(&_callback).unwrap() as &CallbackProxy;
+    fn run(&mut self, _callback: wasmtime::component::Resource<CallbackProxy>, iter: wasmtime::component::Resource<IterProxy>) -> Result<()> {
+        let callback_proxy = self.table.get(&_callback)? as &CallbackProxy;
         let callback = unsafe { Box::from_raw(callback_proxy.ptr) };
-        let callbacks = unsafe { TOXOID_COMPONENT_WORLD.as_mut().unwrap() };
+        let handle = callback.handle;
+        Box::into_raw(callback);
+        let guest = get_guest(self.guest_id)
+            .ok_or_else(|| wasmtime::Error::msg(format!("Guest {} is not loaded", self.guest_id)))?;
         
         // Get a lock on the store and get a mutable reference to the actual Store
-        let mut store_guard = unsafe { STORE.lock().unwrap() };
+        let mut store_guard = guest.store
+            .try_lock()
+            .map_err(|_| wasmtime::Error::msg("Callback can't be run while its guest is running"))?;
         let store = &mut *store_guard;
+        let callback_budget = store.data().limits.callback_budget;
+        store.set_epoch_deadline(budget_ticks(callback_budget));
         
-        callbacks.interface1.call_run(store, iter, callback.handle).unwrap();
+        guest.world.interface1.call_run(store, iter, handle)
     }
 
     fn cb_handle(&mut self, _callback: Resource<toxoid_component::component::ecs::Callback>) -> u64 {
         let callback_proxy = self.table.get(&_callback).unwrap() as &CallbackProxy;
         let callback = unsafe { Box::from_raw(callback_proxy.ptr) };
-        callback.handle
+        let handle = callback.handle;
+        Box::into_raw(callback);
+        handle
     }
 
-    fn drop(&mut self, _callback: Resource<toxoid_component::component::ecs::Callback>) -> Result<(), wasmtime::Error> {
+    fn drop(&mut self, callback: Resource<toxoid_component::component::ecs::Callback>) -> Result<(), wasmtime::Error> {
+        self.table.delete(callback)?;
         Ok(())
     }
 }
@@ -251,13 +812,22 @@ impl toxoid_component::component::ecs::HostSystem for StoreState {
             is_guest: true,
             tick_rate: desc.tick_rate
         });
+        // Tag the system with the guest that owns it so the query trampoline can route the callback
+        system.desc.borrow_mut().ctx = self.guest_id as usize as *mut c_void;
+        let (run_ctx, run_ctx_free) = guest_callback_ctx(self.guest_id, self.instance, callback.cb_handle());
+        system.desc.borrow_mut().run_ctx = run_ctx;
+        system.desc.borrow_mut().run_ctx_free = run_ctx_free;
+        self.registered.systems.push(system.get_id());
+        if self.restart_quarantine.systems.contains(&(self.registered.systems.len() - 1)) {
+            self.quarantine(system.get_id());
+        }
         let id = self
             .table
-            .push::<SystemProxy>(SystemProxy {
-                ptr: Box::into_raw(Box::new(system))
-            })
+            .push::<SystemProxy>(SystemProxy::new(Box::into_raw(Box::new(system))))
             .unwrap();
         Box::into_raw(callback);
+        // The guest handed over the callback resource along with the descriptor
+        self.table.delete(desc.callback).unwrap();
         id
     }
 
@@ -268,6 +838,13 @@ impl toxoid_component::component::ecs::HostSystem for StoreState {
         Box::into_raw(system);
     }
 
+    fn monitor(&mut self, system: Resource<toxoid_component::component::ecs::System>, index: i8, kind: toxoid_component::component::ecs::MonitorKind) -> () {
+        let system_proxy = self.table.get(&system).unwrap() as &SystemProxy;
+        let system = unsafe { Box::from_raw(system_proxy.ptr) };
+        system.monitor(index, map_monitor_kind(kind));
+        Box::into_raw(system);
+    }
+
     fn get_id(&mut self, system: Resource<toxoid_component::component::ecs::System>) -> u64 {
         let system_proxy = self.table.get(&system).unwrap() as &SystemProxy;
         let system = unsafe { Box::from_raw(system_proxy.ptr) };
@@ -280,8 +857,9 @@ impl toxoid_component::component::ecs::HostSystem for StoreState {
         let system_proxy = self.table.get(&system).unwrap() as &SystemProxy;
         let system = unsafe { Box::from_raw(system_proxy.ptr) };
         let callback_handle = system.callback();
+        Box::into_raw(system);
         let callback = <toxoid_host::Callback as toxoid_host::bindings::exports::toxoid::engine::ecs::GuestCallback>::new(callback_handle);
-        self.table.push::<CallbackProxy>(CallbackProxy { ptr: Box::into_raw(Box::new(callback)) }).unwrap()
+        self.table.push::<CallbackProxy>(CallbackProxy::new(Box::into_raw(Box::new(callback)))).unwrap()
     }
 
     fn build(&mut self, system: Resource<toxoid_component::component::ecs::System>) -> () {
@@ -291,12 +869,30 @@ impl toxoid_component::component::ecs::HostSystem for StoreState {
         Box::into_raw(system);
     }
 
-    fn order_by(&mut self, _system: Resource<toxoid_component::component::ecs::System>, _sorting: toxoid_component::component::ecs::SortingDesc) -> () {
-        unimplemented!("Order by not implemented for WASM");
-        // let system_proxy = self.table.get(&system).unwrap() as &SystemProxy;
-        // let mut system = unsafe { Box::from_raw(system_proxy.ptr) };
-        // system.as_mut().order_by(sorting);
-        // Box::into_raw(system);
+    fn order_by(&mut self, system: Resource<toxoid_component::component::ecs::System>, sorting: toxoid_component::component::ecs::SortingDesc) -> Result<()> {
+        let callback_proxy = self.table.get(&sorting.callback)? as &CallbackProxy;
+        let callback = unsafe { Box::from_raw(callback_proxy.ptr) };
+        let handle = callback.handle;
+        Box::into_raw(callback);
+        self.table.delete(sorting.callback)?;
+        // Route the sort through a trampoline that calls the guest's comparator
+        let system_proxy = self.table.get(&system)? as &SystemProxy;
+        let system_id = unsafe { (*system_proxy.ptr).get_id() };
+        let slot = allocate_comparator(self.guest_id, handle, system_id)
+            .ok_or_else(|| wasmtime::Error::msg(format!(
+                "Can't sort system {}, all {} guest comparator slots are in use by sorted systems of loaded guests",
+                system_name(system_id),
+                MAX_GUEST_COMPARATORS
+            )))?;
+        self.registered.comparators.push(slot);
+        let system_proxy = self.table.get(&system)? as &SystemProxy;
+        let mut system = unsafe { Box::from_raw(system_proxy.ptr) };
+        system.as_mut().order_by(toxoid_host::bindings::exports::toxoid::engine::ecs::SortingDesc {
+            id: sorting.id,
+            callback: COMPARE_TRAMPOLINES[slot] as usize as u64
+        });
+        Box::into_raw(system);
+        Ok(())
     }
 
     fn disable(&mut self, system: Resource<toxoid_component::component::ecs::System>) -> () {
@@ -313,7 +909,8 @@ impl toxoid_component::component::ecs::HostSystem for StoreState {
         Box::into_raw(system);
     }
 
-    fn drop(&mut self, _system: Resource<toxoid_component::component::ecs::System>) -> Result<(), wasmtime::Error> {
+    fn drop(&mut self, system: Resource<toxoid_component::component::ecs::System>) -> Result<(), wasmtime::Error> {
+        self.table.delete(system)?;
         Ok(())
     }
 }
@@ -341,13 +938,20 @@ impl toxoid_component::component::ecs::HostObserver for StoreState {
             callback: callback.cb_handle(),
             is_guest: true
         });
+        observer.desc.borrow_mut().ctx = self.guest_id as usize as *mut c_void;
+        let (run_ctx, run_ctx_free) = guest_callback_ctx(self.guest_id, self.instance, callback.cb_handle());
+        observer.desc.borrow_mut().run_ctx = run_ctx;
+        observer.desc.borrow_mut().run_ctx_free = run_ctx_free;
+        self.registered.observers.push(*observer.entity.borrow());
+        if self.restart_quarantine.observers.contains(&(self.registered.observers.len() - 1)) {
+            self.quarantine(*observer.entity.borrow());
+        }
         let id = self
             .table
-            .push::<ObserverProxy>(ObserverProxy {
-                ptr: Box::into_raw(Box::new(observer))
-            })
+            .push::<ObserverProxy>(ObserverProxy::new(Box::into_raw(Box::new(observer))))
             .unwrap();
         Box::into_raw(callback);
+        self.table.delete(desc.callback).unwrap();
         id
     }
 
@@ -355,8 +959,17 @@ impl toxoid_component::component::ecs::HostObserver for StoreState {
         let observer_proxy = self.table.get(&_observer).unwrap() as &ObserverProxy;
         let observer = unsafe { Box::from_raw(observer_proxy.ptr) };
         let callback_handle = observer.callback();
+        Box::into_raw(observer);
         let callback = <toxoid_host::Callback as toxoid_host::bindings::exports::toxoid::engine::ecs::GuestCallback>::new(callback_handle);
-        self.table.push::<CallbackProxy>(CallbackProxy { ptr: Box::into_raw(Box::new(callback)) }).unwrap()
+        self.table.push::<CallbackProxy>(CallbackProxy::new(Box::into_raw(Box::new(callback)))).unwrap()
+    }
+
+    fn get_id(&mut self, observer: Resource<ObserverProxy>) -> u64 {
+        let observer_proxy = self.table.get(&observer).unwrap() as &ObserverProxy;
+        let observer = unsafe { Box::from_raw(observer_proxy.ptr) };
+        let id = observer.get_id();
+        Box::into_raw(observer);
+        id
     }
 
     fn build(&mut self, _observer: Resource<ObserverProxy>) -> () {
@@ -366,31 +979,30 @@ impl toxoid_component::component::ecs::HostObserver for StoreState {
         Box::into_raw(observer);
      }
 
-    fn drop(&mut self, _observer: Resource<ObserverProxy>) -> Result<(), wasmtime::Error> {
+    fn drop(&mut self, observer: Resource<ObserverProxy>) -> Result<(), wasmtime::Error> {
+        self.table.delete(observer)?;
         Ok(())
     }
 }
 
 impl toxoid_component::component::ecs::HostEntity for StoreState {
     fn new(&mut self, desc: toxoid_component::component::ecs::EntityDesc, inherits: Option<toxoid_component::component::ecs::EcsEntityT>) -> Resource<EntityProxy> {
-        let entity = toxoid_host::Entity::new(toxoid_host::bindings::exports::toxoid::engine::ecs::EntityDesc {
-            name: desc.name,
-            add: desc.add,
-            prefab: desc.prefab
-        }, inherits);
+        let entity = toxoid_host::Entity::new(map_entity_desc(desc), inherits);
         // Create boxed component
         let boxed_entity = Box::new(entity);
         let box_ptr = Box::into_raw(boxed_entity);
         // Push entity to resource table
         let id = self
             .table
-            .push::<EntityProxy>(EntityProxy {
-                ptr: box_ptr
-            })
+            .push::<EntityProxy>(EntityProxy::new(box_ptr))
             .unwrap();
         id
     }
 
+    fn create(&mut self, desc: toxoid_component::component::ecs::EntityDesc, inherits: Option<toxoid_component::component::ecs::EcsEntityT>) -> Result<u64, String> {
+        <toxoid_host::Entity as GuestEntity>::create(map_entity_desc(desc), inherits)
+    }
+
     fn get_id(&mut self, entity: Resource<toxoid_component::component::ecs::Entity>) -> u64 {
         let entity_proxy = self.table.get(&entity).unwrap() as &EntityProxy;
         let entity = unsafe { Box::from_raw(entity_proxy.ptr) };
@@ -436,16 +1048,14 @@ impl toxoid_component::component::ecs::HostEntity for StoreState {
         // Push component to resource table
         let id = self
             .table
-            .push::<ComponentProxy>(ComponentProxy { 
-                ptr: boxed_component_ptr
-            })
+            .push::<ComponentProxy>(ComponentProxy::new(boxed_component_ptr))
             .expect("Failed to push component to table");
         id
     }
 
     fn from_id(&mut self, id: u64) -> Resource<EntityProxy> {
         let entity = toxoid_host::Entity::from_id(id) as *mut toxoid_host::Entity; 
-        self.table.push::<EntityProxy>(EntityProxy { ptr: entity }).unwrap()
+        self.table.push::<EntityProxy>(EntityProxy::new(entity)).unwrap()
     }
 
     fn add(&mut self, entity: Resource<toxoid_component::component::ecs::Entity>, component: toxoid_component::component::ecs::EcsEntityT) -> () {
@@ -551,10 +1161,8 @@ impl toxoid_component::component::ecs::HostEntity for StoreState {
         Box::into_raw(entity);
     }
 
-    fn drop(&mut self, _entity: Resource<toxoid_component::component::ecs::Entity>) -> Result<(), wasmtime::Error> {
-        // let entity_proxy = self.table.get(&entity).unwrap() as &EntityProxy;
-        // drop(unsafe { Box::from_raw(entity_proxy.ptr) });
-        // self.table.delete::<EntityProxy>(entity).unwrap();
+    fn drop(&mut self, entity: Resource<toxoid_component::component::ecs::Entity>) -> Result<(), wasmtime::Error> {
+        self.table.delete(entity)?;
         Ok(())
     }
 }
@@ -566,16 +1174,17 @@ impl toxoid_component::component::ecs::HostComponentType for StoreState {
             name: desc.name,
             member_names: desc.member_names,
             member_types: desc.member_types,
+            member_type_ids: desc.member_type_ids,
+            member_counts: desc.member_counts,
         });
+        self.registered.component_types.push(component.get_id());
         // Create boxed component
         let boxed_component = Box::new(component);
         let boxed_component_ptr = Box::into_raw(boxed_component);
         // Push component to resource table
         let id = self
             .table
-            .push::<ComponentTypeProxy>(ComponentTypeProxy { 
-                ptr: boxed_component_ptr as *mut toxoid_host::ComponentType
-            })
+            .push::<ComponentTypeProxy>(ComponentTypeProxy::new(boxed_component_ptr as *mut toxoid_host::ComponentType))
             .unwrap();
         id
     }
@@ -589,378 +1198,415 @@ impl toxoid_component::component::ecs::HostComponentType for StoreState {
         id
     }
 
-    fn drop(&mut self, _component: Resource<toxoid_component::component::ecs::ComponentType>) -> Result<(), wasmtime::Error> {
-        // let component_proxy = self.table.get(&component).unwrap() as &ComponentTypeProxy;
-        // drop(unsafe { Box::from_raw(component_proxy.ptr) });
-        // self.table.delete::<ComponentTypeProxy>(component).unwrap();
+    fn drop(&mut self, component: Resource<toxoid_component::component::ecs::ComponentType>) -> Result<(), wasmtime::Error> {
+        self.table.delete(component)?;
         Ok(())
     }
 }
 
+fn push_components(state: &mut StoreState, components: Vec<toxoid_host::Component>) -> Vec<Resource<ComponentProxy>> {
+    components
+        .into_iter()
+        .map(|component| state.table.push::<ComponentProxy>(ComponentProxy::new(Box::into_raw(Box::new(component)))).unwrap())
+        .collect()
+}
+
 impl toxoid_component::component::ecs::HostComponent for StoreState {
     fn new(&mut self, component_ptr: u64, entity_id: u64, component_type_id: u64) -> Resource<ComponentProxy> {
         let component = toxoid_host::Component::new(component_ptr, entity_id, component_type_id);
         let boxed_component = Box::new(component);
         let boxed_component_ptr = Box::into_raw(boxed_component);
-        self.table.push::<ComponentProxy>(ComponentProxy { ptr: boxed_component_ptr }).unwrap()
+        self.table.push::<ComponentProxy>(ComponentProxy::new(boxed_component_ptr)).unwrap()
     }
 
-    fn from_ptr(&mut self, ptr: u64) -> Resource<ComponentProxy> {
-        let component = toxoid_host::Component::from_ptr_host(ptr);
+    fn from_ptr(&mut self, ptr: u64, component_type: u64) -> Resource<ComponentProxy> {
+        let component = toxoid_host::Component::from_ptr_host(ptr, component_type);
         let boxed_component = Box::new(component);
         let boxed_component_ptr = Box::into_raw(boxed_component);
-        self.table.push::<ComponentProxy>(ComponentProxy { ptr: boxed_component_ptr as *mut toxoid_host::Component }).unwrap()
+        self.table.push::<ComponentProxy>(ComponentProxy::new(boxed_component_ptr as *mut toxoid_host::Component)).unwrap()
+    }
+
+    fn set_member_u8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: u8) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
+        let component = unsafe { Box::from_raw(component_proxy.ptr) };
+        let result = component.write_member(member, FieldType::U8, MemberAccess::Guest, value);
+        Box::into_raw(component);
+        Ok(result?)
     }
 
-    fn set_member_u8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: u8) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<u8> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u8(offset, value);
+        let value = component.read_member(member, FieldType::U8, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> u8 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: u16) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u8(offset);
+        let result = component.write_member(member, FieldType::U16, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: u16) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<u16> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u16(offset, value);
+        let value = component.read_member(member, FieldType::U16, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> u16 {  
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: u32) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u16(offset);
+        let result = component.write_member(member, FieldType::U32, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: u32) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<u32> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u32(offset, value);
+        let value = component.read_member(member, FieldType::U32, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> u32 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: u64) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u32(offset);
+        let result = component.write_member(member, FieldType::U64, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: u64) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<u64> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u64(offset, value);
+        let value = component.read_member(member, FieldType::U64, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> u64 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<i8> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u64(offset);
+        let value = component.read_member(member, FieldType::I8, MemberAccess::Guest);
         Box::into_raw(component);
-        value
+        Ok(value?)
     }
 
-    fn get_member_i8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> i8 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: i8) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i8(offset);
+        let result = component.write_member(member, FieldType::I8, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i8(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: i8) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<i16> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i8(offset, value);
+        let value = component.read_member(member, FieldType::I16, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> i16 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: i16) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i16(offset);
+        let result = component.write_member(member, FieldType::I16, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i16(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: i16) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<i32> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i16(offset, value);
+        let value = component.read_member(member, FieldType::I32, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> i32 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: i32) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i32(offset);
+        let result = component.write_member(member, FieldType::I32, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: i32) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<i64> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i32(offset, value);
+        let value = component.read_member(member, FieldType::I64, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> i64 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: i64) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i64(offset);
+        let result = component.write_member(member, FieldType::I64, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: i64) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_f32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<f32> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i64(offset, value);
+        let value = component.read_member(member, FieldType::F32, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_f32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> f32 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_f32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: f32) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_f32(offset);
+        let result = component.write_member(member, FieldType::F32, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_f32(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: f32) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_f64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<f64> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_f32(offset, value);
+        let value = component.read_member(member, FieldType::F64, MemberAccess::Guest);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_f64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> f64 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_f64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: f64) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_f64(offset);
+        let result = component.write_member(member, FieldType::F64, MemberAccess::Guest, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_f64(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: f64) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_bool(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<bool> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_f64(offset, value);
+        let value = component.read_member::<u8>(member, FieldType::Bool, MemberAccess::Guest)
+            .map(|value| value != 0);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_bool(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> bool {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_bool(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: bool) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_bool(offset);
+        let result = component.write_member(member, FieldType::Bool, MemberAccess::Guest, value as u8);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_bool(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: bool) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_string(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<String> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_bool(offset, value);
+        let value = component.read_string(member);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_string(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> String {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_string(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: String) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_string(offset);
+        let result = component.write_string(member, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_string(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: String) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<u32>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_string(offset, value);
+        let value = component.read_list(member, FieldType::U32List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<u32> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<u8>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u32list(offset);
+        let result = component.write_list(member, FieldType::U8List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<u8>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<u8>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u8list(offset, value);
+        let value = component.read_list(member, FieldType::U8List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<u8> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<u16>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u8list(offset);
+        let result = component.write_list(member, FieldType::U16List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<u16>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<u16>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u16list(offset, value);
+        let value = component.read_list(member, FieldType::U16List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<u16> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<u32>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u16list(offset);
+        let result = component.write_list(member, FieldType::U32List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<u32>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_u64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<u64>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u32list(offset, value);
+        let value = component.read_list(member, FieldType::U64List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_u64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<u64> {   
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_u64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<u64>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_u64list(offset);
+        let result = component.write_list(member, FieldType::U64List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_u64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<u64>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<i8>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_u64list(offset, value);
+        let result = component.write_list(member, FieldType::I8List, value);
         Box::into_raw(component);
+        Ok(result?)
     }
 
-    fn set_member_i8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<i8>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<i8>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i8list(offset, value);
+        let value = component.read_list(member, FieldType::I8List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i8list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<i8> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<i16>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i8list(offset);
+        let result = component.write_list(member, FieldType::I16List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<i16>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<i16>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i16list(offset, value);
+        let value = component.read_list(member, FieldType::I16List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i16list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<i16> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<i32>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i16list(offset);
+        let result = component.write_list(member, FieldType::I32List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<i32>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<i32>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i32list(offset, value);
+        let value = component.read_list(member, FieldType::I32List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<i32> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_i64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<i64>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i32list(offset);
+        let result = component.write_list(member, FieldType::I64List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_i64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<i64>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_i64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<i64>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_i64list(offset, value);
+        let value = component.read_list(member, FieldType::I64List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_i64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<i64> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_f32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<f32>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_i64list(offset);
+        let value = component.read_list(member, FieldType::F32List);
         Box::into_raw(component);
-        value
+        Ok(value?)
     }
 
-    fn get_member_f32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<f32> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_f32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<f32>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_f32list(offset);
+        let result = component.write_list(member, FieldType::F32List, value);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_f32list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<f32>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_f64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: Vec<f64>) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_f32list(offset, value);
+        let result = component.write_list(member, FieldType::F64List, value);
         Box::into_raw(component);
+        Ok(result?)
     }
 
-    fn set_member_f64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: Vec<f64>) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_f64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<Vec<f64>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_f64list(offset, value);
+        let value = component.read_list(member, FieldType::F64List);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_f64list(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> Vec<f64> {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn set_member_pointer(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, value: u64) -> Result<()> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_f64list(offset);
+        let result = component.write_member(member, FieldType::Pointer, MemberAccess::Guest, value as usize);
         Box::into_raw(component);
-        value
+        Ok(result?)
     }
 
-    fn set_member_pointer(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32, value: u64) -> () {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_pointer(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32) -> Result<u64> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        component.set_member_pointer(offset, value);
+        let value = component.read_member::<usize>(member, FieldType::Pointer, MemberAccess::Guest)
+            .map(|value| value as u64);
         Box::into_raw(component);
+        Ok(value?)
     }
 
-    fn get_member_pointer(&mut self, component: Resource<toxoid_component::component::ecs::Component>, offset: u32) -> u64 {
-        let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
+    fn get_member_component(&mut self, component: Resource<toxoid_component::component::ecs::Component>, member: u32, index: u32) -> Result<Resource<ComponentProxy>> {
+        let component_proxy = self.table.get(&component)? as &ComponentProxy;
         let component = unsafe { Box::from_raw(component_proxy.ptr) };
-        let value = component.get_member_pointer(offset);
+        let nested = component.member_component(member, index);
         Box::into_raw(component);
-        value
+        let boxed_component_ptr = Box::into_raw(Box::new(nested?));
+        Ok(self.table.push::<ComponentProxy>(ComponentProxy::new(boxed_component_ptr))?)
     }
 
-    fn drop(&mut self, _component: Resource<toxoid_component::component::ecs::Component>) -> Result<(), wasmtime::Error> {
-        // let component_proxy = self.table.get(&component).unwrap() as &ComponentProxy;
-        // drop(unsafe { Box::from_raw(component_proxy.ptr) });
-        // self.table.delete::<ComponentProxy>(component).unwrap();
+    fn drop(&mut self, component: Resource<toxoid_component::component::ecs::Component>) -> Result<(), wasmtime::Error> {
+        self.table.delete(component)?;
         Ok(())
     }
 }
@@ -969,7 +1615,7 @@ impl toxoid_component::component::ecs::HostQuery for StoreState {
     fn new(&mut self, query_desc: toxoid_component::component::ecs::QueryDesc) -> Resource<toxoid_component::component::ecs::Query> {
         let query = toxoid_host::Query::new(toxoid_host::bindings::exports::toxoid::engine::ecs::QueryDesc { expr: query_desc.expr });
         let ptr = Box::into_raw(Box::new(query));
-        let query_resource = self.table.push(QueryProxy { ptr }).unwrap();
+        let query_resource = self.table.push(QueryProxy::new(ptr)).unwrap();
         query_resource
     }
 
@@ -980,12 +1626,18 @@ impl toxoid_component::component::ecs::HostQuery for StoreState {
         Box::into_raw(query);
     }
 
-    fn order_by(&mut self, _query: Resource<toxoid_component::component::ecs::Query>, _sorting: toxoid_component::component::ecs::SortingDesc) -> () {
-        unimplemented!("Order by not implemented for WASM");
-        // let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
-        // let query = unsafe { Box::from_raw(query_proxy.ptr) };
-        // query.order_by(sorting);
-        // Box::into_raw(query);
+    fn cache(&mut self, query: Resource<toxoid_component::component::ecs::Query>) -> () {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        query.cache();
+        Box::into_raw(query);
+    }
+
+    fn monitor(&mut self, query: Resource<toxoid_component::component::ecs::Query>, index: i8, kind: toxoid_component::component::ecs::MonitorKind) -> () {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        query.monitor(index, map_monitor_kind(kind));
+        Box::into_raw(query);
     }
 
     fn iter(&mut self, query: Resource<toxoid_component::component::ecs::Query>) -> Resource<IterProxy> {
@@ -993,7 +1645,7 @@ impl toxoid_component::component::ecs::HostQuery for StoreState {
         let query = unsafe { Box::from_raw(query_proxy.ptr) };
         let iter = query.iter();
         Box::into_raw(query);
-        self.table.push::<IterProxy>(IterProxy { ptr: iter as *mut toxoid_host::Iter }).unwrap()
+        self.table.push::<IterProxy>(IterProxy::new(iter as *mut toxoid_host::Iter)).unwrap()
     }
 
     fn next(&mut self, query: Resource<toxoid_component::component::ecs::Query>) -> bool {
@@ -1023,9 +1675,7 @@ impl toxoid_component::component::ecs::HostQuery for StoreState {
             // Push component to resource table
             let id = self
                 .table
-                .push::<EntityProxy>(EntityProxy {
-                    ptr: entity
-                })
+                .push::<EntityProxy>(EntityProxy::new(entity))
                 .expect("Failed to push component to table");
             id
         }).collect();
@@ -1041,7 +1691,55 @@ impl toxoid_component::component::ecs::HostQuery for StoreState {
         components
     }
 
-    fn drop(&mut self, _query: Resource<toxoid_component::component::ecs::Query>) -> Result<(), wasmtime::Error> {
+    fn field_views(&mut self, query: Resource<toxoid_component::component::ecs::Query>, index: i8, read_only: bool) -> Vec<Resource<ComponentProxy>> {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        let views = query.field_views(index, read_only);
+        Box::into_raw(query);
+        push_components(self, views)
+    }
+
+    fn changed(&mut self, query: Resource<toxoid_component::component::ecs::Query>) -> bool {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        let changed = query.changed();
+        Box::into_raw(query);
+        changed
+    }
+
+    fn iter_changed(&mut self, query: Resource<toxoid_component::component::ecs::Query>) -> bool {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        let changed = query.iter_changed();
+        Box::into_raw(query);
+        changed
+    }
+
+    fn skip(&mut self, query: Resource<toxoid_component::component::ecs::Query>) {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        query.skip();
+        Box::into_raw(query);
+    }
+
+    fn field_changed(&mut self, query: Resource<toxoid_component::component::ecs::Query>, index: i8) -> bool {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        let changed = query.field_changed(index);
+        Box::into_raw(query);
+        changed
+    }
+
+    fn field_added(&mut self, query: Resource<toxoid_component::component::ecs::Query>, index: i8) -> Vec<bool> {
+        let query_proxy = self.table.get(&query).unwrap() as &QueryProxy;
+        let query = unsafe { Box::from_raw(query_proxy.ptr) };
+        let added = query.field_added(index);
+        Box::into_raw(query);
+        added
+    }
+
+    fn drop(&mut self, query: Resource<toxoid_component::component::ecs::Query>) -> Result<(), wasmtime::Error> {
+        self.table.delete(query)?;
         Ok(())
     }
 }
@@ -1049,8 +1747,9 @@ impl toxoid_component::component::ecs::HostQuery for StoreState {
 impl toxoid_component::component::ecs::HostPhase for StoreState {
     fn new(&mut self, name: String) -> Resource<toxoid_component::component::ecs::Phase> {
         let phase = toxoid_host::Phase::new(name);
+        self.registered.phases.push(phase.get_id());
         let ptr = Box::into_raw(Box::new(phase));
-        let phase_resource = self.table.push(PhaseProxy { ptr }).unwrap();
+        let phase_resource = self.table.push(PhaseProxy::new(ptr)).unwrap();
         phase_resource
     }
 
@@ -1081,7 +1780,8 @@ impl toxoid_component::component::ecs::HostPhase for StoreState {
         id
     }
 
-    fn drop(&mut self, _phase: Resource<toxoid_component::component::ecs::Phase>) -> Result<(), wasmtime::Error> {
+    fn drop(&mut self, phase: Resource<toxoid_component::component::ecs::Phase>) -> Result<(), wasmtime::Error> {
+        self.table.delete(phase)?;
         Ok(())
     }
 }
@@ -1094,8 +1794,9 @@ impl toxoid_component::component::ecs::HostPipeline for StoreState {
             query_desc: query_desc,
             phases: desc.phases
         });
+        self.registered.pipelines.push(pipeline.get_id());
         let ptr = Box::into_raw(Box::new(pipeline));
-        let pipeline_resource = self.table.push(PipelineProxy { ptr }).unwrap();
+        let pipeline_resource = self.table.push(PipelineProxy::new(ptr)).unwrap();
         pipeline_resource
     }
 
@@ -1116,7 +1817,9 @@ impl toxoid_component::component::ecs::HostPipeline for StoreState {
     fn get_id(&mut self, pipeline: Resource<toxoid_component::component::ecs::Pipeline>) -> u64 {
         let pipeline_proxy = self.table.get(&pipeline).unwrap() as &PipelineProxy;
         let pipeline = unsafe { Box::from_raw(pipeline_proxy.ptr) };
-        pipeline.get_id()
+        let id = pipeline.get_id();
+        Box::into_raw(pipeline);
+        id
     }
 
     fn disable(&mut self, pipeline: Resource<toxoid_component::component::ecs::Pipeline>) -> () {
@@ -1133,22 +1836,169 @@ impl toxoid_component::component::ecs::HostPipeline for StoreState {
         Box::into_raw(pipeline);
     }
 
-    fn drop(&mut self, _pipeline: Resource<toxoid_component::component::ecs::Pipeline>) -> Result<(), wasmtime::Error> {
+    fn drop(&mut self, pipeline: Resource<toxoid_component::component::ecs::Pipeline>) -> Result<(), wasmtime::Error> {
+        self.table.delete(pipeline)?;
         Ok(())
     }
 }
 
+// How often the engine epoch is incremented, guest time budgets are measured in these ticks
+const EPOCH_TICK: Duration = Duration::from_millis(5);
+
+// Settings of the WASM engine, read once when the engine is first used
+#[derive(Debug, Clone)]
+pub struct EngineSettings {
+    pub opt_level: OptLevel,
+    pub debug_info: bool,
+    // Directory for precompiled (.cwasm) guest components, keyed by a hash of the WASM bytes.
+    // Components are compiled on every load when this is None.
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl Default for EngineSettings {
+    // Debug builds favour fast compiles and debuggable guests, release builds
+    // optimize guests and cache them so they are only compiled once
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            EngineSettings {
+                opt_level: OptLevel::None,
+                debug_info: true,
+                cache_dir: None,
+            }
+        } else {
+            EngineSettings {
+                opt_level: OptLevel::Speed,
+                debug_info: false,
+                cache_dir: Some(PathBuf::from(DEFAULT_CACHE_DIR)),
+            }
+        }
+    }
+}
+
+pub const DEFAULT_CACHE_DIR: &str = ".toxoid/cache";
+
+static ENGINE_SETTINGS: Lazy<Mutex<EngineSettings>> = Lazy::new(|| Mutex::new(EngineSettings::default()));
+
+// Set the engine settings, must be called before the first guest is loaded
+pub fn configure_engine(settings: EngineSettings) {
+    if Lazy::get(&ENGINE).is_some() {
+        log_runtime(LogLevel::Warn, "WASM engine is already running, engine settings will only apply to the component cache");
+    }
+    *ENGINE_SETTINGS.lock().unwrap() = settings;
+}
+
+pub fn engine_settings() -> EngineSettings {
+    ENGINE_SETTINGS.lock().unwrap().clone()
+}
+
 // Instantiate the WASM engine
 pub static ENGINE: Lazy<Engine> = Lazy::new(|| { 
-    // TODO: Base on debug flag
+    let settings = engine_settings();
     Engine::new(
         Config::new()
-            .debug_info(true)
-            .cranelift_opt_level(OptLevel::None),   
+            .debug_info(settings.debug_info)
+            .cranelift_opt_level(settings.opt_level)
+            // Lets the host interrupt guests that run past their time budget
+            .epoch_interruption(true),
     )
         .unwrap()
 });
 
+static EPOCH_TICKER: Once = Once::new();
+
+// Advance the engine epoch in the background, guests trap once their deadline has passed.
+// Started when the first guest is loaded, so precompiling components doesn't leave a thread running.
+pub fn start_epoch_ticker() {
+    EPOCH_TICKER.call_once(|| {
+        let ticker = ENGINE.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(EPOCH_TICK);
+            ticker.increment_epoch();
+        });
+    });
+}
+
+// Feeds everything hashed into a SHA-256 digest. Unlike `DefaultHasher` the result doesn't change
+// between Rust releases, so cache keys stay valid when the host is rebuilt with another toolchain.
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+}
+
+// Path of the precompiled artifact for the given WASM bytes.
+// The engine's configuration and compatibility hash are part of the key, so changing the
+// engine settings or upgrading wasmtime never picks up a stale artifact.
+fn cache_path(cache_dir: &Path, bytes: &[u8]) -> PathBuf {
+    let settings = engine_settings();
+    let mut hasher = Sha256Hasher(Sha256::new());
+    ENGINE.precompile_compatibility_hash().hash(&mut hasher);
+    hasher.write(format!("{:?}", settings.opt_level).as_bytes());
+    hasher.write(&[settings.debug_info as u8]);
+    hasher.write(bytes);
+    let key = format!("{:x}", hasher.0.finalize());
+    cache_dir.join(key).with_extension("cwasm")
+}
+
+// Compile a guest component, going through the cache when one is configured
+fn compile_component(bytes: &[u8]) -> Result<Component> {
+    let engine = &*ENGINE;
+    let cache_dir = match engine_settings().cache_dir {
+        Some(cache_dir) => cache_dir,
+        None => return Component::new(engine, bytes),
+    };
+    let path = cache_path(&cache_dir, bytes);
+    if path.exists() {
+        // Safety: Only artifacts produced by `Engine::precompile_component` are written to the cache,
+        // and wasmtime checks they were compiled with a compatible configuration
+        match unsafe { Component::deserialize_file(engine, &path) } {
+            Ok(component) => return Ok(component),
+            Err(e) => log_runtime(LogLevel::Warn, &format!("Failed to load cached component {}, recompiling: {:?}", path.display(), e)),
+        }
+    }
+    let serialized = engine.precompile_component(bytes)?;
+    // A cache that can't be written to only costs compile time
+    if let Err(e) = std::fs::create_dir_all(&cache_dir).and_then(|_| std::fs::write(&path, &serialized)) {
+        log_runtime(LogLevel::Warn, &format!("Failed to write cached component {}: {}", path.display(), e));
+    }
+    // Safety: Just produced by `Engine::precompile_component` on this engine
+    unsafe { Component::deserialize(engine, &serialized) }
+}
+
+// Compile a guest component ahead of time.
+// Without an output path the artifact is written to the component cache, where
+// `load_wasm_component` picks it up for the same WASM file. With an output path a .cwasm
+// file is written, which can be loaded directly in place of the .wasm file.
+pub fn precompile_wasm_component(filename: &str, output: Option<&Path>) -> Result<PathBuf> {
+    let bytes = std::fs::read(filename)?;
+    let path = match output {
+        Some(output) => output.to_path_buf(),
+        None => {
+            let cache_dir = engine_settings()
+                .cache_dir
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR));
+            cache_path(&cache_dir, &bytes)
+        }
+    };
+    let serialized = ENGINE.precompile_component(&bytes)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serialized)?;
+    Ok(path)
+}
+
+fn budget_ticks(budget: Duration) -> u64 {
+    (budget.as_micros() / EPOCH_TICK.as_micros()).max(1) as u64
+}
+
 // Create WASM Component Linker
 static LINKER: Lazy<Linker<StoreState>> = Lazy::new(|| {
     let engine = &*ENGINE; // Ensure ENGINE is initialized
@@ -1158,55 +2008,574 @@ static LINKER: Lazy<Linker<StoreState>> = Lazy::new(|| {
     linker
 });
 
-fn new_store() -> Store<StoreState> {
+fn new_store(guest_id: u32, name: &str, manifest: &GuestManifest, quarantine: Quarantine) -> Result<Store<StoreState>> {
     let engine = &*ENGINE; // Ensure ENGINE is initialized
-    Store::new(
+    let mut store = Store::new(
         engine,
         StoreState {
-            ctx: WasiCtxBuilder::new()
-                    // .inherit_stdin()
-                    // .inherit_stdout()
-                    // .inherit_stderr()
-                    .inherit_stdio()
-                    .inherit_env()
-                    .inherit_args()
-                    // .inherit_network()
-                    .build(),
+            ctx: build_wasi_ctx(name, manifest)?,
             table: ResourceTable::new(),
+            guest_id,
+            instance: NEXT_INSTANCE.fetch_add(1, Ordering::SeqCst),
+            name: name.to_string(),
+            world: manifest.world,
+            singletons: HashSet::new(),
+            registered: GuestRegistrations::default(),
+            limits: manifest.limits.clone(),
+            quarantined: HashSet::new(),
+            restart_quarantine: quarantine,
+            assets: HashSet::new(),
+            pending_assets: Vec::new(),
+            current_system: None,
+            callback_iters: HashSet::new(),
         }
-    )
+    );
+    store.limiter(|store_state| store_state);
+    // Instantiating runs the component's start functions, which get the budget of init
+    store.set_epoch_deadline(budget_ticks(manifest.limits.init_budget));
+    Ok(store)
 }
 
-// Create WASM Store
-pub static mut STORE: Lazy<Mutex<Store<StoreState>>> = Lazy::new(|| Mutex::new(new_store()));
-// Crate lazy initialized option
-pub static mut TOXOID_COMPONENT_WORLD: Option<ToxoidComponentWorld> = None;
+// A guest WASM component instance with its own store and resource table
+pub struct GuestInstance {
+    pub id: u32,
+    // See `StoreState::instance`
+    pub instance: u64,
+    pub name: String,
+    pub path: String,
+    // Compiled component, instantiated again when the guest is restarted
+    pub component: Component,
+    pub store: Mutex<Store<StoreState>>,
+    pub world: ToxoidComponentWorld,
+    // Set once a call into the guest failed, a trapped instance can't be entered again so its
+    // callbacks are skipped until it is restarted or reloaded
+    pub disabled: AtomicBool,
+    // Set while init runs with the store locked, callbacks fired meanwhile can't enter the guest
+    pub initializing: AtomicBool,
+}
+
+// Registry of loaded guest components, keyed by guest ID.
+// Guest IDs start at 1 since a null system / observer ctx marks a native callback.
+pub static GUESTS: Lazy<Mutex<HashMap<u32, Arc<GuestInstance>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_GUEST_ID: AtomicU32 = AtomicU32::new(1);
+static NEXT_INSTANCE: AtomicU64 = AtomicU64::new(1);
+// Manifests applied to guests when they are loaded, keyed by guest name
+static GUEST_MANIFESTS: Lazy<Mutex<HashMap<String, GuestManifest>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Set the capabilities and limits of a guest, applied the next time it is loaded
+pub fn set_guest_manifest(name: &str, manifest: GuestManifest) {
+    GUEST_MANIFESTS.lock().unwrap().insert(name.to_string(), manifest);
+}
 
-// TODO: Add the ability to load multiple WASM components
-// instead of overwriting a single guest WASM component
-pub fn load_wasm_component(filename: &str) -> Result<()> {
+// Set the resource limits of a guest, applied the next time it is loaded
+pub fn set_guest_limits(name: &str, limits: GuestLimits) {
+    GUEST_MANIFESTS
+        .lock()
+        .unwrap()
+        .entry(name.to_string())
+        .or_default()
+        .limits = limits;
+}
+
+pub fn guest_manifest(name: &str) -> GuestManifest {
+    GUEST_MANIFESTS
+        .lock()
+        .unwrap()
+        .get(name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+// Whether an error returned from a guest call was caused by the guest going over its limits
+pub fn is_budget_exceeded(error: &wasmtime::Error) -> bool {
+    matches!(error.downcast_ref::<Trap>(), Some(Trap::Interrupt))
+        || error.downcast_ref::<MemoryLimitExceeded>().is_some()
+}
+
+// Disable every system and observer of a guest that failed to initialize
+fn disable_guest(guest: &GuestInstance, store: &mut Store<StoreState>) {
+    guest.disabled.store(true, Ordering::SeqCst);
+    let registered = &store.data().registered;
+    registered
+        .systems
+        .iter()
+        .chain(registered.observers.iter())
+        .for_each(|entity| toxoid_host::Entity { id: *entity }.disable());
+}
+
+pub fn get_guest(guest_id: u32) -> Option<Arc<GuestInstance>> {
+    GUESTS.lock().unwrap().get(&guest_id).cloned()
+}
+
+pub fn get_guest_named(name: &str) -> Option<Arc<GuestInstance>> {
+    GUESTS
+        .lock()
+        .unwrap()
+        .values()
+        .find(|guest| guest.name == name)
+        .cloned()
+}
+
+// Load a guest WASM component under the given name, on the main loop between frames,
+// see `queue_load_wasm_component` for other threads.
+// If a guest with the same name is already loaded it is hot reloaded: the systems, observers,
+// pipelines and phases of the previous instance are torn down while entities and their
+// component data are kept, then init is run again on the new instance. If that init fails,
+// the previous component is restarted.
+pub fn load_wasm_component(name: &str, filename: &str) -> Result<()> {
     let engine = &*ENGINE;
+    // Budgets only apply while the epoch advances
+    start_epoch_ticker();
+
+    // Load the component from disk, precompiled components are loaded as is
+    let component = if filename.ends_with(".cwasm") {
+        // Safety: wasmtime checks the artifact was compiled with a compatible configuration
+        unsafe { Component::deserialize_file(engine, filename)? }
+    } else {
+        compile_component(&std::fs::read(filename)?)?
+    };
+    // New code gets a fresh start, nothing stays quarantined
+    instantiate_guest(name, filename, component, Quarantine::default())
+}
+
+// Instantiate a guest component and run its init, replacing the instance loaded under the same name
+fn instantiate_guest(name: &str, filename: &str, component: Component, quarantine: Quarantine) -> Result<()> {
     let linker = &*LINKER;
+    let previous_guest = get_guest_named(name);
+    let guest_id = match &previous_guest {
+        Some(guest) => guest.id,
+        None => NEXT_GUEST_ID.fetch_add(1, Ordering::SeqCst)
+    };
+
+    let manifest = guest_manifest(name);
+    if !world_exists(manifest.world) {
+        return Err(wasmtime::Error::msg(format!("World {} of guest {} doesn't exist", manifest.world, name)));
+    }
+    let mut store = new_store(guest_id, name, &manifest, quarantine)?;
+    let world = ToxoidComponentWorld::instantiate(&mut store, &component, &linker)?;
+
+    // Only tear down the previous instance once the new one has been instantiated,
+    // so a component that fails to instantiate leaves the running guest untouched.
+    // A running one is started again if the new instance fails to initialize.
+    let mut rollback = None;
+    if let Some(previous_guest) = previous_guest {
+        let mut previous_store = lock_store(&previous_guest);
+        if !previous_guest.disabled.load(Ordering::SeqCst) {
+            let quarantine = previous_store.data().quarantine_by_order();
+            rollback = Some((previous_guest.path.clone(), previous_guest.component.clone(), quarantine));
+        }
+        previous_store.data_mut().teardown();
+    }
+
+    // Register the guest before running init, so systems and comparators it registers refer to
+    // this instance. Init runs with the store locked, callbacks fired by it are rejected since
+    // the guest can't be entered again until init returns.
+    let guest = Arc::new(GuestInstance {
+        id: guest_id,
+        instance: store.data().instance,
+        name: name.to_string(),
+        path: filename.to_string(),
+        component,
+        store: Mutex::new(store),
+        world,
+        disabled: AtomicBool::new(false),
+        initializing: AtomicBool::new(true),
+    });
+    // Replaces the previous instance when reloading
+    GUESTS.lock().unwrap().insert(guest_id, guest.clone());
+
+    let mut store = lock_store(&guest);
+    store.set_epoch_deadline(budget_ticks(manifest.limits.init_budget));
+    // Everything the guest registers while initializing goes into its world
+    let result = with_world(manifest.world, || guest.world.call_init(&mut *store));
+    guest.initializing.store(false, Ordering::SeqCst);
+    if let Err(e) = result {
+        // The instance trapped and can't be entered again
+        disable_guest(&guest, &mut store);
+        // A bad reload doesn't stop the game, the previous component is restarted in place of the
+        // new one. Its memory starts over like after a trap, entities and their data are kept.
+        if let Some((path, component, quarantine)) = rollback {
+            store.data_mut().teardown();
+            drop(store);
+            log_guest(LogLevel::Warn, name, "Restarting the previously loaded component");
+            if let Err(rollback_error) = instantiate_guest(name, &path, component, quarantine) {
+                log_guest(LogLevel::Error, name, &format!("Failed to restart the previously loaded component: {:?}", rollback_error));
+            }
+        }
+        return Err(e.context(format!("Guest {} failed to initialize", name)));
+    }
+
+    Ok(())
+}
+
+// Reload a guest WASM component from the path it was loaded from
+pub fn reload_wasm_component(name: &str) -> Result<()> {
+    let guest = get_guest_named(name)
+        .ok_or_else(|| wasmtime::Error::msg(format!("Guest component {} is not loaded", name)))?;
+    let path = guest.path.clone();
+    drop(guest);
+    load_wasm_component(name, &path)
+}
+
+// Unload a guest WASM component and tear down everything it registered, on the main loop
+// between frames, see `queue_unload_wasm_component` for other threads.
+pub fn unload_wasm_component(name: &str) -> Result<()> {
+    let guest = get_guest_named(name)
+        .ok_or_else(|| wasmtime::Error::msg(format!("Guest component {} is not loaded", name)))?;
+    lock_store(&guest).data_mut().teardown();
+    GUESTS.lock().unwrap().remove(&guest.id);
+    Ok(())
+}
 
-    // Acquire lock before manipulating store
-    let mut store = unsafe { STORE.lock().unwrap() };
+// Restart a guest whose call failed from its compiled component, with the systems and observers
+// that failed so far kept quarantined. The trapped instance can't be entered again, restarting it
+// keeps the rest of the guest running. Entities and component data are kept like on reload.
+fn restart_guest(name: &str) -> Result<()> {
+    let guest = match get_guest_named(name) {
+        Some(guest) => guest,
+        // Unloaded since the restart was queued
+        None => return Ok(())
+    };
+    // Reloaded since the restart was queued
+    if !guest.disabled.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let quarantine = lock_store(&guest).data().quarantine_by_order();
+    let (path, component) = (guest.path.clone(), guest.component.clone());
+    drop(guest);
+    instantiate_guest(name, &path, component, quarantine)
+}
 
-    unsafe {
-        if let Some(world) = TOXOID_COMPONENT_WORLD.take() {
-            drop(world);
+fn queue_restart_guest(name: &str) {
+    let name = name.to_string();
+    toxoid_host::engine::run_between_frames(move || {
+        log_guest(LogLevel::Info, &name, "Restarting guest");
+        if let Err(e) = restart_guest(&name) {
+            log_guest(LogLevel::Error, &name, &format!("Failed to restart guest: {:?}", e));
         }
-        
-        SINGLETON_MAP.clear();
+    });
+}
+
+// Stop a guest after a call into it failed, quarantining the system or observer it failed in.
+// The instance can't be entered again, so the guest is restarted between frames without it.
+fn quarantine_and_restart(guest: &GuestInstance, store: &mut Store<StoreState>, system_id: EcsEntityT) {
+    store.data_mut().quarantine(system_id);
+    // Only the first failure restarts, the instance isn't entered again after it
+    if !guest.disabled.swap(true, Ordering::SeqCst) {
+        queue_restart_guest(&guest.name);
     }
+}
 
-    // Load the component from disk
-    let bytes = std::fs::read(filename)?;
-    let component = Component::new(&engine, bytes)?;
-    
-    unsafe { 
-        TOXOID_COMPONENT_WORLD = Some(ToxoidComponentWorld::instantiate(&mut *store, &component, &linker)?);
-        TOXOID_COMPONENT_WORLD.as_mut().unwrap().call_init(&mut *store)?;
+// Loading, reloading and unloading tear down systems and switch the active world, so they have to
+// run on the main loop. These queue them from other threads, such as a file watcher, to run
+// between frames. Failures are logged.
+pub fn queue_load_wasm_component(name: &str, filename: &str) {
+    let (name, filename) = (name.to_string(), filename.to_string());
+    toxoid_host::engine::run_between_frames(move || {
+        if let Err(e) = load_wasm_component(&name, &filename) {
+            log_guest(LogLevel::Error, &name, &format!("Failed to load WASM component {}: {:?}", filename, e));
+        }
+    });
+}
+
+pub fn queue_unload_wasm_component(name: &str) {
+    let name = name.to_string();
+    toxoid_host::engine::run_between_frames(move || {
+        if let Err(e) = unload_wasm_component(&name) {
+            log_guest(LogLevel::Error, &name, &format!("Failed to unload WASM component: {:?}", e));
+        }
+    });
+}
+
+// Flecs sort callbacks don't take a context pointer, so every guest comparator is given one
+// of a fixed set of trampolines, and the trampoline's slot tells which guest and handle to call.
+type CompareFn = unsafe extern "C" fn(EcsEntityT, *const c_void, EcsEntityT, *const c_void) -> i32;
+
+pub const MAX_GUEST_COMPARATORS: usize = 64;
+
+// Guest ID, comparator handle and sorted system of each trampoline slot
+static GUEST_COMPARATORS: Mutex<[Option<(u32, u64, EcsEntityT)>; MAX_GUEST_COMPARATORS]> = Mutex::new([None; MAX_GUEST_COMPARATORS]);
+
+unsafe extern "C" fn guest_compare<const SLOT: usize>(e1: EcsEntityT, c1: *const c_void, e2: EcsEntityT, c2: *const c_void) -> i32 {
+    run_guest_compare(SLOT, e1, c1 as u64, e2, c2 as u64)
+}
+
+macro_rules! compare_trampolines {
+    ($($slot:literal)*) => { [$(guest_compare::<$slot> as CompareFn),*] };
+}
+
+static COMPARE_TRAMPOLINES: [CompareFn; MAX_GUEST_COMPARATORS] = compare_trampolines!(
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+    32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+);
+
+fn allocate_comparator(guest_id: u32, handle: u64, system: EcsEntityT) -> Option<usize> {
+    let mut comparators = GUEST_COMPARATORS.lock().unwrap();
+    let slot = comparators.iter().position(|comparator| comparator.is_none())?;
+    comparators[slot] = Some((guest_id, handle, system));
+    Some(slot)
+}
+
+fn release_comparator(slot: usize) {
+    GUEST_COMPARATORS.lock().unwrap()[slot] = None;
+}
+
+// Run a guest comparator for Flecs.
+// Sorting happens when Flecs iterates the system's query before running it, outside of any call
+// into the guest. A failing comparator quarantines its system like a failing callback, entities compare equal
+// until the guest is restarted.
+fn run_guest_compare(slot: usize, e1: EcsEntityT, c1: u64, e2: EcsEntityT, c2: u64) -> i32 {
+    let (guest_id, handle, system_id) = match GUEST_COMPARATORS.lock().unwrap()[slot] {
+        Some(comparator) => comparator,
+        None => return 0
+    };
+    let guest = match get_guest(guest_id) {
+        Some(guest) => guest,
+        None => return 0
+    };
+    if guest.disabled.load(Ordering::SeqCst) {
+        return 0;
+    }
+    // The guest can't be re-entered if the sort was started from one of its own calls
+    let mut store = match guest.store.try_lock() {
+        Ok(store) => store,
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) => return 0
     };
+    let callback_budget = store.data().limits.callback_budget;
+    store.set_epoch_deadline(budget_ticks(callback_budget));
+    match guest
+        .world
+        .toxoid_component_component_callbacks()
+        .call_compare(&mut *store, handle, e1, c1, e2, c2) {
+        Ok(order) => order,
+        Err(error) => {
+            let message = format!("Comparator of system {} failed ({:?}): {:?}", system_name(system_id), error_kind(&error), error);
+            log_guest(LogLevel::Error, &guest.name, &message);
+            quarantine_and_restart(&guest, &mut store, system_id);
+            0
+        }
+    }
+}
 
-    Ok(())
-}
\ No newline at end of file
+// Guest and handle of the callback of a guest system or observer. Flecs frees it with the system or
+// observer, however it was deleted, so the closure behind the handle is dropped in the guest too.
+// The run context is free for it since guest systems and observers have no run action.
+struct GuestCallbackCtx {
+    guest_id: u32,
+    instance: u64,
+    handle: u64,
+}
+
+// Callbacks of deleted guest systems and observers. The guest's store may be in use when Flecs
+// deletes them, e.g. when the guest removed the entity itself, so they are freed between frames.
+// Handles are only meaningful to the instance that registered them.
+static FREED_CALLBACKS: Lazy<Mutex<Vec<GuestCallbackCtx>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+fn guest_callback_ctx(guest_id: u32, instance: u64, handle: u64) -> (*mut c_void, Option<unsafe extern "C" fn(*mut c_void)>) {
+    let ctx = Box::into_raw(Box::new(GuestCallbackCtx { guest_id, instance, handle }));
+    (ctx as *mut c_void, Some(free_guest_callback_ctx))
+}
+
+unsafe extern "C" fn free_guest_callback_ctx(ctx: *mut c_void) {
+    let ctx = Box::from_raw(ctx as *mut GuestCallbackCtx);
+    let mut freed = FREED_CALLBACKS.lock().unwrap();
+    if freed.is_empty() {
+        toxoid_host::engine::run_between_frames(free_guest_callbacks);
+    }
+    freed.push(*ctx);
+}
+
+// Hand the callbacks of deleted systems and observers back to their guests
+pub fn free_guest_callbacks() {
+    let freed = std::mem::take(&mut *FREED_CALLBACKS.lock().unwrap());
+    for GuestCallbackCtx { guest_id, instance, handle } in freed {
+        // Unloaded, reloaded and restarted instances dropped their callbacks with their memory,
+        // the instance loaded since numbers its own callbacks from the start again
+        let guest = match get_guest(guest_id) {
+            Some(guest) if guest.instance == instance => guest,
+            _ => continue
+        };
+        if guest.disabled.load(Ordering::SeqCst) {
+            continue;
+        }
+        let mut store = lock_store(&guest);
+        let callback_budget = store.data().limits.callback_budget;
+        store.set_epoch_deadline(budget_ticks(callback_budget));
+        if let Err(error) = guest.world.toxoid_component_component_callbacks().call_free(&mut *store, handle) {
+            let message = format!("Freeing callback {} failed ({:?}): {:?}", handle, error_kind(&error), error);
+            log_guest(LogLevel::Error, &guest.name, &message);
+            // The instance can't be entered again after a trap
+            if !guest.disabled.swap(true, Ordering::SeqCst) {
+                queue_restart_guest(&guest.name);
+            }
+        }
+    }
+}
+
+// Kind of failure of a guest callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuestErrorKind {
+    // The guest went over its time or memory budget
+    BudgetExceeded,
+    // The guest trapped, for example on a panic or an unreachable instruction
+    Trap,
+    // A host function called by the guest failed, for example a lookup of a missing resource
+    Host,
+}
+
+// Error raised when a guest system or observer callback fails
+#[derive(Debug)]
+pub struct GuestCallbackError {
+    pub guest: String,
+    pub system: String,
+    pub system_id: EcsEntityT,
+    pub kind: GuestErrorKind,
+    pub error: wasmtime::Error,
+}
+
+impl std::fmt::Display for GuestCallbackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Guest {} failed in system {} ({:?}): {:?}", self.guest, self.system, self.kind, self.error)
+    }
+}
+
+impl std::error::Error for GuestCallbackError {}
+
+fn error_kind(error: &wasmtime::Error) -> GuestErrorKind {
+    if is_budget_exceeded(error) {
+        GuestErrorKind::BudgetExceeded
+    } else if error.downcast_ref::<Trap>().is_some() {
+        GuestErrorKind::Trap
+    } else {
+        GuestErrorKind::Host
+    }
+}
+
+fn system_name(system_id: EcsEntityT) -> String {
+    let name = toxoid_host::Entity { id: system_id }.get_name();
+    if name.is_empty() {
+        format!("#{}", system_id)
+    } else {
+        name
+    }
+}
+
+// Runtime messages go to the host log sinks
+fn log_runtime(level: LogLevel, message: &str) {
+    toxoid_host::log::log(LogRecord::new(level, "runtime", message));
+}
+
+// Runtime messages about a guest go to the host log sinks, tagged with the guest
+fn log_guest(level: LogLevel, guest: &str, message: &str) {
+    let mut record = LogRecord::new(level, "runtime", message);
+    record.guest = Some(guest.to_string());
+    toxoid_host::log::log(record);
+}
+
+// Lock a guest store, ignoring poisoning from a host panic that was caught during a guest call
+fn lock_store(guest: &GuestInstance) -> MutexGuard<'_, Store<StoreState>> {
+    guest.store.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Hand the ECS iterator of a system or observer callback to the guest
+fn push_callback_iter(store_state: &mut StoreState, iter: *mut toxoid_host::ecs_iter_t) -> Result<Resource<IterProxy>> {
+    let iter = Box::into_raw(Box::new(toxoid_host::Iter::new(iter as u64)));
+    let resource = store_state.table.push::<IterProxy>(IterProxy::new(iter))?;
+    store_state.callback_iters.insert(resource.rep());
+    Ok(resource)
+}
+
+// The iterator only lives as long as the callback, the ECS iterator behind it is gone
+// once the callback returns. One the guest held on to is left empty.
+fn expire_iter(store_state: &mut StoreState, rep: u32) {
+    // Gone from the set if the guest dropped it, the rep may belong to another resource by now
+    if !store_state.callback_iters.remove(&rep) {
+        return;
+    }
+    if let Ok(iter_proxy) = store_state.table.get_mut(&Resource::<IterProxy>::new_borrow(rep)) {
+        if !iter_proxy.ptr.is_null() {
+            drop(unsafe { Box::from_raw(iter_proxy.ptr) });
+            iter_proxy.ptr = std::ptr::null_mut();
+        }
+    }
+}
+
+// Run a system or observer callback on the guest that registered it.
+// A failing callback never takes down the host, the offending system is quarantined and, since a trapped
+// component instance can't be entered again, the guest is restarted between frames without it.
+// `iter` must be the iterator Flecs passed to the callback.
+pub unsafe fn run_guest_callback(guest_id: u32, iter: *mut toxoid_host::ecs_iter_t, handle: u64) -> std::result::Result<(), GuestCallbackError> {
+    let guest = match get_guest(guest_id) {
+        Some(guest) => guest,
+        // Guest was unloaded
+        None => return Ok(())
+    };
+    if guest.disabled.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let system_id = (*iter).system;
+    // Locking the store here would deadlock on the init call holding it
+    if guest.initializing.load(Ordering::SeqCst) {
+        let message = format!("Callback of system {} skipped, guest is still initializing", system_name(system_id));
+        log_guest(LogLevel::Warn, &guest.name, &message);
+        return Ok(());
+    }
+
+    // Get a lock on the store and get a mutable reference to the actual Store
+    let mut store_guard = lock_store(&guest);
+    let store = &mut *store_guard;
+    // The guest may have enabled a quarantined system again
+    if store.data().quarantined.contains(&system_id) {
+        return Ok(());
+    }
+
+    let iter_resource_id = match push_callback_iter(store.data_mut(), iter) {
+        Ok(iter_resource_id) => iter_resource_id,
+        Err(error) => {
+            // The guest wasn't entered, only the system is stopped
+            store.data_mut().quarantine(system_id);
+            return Err(GuestCallbackError {
+                guest: guest.name.clone(),
+                system: system_name(system_id),
+                system_id,
+                kind: GuestErrorKind::Host,
+                error,
+            });
+        }
+    };
+    let iter_rep = iter_resource_id.rep();
+
+    store.data_mut().current_system = Some(system_id);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let callback_budget = store.data().limits.callback_budget;
+        store.set_epoch_deadline(budget_ticks(callback_budget));
+        guest
+            .world
+            .toxoid_component_component_callbacks()
+            .call_run(&mut *store, iter_resource_id, handle)
+    }))
+        .unwrap_or_else(|panic| {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(wasmtime::Error::msg(format!("Host panicked: {}", message)))
+        });
+
+    store.data_mut().current_system = None;
+
+    expire_iter(store.data_mut(), iter_rep);
+
+    result.map_err(|error| {
+        quarantine_and_restart(&guest, store, system_id);
+        GuestCallbackError {
+            guest: guest.name.clone(),
+            system: system_name(system_id),
+            system_id,
+            kind: error_kind(&error),
+            error,
+        }
+    })
+}
@@ -0,0 +1,229 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use bytes::Bytes;
+use wasmtime::Result;
+use wasmtime_wasi::{DirPerms, FilePerms, HostOutputStream, StdoutStream, StreamResult, Subscribe, WasiCtx, WasiCtxBuilder};
+use toxoid_host::log::{log, LogLevel, LogRecord};
+use toxoid_host::worlds::{WorldId, DEFAULT_WORLD};
+use crate::GuestLimits;
+
+// Capabilities and limits of a guest component.
+// The default manifest gives the guest the host's stdio, environment and arguments with no
+// filesystem access, `GuestManifest::sandboxed` is meant for untrusted guests such as mods.
+#[derive(Clone, Debug)]
+pub struct GuestManifest {
+    // Host directories mapped into the guest
+    pub dirs: Vec<PreopenedDir>,
+    // Names of the host environment variables visible to the guest, all of them when None
+    pub inherit_env: Option<Vec<String>>,
+    // Environment variables set for the guest, these take precedence over inherited ones
+    pub env: Vec<(String, String)>,
+    // Arguments passed to the guest, the host's arguments when None
+    pub args: Option<Vec<String>>,
+    pub stdout: GuestOutput,
+    pub stderr: GuestOutput,
+    pub limits: GuestLimits,
+    // ECS world the guest registers its components, systems and observers in
+    pub world: WorldId,
+}
+
+impl Default for GuestManifest {
+    fn default() -> Self {
+        GuestManifest {
+            dirs: Vec::new(),
+            inherit_env: None,
+            env: Vec::new(),
+            args: None,
+            stdout: GuestOutput::Inherit,
+            stderr: GuestOutput::Inherit,
+            limits: GuestLimits::default(),
+            world: DEFAULT_WORLD,
+        }
+    }
+}
+
+impl GuestManifest {
+    // No filesystem, environment or arguments, output goes to the host log
+    pub fn sandboxed() -> Self {
+        GuestManifest {
+            inherit_env: Some(Vec::new()),
+            args: Some(Vec::new()),
+            stdout: GuestOutput::Log,
+            stderr: GuestOutput::Log,
+            ..Default::default()
+        }
+    }
+
+    // Bind the guest to an ECS world other than the default one
+    pub fn in_world(mut self, world: WorldId) -> Self {
+        self.world = world;
+        self
+    }
+
+    // Map a host directory into the guest, read only
+    pub fn read_only_dir(mut self, host_path: impl Into<PathBuf>, guest_path: &str) -> Self {
+        self.dirs.push(PreopenedDir { host_path: host_path.into(), guest_path: guest_path.to_string(), access: DirAccess::ReadOnly });
+        self
+    }
+
+    // Map a host directory into the guest, readable and writable
+    pub fn read_write_dir(mut self, host_path: impl Into<PathBuf>, guest_path: &str) -> Self {
+        self.dirs.push(PreopenedDir { host_path: host_path.into(), guest_path: guest_path.to_string(), access: DirAccess::ReadWrite });
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PreopenedDir {
+    pub host_path: PathBuf,
+    // Path the directory is visible under inside the guest
+    pub guest_path: String,
+    pub access: DirAccess,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+// Where a guest's stdout / stderr is written to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuestOutput {
+    // The host process' stdout / stderr
+    Inherit,
+    // The host log sinks, line by line and tagged with the guest name
+    Log,
+    // Discarded
+    Null,
+}
+
+// Build the WASI context of a guest from its manifest
+pub fn build_wasi_ctx(name: &str, manifest: &GuestManifest) -> Result<WasiCtx> {
+    let mut builder = WasiCtxBuilder::new();
+    match manifest.stdout {
+        GuestOutput::Inherit => { builder.inherit_stdout(); }
+        GuestOutput::Log => { builder.stdout(GuestLogOutput::new(name, false)); }
+        GuestOutput::Null => {}
+    }
+    match manifest.stderr {
+        GuestOutput::Inherit => { builder.inherit_stderr(); }
+        GuestOutput::Log => { builder.stderr(GuestLogOutput::new(name, true)); }
+        GuestOutput::Null => {}
+    }
+    match &manifest.inherit_env {
+        None => { builder.inherit_env(); }
+        Some(names) => {
+            std::env::vars()
+                .filter(|(key, _)| names.contains(key))
+                .for_each(|(key, value)| { builder.env(key, value); });
+        }
+    }
+    builder.envs(&manifest.env);
+    match &manifest.args {
+        None => { builder.inherit_args(); }
+        Some(args) => { builder.args(args); }
+    }
+    for dir in manifest.dirs.iter() {
+        let (dir_perms, file_perms) = match dir.access {
+            DirAccess::ReadOnly => (DirPerms::READ, FilePerms::READ),
+            DirAccess::ReadWrite => (DirPerms::all(), FilePerms::all()),
+        };
+        builder
+            .preopened_dir(&dir.host_path, &dir.guest_path, dir_perms, file_perms)
+            .map_err(|e| e.context(format!("Failed to open {} for guest {}", dir.host_path.display(), name)))?;
+    }
+    Ok(builder.build())
+}
+
+// Guest output stream that writes into the host log.
+// WASI asks for a new stream every time the guest writes, they all share the guest's log
+// so a line written in several parts is logged once.
+struct GuestLogOutput {
+    log: Arc<GuestLog>,
+}
+
+impl GuestLogOutput {
+    fn new(guest: &str, stderr: bool) -> Self {
+        GuestLogOutput {
+            log: Arc::new(GuestLog { guest: guest.to_string(), stderr, line: Mutex::new(Vec::new()) })
+        }
+    }
+}
+
+impl StdoutStream for GuestLogOutput {
+    fn stream(&self) -> Box<dyn HostOutputStream> {
+        Box::new(GuestLogStream { log: self.log.clone() })
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
+}
+
+struct GuestLog {
+    guest: String,
+    stderr: bool,
+    // Output since the last newline
+    line: Mutex<Vec<u8>>,
+}
+
+impl GuestLog {
+    fn log_line(&self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        // Plain output has no level, stderr is logged as warnings
+        let (level, target) = if self.stderr { (LogLevel::Warn, "stderr") } else { (LogLevel::Info, "stdout") };
+        let mut record = LogRecord::new(level, target, &line);
+        record.guest = Some(self.guest.clone());
+        log(record);
+    }
+}
+
+// The last partial line is logged once the guest's store is gone
+impl Drop for GuestLog {
+    fn drop(&mut self) {
+        let line = self.line.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !line.is_empty() {
+            let line = std::mem::take(line);
+            self.log_line(&line);
+        }
+    }
+}
+
+struct GuestLogStream {
+    log: Arc<GuestLog>,
+}
+
+// Most the guest can write at once, and the longest line kept before it's logged without a newline
+const MAX_LOG_WRITE: usize = 1024 * 1024;
+
+#[wasmtime_wasi::async_trait]
+impl Subscribe for GuestLogStream {
+    async fn ready(&mut self) {}
+}
+
+impl HostOutputStream for GuestLogStream {
+    fn write(&mut self, bytes: Bytes) -> StreamResult<()> {
+        let mut line = self.log.line.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        line.extend_from_slice(&bytes);
+        while let Some(end) = line.iter().position(|byte| *byte == b'\n') {
+            let complete: Vec<u8> = line.drain(..=end).collect();
+            self.log.log_line(&complete[..end]);
+        }
+        // Output that never ends its line is logged in pieces
+        if line.len() >= MAX_LOG_WRITE {
+            let partial = std::mem::take(&mut *line);
+            self.log.log_line(&partial);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> StreamResult<()> {
+        // Partial lines are kept until they are complete or the guest is gone
+        Ok(())
+    }
+
+    fn check_write(&mut self) -> StreamResult<usize> {
+        Ok(MAX_LOG_WRITE)
+    }
+}
@@ -0,0 +1,617 @@
+use super::*;
+use std::sync::Once;
+use toxoid_api::{CommandKind, Commands, ComponentType, Entity, Event, Observer, Position, Query, Random, System, World};
+use toxoid_component::component::ecs::{HostEntity, HostIter, HostQuery, QueryDesc};
+
+// The ECS world is global, tests using it take turns
+static WORLD_LOCK: Mutex<()> = Mutex::new(());
+static INIT: Once = Once::new();
+
+// Failures of guest callbacks, by guest
+static GUEST_ERRORS: Mutex<Vec<(String, GuestErrorKind)>> = Mutex::new(Vec::new());
+
+unsafe extern "C" fn native_trampoline(iter: *mut toxoid_host::ecs_iter_t) {
+    let handle = (*iter).callback_ctx as u64;
+    // Guest systems and observers have the ID of their guest in ctx
+    let guest_id = (*iter).ctx as usize as u32;
+    if guest_id != 0 {
+        if let Err(error) = run_guest_callback(guest_id, iter, handle) {
+            GUEST_ERRORS.lock().unwrap().push((error.guest, error.kind));
+        }
+        return;
+    }
+    if let Some(callback) = toxoid_api::get_callback(handle) {
+        callback(&toxoid_api::Iter { iter: toxoid_api::ToxoidIter { ptr: iter as *mut c_void } });
+    }
+}
+
+fn lock_world() -> MutexGuard<'static, ()> {
+    let guard = WORLD_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    INIT.call_once(|| {
+        unsafe {
+            toxoid_host::QUERY_TRAMPOLINE = Some(native_trampoline);
+            toxoid_host::CALLBACK_FREE = Some(toxoid_api::free_callback_ctx);
+        }
+        toxoid_api::components::init();
+    });
+    guard
+}
+
+fn store_state() -> StoreState {
+    new_store(0, "test", &GuestManifest::default(), Quarantine::default())
+        .unwrap()
+        .into_data()
+}
+
+#[test]
+fn query_iterated_in_a_system_leaves_no_proxies() {
+    let _world = lock_world();
+    let mut entity = toxoid_api::Entity::new(None);
+    entity.add::<Position>();
+    let state = Arc::new(Mutex::new(store_state()));
+    let system_state = state.clone();
+    let system = System::dsl_closure("Position", None, move |iter| {
+        let mut state = system_state.lock().unwrap();
+        let state = &mut *state;
+        let callback_iter = push_callback_iter(state, iter.iter.ptr as *mut toxoid_host::ecs_iter_t).unwrap();
+        let callback_rep = callback_iter.rep();
+
+        // Like a guest that drops the callback's iterator and iterates a query,
+        // whose iterator takes over the rep of the dropped one
+        let query = HostQuery::new(state, QueryDesc { expr: "Position".to_string() });
+        HostQuery::build(state, Resource::new_borrow(query.rep()));
+        HostIter::drop(state, callback_iter).unwrap();
+        let query_iter = HostQuery::iter(state, Resource::new_borrow(query.rep()));
+        assert_eq!(query_iter.rep(), callback_rep);
+        let mut count = 0;
+        while HostIter::next(state, Resource::new_borrow(query_iter.rep())) {
+            for entity in HostIter::entities(state, Resource::new_borrow(query_iter.rep())) {
+                count += 1;
+                HostEntity::drop(state, entity).unwrap();
+            }
+        }
+        assert!(count > 0);
+
+        // Expiring the callback's iterator leaves the query's alone
+        expire_iter(state, callback_rep);
+        assert!(!state.table.get(&query_iter).unwrap().ptr.is_null());
+        HostIter::drop(state, query_iter).unwrap();
+        HostQuery::drop(state, query).unwrap();
+    }).build();
+
+    World::progress(toxoid_host::worlds::DEFAULT_WORLD, 0.016);
+    assert_eq!(live_proxies(), 0);
+
+    system.delete();
+    World::remove_entity(entity.get_id());
+    drop(state);
+}
+
+#[test]
+fn range_f32_excludes_max() {
+    let _world = lock_world();
+    Random::set_seed(1);
+    let stream = Random::stream("range");
+    // Small enough next to its bounds that rounding would reach max
+    let (min, max) = (1.0, 1.0 + f32::EPSILON);
+    for _ in 0..1000 {
+        let value = stream.range_f32(min, max);
+        assert!(value >= min && value < max);
+    }
+    assert_eq!(stream.range_f32(2.0, 2.0), 2.0);
+}
+
+mod test_components {
+    use toxoid_api::*;
+
+    component! {
+        Holder {
+            target: EcsEntityT,
+            label: String,
+            items: Vec::<u64>,
+            address: PointerT
+        },
+        Blocker {
+            value: u32
+        },
+        Point {
+            x: i32,
+            y: i32
+        },
+        Segment {
+            start: Point,
+            end: Point
+        },
+        Counter {
+            value: u32
+        },
+        Samples {
+            values: [i16; 3]
+        }
+    }
+}
+use test_components::{Blocker, Counter, Holder, Point, Samples, Segment};
+
+fn lookup(name: &std::ffi::CStr) -> EcsEntityT {
+    unsafe { toxoid_host::ecs_lookup(toxoid_host::WORLD.0, name.as_ptr()) }
+}
+
+#[test]
+fn nested_component_copies_from_views() {
+    let _world = lock_world();
+    Segment::register();
+    let mut entity = Entity::new(None);
+    entity.add::<Segment>();
+    let segment = entity.get::<Segment>();
+    let mut start = Point::default();
+    start.x = 3;
+    start.y = -4;
+    segment.set_start(start);
+    // The view of `start` has no values in its fields
+    segment.set_end(segment.get_start());
+    let end = segment.get_end();
+    assert_eq!((end.get_x(), end.get_y()), (3, -4));
+    World::remove_entity(entity.get_id());
+}
+
+#[test]
+fn closure_entered_again_is_skipped() {
+    let _world = lock_world();
+    let calls = Arc::new(Mutex::new(0));
+    let handle = Arc::new(Mutex::new(None));
+    let (closure_calls, closure_handle) = (calls.clone(), handle.clone());
+    // Like an observer triggering its own event, the closure calls itself
+    let callback = toxoid_api::Callback::from_closure(move |iter| {
+        *closure_calls.lock().unwrap() += 1;
+        let handle = (*closure_handle.lock().unwrap()).unwrap();
+        toxoid_api::get_callback(handle).unwrap()(iter);
+    });
+    *handle.lock().unwrap() = Some(callback.cb_handle());
+
+    let iter = toxoid_api::Iter { iter: toxoid_api::ToxoidIter { ptr: std::ptr::null_mut() } };
+    toxoid_api::get_callback(callback.cb_handle()).unwrap()(&iter);
+    assert_eq!(*calls.lock().unwrap(), 1);
+    toxoid_api::free_callback(callback.cb_handle());
+    assert!(toxoid_api::get_callback(callback.cb_handle()).is_none());
+}
+
+#[test]
+fn typed_query_views_mark_writes_and_keep_reads() {
+    let _world = lock_world();
+    Blocker::register();
+    let mut entity = Entity::new(None);
+    entity.add::<Position>();
+    entity.add::<Blocker>();
+    let sets = Arc::new(Mutex::new(0));
+    let observer_sets = sets.clone();
+    let mut observer = Observer::dsl("Blocker", vec![Event::OnSet], move |iter| {
+        *observer_sets.lock().unwrap() += iter.count();
+    });
+    observer.build();
+
+    let mut query = Query::<(&Position, &mut Blocker)>::typed();
+    query.build();
+    query.each(|_, (position, blocker)| {
+        position.set_x(5);
+        blocker.set_value(9);
+    });
+    // The write through the read-only view failed, the other one fired OnSet
+    assert_eq!(entity.get::<Position>().get_x(), 0);
+    assert_eq!(entity.get::<Blocker>().get_value(), 9);
+    assert_eq!(*sets.lock().unwrap(), 1);
+
+    World::remove_entity(observer.get_id());
+    World::remove_entity(entity.get_id());
+}
+
+fn changed_counters(query: &mut Query<(&Counter, toxoid_api::Changed<Counter>)>) -> Vec<u64> {
+    let mut entities = vec![];
+    query.each(|entity, _| entities.push(entity.get_id()));
+    entities
+}
+
+#[test]
+fn changed_query_sees_writes_once() {
+    let _world = lock_world();
+    Counter::register();
+    let mut entity = Entity::new(None);
+    entity.add::<Counter>();
+    let mut query = Query::<(&Counter, toxoid_api::Changed<Counter>)>::typed();
+    query.build();
+
+    // Tables the query hasn't seen yet count as changed
+    assert_eq!(changed_counters(&mut query), vec![entity.get_id()]);
+    assert!(changed_counters(&mut query).is_empty());
+    entity.get::<Counter>().set_value(4);
+    assert_eq!(changed_counters(&mut query), vec![entity.get_id()]);
+    assert!(changed_counters(&mut query).is_empty());
+
+    World::remove_entity(entity.get_id());
+}
+
+#[test]
+fn changed_filter_fetched_twice_gives_the_same_rows() {
+    let _world = lock_world();
+    Counter::register();
+    let mut entity = Entity::new(None);
+    entity.add::<Counter>();
+    let mut query = Query::<(&Counter, toxoid_api::Changed<Counter>)>::typed();
+    query.build();
+
+    let iter = query.iter();
+    let mut fetched = vec![];
+    while query.next() {
+        fetched.push(iter.fetch::<(&Counter, toxoid_api::Changed<Counter>)>().len());
+        fetched.push(iter.fetch::<(&Counter, toxoid_api::Changed<Counter>)>().len());
+    }
+    assert_eq!(fetched, vec![1, 1]);
+    assert!(changed_counters(&mut query).is_empty());
+
+    World::remove_entity(entity.get_id());
+}
+
+#[test]
+fn added_query_sees_new_entities_only() {
+    let _world = lock_world();
+    Counter::register();
+    let mut old = Entity::new(None);
+    old.add::<Counter>();
+    let mut query = Query::<(&Counter, toxoid_api::Added<Counter>)>::typed();
+    query.build();
+    let mut added = || {
+        let mut entities = vec![];
+        query.each(|entity, _| entities.push(entity.get_id()));
+        entities
+    };
+
+    assert!(added().is_empty());
+    let mut new = Entity::new(None);
+    new.add::<Counter>();
+    assert_eq!(added(), vec![new.get_id()]);
+    assert!(added().is_empty());
+    // Losing the component isn't reported
+    old.remove::<Counter>();
+    assert!(added().is_empty());
+
+    World::remove_entity(old.get_id());
+    World::remove_entity(new.get_id());
+}
+
+#[test]
+fn dropped_query_frees_its_monitors() {
+    let _world = lock_world();
+    Counter::register();
+    let mut entity = Entity::new(None);
+    entity.add::<Counter>();
+    let observers = || {
+        let mut observers = Query::dsl("flecs.core.Observer");
+        observers.build();
+        observers.iter();
+        let mut count = 0;
+        while observers.next() {
+            count += observers.count();
+        }
+        count
+    };
+    let before = observers();
+    let mut query = Query::<(&Counter, toxoid_api::Added<Counter>)>::typed();
+    query.build();
+    assert_eq!(observers(), before + 1);
+    drop(query);
+    assert_eq!(observers(), before);
+    World::remove_entity(entity.get_id());
+}
+
+#[test]
+fn commands_are_applied_at_the_next_flush_point() {
+    let _world = lock_world();
+    let world = World::create();
+    let added = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::new(Mutex::new(0));
+    let (system_added, system_seen) = (added.clone(), seen.clone());
+    let entity = World::with(world, || {
+        Blocker::register();
+        let mut entity = Entity::new(None);
+        entity.add::<Position>();
+        let entity_id = entity.get_id();
+        System::dsl("Position", None, move |_| {
+            Commands::add::<Blocker>(&Entity::from_id(entity_id));
+            system_added.lock().unwrap().push(Entity::from_id(entity_id).has::<Blocker>());
+        })
+        .build();
+        // Same phase, built after the system recording the command
+        System::dsl("Blocker", None, move |iter| {
+            *system_seen.lock().unwrap() += iter.count();
+        })
+        .build();
+        entity_id
+    });
+
+    World::progress(world, 0.0);
+    // Not applied while the phase runs, the flush point of the next phase applies it
+    assert_eq!(*added.lock().unwrap(), vec![false]);
+    assert_eq!(*seen.lock().unwrap(), 0);
+    assert!(World::with(world, || Entity::from_id(entity).has::<Blocker>()));
+    World::progress(world, 0.0);
+    assert_eq!(*seen.lock().unwrap(), 1);
+    assert!(World::with(world, Commands::take_errors).is_empty());
+    assert!(World::destroy(world));
+}
+
+#[test]
+fn commands_on_deleted_entities_are_reported() {
+    let _world = lock_world();
+    Blocker::register();
+    let entity = Entity::new(None);
+    Commands::despawn(&entity);
+    Commands::add::<Blocker>(&entity);
+    Commands::despawn(&entity);
+    let errors = Commands::flush();
+    let kinds: Vec<_> = errors.iter().map(|error| error.kind).collect();
+    assert_eq!(kinds, vec![CommandKind::Add, CommandKind::Despawn]);
+    assert!(errors.iter().all(|error| error.entity == entity.get_id()));
+    assert_eq!(errors[0].component, Blocker::get_id());
+}
+
+#[test]
+fn pair_commands_check_both_parts() {
+    let _world = lock_world();
+    let relationship = Entity::new(None);
+    let target = Entity::new(None);
+    let pair = unsafe { toxoid_host::ecs_make_pair(relationship.get_id(), target.get_id()) };
+    let entity = Entity::new(None);
+    Commands::add_id(entity.get_id(), pair);
+    assert!(Commands::flush().is_empty());
+    let targets = entity.relationship_entities(toxoid_api::Relationship::Custom(relationship.get_id()));
+    assert_eq!(targets.iter().map(Entity::get_id).collect::<Vec<_>>(), vec![target.get_id()]);
+
+    World::remove_entity(target.get_id());
+    Commands::remove_id(entity.get_id(), pair);
+    let errors = Commands::flush();
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].kind, errors[0].component), (CommandKind::Remove, pair));
+
+    World::remove_entity(entity.get_id());
+    World::remove_entity(relationship.get_id());
+}
+
+#[test]
+fn builder_sets_arrays_and_pointers() {
+    let _world = lock_world();
+    Samples::register();
+    Holder::register();
+    let mut entity = Entity::builder()
+        .with(|samples: &mut Samples| samples.values = [1, -2, 3])
+        .with(|holder: &mut Holder| {
+            holder.label = "built".to_string();
+            holder.address = 0xBEEF;
+        })
+        .try_build()
+        .unwrap();
+    assert_eq!(entity.get::<Samples>().get_values(), [1, -2, 3]);
+    let holder = entity.get::<Holder>();
+    assert_eq!(holder.get_label(), "built");
+    // Values of this run keep their addresses, unlike restored snapshots
+    assert_eq!(holder.get_address(), 0xBEEF);
+    World::remove_entity(entity.get_id());
+}
+
+#[test]
+fn builder_rejects_bad_values_before_creating_the_entity() {
+    let _world = lock_world();
+    Counter::register();
+    Samples::register();
+    let mut trailing = 7u32.to_le_bytes().to_vec();
+    trailing.push(0);
+    let result = Entity::builder()
+        .named("TrailingValues")
+        .add::<Position>()
+        .set_encoded(Counter::get_id(), trailing)
+        .try_build();
+    assert!(result.is_err());
+    assert_eq!(lookup(c"TrailingValues"), 0);
+
+    // Two elements for an array of three
+    let mut short = 2u32.to_le_bytes().to_vec();
+    short.extend_from_slice(&[1, 0, 2, 0]);
+    let result = Entity::builder()
+        .named("ShortArray")
+        .set_encoded(Samples::get_id(), short)
+        .try_build();
+    assert!(result.is_err());
+    assert_eq!(lookup(c"ShortArray"), 0);
+
+    // `build` leaves all values out and logs the error
+    let entity = Entity::builder()
+        .with(|counter: &mut Counter| counter.value = 5)
+        .set_encoded(Samples::get_id(), vec![0])
+        .build();
+    assert!(!entity.has::<Counter>());
+    assert!(!entity.has::<Samples>());
+    World::remove_entity(entity.get_id());
+}
+
+// Guest component in the text format, with one system that removes `target` every frame.
+// Like guests built with toxoid_api, freeing a callback makes it do nothing.
+fn guest_wat(target: EcsEntityT, init_traps: bool, run_traps: bool) -> String {
+    let init_trap = if init_traps { "unreachable" } else { "" };
+    let run_trap = if run_traps { "unreachable" } else { "" };
+    format!(r#"
+(component
+  (import "toxoid-component:component/ecs" (instance $ecs
+    (export "iter" (type (sub resource)))
+    (export "callback" (type $callback (sub resource)))
+    (export "system" (type $system (sub resource)))
+    (type $query-desc' (record (field "expr" string)))
+    (export "query-desc" (type $query-desc (eq $query-desc')))
+    (type $system-desc' (record
+      (field "name" (option string))
+      (field "tick-rate" (option s32))
+      (field "callback" (own $callback))
+      (field "query-desc" $query-desc)
+      (field "is-guest" bool)))
+    (export "system-desc" (type $system-desc (eq $system-desc')))
+    (export "[constructor]callback" (func (param "handle" u64) (result (own $callback))))
+    (export "[constructor]system" (func (param "desc" $system-desc) (result (own $system))))
+    (export "[method]system.build" (func (param "self" (borrow $system))))
+    (export "remove-entity" (func (param "entity" u64)))))
+  (alias export $ecs "iter" (type $iter))
+
+  (core module $memory (memory (export "memory") 1))
+  (core instance $memory (instantiate $memory))
+  (core func $callback-new (canon lower (func $ecs "[constructor]callback")))
+  (core func $system-new (canon lower (func $ecs "[constructor]system") (memory $memory "memory")))
+  (core func $system-build (canon lower (func $ecs "[method]system.build")))
+  (core func $remove-entity (canon lower (func $ecs "remove-entity")))
+  (core func $iter-drop (canon resource.drop $iter))
+  (core instance $ecs-funcs
+    (export "callback-new" (func $callback-new))
+    (export "system-new" (func $system-new))
+    (export "system-build" (func $system-build))
+    (export "remove-entity" (func $remove-entity))
+    (export "iter-drop" (func $iter-drop)))
+
+  (core module $guest
+    (import "memory" "memory" (memory 1))
+    (import "ecs" "callback-new" (func $callback-new (param i64) (result i32)))
+    (import "ecs" "system-new" (func $system-new (param i32 i32 i32 i32 i32 i32 i32 i32 i32) (result i32)))
+    (import "ecs" "system-build" (func $system-build (param i32)))
+    (import "ecs" "remove-entity" (func $remove-entity (param i64)))
+    (import "ecs" "iter-drop" (func $iter-drop (param i32)))
+    (func (export "init")
+      {init_trap}
+      ;; Unnamed system without tick rate or terms, with callback 0
+      (call $system-build (call $system-new
+        (i32.const 0) (i32.const 0) (i32.const 0)
+        (i32.const 0) (i32.const 0)
+        (call $callback-new (i64.const 0))
+        (i32.const 0) (i32.const 0)
+        (i32.const 1))))
+    ;; A byte per callback handle, set once it's freed
+    (func (export "run") (param $iter i32) (param $handle i64)
+      (call $iter-drop (local.get $iter))
+      (if (i32.load8_u offset=1024 (i32.wrap_i64 (local.get $handle))) (then (return)))
+      {run_trap}
+      (call $remove-entity (i64.const {target})))
+    (func (export "free") (param $handle i64)
+      (i32.store8 offset=1024 (i32.wrap_i64 (local.get $handle)) (i32.const 1)))
+    (func (export "compare") (param i64 i64 i64 i64 i64) (result i32)
+      (i32.const 0)))
+  (core instance $guest (instantiate $guest
+    (with "memory" (instance $memory))
+    (with "ecs" (instance $ecs-funcs))))
+
+  (func $init (canon lift (core func $guest "init")))
+  (func $run (param "iter" (own $iter)) (param "handle" u64) (canon lift (core func $guest "run")))
+  (func $free (param "handle" u64) (canon lift (core func $guest "free")))
+  (func $compare (param "handle" u64) (param "e1" u64) (param "c1" u64) (param "e2" u64) (param "c2" u64) (result s32)
+    (canon lift (core func $guest "compare")))
+  (instance $callbacks
+    (export "run" (func $run))
+    (export "free" (func $free))
+    (export "compare" (func $compare)))
+  (export "init" (func $init))
+  (export "toxoid-component:component/callbacks" (instance $callbacks)))
+"#)
+}
+
+static GUEST_FILES: AtomicUsize = AtomicUsize::new(0);
+
+// Load a test guest in `world`, reloading the one loaded under the same name
+fn load_test_guest(name: &str, world: toxoid_host::worlds::WorldId, wat: &str) -> Result<()> {
+    set_guest_manifest(name, GuestManifest { world, ..GuestManifest::default() });
+    let file = GUEST_FILES.fetch_add(1, Ordering::SeqCst);
+    let path = std::env::temp_dir().join(format!("toxoid_test_{}_{}_{}.wat", std::process::id(), name, file));
+    std::fs::write(&path, wat).unwrap();
+    let result = load_wasm_component(name, path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    result
+}
+
+// Run a frame of a world and the work queued for after it
+fn run_frame(world: toxoid_host::worlds::WorldId) {
+    World::progress(world, 0.0);
+    toxoid_host::engine::run_frame_tasks();
+}
+
+// Targets are named so they can be looked up once deleted
+fn new_target(world: toxoid_host::worlds::WorldId, name: &std::ffi::CStr) -> EcsEntityT {
+    World::with(world, || Entity::named(name.to_str().unwrap()).get_id())
+}
+
+fn is_alive(world: toxoid_host::worlds::WorldId, name: &std::ffi::CStr) -> bool {
+    World::with(world, || lookup(name) != 0)
+}
+
+#[test]
+fn reloaded_guest_keeps_running_its_systems() {
+    let _world = lock_world();
+    let world = World::create();
+    let first = new_target(world, c"First");
+    load_test_guest("reloaded", world, &guest_wat(first, false, false)).unwrap();
+    run_frame(world);
+    assert!(!is_alive(world, c"First"));
+
+    // The system of the previous instance is freed between frames,
+    // the callback of the new instance has the same handle
+    let second = new_target(world, c"Second");
+    load_test_guest("reloaded", world, &guest_wat(second, false, false)).unwrap();
+    toxoid_host::engine::run_frame_tasks();
+    run_frame(world);
+    assert!(!is_alive(world, c"Second"));
+
+    unload_wasm_component("reloaded").unwrap();
+    assert!(World::destroy(world));
+}
+
+#[test]
+fn failed_reload_restarts_the_previous_component() {
+    let _world = lock_world();
+    let world = World::create();
+    let target = new_target(world, c"RestoredTarget");
+    load_test_guest("rolled_back", world, &guest_wat(target, false, false)).unwrap();
+    let previous = get_guest_named("rolled_back").unwrap().instance;
+
+    assert!(load_test_guest("rolled_back", world, &guest_wat(target, true, false)).is_err());
+    let guest = get_guest_named("rolled_back").unwrap();
+    assert_ne!(guest.instance, previous);
+    assert!(!guest.disabled.load(Ordering::SeqCst));
+    drop(guest);
+    run_frame(world);
+    assert!(!is_alive(world, c"RestoredTarget"));
+
+    unload_wasm_component("rolled_back").unwrap();
+    assert!(World::destroy(world));
+}
+
+#[test]
+fn trapping_system_is_quarantined_and_other_guests_keep_running() {
+    let _world = lock_world();
+    GUEST_ERRORS.lock().unwrap().clear();
+    let world = World::create();
+    let (trapped, healthy) = (new_target(world, c"Trapped"), new_target(world, c"Healthy"));
+    load_test_guest("trapping", world, &guest_wat(trapped, false, true)).unwrap();
+    load_test_guest("healthy", world, &guest_wat(healthy, false, false)).unwrap();
+    let instance = get_guest_named("trapping").unwrap().instance;
+
+    World::progress(world, 0.0);
+    assert_eq!(*GUEST_ERRORS.lock().unwrap(), vec![("trapping".to_string(), GuestErrorKind::Trap)]);
+    assert!(get_guest_named("trapping").unwrap().disabled.load(Ordering::SeqCst));
+    assert!(!is_alive(world, c"Healthy"));
+
+    // Restarted between frames with its system kept disabled
+    toxoid_host::engine::run_frame_tasks();
+    let guest = get_guest_named("trapping").unwrap();
+    assert_ne!(guest.instance, instance);
+    assert!(!guest.disabled.load(Ordering::SeqCst));
+    assert_eq!(lock_store(&guest).data().quarantined.len(), 1);
+    drop(guest);
+    run_frame(world);
+    assert_eq!(GUEST_ERRORS.lock().unwrap().len(), 1);
+    assert!(is_alive(world, c"Trapped"));
+
+    unload_wasm_component("trapping").unwrap();
+    unload_wasm_component("healthy").unwrap();
+    assert!(World::destroy(world));
+}
+
@@ -11,6 +11,15 @@ pub struct SokolRenderer2D {
     pass_action: sg::PassAction,
 }
 
+// Scale from game to window coordinates, never below the minimum window width
+fn window_scale_factor() -> f32 {
+    let game_config = World::get_singleton::<GameConfig>();
+    let window_width = game_config.get_window_width() as f32;
+    let game_width = game_config.get_game_width() as f32;
+    let min_width = game_config.get_min_window_width() as f32;
+    (window_width / game_width).max(min_width / game_width)
+}
+
 pub struct SokolImage {
     pub width: u32,
     pub height: u32,
@@ -461,11 +470,10 @@ impl Renderer2D for SokolRenderer2D {
 
     fn draw_sprite(sprite: &Box<dyn Sprite>, x: f32, y: f32) {
         unsafe {
-            let (window_width, _) = SokolRenderer2D::window_size();
-            // let scale_factor = window_width as f32 / crate::GAME_WIDTH as f32;
+            // Draw using game coordinates directly
             let dest_rect = sgp_rect { 
-                x: 0., 
-                y: 0., 
+                x, 
+                y, 
                 w: sprite.width() as f32, 
                 h: sprite.height() as f32
             };
@@ -476,8 +484,11 @@ impl Renderer2D for SokolRenderer2D {
                 h: sprite.height() as f32 
             };
             let sokol_sprite = sprite.as_any().downcast_ref::<SokolSprite>().unwrap();
+            sgp_reset_color();
+            sgp_set_blend_mode(sgp_blend_mode_SGP_BLENDMODE_BLEND);
             sgp_set_image(0, sg_image { id: sokol_sprite.image.id });
             sgp_draw_textured_rect(0, dest_rect, src_rect);
+            sgp_reset_image(0);
         }
     }
 
@@ -507,41 +518,35 @@ impl Renderer2D for SokolRenderer2D {
         }
     }
 
-    fn draw_filled_rect(pos: &Position, size: &Size, color: &Color) {
+    fn draw_filled_rect(x: f32, y: f32, width: f32, height: f32, color: [f32; 4]) {
         unsafe {
-            let game_config = World::get_singleton::<GameConfig>();
-            let window_width = game_config.get_window_width() as f32;
-            let game_width = game_config.get_game_width() as f32;
-            let min_width = game_config.get_min_window_width() as f32;
-            
-            let scale_factor = (window_width / game_width).max(min_width / game_width);
-            
+            let scale_factor = window_scale_factor();
             sgp_reset_color();
-            sgp_set_color(color.get_r(), color.get_g(), color.get_b(), color.get_a());
+            sgp_set_blend_mode(sgp_blend_mode_SGP_BLENDMODE_BLEND);
+            sgp_set_color(color[0], color[1], color[2], color[3]);
             sgp_draw_filled_rect(
-                pos.get_x() as f32 * scale_factor, 
-                pos.get_y() as f32 * scale_factor, 
-                size.get_width() as f32 * scale_factor, 
-                size.get_height() as f32 * scale_factor
+                x * scale_factor, 
+                y * scale_factor, 
+                width * scale_factor, 
+                height * scale_factor
             );
+            sgp_reset_color();
         }
     }
 
-    fn draw_line(ax: f32, ay: f32, bx: f32, by: f32) {
+    fn draw_line(ax: f32, ay: f32, bx: f32, by: f32, color: [f32; 4]) {
         unsafe {
-            let game_config = World::get_singleton::<GameConfig>();
-            let window_width = game_config.get_window_width() as f32;
-            let game_width = game_config.get_game_width() as f32;
-            let min_width = game_config.get_min_window_width() as f32;
-            
-            let scale_factor = (window_width / game_width).max(min_width / game_width);
-            
+            let scale_factor = window_scale_factor();
+            sgp_reset_color();
+            sgp_set_blend_mode(sgp_blend_mode_SGP_BLENDMODE_BLEND);
+            sgp_set_color(color[0], color[1], color[2], color[3]);
             sgp_draw_line(
                 ax * scale_factor, 
                 ay * scale_factor, 
                 bx * scale_factor, 
                 by * scale_factor
             );
+            sgp_reset_color();
         }
     }
 